@@ -0,0 +1,78 @@
+use egui::{ComboBox, RichText, Ui};
+
+use secalc_core::grid::ascent::{AscentOptions, AscentPlan, Planet};
+use secalc_core::grid::duration::Duration;
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+use secalc_widgets::sparkline::Sparkline;
+
+use crate::App;
+
+/// Options of the ascent result section.
+#[derive(Default)]
+pub struct AscentSection {
+  options: AscentOptions,
+  /// Plan for the calculator digest and options it was planned for, or None if not planned yet.
+  plan: Option<(u64, AscentOptions, AscentPlan)>,
+}
+
+impl AscentSection {
+  /// Invalidates the plan, for example when the data changes.
+  pub fn invalidate(&mut self) {
+    self.plan = None;
+  }
+}
+
+impl App {
+  pub fn show_ascent(&mut self, ui: &mut Ui) {
+    ui.open_collapsing_header("Ascent to Orbit", |ui| {
+      let section = &mut self.ascent;
+      ui.horizontal(|ui| {
+        ui.label("Planet");
+        ComboBox::from_id_source("Ascent Planet")
+          .selected_text(&section.options.planet.name)
+          .show_ui(ui, |ui| {
+            for planet in Planet::presets() {
+              let name = planet.name.clone();
+              ui.selectable_value(&mut section.options.planet, planet, name);
+            }
+          });
+      });
+
+      let digest = self.calculator.digest();
+      let up_to_date = section.plan.as_ref().is_some_and(|(d, o, _)| *d == digest && *o == section.options);
+      if !up_to_date {
+        let plan = self.calculator.plan_ascent(&self.data, &section.options);
+        section.plan = Some((digest, section.options.clone(), plan));
+      }
+      let Some((_, options, plan)) = &section.plan else { return; };
+
+      ui.grid("Ascent Grid", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label(RichText::new("Target Altitude:").underline())
+          .on_hover_text_at_pointer("Altitude above the surface at which the planet has neither gravity nor atmosphere.");
+        ui.right_align_value_with_unit(format!("{}", plan.target_altitude.round()), "m");
+        ui.end_row();
+        ui.label(RichText::new("Time to Orbit:").underline())
+          .on_hover_text_at_pointer(format!("Time to fly straight up from the surface to the target altitude with up thrusters, without exceeding {} m/s, or '-' if the grid does not reach it.", options.speed_limit));
+        ui.right_align_optional_duration(plan.time.map(Duration::from_seconds));
+        ui.end_row();
+        ui.label(RichText::new("Stalls At:").underline())
+          .on_hover_text_at_pointer("Altitude at which the grid stops climbing because up thrusters no longer overcome gravity, for example because the atmosphere is too thin or power or hydrogen runs out.");
+        ui.right_align_optional_value_with_unit(plan.stall_altitude.map(|a| format!("{}", a.round())), "m");
+        ui.end_row();
+        ui.show_row("Power Used:", format!("{:.2}", plan.power_consumed), "MWh");
+        ui.show_row("Hydrogen Used:", format!("{}", plan.hydrogen_consumed.round()), "L");
+      });
+      let line: Vec<_> = plan.samples.iter().map(|s| (s.time, Some(s.altitude))).collect();
+      let response = ui.add(Sparkline::new(&line).size([320.0, 60.0]));
+      if let Some(pos) = response.hover_pos() {
+        let fraction = ((pos.x - response.rect.left()) / response.rect.width()).clamp(0.0, 1.0);
+        let index = (fraction * (plan.samples.len() - 1) as f32).round() as usize;
+        if let Some(sample) = plan.samples.get(index) {
+          response.on_hover_text_at_pointer(format!("{:.0} s: {:.0} m at {:.0} m/s, {:.0}% thrust", sample.time, sample.altitude, sample.speed, sample.thrust));
+        }
+      }
+    });
+  }
+}
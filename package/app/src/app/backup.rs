@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+
+use eframe::App as AppT;
+use egui::{Align2, Button, Context, ScrollArea, TextEdit, Window};
+use serde::{Deserialize, Serialize};
+
+use secalc_core::data::blocks::GridSize;
+use secalc_core::data::custom_blocks::CustomBlock;
+use secalc_core::data::overrides::Overrides;
+use secalc_core::grid::duration::DurationFormat;
+use secalc_core::grid::forces::ForceUnit;
+use secalc_core::grid::preset::OptionPreset;
+use secalc_core::grid::server_profile::ServerProfile;
+use secalc_core::grid::summary::StatSummary;
+use secalc_core::grid::sweep::SweepResult;
+use secalc_core::grid::GridCalculator;
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+
+/// Version of the backup format. Increment when the format changes in a way that older versions cannot read.
+const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Backup of the entire app state.
+#[derive(Serialize, Deserialize)]
+struct Backup {
+  /// Version of the backup format.
+  format_version: u32,
+  /// Version of the app that created the backup.
+  app_version: String,
+  settings: BackupSettings,
+  saved_calculators: HashMap<String, GridCalculator>,
+  #[serde(default)]
+  save_history: HashMap<String, Vec<StatSummary>>,
+  calculator: GridCalculator,
+  grid_size: GridSize,
+  current_calculator: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BackupSettings {
+  enabled_mod_ids: HashSet<u64>,
+  dark_mode: bool,
+  font_size_modifier: i32,
+  increase_contrast: bool,
+  duration_format: DurationFormat,
+  #[serde(default)]
+  force_unit: ForceUnit,
+  #[serde(default)]
+  custom_blocks: Vec<CustomBlock>,
+  #[serde(default)]
+  block_overrides: Overrides,
+  #[serde(default)]
+  only_used_block_sections: HashSet<String>,
+  #[serde(default)]
+  compact_results: Vec<SweepResult>,
+  #[serde(default)]
+  option_presets: Vec<OptionPreset>,
+  #[serde(default)]
+  server_profile: ServerProfile,
+  #[serde(default)]
+  server_profile_enabled: bool,
+}
+
+/// Options of the restore backup window.
+#[derive(Default)]
+pub struct RestoreOptions {
+  text: String,
+  restore_settings: bool,
+  restore_saved_calculators: bool,
+  restore_current_calculator: bool,
+}
+
+impl RestoreOptions {
+  pub fn new() -> Self {
+    Self { restore_settings: true, restore_saved_calculators: true, restore_current_calculator: true, ..Self::default() }
+  }
+}
+
+impl App {
+  fn create_backup(&self) -> Backup {
+    Backup {
+      format_version: BACKUP_FORMAT_VERSION,
+      app_version: env!("CARGO_PKG_VERSION").to_string(),
+      settings: BackupSettings {
+        enabled_mod_ids: self.enabled_mod_ids.clone(),
+        dark_mode: self.dark_mode,
+        font_size_modifier: self.font_size_modifier,
+        increase_contrast: self.increase_contrast,
+        duration_format: self.duration_format,
+        force_unit: self.force_unit,
+        custom_blocks: self.custom_blocks.clone(),
+        block_overrides: self.block_overrides.clone(),
+        only_used_block_sections: self.only_used_block_sections.clone(),
+        compact_results: self.compact_results.clone(),
+        option_presets: self.option_presets.clone(),
+        server_profile: self.server_profile.clone(),
+        server_profile_enabled: self.server_profile_enabled,
+      },
+      saved_calculators: self.saved_calculators.clone(),
+      save_history: self.save_history.clone(),
+      calculator: self.calculator.clone(),
+      grid_size: self.grid_size,
+      current_calculator: self.current_calculator.clone(),
+    }
+  }
+
+  /// Serializes the entire app state into a backup JSON string.
+  pub fn backup_to_json(&self) -> String {
+    serde_json::to_string_pretty(&self.create_backup()).expect("Cannot serialize backup")
+  }
+
+  pub fn show_backup_windows(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+    self.show_backup_window(ctx);
+    self.show_restore_window(ctx, frame);
+  }
+
+  fn show_backup_window(&mut self, ctx: &Context) {
+    let Some(mut text) = self.show_backup_window.take() else { return; };
+    let mut close = false;
+    Window::new("Backup All")
+      .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+      .collapsible(false)
+      .fixed_size([600.0, 500.0])
+      .show(ctx, |ui| {
+        ui.label("Backup of all settings and saved grids. Copy the backup and store it in a file, then restore it on another machine or browser with 'Grid → Restore Backup'.");
+        ui.separator();
+        ScrollArea::vertical().max_height(380.0).show(ui, |ui| {
+          TextEdit::multiline(&mut text).code_editor().interactive(false).desired_width(f32::INFINITY).show(ui);
+        });
+        ui.separator();
+        ui.horizontal(|ui| {
+          if ui.button("Copy").clicked() {
+            ui.output_mut(|o| o.copied_text = text.clone());
+          }
+          if ui.button("Close").clicked() {
+            close = true;
+          }
+        });
+      });
+    if close {
+      self.enable_gui = true;
+    } else {
+      self.show_backup_window = Some(text);
+    }
+  }
+
+  fn show_restore_window(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+    let Some(mut options) = self.show_restore_window.take() else { return; };
+    let mut close = false;
+    Window::new("Restore Backup")
+      .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+      .collapsible(false)
+      .fixed_size([600.0, 500.0])
+      .show(ctx, |ui| {
+        ui.label("Paste a backup created with 'Grid → Backup All', and choose what to restore. Restored saved grids overwrite saved grids with the same name.");
+        ui.separator();
+        ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+          TextEdit::multiline(&mut options.text).code_editor().desired_width(f32::INFINITY).show(ui);
+        });
+        ui.separator();
+        let backup = if options.text.trim().is_empty() {
+          None
+        } else {
+          match serde_json::from_str::<Backup>(&options.text) {
+            Ok(backup) if backup.format_version > BACKUP_FORMAT_VERSION => {
+              ui.colored_label(ui.visuals().warn_fg_color, format!("Backup was created by a newer version of this app ({}) and cannot be restored. Update this app first.", backup.app_version));
+              None
+            }
+            Ok(backup) => {
+              ui.label(format!("Backup created by version {}, with {} saved grid(s).", backup.app_version, backup.saved_calculators.len()));
+              Some(backup)
+            }
+            Err(e) => {
+              ui.colored_label(ui.visuals().warn_fg_color, format!("Not a valid backup: {}", e));
+              None
+            }
+          }
+        };
+        ui.grid("Restore Options Grid", |ui| {
+          ui.label("Settings");
+          ui.checkbox(&mut options.restore_settings, "");
+          ui.end_row();
+          ui.label("Saved Grids");
+          ui.checkbox(&mut options.restore_saved_calculators, "");
+          ui.end_row();
+          ui.label("Current Grid");
+          ui.checkbox(&mut options.restore_current_calculator, "");
+          ui.end_row();
+        });
+        ui.separator();
+        ui.horizontal(|ui| {
+          if ui.add_enabled(backup.is_some(), Button::new("Restore")).clicked() {
+            if let Some(backup) = backup {
+              self.restore_backup(backup, &options, ctx);
+              if let Some(storage) = frame.storage_mut() {
+                self.save(storage);
+              }
+            }
+            close = true;
+          }
+          if ui.button("Cancel").clicked() {
+            close = true;
+          }
+        });
+      });
+    if close {
+      self.enable_gui = true;
+    } else {
+      self.show_restore_window = Some(options);
+    }
+  }
+
+  fn restore_backup(&mut self, backup: Backup, options: &RestoreOptions, ctx: &Context) {
+    self.bulk_update(|app| {
+      if options.restore_settings {
+        let settings = backup.settings;
+        app.enabled_mod_ids = settings.enabled_mod_ids;
+        app.dark_mode = settings.dark_mode;
+        app.font_size_modifier = settings.font_size_modifier;
+        app.increase_contrast = settings.increase_contrast;
+        app.duration_format = settings.duration_format;
+        app.force_unit = settings.force_unit;
+        app.custom_blocks = settings.custom_blocks;
+        app.block_overrides = settings.block_overrides;
+        app.only_used_block_sections = settings.only_used_block_sections;
+        app.compact_results = settings.compact_results;
+        app.option_presets = settings.option_presets;
+        app.server_profile = settings.server_profile;
+        app.server_profile_enabled = settings.server_profile_enabled;
+        app.apply_style(ctx);
+        app.rebuild_data();
+      }
+      if options.restore_saved_calculators {
+        app.saved_calculators.extend(backup.saved_calculators);
+        app.save_history.extend(backup.save_history);
+      }
+      if options.restore_current_calculator {
+        app.calculator = backup.calculator;
+        app.grid_size = backup.grid_size;
+        app.current_calculator_saved = backup.current_calculator.as_ref()
+          .and_then(|name| app.saved_calculators.get(name))
+          .is_some_and(|saved| saved.digest() == app.calculator.digest());
+        app.current_calculator = backup.current_calculator;
+        app.calculate();
+      }
+    });
+  }
+}
@@ -0,0 +1,85 @@
+use egui::{Context, RichText, ScrollArea, TextEdit, Window};
+
+use secalc_core::data::blocks::BlockId;
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+
+/// Options of the block data window.
+#[derive(Default)]
+pub struct BlockDataOptions {
+  /// Block ID or name to filter blocks by.
+  query: String,
+  /// Block to show the data of.
+  selected: Option<BlockId>,
+}
+
+impl App {
+  pub fn show_block_data_window(&mut self, ctx: &Context) {
+    let mut show = self.show_block_data_window;
+    Window::new("Block Data")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Inspect the data of a block, such as the mass of the components it is made of.");
+        ui.separator();
+        let options = &mut self.block_data;
+        ui.add(TextEdit::singleline(&mut options.query).hint_text("Block ID or name").desired_width(300.0));
+        let query = options.query.trim().to_lowercase();
+        if !query.is_empty() {
+          let mut blocks: Vec<_> = self.data.blocks.iter_data()
+            .filter(|b| b.id.to_lowercase().contains(&query) || b.name(&self.data.localization).to_lowercase().contains(&query))
+            .collect();
+          blocks.sort_by(|a, b| a.name(&self.data.localization).cmp(b.name(&self.data.localization)).then_with(|| a.id.cmp(&b.id)));
+          ScrollArea::vertical().id_source("Block Data Matches").max_height(150.0).show(ui, |ui| {
+            for block in blocks {
+              let selected = options.selected.as_ref() == Some(&block.id);
+              if ui.selectable_label(selected, format!("{} ({}, {})", block.name(&self.data.localization), block.size, block.id)).clicked() {
+                options.selected = Some(block.id_cloned());
+              }
+            }
+          });
+        }
+        ui.separator();
+        let Some(block) = options.selected.as_ref().and_then(|id| self.data.blocks.get_data(id)) else {
+          ui.label("Search for a block and select it to inspect its data.");
+          return;
+        };
+        ui.label(RichText::new(format!("{} ({}, {})", block.name(&self.data.localization), block.size, block.id)).strong());
+        if !block.has_physics {
+          ui.label("This block has no physics, and is therefore massless.");
+        }
+        let has_unknown_components = block.unknown_component_ids(&self.data.components).next().is_some();
+        if has_unknown_components {
+          ui.colored_label(ui.visuals().warn_fg_color, "⚠ This block is made of unknown components, which are not included in its mass. Extracting the data again with the mods that define these components may fix this.");
+        }
+        ui.grid("Block Data Components Grid", |ui| {
+          let warn_color = ui.visuals().warn_fg_color;
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          ui.label(RichText::new("Component").strong());
+          ui.right_align_label(RichText::new("Count").strong());
+          ui.right_align_label(RichText::new("Mass per Component").strong());
+          ui.right_align_label(RichText::new("Mass").strong());
+          ui.end_row();
+          for component_mass in block.component_masses(&self.data.components) {
+            match self.data.components.get(&component_mass.component_id) {
+              Some(component) => { ui.label(component.name(&self.data.localization)); }
+              None => { ui.colored_label(warn_color, format!("⚠ {} (unknown)", component_mass.component_id)); }
+            }
+            ui.right_align_value_with_unit(format!("{}", component_mass.count), "#");
+            ui.right_align_optional_value_with_unit(component_mass.mass.map(|m| format!("{:.2}", m)), "kg");
+            ui.right_align_optional_value_with_unit(component_mass.total_mass().map(|m| format!("{:.2}", m)), "kg");
+            ui.end_row();
+          }
+          ui.label(RichText::new("Block Mass").strong());
+          ui.label("");
+          ui.label("");
+          ui.right_align_value_with_unit(format!("{:.2}", block.mass(&self.data.components)), "kg");
+          ui.end_row();
+        });
+      });
+    self.show_block_data_window = show;
+  }
+}
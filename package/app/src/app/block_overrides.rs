@@ -0,0 +1,76 @@
+use egui::{Button, Context, ScrollArea, TextEdit, Window};
+
+use secalc_core::data::overrides::Overrides;
+
+use crate::App;
+
+/// Options of the block overrides window.
+#[derive(Default)]
+pub struct BlockOverridesOptions {
+  /// Block overrides JSON being edited.
+  text: String,
+  error: Option<String>,
+}
+
+impl BlockOverridesOptions {
+  pub fn new(overrides: &Overrides) -> Self {
+    let mut json = Vec::new();
+    let text = if !overrides.is_empty() && overrides.to_json(&mut json).is_ok() {
+      String::from_utf8_lossy(&json).into_owned()
+    } else {
+      String::new()
+    };
+    Self { text, error: None }
+  }
+}
+
+const EXAMPLE: &str = r#"{ "blocks": { "Thrust.LargeBlockLargeThrust": { "force": 8640000, "power_consumption": 33.6 } } }"#;
+
+impl App {
+  pub fn show_block_overrides_window(&mut self, ctx: &Context) {
+    let mut show = self.show_block_overrides_window;
+    let mut apply = None;
+    Window::new("Block Overrides")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Override the force, capacity, power generation, or power consumption of blocks, to match servers that change block stats with mods. Overrides are JSON, mapping block IDs to the stats to override, for example:");
+        ui.code(EXAMPLE);
+        ui.separator();
+        let options = &mut self.block_overrides_options;
+        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+          ui.add(TextEdit::multiline(&mut options.text).code_editor().hint_text("Paste block overrides JSON").desired_rows(8).desired_width(400.0));
+        });
+        if let Some(error) = &options.error {
+          ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        for problem in &self.override_problems {
+          ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}.", problem));
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+          if ui.button("Apply").clicked() {
+            if options.text.trim().is_empty() {
+              apply = Some(Overrides::default());
+            } else {
+              match Overrides::from_json(options.text.as_bytes()) {
+                Ok(overrides) => apply = Some(overrides),
+                Err(e) => options.error = Some(format!("{}: {}", e, std::error::Error::source(&e).map(|s| s.to_string()).unwrap_or_default())),
+              }
+            }
+          }
+          if ui.add_enabled(!self.block_overrides.is_empty(), Button::new("Clear")).clicked() {
+            options.text.clear();
+            apply = Some(Overrides::default());
+          }
+        });
+      });
+    self.show_block_overrides_window = show;
+    if let Some(overrides) = apply {
+      self.block_overrides_options.error = None;
+      self.block_overrides = overrides;
+      self.rebuild_data();
+    }
+  }
+}
@@ -0,0 +1,55 @@
+use std::collections::VecDeque;
+
+use secalc_core::grid::GridCalculated;
+
+/// Least-recently-used cache of calculation results, keyed by calculator digest.
+pub struct CalculatedCache {
+  entries: VecDeque<(u64, GridCalculated)>,
+  capacity: usize,
+  hits: u64,
+  misses: u64,
+}
+
+impl CalculatedCache {
+  pub fn new(capacity: usize) -> Self {
+    Self { entries: VecDeque::with_capacity(capacity), capacity, hits: 0, misses: 0 }
+  }
+
+  /// Gets the cached result for `digest`, or calculates it with `calculate` and caches it.
+  pub fn get_or_calculate(&mut self, digest: u64, calculate: impl FnOnce() -> GridCalculated) -> GridCalculated {
+    if let Some(index) = self.entries.iter().position(|(d, _)| *d == digest) {
+      self.hits += 1;
+      let entry = self.entries.remove(index).unwrap(); // Unwrap OK: index was just found.
+      let calculated = entry.1.clone();
+      self.entries.push_front(entry);
+      calculated
+    } else {
+      self.misses += 1;
+      let calculated = calculate();
+      if self.entries.len() >= self.capacity {
+        self.entries.pop_back();
+      }
+      self.entries.push_front((digest, calculated.clone()));
+      calculated
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.entries.clear();
+    self.hits = 0;
+    self.misses = 0;
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize { self.entries.len() }
+  #[inline]
+  pub fn capacity(&self) -> usize { self.capacity }
+  #[inline]
+  pub fn hits(&self) -> u64 { self.hits }
+  #[inline]
+  pub fn misses(&self) -> u64 { self.misses }
+}
+
+impl Default for CalculatedCache {
+  fn default() -> Self { Self::new(16) }
+}
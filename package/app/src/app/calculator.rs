@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use egui::{Button, ComboBox, DragValue, Response, RichText, Ui};
+
+use secalc_core::data::blocks::{BlockData, BlockId, GridSize};
+use secalc_core::data::Data;
+use secalc_core::grid::{BatteryMode, GridCalculator, HydrogenTankMode, PowerSourcePriority};
+use secalc_core::grid::warning::Warning;
+use secalc_widgets::calculator::{CalculatorUi, COUNT_DRAG_SPEED};
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+use crate::app::tour::{collapsing_target_rect, TourTarget};
+
+impl App {
+  pub fn show_calculator(&mut self, ui: &mut Ui) -> bool {
+    let mut changed = false;
+    let options = ui.open_collapsing_header("Options", |ui| {
+      ui.horizontal_top(|ui| {
+        ui.grid("Options Grid 1", |ui| {
+          let mut ui = CalculatorUi::new(ui, self.number_separator_policy, 100.0 + (self.font_size_modifier * 2) as f32);
+          ui.edit_suffix_row("Gravity Multiplier", "x", &mut self.calculator.gravity_multiplier, 0.005, 0.0..=f64::INFINITY, self.calculator_default.gravity_multiplier);
+          ui.edit_suffix_row("Container Multiplier", "x", &mut self.calculator.container_multiplier, 0.005, 0.0..=f64::INFINITY, self.calculator_default.container_multiplier);
+          ui.edit_suffix_row(RichText::new("Planetary Influence").underline(), "x", &mut self.calculator.planetary_influence, 0.005, 0.0..=1.0, self.calculator_default.planetary_influence)
+            .on_hover_text_at_pointer("How close to the ground level of a planet's atmosphere the grid is, with 1.0 being on or below ground level, and 0.0 being in vacuum. Lower values negatively affect atmospheric thrusters, and positively affect ion thrusters.");
+          ui.edit_suffix_row("Additional Mass", "kg", &mut self.calculator.additional_mass, 1000.0, 0.0..=f64::INFINITY, self.calculator_default.additional_mass);
+          ui.edit_suffix_row(RichText::new("External Lift").underline(), "N", &mut self.calculator.external_lift_force, 1000.0, 0.0..=f64::INFINITY, self.calculator_default.external_lift_force)
+            .on_hover_text_at_pointer("Force lifting the grid against gravity from outside its thrusters, for example from balloon or anti-gravity mods. Reduces the force thrusters need to counter gravity.");
+          ui.edit_suffix_row("Crew", "#", &mut self.calculator.crew_count, COUNT_DRAG_SPEED, 0..=u64::MAX, self.calculator_default.crew_count);
+          ui.edit_suffix_row(RichText::new("Mass per Crew").underline(), "kg", &mut self.calculator.crew_mass_per_engineer, 1.0, 0.0..=f64::INFINITY, self.calculator_default.crew_mass_per_engineer)
+            .on_hover_text_at_pointer("Mass of a single engineer, including their suit and the tools and items they carry.");
+          ui.edit_suffix_row(RichText::new("Hover Pitch").underline(), "°", &mut self.calculator.hover_pitch, 0.5, -90.0..=90.0, self.calculator_default.hover_pitch)
+            .on_hover_text_at_pointer("Pitch of the grid relative to the horizon when hovering in gravity, with positive values being nose-down. Used to calculate the up and front/back thrust needed to hover at that pitch.");
+          ui.edit_suffix_row(RichText::new("Mining Rate").underline(), "L/s", &mut self.calculator.mining_rate, 1.0, 0.0..=f64::INFINITY, self.calculator_default.mining_rate)
+            .on_hover_text_at_pointer("Rate at which drills fill inventories with ore when mining while hovering. Used to calculate mining results. Set to 0 to use the estimated ore rate of drills.");
+          ui.edit_suffix_row(RichText::new("Refinery Ore Rate").underline(), "kg/s", &mut self.calculator.refinery_ore_rate, 1.0, 0.0..=f64::INFINITY, self.calculator_default.refinery_ore_rate)
+            .on_hover_text_at_pointer("Ore that a refinery with a refine speed of 1 processes per second, which depends on the type of ore: 20 for iron ore. Used to calculate production results.");
+          ui.edit_suffix_row(RichText::new("Refinery Ingot Yield").underline(), "%", &mut self.calculator.refinery_ingot_yield, 1.0, 0.0..=100.0, self.calculator_default.refinery_ingot_yield)
+            .on_hover_text_at_pointer("Mass of ingots that a refinery produces from processed ore, before its material efficiency, which depends on the type of ore: 70% for iron ore. Used to calculate production results.");
+          ui.edit_suffix_row(RichText::new("Descent Altitude").underline(), "m", &mut self.calculator.descent_altitude, 10.0, 0.0..=f64::INFINITY, self.calculator_default.descent_altitude)
+            .on_hover_text_at_pointer("Altitude above the ground at which the grid starts braking its descent with up thrusters. Used to calculate the maximum safe descent speed.");
+          ui.edit_suffix_row(RichText::new("Reaction Time").underline(), "s", &mut self.calculator.descent_reaction_time, 0.1, 0.0..=f64::INFINITY, self.calculator_default.descent_reaction_time)
+            .on_hover_text_at_pointer("Time between deciding to brake and the up thrusters firing when descending, during which the grid falls freely. Used to calculate the maximum safe descent speed.");
+          ui.edit_suffix_row(RichText::new("Braking Speed").underline(), "m/s", &mut self.calculator.braking_speed, 1.0, 0.0..=f64::INFINITY, self.calculator_default.braking_speed)
+            .on_hover_text_at_pointer("Speed from which the grid brakes to a stop with its thrusters. Used to calculate the stopping distance and time per direction.");
+          let dimensions_hover_text = "Outer size of the grid along this axis. Used to estimate how fast gyroscopes can rotate the grid, treating it as a solid box. Leave all sizes at 0 to skip the estimate.";
+          ui.edit_suffix_row(RichText::new("Width").underline(), "m", &mut self.calculator.dimensions.width, 0.5, 0.0..=f64::INFINITY, self.calculator_default.dimensions.width)
+            .on_hover_text_at_pointer(dimensions_hover_text);
+          ui.edit_suffix_row(RichText::new("Height").underline(), "m", &mut self.calculator.dimensions.height, 0.5, 0.0..=f64::INFINITY, self.calculator_default.dimensions.height)
+            .on_hover_text_at_pointer(dimensions_hover_text);
+          ui.edit_suffix_row(RichText::new("Length").underline(), "m", &mut self.calculator.dimensions.length, 0.5, 0.0..=f64::INFINITY, self.calculator_default.dimensions.length)
+            .on_hover_text_at_pointer(dimensions_hover_text);
+          ui.edit_percentage_row("Thruster Power", &mut self.calculator.thruster_power, self.calculator_default.thruster_power);
+          ui.checkbox_suffix_row("Disabled Thrusters Off", "", &mut self.calculator.disabled_thrusters_off, self.calculator_default.disabled_thrusters_off);
+          ui.edit_percentage_row("Wheel Power", &mut self.calculator.wheel_power, self.calculator_default.wheel_power);
+          ui.checkbox_suffix_row("Charge Railguns", "", &mut self.calculator.railgun_charging, self.calculator_default.railgun_charging);
+          ui.checkbox_suffix_row("Charge Jump Drives", "", &mut self.calculator.jump_drive_charging, self.calculator_default.jump_drive_charging);
+          ui.combobox_suffix_row("Battery Mode", "Battery Mode", "", &mut self.calculator.battery_mode, BatteryMode::items(), self.calculator_default.battery_mode);
+          ui.edit_percentage_row("Battery Fill", &mut self.calculator.battery_fill, self.calculator_default.battery_fill);
+          ui.edit_percentage_row(RichText::new("Functional Build Progress").underline(), &mut self.calculator.functional_build_progress, self.calculator_default.functional_build_progress)
+            .on_hover_text_at_pointer("Build progress at or above which blocks are functional. Blocks below this progress only contribute the mass of their placed components. Set the build progress of a block by right-clicking it in the block lists.");
+          changed |= ui.changed()
+        });
+        ui.grid("Options Grid 2", |ui| {
+          let mut ui = CalculatorUi::new(ui, self.number_separator_policy, 90.0 + (self.font_size_modifier * 2) as f32);
+          ui.combobox_suffix_row("Hydrogen Tanks Mode", "Hydrogen Tanks Mode", "", &mut self.calculator.hydrogen_tank_mode, HydrogenTankMode::items(), self.calculator_default.hydrogen_tank_mode);
+          ui.edit_percentage_row("Hydrogen Tanks Fill", &mut self.calculator.hydrogen_tank_fill, self.calculator_default.hydrogen_tank_fill);
+          ui.checkbox_suffix_row("Tanks Always Use Power", "", &mut self.calculator.hydrogen_tank_always_operational, self.calculator_default.hydrogen_tank_always_operational);
+          ui.checkbox_suffix_row("Engines Enabled", "", &mut self.calculator.hydrogen_engine_enabled, self.calculator_default.hydrogen_engine_enabled);
+          ui.edit_percentage_row("Engines Fill", &mut self.calculator.hydrogen_engine_fill, self.calculator_default.hydrogen_engine_fill);
+          ui.combobox_suffix_row("Power Source Priority", "Power Source Priority", "", &mut self.calculator.power_source_priority, PowerSourcePriority::items(), self.calculator_default.power_source_priority);
+          ui.edit_percentage_row(RichText::new("Safety Margin").underline(), &mut self.calculator.safety_margin, self.calculator_default.safety_margin)
+            .on_hover_text_at_pointer("Percentage of total consumption that power and hydrogen balances must exceed to be within budget. Shown as the budget column in the power and hydrogen tables, and included in the generators needed for full burn.");
+          ui.checkbox_suffix_row("Oxygen Throttles Generators", "", &mut self.calculator.generator_oxygen_throttling, self.calculator_default.generator_oxygen_throttling);
+          ui.edit_suffix_row(RichText::new("Oxygen Demand").underline(), "L/s", &mut self.calculator.oxygen_demand, 1.0, 0.0..=f64::INFINITY, self.calculator_default.oxygen_demand)
+            .on_hover_text_at_pointer("Oxygen requested by consumers other than air vents, for example engineers refilling their suits. Together with pressurizing air vents, the demand that oxygen farms do not cover is requested from O2/H2 generators. When 'Oxygen Throttles Generators' is enabled, generators run to cover both hydrogen and oxygen demand, consuming more ice. Otherwise, generators only run to cover hydrogen demand, and excess oxygen is vented.");
+          ui.checkbox_suffix_row("Air Vents Pressurizing", "", &mut self.calculator.air_vents_pressurizing, self.calculator_default.air_vents_pressurizing);
+          ui.edit_percentage_row("Oxygen Tanks Fill", &mut self.calculator.oxygen_tank_fill, self.calculator_default.oxygen_tank_fill);
+          ui.edit_percentage_row("Ice-only Fill", &mut self.calculator.ice_only_fill, self.calculator_default.ice_only_fill);
+          ui.edit_percentage_row("Ore-only Fill", &mut self.calculator.ore_only_fill, self.calculator_default.ore_only_fill);
+          ui.edit_percentage_row("Any-fill with Ice", &mut self.calculator.any_fill_with_ice, self.calculator_default.any_fill_with_ice);
+          ui.edit_percentage_row("Any-fill with Ore", &mut self.calculator.any_fill_with_ore, self.calculator_default.any_fill_with_ore);
+          ui.edit_percentage_row("Any-fill with Steel Plates", &mut self.calculator.any_fill_with_steel_plates, self.calculator_default.any_fill_with_steel_plates);
+          ui.edit_percentage_row("Any-fill with H2 Bottles", &mut self.calculator.any_fill_with_hydrogen_bottles, self.calculator_default.any_fill_with_hydrogen_bottles);
+          ui.edit_percentage_row("Any-fill with O2 Bottles", &mut self.calculator.any_fill_with_oxygen_bottles, self.calculator_default.any_fill_with_oxygen_bottles);
+          ui.edit_percentage_row("Ammo Fill", &mut self.calculator.ammo_fill, self.calculator_default.ammo_fill);
+          ui.checkbox_suffix_row("Round Items to Stacks", "", &mut self.calculator.round_items_to_stacks, self.calculator_default.round_items_to_stacks);
+          ui.checkbox_suffix_row("Ignore Hidden Blocks", "", &mut self.calculator.ignore_hidden_blocks, self.calculator_default.ignore_hidden_blocks);
+          changed |= ui.changed()
+        });
+      });
+      changed |= self.show_any_fill_items(ui);
+    });
+    self.tour.set_target_rect(TourTarget::Options, collapsing_target_rect(ui, &options));
+    let block_edit_size = 40.0 + self.font_size_modifier as f32;
+    let blocks = ui.open_collapsing_header("Grid", |ui| {
+      ComboBox::from_id_source("Grid Size")
+        .selected_text(format!("{}", self.grid_size))
+        .show_ui(ui, |ui| {
+          ui.selectable_value(&mut self.grid_size, GridSize::Small, "Small");
+          ui.selectable_value(&mut self.grid_size, GridSize::Large, "Large");
+        });
+      let only_used = self.only_used_block_sections.contains("Thrusters");
+      let mut toggle_only_used = only_used;
+      ui.open_collapsing_header_with_grid_and_toggle("Thrusters", "Only Used", &mut toggle_only_used, |ui| {
+        let mut ui = CalculatorUi::new(ui, self.number_separator_policy, block_edit_size);
+        ui.header_count_directed_enabled_row(&mut self.calculator.thruster_direction_enabled, &self.calculator.direction_labels);
+        let without_force = self.calculated.warnings.iter().find_map(|w| match w {
+          Warning::AtmosphericThrustersWithoutForce { ids } => Some(ids),
+          _ => None,
+        });
+        let mut unused = Vec::new();
+        let mut edited = Vec::new();
+        for data in self.data.blocks.thruster_blocks(self.grid_size, &self.enabled_mod_ids) {
+          if !self.calculator.is_block_shown(&data.id) { continue; }
+          if !is_block_listed(&self.calculator, &mut self.revealed_blocks, &data.id, only_used) {
+            unused.push((data.id_cloned(), data.name(&self.data.localization).to_string()));
+            continue;
+          }
+          let mut label = RichText::new(block_label(&self.calculator, &data.id, data.name(&self.data.localization)));
+          let is_without_force = without_force.is_some_and(|ids| ids.contains(&data.id));
+          if is_without_force {
+            label = label.weak();
+          }
+          let count_per_direction = self.calculator.directional_blocks.entry(data.id_cloned()).or_default();
+          let previous_count_per_direction = count_per_direction.clone();
+          let response = ui.edit_count_directed_row(label, count_per_direction);
+          if *count_per_direction != previous_count_per_direction {
+            edited.push(data.id_cloned());
+          }
+          let response = if is_without_force {
+            response.on_hover_text_at_pointer("Provides no force at the current planetary influence.")
+          } else {
+            with_description(response, &self.data, &data.id)
+          };
+          response.context_menu(|ui| block_menu(ui, &mut self.calculator, &data.id, &mut changed));
+        }
+        if let Some(id) = ui.add_block_row("Thrusters Add Block", unused) {
+          self.revealed_blocks.insert(id);
+        }
+        changed |= ui.changed();
+        self.record_block_edits(edited);
+      });
+      self.set_only_used_block_section("Thrusters", toggle_only_used);
+      ui.horizontal(|ui| {
+        ui.vertical(|ui| {
+          let blocks = collect_blocks(&self.data, self.data.blocks.storage_blocks(self.grid_size, &self.enabled_mod_ids));
+          changed |= self.show_blocks_section(ui, "Storage", blocks, block_edit_size);
+          let blocks = collect_blocks(&self.data, self.data.blocks.wheel_suspension_blocks(self.grid_size, &self.enabled_mod_ids));
+          changed |= self.show_blocks_section(ui, "Wheel Suspensions", blocks, block_edit_size);
+          let blocks = collect_blocks(&self.data, self.data.blocks.weapon_blocks(self.grid_size, &self.enabled_mod_ids));
+          changed |= self.show_blocks_section(ui, "Weapons", blocks, block_edit_size);
+        });
+        ui.vertical(|ui| {
+          let blocks = collect_blocks(&self.data, self.data.blocks.power_blocks(self.grid_size, &self.enabled_mod_ids));
+          changed |= self.show_blocks_section(ui, "Power", blocks, block_edit_size);
+          let blocks = collect_blocks(&self.data, self.data.blocks.hydrogen_blocks(self.grid_size, &self.enabled_mod_ids));
+          changed |= self.show_blocks_section(ui, "Hydrogen", blocks, block_edit_size);
+          let blocks = collect_blocks(&self.data, self.data.blocks.oxygen_blocks(self.grid_size, &self.enabled_mod_ids));
+          changed |= self.show_blocks_section(ui, "Oxygen", blocks, block_edit_size);
+          let blocks = collect_blocks(&self.data, self.data.blocks.other_blocks(self.grid_size, &self.enabled_mod_ids));
+          changed |= self.show_blocks_section(ui, "Other", blocks, block_edit_size);
+        });
+      });
+      changed |= self.show_subgrids(ui);
+    });
+    self.tour.set_target_rect(TourTarget::Blocks, collapsing_target_rect(ui, &blocks));
+    changed
+  }
+
+  /// Shows the block list section with `header` for `blocks`, given as (ID, name) pairs. Returns whether a block count
+  /// was changed.
+  fn show_blocks_section(&mut self, ui: &mut Ui, header: &str, blocks: Vec<(BlockId, String)>, block_edit_size: f32) -> bool {
+    let mut changed = false;
+    let only_used = self.only_used_block_sections.contains(header);
+    let mut toggle_only_used = only_used;
+    ui.open_collapsing_header_with_grid_and_toggle(header, "Only Used", &mut toggle_only_used, |ui| {
+      let mut ui = CalculatorUi::new(ui, self.number_separator_policy, block_edit_size);
+      let mut unused = Vec::new();
+      let mut edited = Vec::new();
+      for (id, name) in blocks {
+        if !self.calculator.is_block_shown(&id) { continue; }
+        if !is_block_listed(&self.calculator, &mut self.revealed_blocks, &id, only_used) {
+          unused.push((id, name));
+          continue;
+        }
+        let label = block_label(&self.calculator, &id, &name);
+        let count = self.calculator.blocks.entry(id.clone()).or_default();
+        let previous_count = *count;
+        let response = ui.edit_count_row(label, count);
+        if *count != previous_count {
+          edited.push(id.clone());
+        }
+        let response = with_description(response, &self.data, &id);
+        response.context_menu(|ui| block_menu(ui, &mut self.calculator, &id, &mut changed));
+      }
+      if let Some(id) = ui.add_block_row((header, "Add Block"), unused) {
+        self.revealed_blocks.insert(id);
+      }
+      changed |= ui.changed();
+      self.record_block_edits(edited);
+    });
+    self.set_only_used_block_section(header, toggle_only_used);
+    changed
+  }
+
+  fn set_only_used_block_section(&mut self, header: &str, only_used: bool) {
+    if only_used {
+      self.only_used_block_sections.insert(header.to_string());
+    } else {
+      self.only_used_block_sections.remove(header);
+    }
+  }
+}
+
+/// Collects the IDs and names of `blocks`.
+fn collect_blocks<'a>(data: &Data, blocks: impl Iterator<Item=&'a BlockData>) -> Vec<(BlockId, String)> {
+  blocks.map(|b| (b.id_cloned(), b.name(&data.localization).to_string())).collect()
+}
+
+/// Returns whether block `id` should be listed in a block list section that shows `only_used` blocks or all blocks.
+/// Used blocks are remembered in `revealed_blocks`, so that they stay listed when their count is set back to 0.
+fn is_block_listed(calculator: &GridCalculator, revealed_blocks: &mut HashSet<BlockId>, id: &BlockId, only_used: bool) -> bool {
+  if calculator.has_block(id) {
+    if !revealed_blocks.contains(id) {
+      revealed_blocks.insert(id.clone());
+    }
+    return true;
+  }
+  !only_used || revealed_blocks.contains(id)
+}
+
+/// Shows the description of block `id` when hovering over `response`, if the block has a description.
+fn with_description(response: Response, data: &Data, id: &BlockId) -> Response {
+  match data.blocks.get_data(id).and_then(|b| b.description(&data.localization)) {
+    Some(description) => response.on_hover_text_at_pointer(description),
+    None => response,
+  }
+}
+
+/// Gets the label of block `id` with `name`, including its build progress if it is not fully built.
+fn block_label(calculator: &GridCalculator, id: &BlockId, name: &str) -> String {
+  let build_progress = calculator.block_build_progress(id);
+  if build_progress < 100.0 {
+    format!("{} ({:.0}%)", name, build_progress)
+  } else {
+    name.to_string()
+  }
+}
+
+/// Shows a context menu for block `id` of `calculator`, for editing its build progress and excluding it from the block
+/// lists.
+fn block_menu(ui: &mut Ui, calculator: &mut GridCalculator, id: &BlockId, changed: &mut bool) {
+  ui.horizontal(|ui| {
+    ui.label("Build Progress");
+    let mut build_progress = calculator.block_build_progress(id);
+    if ui.add(DragValue::new(&mut build_progress).speed(0.5).clamp_range(0.0..=100.0).suffix("%")).changed() {
+      if build_progress < 100.0 {
+        calculator.build_progress.insert(id.clone(), build_progress);
+      } else {
+        calculator.build_progress.remove(id);
+      }
+      *changed = true;
+    }
+  }).response.on_hover_text("Build progress of this block, for estimating partially built grids. Scales the mass of the block, and disables it when below the functional build progress.");
+  let response = ui.add_enabled(!calculator.has_block(id), Button::new("Hide in this Grid"))
+    .on_hover_text("Hide this block in the block lists of this grid. Only blocks with a count of 0 can be hidden. Hidden blocks can be shown again with 'Grid -> Show Hidden Blocks'.")
+    .on_disabled_hover_text("Only blocks with a count of 0 can be hidden.");
+  if response.clicked() {
+    calculator.excluded_blocks.insert(id.clone());
+    *changed = true;
+    ui.close_menu();
+  }
+}
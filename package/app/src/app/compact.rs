@@ -0,0 +1,69 @@
+use egui::{CentralPanel, Color32, Context, Frame, Grid, RichText, Sense, Ui, ViewportBuilder, ViewportClass, ViewportId, Window};
+use egui::style::Margin;
+
+use secalc_core::grid::duration::Duration;
+use secalc_core::grid::sweep::SweepResult;
+
+use crate::App;
+
+impl App {
+  /// Shows the compact mode window: an always-on-top window with a transparent background, showing only the results in
+  /// `self.compact_results` with large text, for example for streaming overlays. Shown as a regular window when
+  /// secondary windows are not supported, such as on the web.
+  pub fn show_compact_window(&mut self, ctx: &Context) {
+    if !self.show_compact_window { return; }
+    let builder = ViewportBuilder::default()
+      .with_title("Space Engineers Calculator - Compact")
+      .with_inner_size([300.0, 200.0])
+      .with_always_on_top()
+      .with_transparent(true);
+    ctx.show_viewport_immediate(ViewportId::from_hash_of("Compact"), builder, |ctx, class| {
+      if class == ViewportClass::Embedded {
+        let mut show = true;
+        Window::new("Compact")
+          .open(&mut show)
+          .collapsible(false)
+          .resizable(false)
+          .show(ctx, |ui| self.show_compact_results(ui));
+        self.show_compact_window = show;
+      } else {
+        let frame = Frame::none().fill(Color32::TRANSPARENT).inner_margin(Margin::same(8.0));
+        CentralPanel::default().frame(frame).show(ctx, |ui| self.show_compact_results(ui));
+        if ctx.input(|i| i.viewport().close_requested()) {
+          self.show_compact_window = false;
+        }
+      }
+    });
+  }
+
+  fn show_compact_results(&mut self, ui: &mut Ui) {
+    let size = 24.0 + self.font_size_modifier as f32;
+    let response = Grid::new("Compact Grid").num_columns(2).show(ui, |ui| {
+      for result in &self.compact_results {
+        ui.label(RichText::new(format!("{}", result)).size(size));
+        let value = match (result, result.get(&self.calculated)) {
+          (_, None) => "-".to_string(),
+          (SweepResult::BatteryDuration, Some(hours)) => Duration::from_hours(hours).format(self.duration_format, 2),
+          (_, Some(value)) => format!("{:.2} {}", value, result.unit()),
+        };
+        ui.label(RichText::new(value).size(size).strong());
+        ui.end_row();
+      }
+    }).response;
+    if self.compact_results.is_empty() {
+      ui.label("Right-click to choose results to show.");
+    }
+    ui.interact(ui.max_rect(), response.id.with("Menu"), Sense::click()).context_menu(|ui| {
+      for result in SweepResult::items() {
+        let mut shown = self.compact_results.contains(&result);
+        if ui.checkbox(&mut shown, format!("{}", result)).changed() {
+          if shown {
+            self.compact_results.push(result);
+          } else {
+            self.compact_results.retain(|r| *r != result);
+          }
+        }
+      }
+    });
+  }
+}
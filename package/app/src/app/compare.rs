@@ -0,0 +1,89 @@
+use egui::{CollapsingHeader, Grid, Ui};
+use thousands::SeparatorPolicy;
+
+use secalc_core::grid::direction::Direction;
+use secalc_core::grid::duration::DurationFormat;
+use secalc_core::grid::diff::GridCalculatedDiff;
+use secalc_core::grid::GridCalculated;
+use secalc_widgets::result::ResultUi;
+
+/// Key statistic of a calculation result, for comparing results.
+struct KeyStat {
+  label: &'static str,
+  value: Option<f64>,
+  unit: &'static str,
+}
+
+fn key_stats(calculated: &GridCalculated) -> [KeyStat; 8] {
+  let up = &calculated.thruster_acceleration[Direction::Up];
+  [
+    KeyStat { label: "Mass (Empty)", value: Some(calculated.total_mass_empty.to_kilograms()), unit: "kg" },
+    KeyStat { label: "Mass (Filled)", value: Some(calculated.total_mass_filled.to_kilograms()), unit: "kg" },
+    KeyStat { label: "Up Acceleration (Filled, Gravity)", value: up.acceleration_filled_gravity, unit: "m/s²" },
+    KeyStat { label: "Power Generation", value: Some(calculated.power_generation.to_megawatts()), unit: "MW" },
+    KeyStat { label: "Power Balance (Full Thrust)", value: Some(calculated.power_upto_left_right_thruster.balance.to_megawatts()), unit: "MW" },
+    KeyStat { label: "Hydrogen Balance (Full Thrust)", value: Some(calculated.hydrogen_upto_left_right_thruster.balance_without_tank), unit: "L/s" },
+    KeyStat { label: "Battery Capacity", value: calculated.battery.as_ref().map(|b| b.capacity.to_megawatt_hours()), unit: "MWh" },
+    KeyStat { label: "Jump Range (Filled)", value: calculated.jump_drive.as_ref().and_then(|j| j.max_distance_filled), unit: "km" },
+  ]
+}
+
+/// Shows key statistics of `before` and `after` side by side, as rows of a grid.
+pub fn show_key_stats_comparison(ui: &mut Ui, number_separator_policy: SeparatorPolicy<'static>, before: &GridCalculated, after: &GridCalculated) {
+  let mut ui = ResultUi::new(ui, number_separator_policy, DurationFormat::default());
+  ui.label("");
+  ui.right_align_label("Before");
+  ui.right_align_label("After");
+  ui.end_row();
+  for (before, after) in key_stats(before).into_iter().zip(key_stats(after)) {
+    ui.label(before.label);
+    ui.right_align_optional_value_with_unit(before.value.map(|v| format!("{:.2}", v)), before.unit);
+    ui.right_align_optional_value_with_unit(after.value.map(|v| format!("{:.2}", v)), after.unit);
+    ui.end_row();
+  }
+}
+
+/// Shows key statistics of `reference` labelled `reference_label` and `current` side by side with their difference, as
+/// rows of a grid.
+pub fn show_key_stats_difference(ui: &mut Ui, number_separator_policy: SeparatorPolicy<'static>, reference_label: &str, reference: &GridCalculated, current: &GridCalculated) {
+  let mut ui = ResultUi::new(ui, number_separator_policy, DurationFormat::default());
+  ui.label("");
+  ui.right_align_label(reference_label);
+  ui.right_align_label("This Grid");
+  ui.right_align_label("Difference");
+  ui.end_row();
+  for (reference, current) in key_stats(reference).into_iter().zip(key_stats(current)) {
+    ui.label(reference.label);
+    ui.right_align_optional_value_with_unit(reference.value.map(|v| format!("{:.2}", v)), reference.unit);
+    ui.right_align_optional_value_with_unit(current.value.map(|v| format!("{:.2}", v)), current.unit);
+    let difference = reference.value.zip(current.value).map(|(reference, current)| format!("{:+.2}", current - reference));
+    ui.right_align_optional_value_with_unit(difference, current.unit);
+    ui.end_row();
+  }
+}
+
+/// Shows all differing values of `diff` in a collapsing header that is closed by default, as the list can be long.
+pub fn show_calculated_diff(ui: &mut Ui, number_separator_policy: SeparatorPolicy<'static>, diff: impl FnOnce() -> GridCalculatedDiff) {
+  CollapsingHeader::new("All Differences").default_open(false).show(ui, |ui| {
+    let diff = diff();
+    if diff.is_empty() {
+      ui.label("No differences");
+      return;
+    }
+    Grid::new("All Differences Grid").striped(true).min_col_width(1.0).show(ui, |ui| {
+      let mut ui = ResultUi::new(ui, number_separator_policy, DurationFormat::default());
+      ui.label("Value");
+      ui.right_align_label("Before");
+      ui.right_align_label("After");
+      ui.right_align_label("Difference");
+      ui.end_row();
+      for field in &diff.fields {
+        ui.label(&field.path);
+        ui.right_align_optional_value_with_unit(field.before.map(|v| format!("{:.2}", v)), "");
+        ui.right_align_optional_value_with_unit(field.after.map(|v| format!("{:.2}", v)), "");
+        ui.right_align_optional_value_with_unit(field.delta().map(|v| format!("{:+.2}", v)), "");
+        ui.end_row();
+      }
+    });
+  });
+}
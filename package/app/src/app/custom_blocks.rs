@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use eframe::App as AppT;
+use egui::{Align2, Button, ComboBox, Context, DragValue, ScrollArea, TextEdit, Ui, Window};
+
+use secalc_core::data::blocks::{GridSize, ThrusterType};
+use secalc_core::data::custom_blocks::{CustomBlock, CustomBlockDetails, CustomBlockKind};
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+
+impl App {
+  /// Rebuilds the data from the extracted data, the custom blocks, and the block overrides, and recalculates.
+  pub fn rebuild_data(&mut self) {
+    let mut data = self.extracted_data.clone();
+    data.add_custom_blocks(&self.custom_blocks);
+    self.override_problems = data.apply_overrides(&self.block_overrides);
+    self.data = data;
+    // Cached results were calculated with the previous data.
+    self.calculated_cache.clear();
+    self.sensitivity.invalidate();
+    self.ascent.invalidate();
+    self.calculate();
+  }
+
+  pub fn show_custom_blocks_window(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+    let Some(mut custom_blocks) = self.show_custom_blocks_window.take() else { return; };
+    let mut close = false;
+    Window::new("Custom Blocks")
+      .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+      .collapsible(false)
+      .fixed_size([500.0, 500.0])
+      .show(ctx, |ui| {
+        ui.label("Define blocks that do not exist in the game, for theory-crafting or prototyping mod ideas. Custom blocks are shown in the block lists of their kind and grid size.");
+        ui.separator();
+        let mut remove = None;
+        ScrollArea::vertical().max_height(380.0).auto_shrink([false; 2]).show(ui, |ui| {
+          for (index, custom_block) in custom_blocks.iter_mut().enumerate() {
+            ui.grid(("Custom Block Grid", index), |ui| {
+              show_custom_block(ui, index, custom_block);
+            });
+            if ui.danger_button("Remove").clicked() {
+              remove = Some(index);
+            }
+            ui.separator();
+          }
+          if ui.button("Add").clicked() {
+            custom_blocks.push(CustomBlock::default());
+          }
+        });
+        if let Some(index) = remove {
+          custom_blocks.remove(index);
+        }
+        ui.separator();
+
+        let mut ids = HashSet::new();
+        let duplicate = custom_blocks.iter().find(|b| !ids.insert(b.id()));
+        let empty = custom_blocks.iter().any(|b| b.name.trim().is_empty());
+        if let Some(duplicate) = duplicate {
+          ui.colored_label(ui.visuals().warn_fg_color, format!("Multiple {} grid custom blocks are named '{}'. Names must be unique per grid size.", duplicate.size, duplicate.name));
+        } else if empty {
+          ui.colored_label(ui.visuals().warn_fg_color, "Custom blocks must have a name.");
+        }
+        let valid = duplicate.is_none() && !empty;
+        ui.horizontal(|ui| {
+          if ui.add_enabled(valid, Button::new("Apply")).clicked() {
+            self.custom_blocks = std::mem::take(&mut custom_blocks);
+            self.rebuild_data();
+            if let Some(storage) = frame.storage_mut() {
+              self.save(storage);
+            }
+            close = true;
+          }
+          if ui.button("Cancel").clicked() {
+            close = true;
+          }
+        });
+      });
+    if close {
+      self.enable_gui = true;
+    } else {
+      self.show_custom_blocks_window = Some(custom_blocks);
+    }
+  }
+}
+
+fn show_custom_block(ui: &mut Ui, index: usize, custom_block: &mut CustomBlock) {
+  ui.label("Name");
+  ui.add(TextEdit::singleline(&mut custom_block.name).desired_width(200.0));
+  ui.end_row();
+  ui.label("Grid Size");
+  ComboBox::from_id_source(("Custom Block Grid Size", index))
+    .selected_text(format!("{}", custom_block.size))
+    .show_ui(ui, |ui| {
+      for size in [GridSize::Small, GridSize::Large] {
+        ui.selectable_value(&mut custom_block.size, size, format!("{}", size));
+      }
+    });
+  ui.end_row();
+  ui.label("Kind");
+  let mut kind = custom_block.details.kind();
+  ComboBox::from_id_source(("Custom Block Kind", index))
+    .selected_text(format!("{}", kind))
+    .show_ui(ui, |ui| {
+      for k in CustomBlockKind::items() {
+        ui.selectable_value(&mut kind, k, format!("{}", k));
+      }
+    });
+  if kind != custom_block.details.kind() {
+    custom_block.details = kind.default_details();
+  }
+  ui.end_row();
+  ui.label("Mass");
+  ui.add(DragValue::new(&mut custom_block.mass).speed(10.0).clamp_range(0.0..=f64::INFINITY).suffix("kg"));
+  ui.end_row();
+  match &mut custom_block.details {
+    CustomBlockDetails::Thruster { ty, force, max_consumption } => {
+      ui.label("Thruster Type");
+      ComboBox::from_id_source(("Custom Block Thruster Type", index))
+        .selected_text(format!("{}", ty))
+        .show_ui(ui, |ui| {
+          for t in [ThrusterType::Atmospheric, ThrusterType::Ion, ThrusterType::Hydrogen] {
+            ui.selectable_value(ty, t, format!("{}", t));
+          }
+        });
+      ui.end_row();
+      ui.label("Force");
+      ui.add(DragValue::new(force).speed(1000.0).clamp_range(0.0..=f64::INFINITY).suffix("N"));
+      ui.end_row();
+      ui.label("Maximum Consumption");
+      let suffix = if *ty == ThrusterType::Hydrogen { "L/s" } else { "MW" };
+      ui.add(DragValue::new(max_consumption).speed(0.01).clamp_range(0.0..=f64::INFINITY).suffix(suffix));
+      ui.end_row();
+    }
+    CustomBlockDetails::Battery { capacity, input, output } => {
+      ui.label("Capacity");
+      ui.add(DragValue::new(capacity).speed(0.01).clamp_range(0.0..=f64::INFINITY).suffix("MWh"));
+      ui.end_row();
+      ui.label("Maximum Input");
+      ui.add(DragValue::new(input).speed(0.01).clamp_range(0.0..=f64::INFINITY).suffix("MW"));
+      ui.end_row();
+      ui.label("Maximum Output");
+      ui.add(DragValue::new(output).speed(0.01).clamp_range(0.0..=f64::INFINITY).suffix("MW"));
+      ui.end_row();
+    }
+    CustomBlockDetails::Reactor { max_power_generation } => {
+      ui.label("Maximum Generation");
+      ui.add(DragValue::new(max_power_generation).speed(0.01).clamp_range(0.0..=f64::INFINITY).suffix("MW"));
+      ui.end_row();
+    }
+    CustomBlockDetails::HydrogenTank { capacity } => {
+      ui.label("Capacity");
+      ui.add(DragValue::new(capacity).speed(1000.0).clamp_range(0.0..=f64::INFINITY).suffix("L"));
+      ui.end_row();
+    }
+    CustomBlockDetails::Container { inventory_volume } => {
+      ui.label("Inventory Volume");
+      ui.add(DragValue::new(inventory_volume).speed(100.0).clamp_range(0.0..=f64::INFINITY).suffix("L"));
+      ui.end_row();
+    }
+  }
+}
@@ -0,0 +1,72 @@
+use egui::{ComboBox, Context, DragValue, Window};
+
+use secalc_core::grid::docking::DockSupply;
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+
+/// Options of the dock charging window.
+#[derive(Default)]
+pub struct DockChargingOptions {
+  supply: DockSupply,
+  /// Name of the saved grid to take the supply from, or None to enter the supply manually.
+  station: Option<String>,
+}
+
+impl App {
+  pub fn show_dock_charging_window(&mut self, ctx: &Context) {
+    let mut show = self.show_dock_charging_window;
+    Window::new("Dock Charging")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Time to charge batteries and fill hydrogen tanks and engines of this grid when docked via a connector to a station that supplies power and hydrogen.");
+        ui.separator();
+        let options = &mut self.dock_charging;
+        if options.station.as_ref().is_some_and(|name| !self.saved_calculators.contains_key(name)) {
+          options.station = None;
+        }
+        ui.grid("Dock Charging Supply Grid", |ui| {
+          ui.label("Station");
+          ComboBox::from_id_source("Dock Charging Station")
+            .selected_text(options.station.as_deref().unwrap_or("Manual"))
+            .show_ui(ui, |ui| {
+              ui.selectable_value(&mut options.station, None, "Manual");
+              let mut names: Vec<_> = self.saved_calculators.keys().collect();
+              names.sort();
+              for name in names {
+                ui.selectable_value(&mut options.station, Some(name.clone()), name);
+              }
+            });
+          ui.end_row();
+          if let Some(station) = options.station.as_ref().and_then(|name| self.saved_calculators.get(name)) {
+            let calculated = self.calculated_cache.get_or_calculate(station.digest(), || station.calculate(&self.data));
+            options.supply = DockSupply::from_station(&calculated);
+          }
+          let manual = options.station.is_none();
+          ui.label("Power Surplus");
+          ui.add_enabled(manual, DragValue::new(&mut options.supply.power).speed(0.1).clamp_range(0.0..=f64::INFINITY).suffix("MW"));
+          ui.end_row();
+          ui.label("Hydrogen Surplus");
+          ui.add_enabled(manual, DragValue::new(&mut options.supply.hydrogen).speed(10.0).clamp_range(0.0..=f64::INFINITY).suffix("L/s"));
+          ui.end_row();
+        });
+        if options.station.is_some() {
+          ui.label("Surplus of the station with thrusters at full burn, without charging or filling its own batteries and tanks.");
+        }
+        ui.separator();
+
+        let charging = self.calculated.dock_charging(&self.calculator, &options.supply);
+        ui.grid("Dock Charging Result Grid", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          ui.show_optional_duration_row("Charge Batteries:", charging.battery_charge_duration);
+          ui.show_optional_duration_row("Fill Hydrogen Tanks:", charging.hydrogen_tank_fill_duration);
+          ui.show_optional_duration_row("Fill Hydrogen Engines:", charging.hydrogen_engine_fill_duration);
+          ui.show_optional_duration_row("All Full:", charging.total_duration);
+        });
+      });
+    self.show_dock_charging_window = show;
+  }
+}
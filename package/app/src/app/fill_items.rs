@@ -0,0 +1,48 @@
+use egui::{CollapsingHeader, DragValue, Ui};
+
+use secalc_core::grid::items::FillItem;
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+
+impl App {
+  /// Shows the items that override the any fills, with a checkbox to override the any fills, and buttons to add and
+  /// remove items. Returns whether the items were changed.
+  pub fn show_any_fill_items(&mut self, ui: &mut Ui) -> bool {
+    let mut changed = false;
+    CollapsingHeader::new("Any-fill Items").show(ui, |ui| {
+      let mut overridden = self.calculator.any_fill_items.is_some();
+      if ui.checkbox(&mut overridden, "Override Any-fill").on_hover_text("Fill inventories of any type with the items below instead of the any-fill options, for example to fill them with components.").changed() {
+        self.calculator.any_fill_items = overridden.then(|| self.calculator.any_fill_items_from_options(&self.data));
+        changed = true;
+      }
+      let Some(items) = &mut self.calculator.any_fill_items else { return; };
+      let mut remove = None;
+      ui.grid("Any-fill Items Grid", |ui| {
+        for header in ["Item", "Volume", "Mass", "Fill", ""] {
+          ui.label(header);
+        }
+        ui.end_row();
+        for (index, item) in items.iter_mut().enumerate() {
+          changed |= ui.text_edit_singleline(&mut item.name).changed();
+          changed |= ui.add(DragValue::new(&mut item.volume).speed(0.1).clamp_range(0.0..=f64::INFINITY).suffix(" L")).changed();
+          changed |= ui.add(DragValue::new(&mut item.mass).speed(0.1).clamp_range(0.0..=f64::INFINITY).suffix(" kg")).changed();
+          changed |= ui.add(DragValue::new(&mut item.fill).speed(0.5).clamp_range(0.0..=100.0).suffix("%")).changed();
+          if ui.button("Remove").clicked() {
+            remove = Some(index);
+          }
+          ui.end_row();
+        }
+      });
+      if let Some(index) = remove {
+        items.remove(index);
+        changed = true;
+      }
+      if ui.button("Add Item").clicked() {
+        items.push(FillItem::new("Item", 1.0, 1.0, 0.0));
+        changed = true;
+      }
+    });
+    changed
+  }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeSet;
+
+use egui::{CollapsingHeader, Context, ScrollArea, Window};
+
+use secalc_core::grid::duration::Duration;
+use secalc_core::grid::fleet::FleetCalculated;
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+
+/// Options of the fleet window.
+#[derive(Default)]
+pub struct FleetOptions {
+  /// Names of the saved grids in the fleet.
+  selected: BTreeSet<String>,
+}
+
+impl App {
+  pub fn show_fleet_window(&mut self, ctx: &Context) {
+    let mut show = self.show_fleet_window;
+    Window::new("Fleet")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Aggregate statistics of several saved grids, for example a carrier with its drones.");
+        ui.separator();
+        let options = &mut self.fleet;
+        options.selected.retain(|name| self.saved_calculators.contains_key(name));
+        let mut names: Vec<_> = self.saved_calculators.keys().cloned().collect();
+        names.sort();
+        if names.is_empty() {
+          ui.label("There are no saved grids. Save grids with 'Grid -> Save As' to add them to the fleet.");
+          return;
+        }
+        ScrollArea::vertical().id_source("Fleet Grids").max_height(150.0).show(ui, |ui| {
+          for name in names {
+            let mut selected = options.selected.contains(&name);
+            if ui.checkbox(&mut selected, &name).changed() {
+              if selected {
+                options.selected.insert(name);
+              } else {
+                options.selected.remove(&name);
+              }
+            }
+          }
+        });
+        ui.separator();
+
+        let grids: Vec<_> = options.selected.iter()
+          .filter_map(|name| self.saved_calculators.get(name))
+          .map(|calculator| (calculator, self.calculated_cache.get_or_calculate(calculator.digest(), || calculator.calculate(&self.data))))
+          .collect();
+        let welder_speed_multiplier = if self.server_profile_enabled { self.server_profile.welder_speed_multiplier() } else { 1.0 };
+        let fleet = FleetCalculated::calculate(grids.iter().map(|(calculator, calculated)| (*calculator, calculated)), &self.data, welder_speed_multiplier);
+        ui.grid("Fleet Grid", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          ui.show_row("Grids:", format!("{}", fleet.grid_count), "#");
+          ui.show_row("Blocks:", format!("{}", fleet.block_count), "#");
+          ui.show_optional_row("PCU:", fleet.pcu.map(|pcu| format!("{}", pcu)), "#");
+          ui.show_optional_duration_row("Build Time:", fleet.build_time.map(Duration::from_seconds));
+          ui.show_row("Mass (Empty):", format!("{:.2}", fleet.total_mass_empty), "kg");
+          ui.show_row("Mass (Filled):", format!("{:.2}", fleet.total_mass_filled), "kg");
+          ui.show_row("Power Generation:", format!("{:.2}", fleet.power_generation), "MW");
+          ui.show_row("Hydrogen Generation:", format!("{:.2}", fleet.hydrogen_generation), "L/s");
+          ui.show_row("Hydrogen Consumption:", format!("{:.2}", fleet.hydrogen_consumption), "L/s");
+        });
+        if fleet.pcu.is_none() {
+          ui.label("PCU is unknown for some blocks. Extract the game data again to include PCU.");
+        }
+        if fleet.build_time.is_none() {
+          ui.label("Build time is unknown for some blocks. Extract the game data again to include build times.");
+        } else {
+          ui.label(format!("Build time with a single welder at a welder speed multiplier of {:.2}x, set in the server profile.", welder_speed_multiplier));
+        }
+        CollapsingHeader::new("Build Cost").show(ui, |ui| {
+          ui.grid("Fleet Build Cost Grid", |ui| {
+            let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+            for (component_id, count) in &fleet.build_cost {
+              let name = self.data.components.get(component_id).map_or(component_id.as_str(), |c| c.name(&self.data.localization));
+              ui.show_row(name, format!("{:.0}", count), "#");
+            }
+          });
+        });
+      });
+    self.show_fleet_window = show;
+  }
+}
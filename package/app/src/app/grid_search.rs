@@ -0,0 +1,59 @@
+use egui::{CollapsingHeader, Context, ScrollArea, TextEdit, Window};
+
+use secalc_core::grid::search::search_grids;
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+
+/// Options of the grid search window.
+#[derive(Default)]
+pub struct GridSearchOptions {
+  /// Block ID or name to search for.
+  query: String,
+}
+
+impl App {
+  pub fn show_grid_search_window(&mut self, ctx: &Context) {
+    let mut show = self.show_grid_search_window;
+    Window::new("Search Grids")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Search all saved grids for blocks by ID or name, for example to find which grids use a certain thruster.");
+        ui.separator();
+        let options = &mut self.grid_search;
+        ui.add(TextEdit::singleline(&mut options.query).hint_text("Block ID or name").desired_width(300.0));
+        ui.separator();
+        if self.saved_calculators.is_empty() {
+          ui.label("There are no saved grids. Save grids with 'Grid -> Save As' to search them.");
+          return;
+        }
+        if options.query.trim().is_empty() { return; }
+        let grids = self.saved_calculators.iter().map(|(name, calculator)| (name.as_str(), calculator));
+        let matches = search_grids(grids, &self.data, &options.query);
+        if matches.is_empty() {
+          ui.label("No saved grids contain matching blocks.");
+          return;
+        }
+        ScrollArea::vertical().id_source("Search Grids Matches").max_height(400.0).show(ui, |ui| {
+          for grid_match in &matches {
+            CollapsingHeader::new(format!("{} ({})", grid_match.grid_name, grid_match.count()))
+              .id_source(("Search Grids Match", grid_match.grid_name))
+              .default_open(true)
+              .show(ui, |ui| {
+                ui.grid(("Search Grids Match Grid", grid_match.grid_name), |ui| {
+                  let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+                  for (id, count) in &grid_match.blocks {
+                    let name = self.data.blocks.get_data(id).map_or(id.as_str(), |b| b.name(&self.data.localization));
+                    ui.show_row(name, format!("{}", count), "#");
+                  }
+                });
+              });
+          }
+        });
+      });
+    self.show_grid_search_window = show;
+  }
+}
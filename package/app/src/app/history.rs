@@ -0,0 +1,76 @@
+use secalc_core::grid::GridCalculator;
+
+use crate::App;
+
+/// Changes within this duration of the previous change are undone together, so that dragging a value is one change (s).
+const MERGE_DURATION: f64 = 1.0;
+/// Maximum number of changes that can be undone.
+const MAX_UNDO: usize = 100;
+
+/// Undo and redo history of the calculator.
+#[derive(Default)]
+pub struct History {
+  undo: Vec<GridCalculator>,
+  redo: Vec<GridCalculator>,
+  /// Calculator as of the last recorded change, with its digest, or None if nothing was recorded yet.
+  current: Option<(u64, GridCalculator)>,
+  /// Time of the last recorded change (s)
+  last_change_time: f64,
+}
+
+impl App {
+  /// Records the calculator in the history at `time` (s) if it changed since the last recorded change. Changes from all
+  /// sources are recorded, including loading grids and applying presets.
+  pub fn record_history(&mut self, time: f64) {
+    let digest = self.calculator.digest();
+    let history = &mut self.history;
+    let Some((current_digest, current)) = &mut history.current else {
+      history.current = Some((digest, self.calculator.clone()));
+      return;
+    };
+    if *current_digest == digest { return; }
+
+    if time - history.last_change_time > MERGE_DURATION || history.undo.is_empty() {
+      history.undo.push(std::mem::replace(current, self.calculator.clone()));
+      if history.undo.len() > MAX_UNDO {
+        history.undo.remove(0);
+      }
+    } else {
+      *current = self.calculator.clone();
+    }
+    *current_digest = digest;
+    history.redo.clear();
+    history.last_change_time = time;
+  }
+
+  /// Undoes the last change of the calculator, if any.
+  pub fn undo(&mut self) {
+    let Some(calculator) = self.history.undo.pop() else { return; };
+    if let Some((_, current)) = self.history.current.take() {
+      self.history.redo.push(current);
+    }
+    self.apply_history(calculator);
+  }
+
+  /// Redoes the last undone change of the calculator, if any.
+  pub fn redo(&mut self) {
+    let Some(calculator) = self.history.redo.pop() else { return; };
+    if let Some((_, current)) = self.history.current.take() {
+      self.history.undo.push(current);
+    }
+    self.apply_history(calculator);
+  }
+
+  fn apply_history(&mut self, calculator: GridCalculator) {
+    self.history.current = Some((calculator.digest(), calculator.clone()));
+    // Changes right after undoing or redoing are not merged into the change before.
+    self.history.last_change_time = f64::NEG_INFINITY;
+    self.calculator = calculator;
+    self.current_calculator_saved = false;
+    self.calculate();
+  }
+
+  pub fn can_undo(&self) -> bool { !self.history.undo.is_empty() }
+
+  pub fn can_redo(&self) -> bool { !self.history.redo.is_empty() }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use egui::{Context, Event, Key, KeyboardShortcut, Modifiers, RichText, Ui};
+use serde::{Deserialize, Serialize};
+
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+
+/// Action that can be bound to a keyboard shortcut.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum Action {
+  Save,
+  SaveAs,
+  Load,
+  Reset,
+  Undo,
+  Redo,
+  SearchGrids,
+  Settings,
+  Tour,
+}
+
+impl Action {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use Action::*;
+    const ITEMS: [Action; 9] = [Save, SaveAs, Load, Reset, Undo, Redo, SearchGrids, Settings, Tour];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for Action {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use Action::*;
+    match self {
+      Save => f.write_str("Save"),
+      SaveAs => f.write_str("Save As"),
+      Load => f.write_str("Load"),
+      Reset => f.write_str("Reset"),
+      Undo => f.write_str("Undo"),
+      Redo => f.write_str("Redo"),
+      SearchGrids => f.write_str("Search Grids"),
+      Settings => f.write_str("Settings"),
+      Tour => f.write_str("Tour"),
+    }
+  }
+}
+
+/// Keyboard shortcuts bound to actions. Actions without a shortcut are unbound.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct KeyBindings {
+  bindings: HashMap<Action, KeyboardShortcut>,
+}
+
+impl Default for KeyBindings {
+  fn default() -> Self {
+    use Action::*;
+    let shortcut = KeyboardShortcut::new;
+    let command_shift = Modifiers::COMMAND.plus(Modifiers::SHIFT);
+    let bindings = HashMap::from([
+      (Save, shortcut(Modifiers::COMMAND, Key::S)),
+      (SaveAs, shortcut(command_shift, Key::S)),
+      (Load, shortcut(Modifiers::COMMAND, Key::O)),
+      (Reset, shortcut(command_shift, Key::N)),
+      (Undo, shortcut(Modifiers::COMMAND, Key::Z)),
+      (Redo, shortcut(command_shift, Key::Z)),
+      (SearchGrids, shortcut(Modifiers::COMMAND, Key::F)),
+      (Settings, shortcut(Modifiers::COMMAND, Key::Comma)),
+      (Tour, shortcut(Modifiers::NONE, Key::F1)),
+    ]);
+    Self { bindings }
+  }
+}
+
+impl KeyBindings {
+  /// Gets the shortcut bound to `action`, or None if it is unbound.
+  pub fn get(&self, action: Action) -> Option<KeyboardShortcut> {
+    self.bindings.get(&action).copied()
+  }
+
+  /// Binds `action` to `shortcut`, or unbinds it if `shortcut` is None.
+  pub fn set(&mut self, action: Action, shortcut: Option<KeyboardShortcut>) {
+    match shortcut {
+      Some(shortcut) => self.bindings.insert(action, shortcut),
+      None => self.bindings.remove(&action),
+    };
+  }
+
+  /// Gets the actions other than `action` that are bound to the same shortcut as `action`, in order.
+  pub fn conflicts(&self, action: Action) -> Vec<Action> {
+    let Some(shortcut) = self.get(action) else { return Vec::new(); };
+    Action::items().into_iter()
+      .filter(|a| *a != action && self.get(*a) == Some(shortcut))
+      .collect()
+  }
+
+  /// Consumes the first pressed shortcut in `ctx`, returning its action, or None if no bound shortcut was pressed.
+  /// Shortcuts with more modifiers are checked first, so that for example `Ctrl+Shift+S` does not also match `Ctrl+S`.
+  pub fn consume(&self, ctx: &Context) -> Option<Action> {
+    let mut bindings: Vec<_> = Action::items().into_iter()
+      .filter_map(|action| self.get(action).map(|shortcut| (action, shortcut)))
+      .collect();
+    bindings.sort_by_key(|(_, shortcut)| std::cmp::Reverse(modifier_count(shortcut.modifiers)));
+    ctx.input_mut(|input| bindings.into_iter().find(|(_, shortcut)| input.consume_shortcut(shortcut)).map(|(action, _)| action))
+  }
+}
+
+/// Normalizes pressed `modifiers` to the platform-independent command modifier, so that recorded shortcuts compare equal
+/// to the default shortcuts, and work on all platforms.
+fn normalize(modifiers: Modifiers) -> Modifiers {
+  Modifiers { alt: modifiers.alt, shift: modifiers.shift, command: modifiers.command || modifiers.ctrl || modifiers.mac_cmd, ..Modifiers::NONE }
+}
+
+fn modifier_count(modifiers: Modifiers) -> usize {
+  [modifiers.alt, modifiers.shift, modifiers.ctrl || modifiers.command || modifiers.mac_cmd].into_iter().filter(|m| *m).count()
+}
+
+impl App {
+  /// Performs the action of the first pressed keyboard shortcut, unless the GUI is disabled, a shortcut is being
+  /// recorded, or text is being edited.
+  pub fn handle_key_bindings(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+    if !self.enable_gui || self.recording_key_binding.is_some() || ctx.wants_keyboard_input() { return; }
+    if let Some(action) = self.key_bindings.consume(ctx) {
+      self.perform_action(action, frame);
+    }
+  }
+
+  /// Performs `action`, as if it was chosen from the menu.
+  pub fn perform_action(&mut self, action: Action, frame: &mut eframe::Frame) {
+    match action {
+      Action::Save => {
+        if let Some(name) = self.current_calculator.clone() {
+          self.save_calculator(name);
+          self.current_calculator_saved = true;
+        } else {
+          self.enable_gui = false;
+          self.show_save_as_window = Some(String::new());
+        }
+        if let Some(storage) = frame.storage_mut() {
+          eframe::App::save(self, storage);
+        }
+      }
+      Action::SaveAs => {
+        self.enable_gui = false;
+        self.show_save_as_window = Some(self.current_calculator.clone().unwrap_or_default());
+      }
+      Action::Load => {
+        self.enable_gui = false;
+        if self.current_calculator_saved {
+          self.show_load_window = true;
+        } else {
+          self.show_load_confirm_window = true;
+        }
+      }
+      Action::Reset => {
+        self.enable_gui = false;
+        self.show_reset_confirm_window = true;
+      }
+      Action::Undo => self.undo(),
+      Action::Redo => self.redo(),
+      Action::SearchGrids => self.show_grid_search_window = !self.show_grid_search_window,
+      Action::Settings => self.show_settings_window = !self.show_settings_window,
+      Action::Tour => self.tour.start(),
+    }
+  }
+
+  /// Formats the shortcut bound to `action` for showing next to menu items, or an empty string if it is unbound.
+  pub fn format_key_binding(&self, ctx: &Context, action: Action) -> String {
+    self.key_bindings.get(action).map(|s| ctx.format_shortcut(&s)).unwrap_or_default()
+  }
+
+  /// Shows the keyboard shortcut settings: the shortcut of each action with buttons to record and clear it, and
+  /// conflicts between shortcuts.
+  pub fn show_key_bindings_settings(&mut self, ui: &mut Ui) {
+    if let Some(action) = self.recording_key_binding {
+      let pressed = ui.input(|input| input.events.iter().find_map(|event| match event {
+        Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+        _ => None,
+      }));
+      match pressed {
+        Some((Key::Escape, _)) => self.recording_key_binding = None,
+        Some((key, modifiers)) => {
+          self.key_bindings.set(action, Some(KeyboardShortcut::new(normalize(modifiers), key)));
+          self.recording_key_binding = None;
+        }
+        None => {}
+      }
+    }
+
+    ui.open_collapsing_header_with_grid("Keyboard Shortcuts", |ui| {
+      for action in Action::items() {
+        ui.label(format!("{}", action));
+        let text = if self.recording_key_binding == Some(action) {
+          "Press a key...".to_string()
+        } else {
+          self.key_bindings.get(action).map_or_else(|| "-".to_string(), |s| ui.ctx().format_shortcut(&s))
+        };
+        if ui.button(text).on_hover_text("Click to record a new shortcut, then press the keys. Escape cancels.").clicked() {
+          self.recording_key_binding = Some(action);
+        }
+        if ui.button("Clear").clicked() {
+          self.key_bindings.set(action, None);
+        }
+        let conflicts = self.key_bindings.conflicts(action);
+        if conflicts.is_empty() {
+          ui.label("");
+        } else {
+          let conflicts = conflicts.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ");
+          ui.label(RichText::new(format!("⚠ Conflicts with {}", conflicts)).color(ui.visuals().warn_fg_color));
+        }
+        ui.end_row();
+      }
+      if ui.button("Reset to Defaults").clicked() {
+        self.key_bindings = KeyBindings::default();
+        self.recording_key_binding = None;
+      }
+      ui.end_row();
+    });
+  }
+}
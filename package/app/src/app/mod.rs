@@ -0,0 +1,636 @@
+use std::collections::{HashMap, HashSet};
+
+use egui::{Align, Button, CentralPanel, Color32, Context, Frame, Grid, Layout, menu, Rounding, ScrollArea, Separator, Style, TextEdit, Vec2, Visuals};
+use egui::style::Margin;
+use egui_extras::{Size, StripBuilder};
+use thousands::SeparatorPolicy;
+
+use secalc_core::data::blocks::{BlockId, GridSize};
+use secalc_core::data::custom_blocks::CustomBlock;
+use secalc_core::data::overrides::{OverrideProblem, Overrides};
+use secalc_core::data::Data;
+use secalc_core::data::validate::DataValidation;
+use secalc_core::grid::{GridCalculated, GridCalculator};
+use secalc_core::grid::direction::Direction;
+use secalc_core::grid::duration::DurationFormat;
+use secalc_core::grid::forces::ForceUnit;
+use secalc_core::grid::preset::OptionPreset;
+use secalc_core::grid::server_profile::ServerProfile;
+use secalc_core::grid::summary::StatSummary;
+use secalc_core::grid::sweep::SweepResult;
+use secalc_core::grid::thruster_ranking::ThrusterRankOrder;
+
+use crate::app::ascent::AscentSection;
+use crate::app::backup::RestoreOptions;
+use crate::app::cache::CalculatedCache;
+use crate::app::dock_charging::DockChargingOptions;
+use crate::app::fleet::FleetOptions;
+use crate::app::block_data::BlockDataOptions;
+use crate::app::block_overrides::BlockOverridesOptions;
+use crate::app::grid_search::GridSearchOptions;
+use crate::app::history::History;
+use crate::app::keybindings::{Action, KeyBindings};
+use crate::app::reference::ReferenceComparison;
+use crate::app::scale::ScaleOptions;
+use crate::app::sensitivity::SensitivityOptions;
+use crate::app::server_profile::ServerProfileOptions;
+use crate::app::thruster_mix::ThrusterMixOptions;
+use crate::app::tour::{Tour, TourTarget};
+use crate::app::usage_stats::UsageStats;
+
+mod ascent;
+mod backup;
+mod block_data;
+mod block_overrides;
+mod cache;
+mod calculator;
+mod compact;
+mod compare;
+mod custom_blocks;
+mod dock_charging;
+mod fill_items;
+mod fleet;
+mod grid_search;
+mod history;
+mod keybindings;
+mod preset;
+mod reference;
+mod result;
+mod subgrid;
+mod window;
+mod save_load;
+mod scale;
+mod sensitivity;
+mod server_profile;
+mod thruster_mix;
+mod thruster_ranking;
+mod tour;
+mod usage_stats;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct App {
+  #[serde(skip)] extracted_data: Data,
+  #[serde(skip)] data: Data,
+  #[serde(skip)] number_separator_policy: SeparatorPolicy<'static>,
+  #[serde(skip)] calculator_default: GridCalculator,
+  #[serde(skip)] calculated: GridCalculated,
+  #[serde(skip)] calculated_cache: CalculatedCache,
+  /// Number of bulk updates in progress. Recalculation is deferred until the last bulk update ends.
+  #[serde(skip)] bulk_updates: usize,
+  /// Whether recalculation was requested during a bulk update.
+  #[serde(skip)] calculate_deferred: bool,
+  #[serde(skip)] style_default: Style,
+
+  #[serde(skip)] enable_gui: bool,
+  #[serde(skip)] show_load_window: bool,
+  #[serde(skip)] show_load_confirm_window: bool,
+  #[serde(skip)] show_delete_confirm_window: Option<String>,
+  #[serde(skip)] show_save_as_window: Option<String>,
+  #[serde(skip)] show_save_as_confirm_window: Option<String>,
+  #[serde(skip)] show_reset_confirm_window: bool,
+  #[serde(skip)] show_scale_window: Option<ScaleOptions>,
+  #[serde(skip)] show_thruster_mix_window: Option<ThrusterMixOptions>,
+  #[serde(skip)] show_backup_window: Option<String>,
+  #[serde(skip)] show_restore_window: Option<RestoreOptions>,
+  #[serde(skip)] show_custom_blocks_window: Option<Vec<CustomBlock>>,
+  #[serde(skip)] show_block_overrides_window: bool,
+  #[serde(skip)] block_overrides_options: BlockOverridesOptions,
+  #[serde(skip)] override_problems: Vec<OverrideProblem>,
+
+  #[serde(skip)] show_thruster_ranking_window: bool,
+  #[serde(skip)] thruster_rank_order: ThrusterRankOrder,
+  #[serde(skip)] show_sensitivity_window: bool,
+  #[serde(skip)] sensitivity: SensitivityOptions,
+  #[serde(skip)] show_dock_charging_window: bool,
+  #[serde(skip)] dock_charging: DockChargingOptions,
+  #[serde(skip)] show_fleet_window: bool,
+  #[serde(skip)] fleet: FleetOptions,
+  #[serde(skip)] show_grid_search_window: bool,
+  #[serde(skip)] grid_search: GridSearchOptions,
+  #[serde(skip)] show_server_profile_window: bool,
+  #[serde(skip)] server_profile_options: ServerProfileOptions,
+  #[serde(skip)] show_compact_window: bool,
+  #[serde(skip)] show_usage_stats_window: bool,
+  /// Time of the previous frame in this session (s), or None before the first frame.
+  #[serde(skip)] usage_session_time: Option<f64>,
+  #[serde(skip)] show_settings_window: bool,
+  #[serde(skip)] show_about_window: bool,
+  #[serde(skip)] show_data_problems_window: bool,
+  #[serde(skip)] data_validation: DataValidation,
+  #[serde(skip)] show_debug_gui_settings_window: bool,
+  #[serde(skip)] show_debug_gui_inspection_window: bool,
+  #[serde(skip)] show_debug_gui_memory_window: bool,
+  #[serde(skip)] show_debug_calculation_cache_window: bool,
+  #[serde(skip)] show_block_data_window: bool,
+  #[serde(skip)] block_data: BlockDataOptions,
+  #[serde(skip)] revealed_blocks: HashSet<BlockId>,
+  #[serde(skip)] new_option_preset_name: String,
+  #[serde(skip)] tour: Tour,
+  #[serde(skip)] reference_comparison: ReferenceComparison,
+  #[serde(skip)] ascent: AscentSection,
+  #[serde(skip)] recording_key_binding: Option<Action>,
+  #[serde(skip)] history: History,
+
+  first_time: bool,
+  enabled_mod_ids: HashSet<u64>,
+  dark_mode: bool,
+  font_size_modifier: i32,
+  increase_contrast: bool,
+  duration_format: DurationFormat,
+  force_unit: ForceUnit,
+  custom_blocks: Vec<CustomBlock>,
+  block_overrides: Overrides,
+  only_used_block_sections: HashSet<String>,
+  compact_results: Vec<SweepResult>,
+  option_presets: Vec<OptionPreset>,
+  server_profile: ServerProfile,
+  server_profile_enabled: bool,
+  usage_stats_enabled: bool,
+  usage_stats: UsageStats,
+  key_bindings: KeyBindings,
+
+  calculator: GridCalculator,
+  grid_size: GridSize,
+
+  saved_calculators: HashMap<String, GridCalculator>,
+  /// Stat summaries of saved grids at each save, oldest first.
+  save_history: HashMap<String, Vec<StatSummary>>,
+  current_calculator: Option<String>,
+  current_calculator_saved: bool,
+}
+
+impl App {
+  pub fn new(ctx: &eframe::CreationContext<'_>) -> Self {
+    let mut app = if let Some(storage) = ctx.storage {
+      eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default()
+    } else {
+      Self { dark_mode: ctx.egui_ctx.style().visuals.dark_mode, ..Self::default() }
+    };
+    app.bulk_update(|app| {
+      if ctx.storage.is_some() {
+        app.apply_style(&ctx.egui_ctx);
+        app.rebuild_data();
+      }
+      app.data_validation = app.data.validate();
+      app.show_data_problems_window = !app.data_validation.is_valid();
+      app.calculate();
+    });
+    app
+  }
+
+  /// Gets the current calculator.
+  pub fn calculator(&self) -> &GridCalculator { &self.calculator }
+
+  /// Sets the current calculator to `calculator`, marking it as not saved, and recalculates.
+  pub fn set_calculator(&mut self, calculator: GridCalculator) {
+    self.calculator = calculator;
+    self.current_calculator_saved = false;
+    self.calculate();
+  }
+
+  /// Gets the results of the current calculator.
+  pub fn calculated(&self) -> &GridCalculated { &self.calculated }
+
+  /// Gets the block data, including custom blocks.
+  pub fn data(&self) -> &Data { &self.data }
+
+  /// Gets the grid size of which blocks are shown.
+  pub fn grid_size(&self) -> GridSize { self.grid_size }
+
+  /// Gets the saved calculators by name.
+  pub fn saved_calculators(&self) -> &HashMap<String, GridCalculator> { &self.saved_calculators }
+
+  /// Gets the name the current calculator was loaded from or saved under, or None if it was never saved.
+  pub fn current_calculator(&self) -> Option<&str> { self.current_calculator.as_deref() }
+
+  /// Returns whether the current calculator has no unsaved changes.
+  pub fn is_current_calculator_saved(&self) -> bool { self.current_calculator_saved }
+
+  /// Gets the IDs of mods whose blocks are shown.
+  pub fn enabled_mod_ids(&self) -> &HashSet<u64> { &self.enabled_mod_ids }
+
+  /// Shows the blocks of mod `id` if `enabled`, otherwise hides them.
+  pub fn set_mod_enabled(&mut self, id: u64, enabled: bool) {
+    if enabled {
+      self.enabled_mod_ids.insert(id);
+    } else {
+      self.enabled_mod_ids.remove(&id);
+    }
+  }
+
+  /// Applies several changes with `update`, recalculating once afterwards instead of after each change that
+  /// recalculates. Bulk updates can be nested, in which case recalculation happens after the outermost one.
+  fn bulk_update<R>(&mut self, update: impl FnOnce(&mut Self) -> R) -> R {
+    self.bulk_updates += 1;
+    let result = update(self);
+    self.bulk_updates -= 1;
+    if self.bulk_updates == 0 && self.calculate_deferred {
+      self.calculate_deferred = false;
+      self.calculate();
+    }
+    result
+  }
+
+  /// Recalculates the current calculator, or defers recalculation until the end of the current bulk update.
+  fn calculate(&mut self) {
+    if self.bulk_updates > 0 {
+      self.calculate_deferred = true;
+      return;
+    }
+    self.record_calculation();
+    let digest = self.calculator.digest();
+    self.calculated = self.calculated_cache.get_or_calculate(digest, || self.calculator.calculate(&self.data));
+    if self.server_profile_enabled {
+      self.calculated.warnings.extend(self.server_profile.check(&self.calculator, &self.data));
+    }
+  }
+
+  fn apply_style(&mut self, ctx: &Context) {
+    let mut style = (*ctx.style()).clone(); // Clone entire style, not the Arc.
+    // Text style
+    for (text_style, font_id) in style.text_styles.iter_mut() {
+      if let Some(default_font_id) = self.style_default.text_styles.get(text_style) {
+        font_id.size = default_font_id.size + self.font_size_modifier as f32;
+      }
+    }
+    // Spacing
+    style.spacing.item_spacing = Vec2::new(8.0, 2.0);
+    style.spacing.button_padding = Vec2::new(4.0, 2.0);
+    // Visuals
+    let mut visuals = if self.dark_mode {
+      let mut dark = Visuals::dark();
+      if self.increase_contrast {
+        dark.override_text_color = Some(Color32::from_rgb(210, 210, 210));
+        dark.widgets.noninteractive.bg_fill = Color32::from_rgb(20, 20, 20);
+      }
+      dark
+    } else {
+      let mut light = Visuals::light();
+      if self.increase_contrast {
+        light.override_text_color = Some(Color32::from_rgb(0, 0, 0));
+        light.widgets.noninteractive.bg_fill = Color32::from_rgb(255, 255, 255);
+      }
+      light
+    };
+    visuals.widgets.noninteractive.rounding = Rounding::ZERO;
+    visuals.widgets.inactive.rounding = Rounding::ZERO;
+    visuals.widgets.hovered.rounding = Rounding::ZERO;
+    visuals.widgets.active.rounding = Rounding::ZERO;
+    visuals.widgets.open.rounding = Rounding::ZERO;
+    visuals.window_rounding = Rounding::ZERO;
+    style.visuals = visuals;
+    // Apply style
+    ctx.set_style(style);
+  }
+}
+
+impl Default for App {
+  fn default() -> Self {
+    let data = {
+      let bytes: &[u8] = include_bytes!("../../../../data/data.json");
+      Data::from_json(bytes).expect("Cannot read data")
+    };
+    let number_separator_policy = SeparatorPolicy {
+      separator: "·",
+      groups: &[3],
+      digits: thousands::digits::ASCII_DECIMAL,
+    };
+    Self {
+      extracted_data: data.clone(),
+      data,
+      number_separator_policy,
+      calculator_default: GridCalculator::default(),
+      calculated: GridCalculated::default(),
+      calculated_cache: CalculatedCache::default(),
+      bulk_updates: 0,
+      calculate_deferred: false,
+      style_default: Style::default(),
+
+      enable_gui: true,
+      show_load_window: false,
+      show_load_confirm_window: false,
+      show_delete_confirm_window: None,
+      show_save_as_window: None,
+      show_save_as_confirm_window: None,
+      show_reset_confirm_window: false,
+      show_scale_window: None,
+      show_thruster_mix_window: None,
+      show_backup_window: None,
+      show_restore_window: None,
+      show_custom_blocks_window: None,
+      show_block_overrides_window: false,
+      block_overrides_options: BlockOverridesOptions::default(),
+      override_problems: Vec::new(),
+
+      show_thruster_ranking_window: false,
+      thruster_rank_order: ThrusterRankOrder::default(),
+      show_sensitivity_window: false,
+      sensitivity: SensitivityOptions::default(),
+      show_dock_charging_window: false,
+      dock_charging: DockChargingOptions::default(),
+      show_fleet_window: false,
+      fleet: FleetOptions::default(),
+      show_grid_search_window: false,
+      grid_search: GridSearchOptions::default(),
+      show_server_profile_window: false,
+      server_profile_options: ServerProfileOptions::default(),
+      show_compact_window: false,
+      show_usage_stats_window: false,
+      usage_session_time: None,
+      show_settings_window: false,
+      show_about_window: false,
+      show_data_problems_window: false,
+      data_validation: DataValidation::default(),
+      show_debug_gui_settings_window: false,
+      show_debug_gui_inspection_window: false,
+      show_debug_gui_memory_window: false,
+      show_debug_calculation_cache_window: false,
+      show_block_data_window: false,
+      block_data: BlockDataOptions::default(),
+      revealed_blocks: Default::default(),
+      new_option_preset_name: String::new(),
+      tour: Tour::default(),
+      reference_comparison: ReferenceComparison::default(),
+      ascent: AscentSection::default(),
+      recording_key_binding: None,
+      history: History::default(),
+
+      first_time: true,
+
+      enabled_mod_ids: Default::default(),
+      dark_mode: true,
+      font_size_modifier: 4,
+      increase_contrast: false,
+      duration_format: DurationFormat::default(),
+      force_unit: ForceUnit::default(),
+      custom_blocks: Vec::new(),
+      block_overrides: Overrides::default(),
+      only_used_block_sections: Default::default(),
+      compact_results: vec![SweepResult::UpAcceleration, SweepResult::PowerBalance, SweepResult::HydrogenBalance],
+      option_presets: Vec::new(),
+      server_profile: ServerProfile::default(),
+      server_profile_enabled: false,
+      usage_stats_enabled: false,
+      usage_stats: UsageStats::default(),
+      key_bindings: KeyBindings::default(),
+
+      calculator: GridCalculator::default(),
+      grid_size: GridSize::default(),
+
+      saved_calculators: Default::default(),
+      save_history: Default::default(),
+      current_calculator: None,
+      current_calculator_saved: false,
+    }
+  }
+}
+
+impl eframe::App for App {
+  fn update(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+    self.record_session_time(ctx);
+    self.handle_key_bindings(ctx, frame);
+    let central_frame = Frame::none().fill(ctx.style().visuals.window_fill()).inner_margin(Margin::same(4.0));
+    CentralPanel::default().frame(central_frame).show(ctx, |ui| {
+      ui.add_enabled_ui(self.enable_gui, |ui| {
+        StripBuilder::new(ui)
+          .size(Size::exact(20.0 + (self.font_size_modifier.max(0) as f32 / 2.0)))
+          .size(Size::exact(1.0))
+          .size(Size::remainder())
+          .vertical(|mut strip| {
+            // Top panel with menu
+            strip.cell(|ui| {
+              ui.add_enabled_ui(self.enable_gui, |ui| {
+                menu::bar(ui, |ui| {
+                  let grid_menu = ui.menu_button("Grid", |ui| {
+                    for action in [Action::Save, Action::SaveAs, Action::Load] {
+                      if ui.add(Button::new(action.to_string()).shortcut_text(self.format_key_binding(ctx, action))).clicked() {
+                        self.perform_action(action, frame);
+                        ui.close_menu();
+                      }
+                    }
+                    ui.separator();
+                    if ui.add_enabled(self.can_undo(), Button::new("Undo").shortcut_text(self.format_key_binding(ctx, Action::Undo))).clicked() {
+                      self.undo();
+                      ui.close_menu();
+                    }
+                    if ui.add_enabled(self.can_redo(), Button::new("Redo").shortcut_text(self.format_key_binding(ctx, Action::Redo))).clicked() {
+                      self.redo();
+                      ui.close_menu();
+                    }
+                    ui.separator();
+                    let hidden_block_count = self.calculator.excluded_blocks.len();
+                    if ui.add_enabled(hidden_block_count != 0, Button::new(format!("Show Hidden Blocks ({})", hidden_block_count))).clicked() {
+                      self.calculator.excluded_blocks.clear();
+                      self.current_calculator_saved = false;
+                      ui.close_menu();
+                    }
+                    ui.menu_button("Direction Labels", |ui| {
+                      Grid::new("Direction Labels Grid").show(ui, |ui| {
+                        for direction in Direction::items() {
+                          ui.label(direction.name());
+                          let edit = TextEdit::singleline(self.calculator.direction_labels.custom_mut(direction))
+                            .hint_text(direction.name())
+                            .desired_width(100.0);
+                          if ui.add(edit).changed() {
+                            self.current_calculator_saved = false;
+                          }
+                          ui.end_row();
+                        }
+                      }).response.on_hover_text("Custom labels of directions in this grid, for example 'Forward' instead of 'Front'. Leave empty to use the default label.");
+                    });
+                    if ui.button("Scale").clicked() {
+                      self.enable_gui = false;
+                      self.show_scale_window = Some(ScaleOptions::default());
+                      ui.close_menu();
+                    }
+                    if ui.button("Thruster Mix Wizard (Experimental)").clicked() {
+                      self.enable_gui = false;
+                      self.show_thruster_mix_window = Some(ThrusterMixOptions::default());
+                      ui.close_menu();
+                    }
+                    if ui.button("Custom Blocks").clicked() {
+                      self.enable_gui = false;
+                      self.show_custom_blocks_window = Some(self.custom_blocks.clone());
+                      ui.close_menu();
+                    }
+                    if ui.button("Block Overrides").clicked() {
+                      self.block_overrides_options = BlockOverridesOptions::new(&self.block_overrides);
+                      self.show_block_overrides_window = true;
+                      ui.close_menu();
+                    }
+                    if ui.button("Copy Block List").on_hover_text("Copy a plain text list of the blocks of this grid with their counts and directions, for pasting into a post or as a shopping list.").clicked() {
+                      let text = secalc_core::interop::block_list::to_text(&self.calculator, &self.data);
+                      ui.output_mut(|o| o.copied_text = text);
+                      ui.close_menu();
+                    }
+                    if ui.button("Copy Block Counts XML").clicked() {
+                      let xml = secalc_core::interop::block_counts::to_xml(&self.calculator.block_counts());
+                      ui.output_mut(|o| o.copied_text = xml);
+                      ui.close_menu();
+                    }
+                    if ui.button("Copy Resource Chain DOT").on_hover_text("Copy the power and hydrogen chains of this grid as a Graphviz DOT graph, for generating diagrams.").clicked() {
+                      let dot = secalc_core::interop::resource_chain::to_dot(&self.calculator, &self.calculated);
+                      ui.output_mut(|o| o.copied_text = dot);
+                      ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Backup All").clicked() {
+                      self.enable_gui = false;
+                      self.show_backup_window = Some(self.backup_to_json());
+                      ui.close_menu();
+                    }
+                    if ui.button("Restore Backup").clicked() {
+                      self.enable_gui = false;
+                      self.show_restore_window = Some(RestoreOptions::new());
+                      ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.add(Button::new("Reset").shortcut_text(self.format_key_binding(ctx, Action::Reset))).clicked() {
+                      self.perform_action(Action::Reset, frame);
+                      ui.close_menu();
+                    }
+                  });
+                  self.tour.set_target_rect(TourTarget::GridMenu, grid_menu.response.rect);
+                  ui.menu_button("Preset", |ui| {
+                    self.show_preset_menu(ui);
+                  });
+                  ui.menu_button("Window", |ui| {
+                    if ui.checkbox(&mut self.show_thruster_ranking_window, "Choose a Thruster").clicked() {
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_sensitivity_window, "Sensitivity").clicked() {
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_dock_charging_window, "Dock Charging").clicked() {
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_fleet_window, "Fleet").clicked() {
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_grid_search_window, "Search Grids").clicked() {
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_server_profile_window, "Server Profile").clicked() {
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_compact_window, "Compact Mode").clicked() {
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_usage_stats_window, "Usage Statistics").clicked() {
+                      ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.checkbox(&mut self.show_settings_window, "Settings").clicked() {
+                      ui.close_menu();
+                    }
+                    ui.separator();
+                    ui.menu_button("Debug", |ui| {
+                      if ui.checkbox(&mut self.show_debug_gui_settings_window, "GUI Settings").clicked() {
+                        ui.close_menu();
+                      }
+                      if ui.checkbox(&mut self.show_debug_gui_inspection_window, "GUI Inspections").clicked() {
+                        ui.close_menu();
+                      }
+                      if ui.checkbox(&mut self.show_debug_gui_memory_window, "GUI Memory").clicked() {
+                        ui.close_menu();
+                      }
+                      if ui.checkbox(&mut self.show_debug_calculation_cache_window, "Calculation Cache").clicked() {
+                        ui.close_menu();
+                      }
+                      if ui.checkbox(&mut self.show_block_data_window, "Block Data").clicked() {
+                        ui.close_menu();
+                      }
+                    });
+                  });
+                  ui.menu_button("Help", |ui| {
+                    if ui.button("Tour").clicked() {
+                      self.tour.start();
+                      ui.close_menu();
+                    }
+                    if ui.checkbox(&mut self.show_about_window, "About").clicked() {
+                      ui.close_menu();
+                    }
+                  });
+                  ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if self.dark_mode {
+                      if ui.add(Button::new("☀")).clicked() {
+                        self.dark_mode = false;
+                        self.apply_style(ctx);
+                      }
+                    } else {
+                      if ui.add(Button::new("🌙")).clicked() {
+                        self.dark_mode = true;
+                        self.apply_style(ctx);
+                      }
+                    }
+                  });
+                });
+              });
+            });
+            // Horizontal line
+            strip.cell(|ui| { ui.add(Separator::default().spacing(0.0).horizontal()); });
+            // Main content panel
+            strip.strip(|strip_builder| {
+              let layout = Layout::top_down(Align::LEFT);
+              strip_builder
+                .cell_layout(layout)
+                .size(Size::remainder())
+                .size(Size::exact(1.0))
+                .size(Size::remainder())
+                .horizontal(|mut strip| {
+                  // Calculator
+                  strip.cell(|ui| {
+                    ScrollArea::both()
+                      .id_source("Calculator Scroll")
+                      .auto_shrink([false; 2])
+                      .show(ui, |ui| {
+                        if self.show_calculator(ui) {
+                          self.calculate();
+                          self.current_calculator_saved = false;
+                        }
+                      });
+                  });
+                  // Vertical line
+                  strip.cell(|ui| { ui.add(Separator::default().spacing(0.0).vertical()); });
+                  // Result (calculated)
+                  strip.cell(|ui| {
+                    self.tour.set_target_rect(TourTarget::Results, ui.max_rect());
+                    ScrollArea::both()
+                      .id_source("Result Scroll")
+                      .auto_shrink([false; 2])
+                      .show(ui, |ui| {
+                        self.show_results(ui, ctx);
+                      });
+                  });
+                });
+            });
+          });
+      });
+    });
+    // Windows
+    self.show_save_load_reset_windows(ctx, frame);
+    self.show_settings_windows(ctx, frame);
+    self.show_scale_window(ctx);
+    self.show_thruster_mix_window(ctx);
+    self.show_custom_blocks_window(ctx, frame);
+    self.show_backup_windows(ctx, frame);
+    self.show_compact_window(ctx);
+    self.show_tour(ctx);
+    self.record_history(ctx.input(|i| i.time));
+  }
+
+  fn save(&mut self, storage: &mut dyn eframe::Storage) {
+    eframe::set_value(storage, eframe::APP_KEY, self);
+  }
+
+  fn clear_color(&self, visuals: &Visuals) -> [f32; 4] {
+    // The clear color applies to all viewports. Clear to transparent while the compact mode window is shown, so that
+    // its background is transparent. The main window is not affected, as its central panel is filled.
+    if self.show_compact_window {
+      [0.0; 4]
+    } else {
+      visuals.window_fill().to_normalized_gamma_f32()
+    }
+  }
+}
@@ -0,0 +1,49 @@
+use egui::{Button, TextEdit, Ui};
+
+use secalc_core::grid::preset::OptionPreset;
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+
+impl App {
+  /// Shows the contents of the preset menu, for applying built-in and custom presets, and saving the current options
+  /// as a custom preset.
+  pub fn show_preset_menu(&mut self, ui: &mut Ui) {
+    let mut applied = None;
+    for preset in OptionPreset::built_in().iter().chain(self.option_presets.iter()) {
+      if ui.selectable_label(preset.is_applied_to(&self.calculator), &preset.name).clicked() {
+        applied = Some(preset.clone());
+      }
+    }
+    if let Some(preset) = applied {
+      preset.apply(&mut self.calculator);
+      self.current_calculator_saved = false;
+      self.calculate();
+      ui.close_menu();
+    }
+
+    if !self.option_presets.is_empty() {
+      ui.separator();
+      let mut remove = None;
+      for (index, preset) in self.option_presets.iter().enumerate() {
+        if ui.danger_button(format!("Remove '{}'", preset.name)).clicked() {
+          remove = Some(index);
+        }
+      }
+      if let Some(index) = remove {
+        self.option_presets.remove(index);
+      }
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+      ui.add(TextEdit::singleline(&mut self.new_option_preset_name).hint_text("Preset name").desired_width(120.0));
+      let name = self.new_option_preset_name.trim();
+      let valid = !name.is_empty() && !OptionPreset::built_in().iter().chain(self.option_presets.iter()).any(|p| p.name == name);
+      if ui.add_enabled(valid, Button::new("Save Current")).on_hover_text("Save the battery, hydrogen tank, hydrogen engine, railgun, and jump drive options of the current grid as a preset.").clicked() {
+        self.option_presets.push(OptionPreset::from_calculator(name, &self.calculator));
+        self.new_option_preset_name.clear();
+      }
+    });
+  }
+}
@@ -0,0 +1,50 @@
+use egui::{ComboBox, Grid, Ui};
+
+use secalc_core::grid::GridCalculated;
+use secalc_core::grid::reference::REFERENCE_GRIDS;
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+use crate::app::compare::{show_calculated_diff, show_key_stats_difference};
+
+/// Reference grid that the results of the current grid are compared against.
+#[derive(Default)]
+pub struct ReferenceComparison {
+  /// Index into [`REFERENCE_GRIDS`] of the reference grid, or None if not comparing.
+  index: Option<usize>,
+  /// Result of the reference grid.
+  calculated: GridCalculated,
+}
+
+impl App {
+  /// Shows a dropdown for choosing a reference grid, and key statistics of the current grid compared against the chosen
+  /// reference grid.
+  pub fn show_reference_comparison(&mut self, ui: &mut Ui) {
+    let comparison = &mut self.reference_comparison;
+    let mut index = comparison.index;
+    ui.horizontal(|ui| {
+      ui.label("Compare against reference");
+      ComboBox::from_id_source("Reference Grid")
+        .selected_text(index.map_or("None", |i| REFERENCE_GRIDS[i].name))
+        .show_ui(ui, |ui| {
+          ui.selectable_value(&mut index, None, "None");
+          for (i, reference) in REFERENCE_GRIDS.iter().enumerate() {
+            ui.selectable_value(&mut index, Some(i), reference.name);
+          }
+        }).response.on_hover_text("Compare the results of this grid against a ship approximating a vanilla ship, to judge whether its numbers are reasonable.");
+    });
+    if index != comparison.index {
+      comparison.index = index;
+      if let Some(index) = index {
+        comparison.calculated = REFERENCE_GRIDS[index].calculator().calculate(&self.data);
+      }
+    }
+    let Some(index) = comparison.index else { return };
+    ui.open_collapsing_header("Reference Comparison", |ui| {
+      Grid::new("Reference Comparison Grid").striped(true).min_col_width(1.0).show(ui, |ui| {
+        show_key_stats_difference(ui, self.number_separator_policy, REFERENCE_GRIDS[index].name, &comparison.calculated, &self.calculated);
+      });
+      show_calculated_diff(ui, self.number_separator_policy, || comparison.calculated.diff(&self.calculated));
+    });
+  }
+}
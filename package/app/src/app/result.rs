@@ -0,0 +1,634 @@
+use egui::{ComboBox, Context, RichText, Ui, Vec2};
+
+use secalc_core::data::validate::GridProblem;
+use secalc_core::grid::PowerChainGroup;
+use secalc_core::grid::direction::Direction;
+use secalc_core::grid::duration::{Duration, DurationFormat};
+use secalc_core::grid::forces::ForceUnit;
+use secalc_core::grid::gyroscope::RotationAxis;
+use secalc_core::grid::units::Power;
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+
+impl App {
+  pub fn show_results(&mut self, ui: &mut Ui, ctx: &Context) {
+    self.show_reference_comparison(ui);
+    for warning in &self.calculated.warnings {
+      ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", warning));
+    }
+    for problem in self.calculator.validate(&self.data).problems {
+      if let GridProblem::HiddenBlock { ignored: true, .. } = problem { continue; } // Already shown as a warning.
+      ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}.", problem));
+    }
+    self.show_grid_breakdown(ui);
+    ui.horizontal(|ui| {
+      ui.open_collapsing_header_with_grid("Volume", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.show_row("Any", format!("{}", self.calculated.total_volume_any.to_liters().round()), "L");
+        ui.show_row("Ore", format!("{}", self.calculated.total_volume_ore.to_liters().round()), "L");
+        ui.show_row("Ice", format!("{}", self.calculated.total_volume_ice.to_liters().round()), "L");
+        ui.show_row("Ore-only", format!("{}", self.calculated.total_volume_ore_only.to_liters().round()), "L");
+        ui.show_row("Ice-only", format!("{}", self.calculated.total_volume_ice_only.to_liters().round()), "L");
+        ui.show_row("Components-only", format!("{}", self.calculated.total_volume_components_only.to_liters().round()), "L");
+      });
+      ui.vertical(|ui| {
+        ui.open_collapsing_header_with_grid("Mass", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          ui.show_row("Empty", format!("{}", self.calculated.total_mass_empty.to_kilograms().round()), "kg");
+          ui.show_row("Filled", format!("{}", self.calculated.total_mass_filled.to_kilograms().round()), "kg");
+          ui.show_row("Crew", format!("{}", self.calculated.crew_mass.to_kilograms().round()), "kg");
+        });
+        ui.open_collapsing_header_with_grid("Items", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          ui.label(RichText::new("Inventory Multiplier").underline())
+            .on_hover_text_at_pointer("Container multiplier from the options, which multiplies the volume of inventories. Item counts are based on the multiplied volumes.");
+          ui.right_align_value_with_unit(format!("{:.2}", self.calculator.container_multiplier), "x");
+          ui.label(RichText::new("Volume per Item").underline())
+            .on_hover_text_at_pointer("Volume of one item relative to inventory volumes without the inventory multiplier: the volume of the item divided by the inventory multiplier.");
+          ui.end_row();
+          for item in &self.calculated.items {
+            ui.label(&item.name);
+            ui.right_align_value_with_unit(format!("{}", item.count.round()), "#");
+            ui.right_align_value_with_unit(format!("{:.2}", item.effective_volume.to_liters()), "L")
+              .on_hover_text_at_pointer(format!("{:.2} L per item at 1x inventory multiplier", item.volume.to_liters()));
+            ui.end_row();
+          }
+          if self.calculator.round_items_to_stacks {
+            ui.show_optional_row("Steel Plate Stacks", self.calculated.total_stacks_steel_plate.map(|s| format!("{}", s)), "#");
+            ui.show_optional_row("H2 Bottle Stacks", self.calculated.total_stacks_hydrogen_bottle.map(|s| format!("{}", s)), "#");
+            ui.show_optional_row("O2 Bottle Stacks", self.calculated.total_stacks_oxygen_bottle.map(|s| format!("{}", s)), "#");
+          }
+          ui.show_row("O2 in Bottles", format!("{}", self.calculated.oxygen_bottle_capacity.to_liters().round()), "L");
+        });
+        ui.open_collapsing_header_with_grid("Efficiency", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          let efficiency = self.calculated.efficiency();
+          ui.label(RichText::new("Power/Mass").underline())
+            .on_hover_text_at_pointer("Power generation per tonne of empty mass.");
+          ui.right_align_optional_value_with_unit(efficiency.power_generation_per_mass.map(|p| format!("{:.3}", p)), "MW/t");
+          ui.end_row();
+          ui.label(RichText::new("Battery/Mass").underline())
+            .on_hover_text_at_pointer("Battery capacity per tonne of empty mass.");
+          ui.right_align_optional_value_with_unit(efficiency.battery_capacity_per_mass.map(|c| format!("{:.3}", c)), "MWh/t");
+          ui.end_row();
+          ui.label(RichText::new("Hydrogen/Mass").underline())
+            .on_hover_text_at_pointer("Hydrogen tank capacity per tonne of empty mass.");
+          ui.right_align_optional_value_with_unit(efficiency.hydrogen_capacity_per_mass.map(|c| format!("{}", c.round())), "L/t");
+          ui.end_row();
+          ui.label(RichText::new("Thrust/Power").underline())
+            .on_hover_text_at_pointer("Thrust of all directions per MW of thruster power consumption. Not shown for grids with hydrogen thrusters, as their thrust does not consume power.");
+          ui.right_align_optional_value_with_unit(efficiency.thrust_per_power.map(|t| format!("{:.2}", t / 1000.0)), "kN/MW");
+          ui.end_row();
+        });
+      });
+      ui.vertical(|ui| {
+        ui.open_collapsing_header_with_grid("Forces", |ui| {
+          ui.label("Unit");
+          ComboBox::from_id_source("Force Unit")
+            .selected_text(format!("{}", self.force_unit))
+            .show_ui(ui, |ui| {
+              for unit in ForceUnit::items() {
+                ui.selectable_value(&mut self.force_unit, unit, format!("{}", unit));
+              }
+            });
+          ui.end_row();
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          for (source, force) in self.calculated.forces.iter() {
+            ui.show_row(source.label(&self.calculator.direction_labels), format!("{:.2}", self.force_unit.convert(force)), self.force_unit.symbol());
+          }
+        });
+        ui.open_collapsing_header_with_grid("Pitched Hover", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          ui.pitched_hover_rows("Filled", &self.calculated.pitched_hover_filled, &self.calculator.direction_labels);
+          ui.pitched_hover_rows("Empty", &self.calculated.pitched_hover_empty, &self.calculator.direction_labels);
+        });
+        ui.open_collapsing_header_with_grid("Descent", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          let descent = self.calculated.descent.as_ref();
+          ui.show_optional_row("Braking Force", descent.map(|d| format!("{:.2}", self.force_unit.convert(d.braking_force))), self.force_unit.symbol());
+          ui.show_optional_row("Deceleration", descent.and_then(|d| d.deceleration).map(|d| format!("{:.2}", d)), "m/s²");
+          ui.show_optional_row("Max Safe Speed", descent.and_then(|d| d.max_safe_speed).map(|s| format!("{:.2}", s)), "m/s");
+        });
+        ui.open_collapsing_header_with_grid("Top Contributors", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          let name = |id| self.data.blocks.get_data(id).map(|d| d.name(&self.data.localization)).unwrap_or(id);
+          ui.show_optional_row("Heaviest Block:", self.calculated.heaviest_block.as_ref().map(|b| format!("{}", b.value.round())), "kg");
+          if let Some(block) = &self.calculated.heaviest_block {
+            ui.label(format!("  {}", name(&block.id)));
+            ui.end_row();
+          }
+          ui.label(RichText::new("Mass").strong());
+          ui.end_row();
+          for contributor in &self.calculated.top_mass_contributors {
+            let label = match contributor.direction {
+              Some(direction) => format!("  {} ({})", name(&contributor.id), self.calculator.direction_labels.get(direction)),
+              None => format!("  {}", name(&contributor.id)),
+            };
+            ui.show_row(label, format!("{}", contributor.value.round()), "kg");
+          }
+          ui.label(RichText::new("Power").strong());
+          ui.end_row();
+          for contributor in &self.calculated.top_power_consumption_contributors {
+            ui.show_row(format!("  {}", name(&contributor.id)), format!("{:.2}", contributor.value), "MW");
+          }
+        });
+      });
+    });
+    ui.horizontal(|ui| {
+      ui.open_collapsing_header_with_grid("Thruster Acceleration & Force", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label("Direction");
+        ui.vertical_separator_unpadded();
+        ui.label("Filled");
+        ui.label("");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Empty");
+        ui.label("");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Force");
+        ui.end_row();
+
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Gravity");
+        ui.vertical_separator_unpadded();
+        ui.label("No grav.");
+        ui.vertical_separator_unpadded();
+        ui.label("Gravity");
+        ui.vertical_separator_unpadded();
+        ui.label("No grav.");
+        ui.vertical_separator_unpadded();
+        ui.label("");
+        ui.end_row();
+
+        for direction in Direction::items() {
+          ui.acceleration_row(direction, &self.calculator.direction_labels, &self.calculated.thruster_acceleration, |filled, gravity| self.calculated.explain_acceleration(&self.calculator, direction, filled, gravity), self.force_unit, ctx);
+        }
+      });
+      ui.open_collapsing_header_with_grid("Thruster Mass", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label("Direction");
+        ui.right_align_label("Mass");
+        ui.label(RichText::new("Share").underline())
+          .on_hover_text_at_pointer("Share of the empty mass of the grid. Thrusters in one direction with a large share, such as heavy braking thrusters, shift the center of mass towards them.");
+        ui.end_row();
+        let total_mass = self.calculated.total_mass_empty.to_kilograms();
+        for direction in Direction::items() {
+          let mass = self.calculated.thruster_mass[direction].to_kilograms();
+          ui.label(self.calculator.direction_labels.get(direction));
+          ui.right_align_value_with_unit(format!("{}", mass.round()), "kg");
+          ui.right_align_optional_value_with_unit((total_mass > 0.0).then(|| format!("{:.1}", mass / total_mass * 100.0)), "%");
+          ui.end_row();
+        }
+      });
+    });
+    ui.horizontal(|ui| {
+      ui.open_collapsing_header_with_grid("Thruster Braking", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label("Direction");
+        ui.vertical_separator_unpadded();
+        ui.label("Filled");
+        for _ in 0..4 { ui.label(""); }
+        ui.vertical_separator_unpadded();
+        ui.label("Empty");
+        for _ in 0..4 { ui.label(""); }
+        ui.end_row();
+
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Gravity");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("No grav.");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Gravity");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("No grav.");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.end_row();
+
+        for direction in Direction::items() {
+          ui.braking_row(direction, &self.calculator.direction_labels, &self.calculated.thruster_acceleration);
+        }
+      });
+    });
+    ui.open_collapsing_header("Power", |ui| {
+      ui.grid_unstriped("Power Grid 1", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.show_row("Generation:", format!("{:.2}", self.calculated.power_generation), "MW");
+        ui.horizontal_separator_unpadded();
+        ui.horizontal_separator_unpadded();
+        ui.end_row();
+      });
+      ui.allocate_space(Vec2::new(0.0, 1.0));
+      ui.grid("Power Grid 2", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label("Group Name");
+        ui.vertical_separator_unpadded();
+        ui.label("Consumption");
+        ui.label("");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Balance");
+        ui.label("");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Duration");
+        ui.label("");
+        ui.end_row();
+
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Group");
+        ui.vertical_separator_unpadded();
+        ui.label("Total");
+        ui.vertical_separator_unpadded();
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label(RichText::new("Budget").underline())
+          .on_hover_text_at_pointer("Power balance in the row, minus the safety margin of the total consumption. The first row with a negative budget is marked as over budget.");
+        ui.vertical_separator_unpadded();
+        ui.label(RichText::new("Batteries").underline())
+          .on_hover_text_at_pointer("Duration until batteries are empty at the total consumption in the row. Does not take into account charging the batteries via any means.");
+        ui.vertical_separator_unpadded();
+        ui.label(RichText::new("Engines").underline())
+          .on_hover_text_at_pointer("Duration until hydrogen engines are empty at the total consumption in the row. Does not take into account filling the engines via generators or tanks.");
+        ui.end_row();
+
+        let power_formatter = |v| format!("{:.2}", v);
+        let labels = &self.calculator.direction_labels;
+        let unpowered_color = ui.visuals().error_fg_color;
+        let over_budget_color = ui.visuals().warn_fg_color;
+        let power_label = |label: &str, group: PowerChainGroup| {
+          if self.calculated.first_unpowered_group == Some(group) {
+            RichText::new(format!("{} ⚠", label)).color(unpowered_color)
+          } else if self.calculated.first_over_budget_group == Some(group) {
+            RichText::new(format!("{} ⚠", label)).color(over_budget_color)
+          } else {
+            RichText::new(label)
+          }
+        };
+        ui.power_row("Idle", power_formatter, &self.calculated.power_idle, self.calculated.explain_power(&self.calculator, &self.calculated.power_idle));
+        ui.power_row(power_label("Charge Railguns", PowerChainGroup::RailgunCharge), power_formatter, &self.calculated.power_railgun_charge, self.calculated.explain_power(&self.calculator, &self.calculated.power_railgun_charge));
+        ui.power_row(power_label("+ Utility", PowerChainGroup::Utility), power_formatter, &self.calculated.power_upto_utility, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_utility));
+        ui.power_row(power_label("+ Wheel Suspensions", PowerChainGroup::WheelSuspension), power_formatter, &self.calculated.power_upto_wheel_suspension, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_wheel_suspension));
+        ui.power_row(power_label("+ Charge Jump Drives", PowerChainGroup::JumpDriveCharge), power_formatter, &self.calculated.power_upto_jump_drive_charge, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_jump_drive_charge));
+        ui.power_row(power_label("+ O2/H2 Generators", PowerChainGroup::Generator), power_formatter, &self.calculated.power_upto_generator, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_generator));
+        ui.power_row(power_label(&format!("+ {} Thrusters", labels.pair(Direction::Up, Direction::Down)), PowerChainGroup::UpDownThruster), power_formatter, &self.calculated.power_upto_up_down_thruster, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_up_down_thruster));
+        ui.power_row(power_label(&format!("+ {} Thrusters", labels.pair(Direction::Front, Direction::Back)), PowerChainGroup::FrontBackThruster), power_formatter, &self.calculated.power_upto_front_back_thruster, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_front_back_thruster));
+        ui.power_row(power_label(&format!("+ {} Thrusters", labels.pair(Direction::Left, Direction::Right)), PowerChainGroup::LeftRightThruster), power_formatter, &self.calculated.power_upto_left_right_thruster, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_left_right_thruster));
+        ui.power_row(power_label("+ Charge Batteries", PowerChainGroup::BatteryCharge), power_formatter, &self.calculated.power_upto_battery_charge, self.calculated.explain_power(&self.calculator, &self.calculated.power_upto_battery_charge));
+        if let Some(group) = self.calculated.power_worst_thruster_group {
+          ui.power_row(format!("Worst: {}", group.label(labels)), power_formatter, &self.calculated.power_worst_thruster, self.calculated.explain_power(&self.calculator, &self.calculated.power_worst_thruster));
+        }
+      });
+      if let Some(group) = self.calculated.first_unpowered_group {
+        ui.colored_label(ui.visuals().error_fg_color, format!("Power consumption exceeds generation starting at '{}': these and all lower priority consumers, such as thrusters, will cut out.", group));
+      } else if let Some(group) = self.calculated.first_over_budget_group {
+        ui.colored_label(ui.visuals().warn_fg_color, format!("Power consumption plus the {:.0}% safety margin exceeds generation starting at '{}'.", self.calculator.safety_margin, group));
+      }
+      ui.open_collapsing_header_with_grid("Power Consumption Shares", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        for (group, fraction) in self.calculated.power_shares.iter().filter(|(_, f)| *f > 0.0) {
+          ui.share_row(group.label(&self.calculator.direction_labels), *fraction);
+        }
+      });
+      ui.open_collapsing_header_with_grid("Thruster Power per Type", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label("");
+        ui.label("Ion");
+        ui.label("Atmospheric");
+        ui.end_row();
+        let ion = &self.calculated.ion_thruster_consumption;
+        let atmospheric = &self.calculated.atmospheric_thruster_consumption;
+        for direction in Direction::items() {
+          ui.label(format!("{}:", self.calculator.direction_labels.get(direction)));
+          ui.right_align_value_with_unit(format!("{:.2}", ion[direction].to_megawatts()), "MW");
+          ui.right_align_value_with_unit(format!("{:.2}", atmospheric[direction].to_megawatts()), "MW");
+          ui.end_row();
+        }
+        ui.label(RichText::new("Total:").underline())
+          .on_hover_text_at_pointer("Maximum power consumption of ion and atmospheric thrusters in all directions, at the current planetary influence. Ion thrusters in atmosphere and atmospheric thrusters in space consume less power, as they are less effective.");
+        ui.right_align_value_with_unit(format!("{:.2}", ion.iter().copied().sum::<Power>().to_megawatts()), "MW");
+        ui.right_align_value_with_unit(format!("{:.2}", atmospheric.iter().copied().sum::<Power>().to_megawatts()), "MW");
+        ui.end_row();
+      });
+    });
+    ui.horizontal(|ui| {
+      ui.open_collapsing_header_with_grid("Railgun", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let railgun = self.calculated.railgun.as_ref();
+        ui.show_optional_row("Capacity:", railgun.map(|r| format!("{:.2}", r.capacity.to_megawatt_hours())), "MWh");
+        ui.show_optional_row("Maximum Input:", railgun.map(|r| format!("{:.2}", r.maximum_input.to_megawatts())), "MW");
+        ui.show_optional_duration_row("Charge Duration:", railgun.and_then(|r| r.charge_duration));
+        ui.label(RichText::new("Wasted Surplus:").underline())
+          .on_hover_text_at_pointer("Surplus power left after charging the railguns at their maximum input, which they cannot absorb. Consumers later in the power chain may still use it. Only shown when railguns are charging.");
+        ui.right_align_optional_value_with_unit(railgun.and_then(|r| r.wasted_surplus).map(|w| format!("{:.2}", w.to_megawatts())), "MW");
+        ui.end_row();
+      });
+      ui.open_collapsing_header_with_grid("Jump Drive", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let jump_drive = self.calculated.jump_drive.as_ref();
+        ui.show_optional_row("Capacity:", jump_drive.map(|j| format!("{:.2}", j.capacity.to_megawatt_hours())), "MWh");
+        ui.show_optional_duration_row("Charge Duration:", jump_drive.and_then(|j| j.charge_duration));
+        ui.show_optional_row("Maximum Input:", jump_drive.map(|j| format!("{:.2}", j.maximum_input.to_megawatts())), "MW");
+        ui.label(RichText::new("Wasted Surplus:").underline())
+          .on_hover_text_at_pointer("Surplus power left after charging the jump drives at their maximum input, which they cannot absorb. Consumers later in the power chain may still use it. Only shown when jump drives are charging.");
+        ui.right_align_optional_value_with_unit(jump_drive.and_then(|j| j.wasted_surplus).map(|w| format!("{:.2}", w.to_megawatts())), "MW");
+        ui.end_row();
+        ui.label(RichText::new("Min Battery Fill:").underline())
+          .on_hover_text_at_pointer("Minimum battery fill needed to fully charge the jump drives without the batteries running empty, given the current power generation and consumption. Values above 100% mean the batteries cannot provide enough power.");
+        ui.right_align_optional_value_with_unit(jump_drive.and_then(|j| j.minimum_battery_fill).map(|f| format!("{:.1}", f)), "%");
+        ui.end_row();
+        ui.show_optional_row("Max Range (Empty):", jump_drive.and_then(|j| j.max_distance_empty).map(|d| format!("{:.2}", d)), "km");
+        ui.show_optional_row("Max Range (Filled):", jump_drive.and_then(|j| j.max_distance_filled).map(|d| format!("{:.2}", d)), "km");
+      });
+      ui.open_collapsing_header_with_grid("Battery", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let battery = self.calculated.battery.as_ref();
+        ui.show_optional_row("Capacity:", battery.map(|b| format!("{:.2}", b.capacity.to_megawatt_hours())), "MWh");
+        ui.show_optional_row("Maximum Input:", battery.map(|b| format!("{:.2}", b.maximum_input.to_megawatts())), "MW");
+        ui.show_optional_row("Maximum Output:", battery.map(|b| format!("{:.2}", b.maximum_output.to_megawatts())), "MW");
+        ui.show_optional_duration_row("Charge Duration:", battery.and_then(|b| b.charge_duration));
+        ui.label(RichText::new("Wasted Surplus:").underline())
+          .on_hover_text_at_pointer("Surplus power that cannot be used to charge the batteries because it exceeds their maximum input. Only shown when batteries are charging.");
+        ui.right_align_optional_value_with_unit(battery.and_then(|b| b.wasted_surplus).map(|w| format!("{:.2}", w.to_megawatts())), "MW");
+        ui.end_row();
+      });
+      ui.open_collapsing_header_with_grid("Silent Running", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let silent_running = self.calculated.silent_running.as_ref();
+        ui.label(RichText::new("Idle:").underline())
+          .on_hover_text_at_pointer("Duration until batteries are empty when running silent: reactors and hydrogen engines off, railguns and jump drives not charging, and batteries discharging.");
+        ui.right_align_optional_duration(silent_running.and_then(|s| s.idle));
+        ui.end_row();
+        ui.show_optional_duration_row("Utility:", silent_running.and_then(|s| s.utility));
+        ui.show_optional_duration_row("Full Thrust:", silent_running.and_then(|s| s.full_thrust));
+      });
+      ui.open_collapsing_header_with_grid("Fuel Cost", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let fuel_cost = self.calculated.fuel_cost.as_ref();
+        ui.label(RichText::new("Charge Needed:").underline())
+          .on_hover_text_at_pointer("Energy needed to fully charge batteries and jump drives from empty, including 20% charging losses.");
+        ui.right_align_optional_value_with_unit(fuel_cost.map(|f| format!("{:.2}", f.energy().to_megawatt_hours())), "MWh");
+        ui.end_row();
+        ui.show_optional_row("Uranium (Reactors):", fuel_cost.and_then(|f| f.uranium_mass).map(|m| format!("{:.2}", m.to_kilograms())), "kg");
+        ui.show_optional_row("Hydrogen (Engines):", fuel_cost.and_then(|f| f.hydrogen).map(|h| format!("{}", h.to_liters().round())), "L");
+        ui.label(RichText::new("Ice (Generators):").underline())
+          .on_hover_text_at_pointer("Ice that generators convert into the hydrogen that hydrogen engines burn to generate the charge.");
+        ui.right_align_optional_value_with_unit(fuel_cost.and_then(|f| f.ice_mass).map(|m| format!("{:.2}", m.to_kilograms())), "kg");
+        ui.end_row();
+      });
+    });
+    ui.open_collapsing_header("Hydrogen", |ui| {
+      ui.grid_unstriped("Hydrogen Grid 1", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.show_row("Generation:", format!("{}", self.calculated.hydrogen_generation.round()), "L/s");
+        ui.label(RichText::new("Bottle Reserve:").underline())
+          .on_hover_text_at_pointer("Hydrogen stored in hydrogen bottles in inventories, which refill hydrogen tanks and extend their durations.");
+        ui.right_align_value_with_unit(format!("{}", self.calculated.hydrogen_bottle_capacity.to_liters().round()), "L");
+        ui.end_row();
+        ui.horizontal_separator_unpadded();
+        ui.horizontal_separator_unpadded();
+        ui.end_row();
+      });
+      ui.allocate_space(Vec2::new(0.0, 1.0));
+      ui.grid("Hydrogen Grid 2", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label("Group Name");
+        ui.vertical_separator_unpadded();
+        ui.label("Consumption");
+        ui.label("");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Balance");
+        ui.label("");
+        ui.label("");
+        ui.label("");
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Duration");
+        ui.end_row();
+
+        ui.label("");
+        ui.vertical_separator_unpadded();
+        ui.label("Group");
+        ui.vertical_separator_unpadded();
+        ui.label("Total");
+        ui.vertical_separator_unpadded();
+        ui.label(RichText::new("w/o Tanks").underline())
+          .on_hover_text_at_pointer("Hydrogen balance in the row, without tanks providing hydrogen.");
+        ui.vertical_separator_unpadded();
+        ui.label(RichText::new("w Tanks").underline())
+          .on_hover_text_at_pointer("Hydrogen balance in the row, with tanks providing hydrogen.");
+        ui.vertical_separator_unpadded();
+        ui.label(RichText::new("Budget").underline())
+          .on_hover_text_at_pointer("Hydrogen balance in the row with tanks providing hydrogen, minus the safety margin of the total consumption.");
+        ui.vertical_separator_unpadded();
+        ui.label(RichText::new("Tanks").underline())
+          .on_hover_text_at_pointer("Duration until hydrogen tanks are empty at the total consumption in the row. Does not take into account filling the tank via generators or other tanks.");
+        ui.end_row();
+
+        let hydrogen_formatter = |v| format!("{:.2}", v);
+        let labels = &self.calculator.direction_labels;
+        ui.hydrogen_row("Idle", hydrogen_formatter, &self.calculated.hydrogen_idle, self.calculated.explain_hydrogen(&self.calculator, &self.calculated.hydrogen_idle));
+        ui.hydrogen_row("Fill Engines", hydrogen_formatter, &self.calculated.hydrogen_engine_fill, self.calculated.explain_hydrogen(&self.calculator, &self.calculated.hydrogen_engine_fill));
+        ui.hydrogen_row(format!("+ {} Thrusters", labels.pair(Direction::Up, Direction::Down)), hydrogen_formatter, &self.calculated.hydrogen_upto_up_down_thruster, self.calculated.explain_hydrogen(&self.calculator, &self.calculated.hydrogen_upto_up_down_thruster));
+        ui.hydrogen_row(format!("+ {} Thrusters", labels.pair(Direction::Front, Direction::Back)), hydrogen_formatter, &self.calculated.hydrogen_upto_front_back_thruster, self.calculated.explain_hydrogen(&self.calculator, &self.calculated.hydrogen_upto_front_back_thruster));
+        ui.hydrogen_row(format!("+ {} Thrusters", labels.pair(Direction::Left, Direction::Right)), hydrogen_formatter, &self.calculated.hydrogen_upto_left_right_thruster, self.calculated.explain_hydrogen(&self.calculator, &self.calculated.hydrogen_upto_left_right_thruster));
+        ui.hydrogen_row("+ Fill Tanks", hydrogen_formatter, &self.calculated.hydrogen_upto_tank_fill, self.calculated.explain_hydrogen(&self.calculator, &self.calculated.hydrogen_upto_tank_fill));
+        if let Some(group) = self.calculated.hydrogen_worst_thruster_group {
+          ui.hydrogen_row(format!("Worst: {}", group.label(labels)), hydrogen_formatter, &self.calculated.hydrogen_worst_thruster, self.calculated.explain_hydrogen(&self.calculator, &self.calculated.hydrogen_worst_thruster));
+        }
+      });
+      ui.allocate_space(Vec2::new(0.0, 1.0));
+      ui.grid("Hydrogen Grid 3", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let requirement = self.calculated.hydrogen_generator_requirement.as_ref();
+        ui.label(RichText::new("Generators for Full Burn:").underline())
+          .on_hover_text_at_pointer("Number of O2/H2 generators (of the kind on the grid) needed to keep the hydrogen balance above the safety margin when filling engines and using the worst direction pair of thrusters at full power, without tanks providing hydrogen.");
+        ui.right_align_optional_value_with_unit(requirement.map(|r| format!("{}", r.generator_count)), "#");
+        ui.end_row();
+        ui.show_optional_row("Additional Generators:", requirement.map(|r| format!("{}", r.additional_generator_count)), "#");
+        ui.show_optional_row("Ice Consumption:", requirement.map(|r| format!("{:.2}", r.ice_consumption)), "#/s");
+      });
+      ui.allocate_space(Vec2::new(0.0, 1.0));
+      ui.grid("Hydrogen Grid 4", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        ui.label(RichText::new("Thruster Idle").underline())
+          .on_hover_text_at_pointer("Idle hydrogen consumption of hydrogen thrusters per direction, included in the 'Idle' row. Thrusters in disabled directions have no idle consumption when 'Disabled Thrusters Off' is enabled.");
+        ui.end_row();
+        for (direction, idle) in self.calculated.hydrogen_thruster_idle.iter_with_direction() {
+          ui.show_row(format!("{}:", self.calculator.direction_labels.get(direction)), format!("{:.2}", idle), "L/s");
+        }
+      });
+      ui.open_collapsing_header_with_grid("Hydrogen Consumption Shares", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        for (group, fraction) in self.calculated.hydrogen_shares.iter().filter(|(_, f)| *f > 0.0) {
+          ui.share_row(group.label(&self.calculator.direction_labels), *fraction);
+        }
+      });
+    });
+    ui.horizontal(|ui| {
+      ui.open_collapsing_header_with_grid("Hydrogen Tank", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let hydrogen_tank = self.calculated.hydrogen_tank.as_ref();
+        ui.show_optional_row("Capacity:", hydrogen_tank.map(|c| format!("{}", c.capacity.to_liters().round())), "L");
+        ui.show_optional_row("Maximum Input:", hydrogen_tank.map(|c| format!("{}", c.maximum_input.round())), "L/s");
+        ui.show_optional_row("Maximum Output:", hydrogen_tank.map(|c| format!("{}", c.maximum_output.round())), "L/s");
+        ui.show_optional_duration_row("Fill Duration:", hydrogen_tank.and_then(|t| t.fill_duration));
+      });
+      ui.open_collapsing_header_with_grid("Hydrogen Engine", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let hydrogen_engine = self.calculated.hydrogen_engine.as_ref();
+        ui.show_optional_row("Capacity:", hydrogen_engine.map(|c| format!("{}", c.capacity.to_liters().round())), "L");
+        ui.show_optional_row("Maximum Fuel Consumption:", hydrogen_engine.map(|c| format!("{}", c.maximum_fuel_consumption.round())), "L/s");
+        ui.show_optional_row("Maximum Output:", hydrogen_engine.map(|c| format!("{:.2}", c.maximum_output.to_megawatts())), "MW");
+        ui.show_optional_row("Maximum Refilling Input:", hydrogen_engine.map(|c| format!("{}", c.maximum_refilling_input.round())), "L/s");
+        ui.show_optional_duration_row("Fill Duration:", hydrogen_engine.and_then(|e| e.fill_duration));
+      });
+      ui.open_collapsing_header_with_grid("Generators", |ui| {
+        let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+        let generator = self.calculated.generator.as_ref();
+        ui.label(RichText::new("Utilization:").underline())
+          .on_hover_text_at_pointer("Fraction of time that O2/H2 generators run to cover the total hydrogen consumption upto filling tanks, and the oxygen demand when oxygen throttles generators.");
+        ui.right_align_optional_value_with_unit(generator.map(|g| format!("{:.2}", g.utilization)), "%");
+        ui.end_row();
+        ui.show_optional_row("Ice Consumption:", generator.map(|g| format!("{:.2}", g.ice_consumption)), "#/s");
+        ui.show_optional_duration_row("Ice Duration:", generator.and_then(|g| g.ice_duration));
+        ui.show_optional_row("Hydrogen Output:", generator.map(|g| format!("{:.2}", g.hydrogen_output)), "L/s");
+        ui.show_optional_row("Oxygen Output:", generator.map(|g| format!("{:.2}", g.oxygen_output)), "L/s");
+        ui.label(RichText::new("Oxygen Balance:").underline())
+          .on_hover_text_at_pointer("Oxygen output minus the oxygen demand that oxygen farms do not cover. A positive balance is vented.");
+        ui.right_align_optional_value_with_unit(generator.map(|g| format!("{:.2}", g.oxygen_balance)), "L/s");
+        ui.end_row();
+      });
+    });
+    self.show_ascent(ui);
+    ui.open_collapsing_header_with_grid("Oxygen", |ui| {
+      let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+      let oxygen = self.calculated.oxygen.as_ref();
+      ui.show_optional_row("Generators:", oxygen.map(|o| format!("{:.2}", o.generator_generation)), "L/s");
+      ui.show_optional_row("Farms:", oxygen.map(|o| format!("{:.2}", o.farm_generation)), "L/s");
+      ui.show_optional_row("Air Vents:", oxygen.map(|o| format!("{:.2}", o.air_vent_consumption)), "L/s");
+      ui.show_optional_row("Other Demand:", oxygen.map(|o| format!("{:.2}", o.demand)), "L/s");
+      ui.label(RichText::new("Balance:").underline())
+        .on_hover_text_at_pointer("Oxygen generation of generators and farms minus consumption of air vents and other demand, without oxygen tanks providing oxygen.");
+      ui.right_align_optional_value_with_unit(oxygen.map(|o| format!("{:.2}", o.balance)), "L/s");
+      ui.end_row();
+      ui.show_optional_row("Tank Capacity:", oxygen.map(|o| format!("{:.0}", o.tank_capacity.to_liters())), "L");
+      ui.label(RichText::new("Bottle Reserve:").underline())
+        .on_hover_text_at_pointer("Oxygen stored in oxygen bottles in inventories, which refill oxygen tanks and extend their duration.");
+      ui.right_align_value_with_unit(format!("{}", self.calculated.oxygen_bottle_capacity.to_liters().round()), "L");
+      ui.end_row();
+      ui.show_optional_duration_row("Tank Duration:", oxygen.and_then(|o| o.tank_duration));
+    });
+    ui.open_collapsing_header_with_grid("Mining", |ui| {
+      let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+      let drilling = self.calculated.drilling.as_ref();
+      let mining = self.calculated.mining.as_ref();
+      ui.show_optional_row("Drills:", drilling.map(|d| format!("{}", d.count)), "");
+      ui.label(RichText::new("Drill Ore Rate:").underline())
+        .on_hover_text_at_pointer("Estimated best-case rate at which all drills collect ore, when drilling into solid ore. Actual rates are lower.");
+      ui.right_align_optional_value_with_unit(drilling.map(|d| format!("{:.2}", d.ore_rate)), "L/s");
+      ui.end_row();
+      ui.show_optional_row("Drill Inventories:", drilling.map(|d| format!("{}", d.drill_volume.round())), "L");
+      ui.show_optional_row("Storage Inventories:", drilling.map(|d| format!("{}", d.storage_volume.round())), "L");
+      ui.label(RichText::new("Cargo Full In (Drills):").underline())
+        .on_hover_text_at_pointer("Duration until inventories are full of ore when drilling at the drill ore rate, starting with empty inventories.");
+      ui.right_align_optional_duration(drilling.and_then(|d| d.fill_duration));
+      ui.end_row();
+      ui.label(RichText::new("Cargo Full In:").underline())
+        .on_hover_text_at_pointer("Duration until inventories are full of ore when mining at the mining rate while hovering, starting with empty inventories. Uses the drill ore rate if the mining rate in the options is 0.");
+      ui.right_align_optional_duration(mining.map(|m| m.fill_duration));
+      ui.end_row();
+      ui.show_optional_row("Power Consumption:", mining.map(|m| format!("{:.2}", m.power_consumption.to_megawatts())), "MW");
+      ui.label(RichText::new("Power Lasts:").underline())
+        .on_hover_text_at_pointer("Duration until power runs out while mining, or '∞' if power generation without batteries covers consumption.");
+      ui.right_align_optional_value_with_unit(mining.map(|m| format_lasts(m.power_duration, m.power_lasts(), self.duration_format)), "");
+      ui.end_row();
+      ui.show_optional_row("Hydrogen Consumption:", mining.map(|m| format!("{:.2}", m.hydrogen_consumption)), "L/s");
+      ui.label(RichText::new("Hydrogen Lasts:").underline())
+        .on_hover_text_at_pointer("Duration until hydrogen runs out while mining, or '∞' if generators cover consumption.");
+      ui.right_align_optional_value_with_unit(mining.map(|m| format_lasts(m.hydrogen_duration, m.hydrogen_lasts(), self.duration_format)), "");
+      ui.end_row();
+      ui.show_optional_row("Mass (Full):", mining.map(|m| format!("{}", m.mass_full.to_kilograms().round())), "kg");
+      ui.label(RichText::new("Ascent (Full):").underline())
+        .on_hover_text_at_pointer("Upwards acceleration in gravity when inventories are full of ore. Negative values mean the grid cannot ascend when full.");
+      ui.right_align_optional_value_with_unit(mining.and_then(|m| m.ascent_acceleration_full).map(|a| format!("{:.2}", a)), "m/s²");
+      ui.end_row();
+    });
+    ui.open_collapsing_header_with_grid("Armament", |ui| {
+      let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+      let armament = self.calculated.armament.as_ref();
+      ui.label("");
+      ui.label("Count");
+      ui.label("Ammo Storage");
+      ui.label("Idle Power");
+      ui.label("Operational Power");
+      ui.end_row();
+      for group in armament.map(|a| a.groups.as_slice()).unwrap_or_default() {
+        ui.label(format!("{}:", group.group));
+        ui.right_align_value_with_unit(format!("{}", group.count), "");
+        ui.right_align_value_with_unit(format!("{}", group.ammo_volume.to_liters().round()), "L");
+        ui.right_align_value_with_unit(format!("{:.3}", group.idle_power_consumption.to_megawatts()), "MW");
+        ui.right_align_value_with_unit(format!("{:.3}", group.operational_power_consumption.to_megawatts()), "MW");
+        ui.end_row();
+      }
+      ui.label("Total:");
+      ui.right_align_optional_value_with_unit(armament.map(|a| format!("{}", a.groups.iter().map(|g| g.count).sum::<f64>())), "");
+      ui.right_align_optional_value_with_unit(armament.map(|a| format!("{}", a.ammo_volume.to_liters().round())), "L");
+      ui.right_align_optional_value_with_unit(armament.map(|a| format!("{:.3}", a.idle_power_consumption.to_megawatts())), "MW");
+      ui.right_align_optional_value_with_unit(armament.map(|a| format!("{:.3}", a.operational_power_consumption.to_megawatts())), "MW");
+      ui.end_row();
+      ui.label(RichText::new("Ammo Mass:").underline())
+        .on_hover_text_at_pointer("Mass of the ammo in weapons at the ammo fill in the options, included in the filled mass. Estimated with gatling ammo boxes for turrets and fixed weapons, and rifle magazines for interior turrets.");
+      ui.right_align_optional_value_with_unit(armament.map(|a| format!("{}", a.ammo_mass.to_kilograms().round())), "kg");
+      ui.end_row();
+    });
+    ui.open_collapsing_header_with_grid("Gyroscopes", |ui| {
+      let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+      let gyroscope = self.calculated.gyroscope.as_ref();
+      ui.show_optional_row("Gyroscopes:", gyroscope.map(|g| format!("{}", g.count)), "");
+      ui.show_optional_row("Torque:", gyroscope.map(|g| format!("{:.0}", g.torque)), "N·m");
+      ui.show_optional_row("Power Consumption:", gyroscope.map(|g| format!("{:.2}", g.maximum_input)), "MW");
+      for axis in RotationAxis::items() {
+        let rotation = gyroscope.and_then(|g| g.rotation(axis));
+        ui.label(RichText::new(format!("{} (Filled):", axis)).underline())
+          .on_hover_text_at_pointer("Estimated rotational acceleration when filled, treating the grid as a solid box with the width, height, and length in the options. Actual acceleration depends on the shape of the grid.");
+        ui.right_align_optional_value_with_unit(rotation.and_then(|r| r.acceleration_filled).map(|a| format!("{:.2}", a)), "°/s²");
+        ui.end_row();
+      }
+    });
+    ui.open_collapsing_header_with_grid("Production", |ui| {
+      let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+      let production = self.calculated.production.as_ref();
+      ui.show_optional_row("Refineries:", production.map(|p| format!("{}", p.refinery_count)), "");
+      ui.show_optional_row("Ore Rate:", production.map(|p| format!("{:.2}", p.ore_rate)), "kg/s");
+      ui.show_optional_row("Ingot Rate:", production.map(|p| format!("{:.2}", p.ingot_rate)), "kg/s");
+      ui.show_optional_row("Assemblers:", production.map(|p| format!("{}", p.assembler_count)), "");
+      ui.show_optional_row("Assembly Speed:", production.map(|p| format!("{:.2}", p.assembly_speed)), "x");
+      ui.show_optional_row("Power Consumption:", production.map(|p| format!("{:.2}", p.maximum_input)), "MW");
+      ui.label(RichText::new("Process Ore:").underline())
+        .on_hover_text_at_pointer("Duration until refineries have processed all ore in inventories at the ore fill levels in the options.");
+      ui.right_align_optional_duration(production.and_then(|p| p.process_duration));
+      ui.end_row();
+    });
+  }
+}
+
+/// Formats how long a resource lasts while mining, and whether it lasts until inventories are full.
+fn format_lasts(duration: Option<Duration>, lasts: bool, format: DurationFormat) -> String {
+  let duration = match duration {
+    Some(duration) => duration.format(format, 2),
+    None => "∞".to_string(),
+  };
+  format!("{} ({})", duration, if lasts { "lasts" } else { "runs out" })
+}
@@ -3,10 +3,71 @@ use eframe::emath::Align;
 use egui::{Align2, Context, Layout, RichText, TextEdit, Window};
 use egui_extras::{Column, TableBuilder};
 
+use secalc_core::grid::GridCalculator;
+use secalc_core::grid::summary::StatSummary;
+use secalc_widgets::extensions::UiExtensions;
+
 use crate::App;
-use crate::widget::UiExtensions;
+
+/// Maximum number of stat summaries kept in the save history of a grid.
+const MAX_SAVE_HISTORY: usize = 20;
 
 impl App {
+  /// Saves the current calculator under `name`, and records a summary of its results in the save history of `name`.
+  pub fn save_calculator(&mut self, name: String) {
+    let history = self.save_history.entry(name.clone()).or_default();
+    history.push(StatSummary::from_calculated(&self.calculated));
+    if history.len() > MAX_SAVE_HISTORY {
+      history.remove(0);
+    }
+    self.saved_calculators.insert(name, self.calculator.clone());
+  }
+
+  /// Loads `calculator` as the current calculator, saved under `name`, recalculating once after applying it.
+  pub fn load_calculator(&mut self, name: String, calculator: GridCalculator) {
+    self.bulk_update(|app| {
+      app.calculator = calculator;
+      app.current_calculator = Some(name);
+      app.current_calculator_saved = true;
+      app.calculate();
+    });
+  }
+
+  /// Saves the current calculator under `name`, and makes `name` the current calculator.
+  pub fn save_calculator_as(&mut self, name: String) {
+    self.save_calculator(name.clone());
+    self.current_calculator = Some(name);
+    self.current_calculator_saved = true;
+  }
+
+  /// Deletes saved calculator `name` and its save history. The current calculator becomes unsaved if it was `name`.
+  pub fn delete_calculator(&mut self, name: &str) {
+    self.saved_calculators.remove(name);
+    self.save_history.remove(name);
+    if Some(name) == self.current_calculator.as_deref() {
+      self.current_calculator = None;
+      self.current_calculator_saved = false;
+    }
+  }
+
+  /// Resets the current calculator to its defaults.
+  pub fn reset_calculator(&mut self) {
+    self.calculator = self.calculator_default.clone();
+    self.calculate();
+    self.current_calculator = None;
+    self.current_calculator_saved = true; // True because the calculator is reset and not worth saving.
+  }
+
+  /// Gets the changes of key stats of saved grid `name` between its last two saves, or None if it was saved less than
+  /// twice or nothing changed.
+  fn changes_since_last_save(&self, name: &str) -> Option<String> {
+    let history = self.save_history.get(name)?;
+    let [.., older, newer] = history.as_slice() else { return None; };
+    let changes = older.changes(newer);
+    if changes.is_empty() { return None; }
+    Some(changes.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "))
+  }
+
   pub fn show_save_load_reset_windows(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
     self.show_load_window(ctx, frame);
     self.show_load_confirm_window(ctx);
@@ -41,6 +102,9 @@ impl App {
                       RichText::new(name)
                     };
                     ui.label(text);
+                    if let Some(changes) = self.changes_since_last_save(name) {
+                      ui.label(RichText::new("ⓘ").weak()).on_hover_text(format!("Since last save: {}", changes));
+                    }
                   });
                   row.col(|ui| {
                     if ui.button("Load").clicked() {
@@ -54,10 +118,7 @@ impl App {
               }
             });
           if let Some((name, calculator)) = load_clicked {
-            self.calculator = calculator;
-            self.calculate();
-            self.current_calculator = Some(name);
-            self.current_calculator_saved = true;
+            self.load_calculator(name, calculator);
             if let Some(storage) = frame.storage_mut() {
               self.save(storage);
             }
@@ -117,11 +178,7 @@ impl App {
           ui.horizontal(|ui| {
             if ui.danger_button("Delete").clicked() {
               let name = self.show_delete_confirm_window.take().unwrap();
-              self.saved_calculators.remove(&name);
-              if Some(name) == self.current_calculator {
-                self.current_calculator = None;
-                self.current_calculator_saved = false;
-              }
+              self.delete_calculator(&name);
 
               self.show_delete_confirm_window = None;
               self.show_load_window = true;
@@ -157,9 +214,7 @@ impl App {
                 self.show_save_as_window = None;
                 self.show_save_as_confirm_window = Some(name)
               } else {
-                self.saved_calculators.insert(name.clone(), self.calculator.clone());
-                self.current_calculator = Some(name);
-                self.current_calculator_saved = true;
+                self.save_calculator_as(name);
                 if let Some(storage) = frame.storage_mut() {
                   self.save(storage);
                 }
@@ -191,9 +246,7 @@ impl App {
           ui.horizontal(|ui| {
             if ui.danger_button("Overwrite").clicked() {
               let name = self.show_save_as_confirm_window.take().unwrap();
-              self.saved_calculators.insert(name.clone(), self.calculator.clone());
-              self.current_calculator = Some(name);
-              self.current_calculator_saved = true;
+              self.save_calculator_as(name);
               if let Some(storage) = frame.storage_mut() {
                 self.save(storage);
               }
@@ -223,10 +276,7 @@ impl App {
             if ui.danger_button("Reset").clicked() {
               self.enable_gui = true;
               self.show_reset_confirm_window = false;
-              self.calculator = self.calculator_default.clone();
-              self.calculate();
-              self.current_calculator = None;
-              self.current_calculator_saved = true; // True because the calculator is reset and not worth saving.
+              self.reset_calculator();
             }
             if ui.button("Cancel").clicked() {
               self.enable_gui = true;
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use egui::{Align2, ComboBox, Context, DragValue, Window};
+
+use secalc_core::data::blocks::BlockCategory;
+use secalc_core::grid::ScaleRounding;
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+use crate::app::compare::show_key_stats_comparison;
+
+/// Options of the scale grid window.
+pub struct ScaleOptions {
+  factor: f64,
+  rounding: ScaleRounding,
+  categories: HashSet<BlockCategory>,
+}
+
+impl Default for ScaleOptions {
+  fn default() -> Self {
+    Self {
+      factor: 2.0,
+      rounding: ScaleRounding::default(),
+      categories: BlockCategory::items().into_iter().collect(),
+    }
+  }
+}
+
+impl App {
+  pub fn show_scale_window(&mut self, ctx: &Context) {
+    let Some(mut options) = self.show_scale_window.take() else { return; };
+    let mut close = false;
+    Window::new("Scale Grid")
+      .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.grid("Scale Options Grid", |ui| {
+          ui.label("Factor");
+          ui.add(DragValue::new(&mut options.factor).speed(0.01).clamp_range(0.0..=f64::INFINITY).suffix("x"));
+          ui.end_row();
+          ui.label("Rounding");
+          ComboBox::from_id_source("Scale Rounding")
+            .selected_text(format!("{}", options.rounding))
+            .show_ui(ui, |ui| {
+              for rounding in ScaleRounding::items() {
+                ui.selectable_value(&mut options.rounding, rounding, format!("{}", rounding));
+              }
+            });
+          ui.end_row();
+          for category in BlockCategory::items() {
+            ui.label(format!("{}", category));
+            let mut enabled = options.categories.contains(&category);
+            if ui.checkbox(&mut enabled, "").changed() {
+              if enabled {
+                options.categories.insert(category);
+              } else {
+                options.categories.remove(&category);
+              }
+            }
+            ui.end_row();
+          }
+        });
+        ui.separator();
+
+        let mut scaled = self.calculator.clone();
+        scaled.scale_block_counts(options.factor, options.rounding, |id| {
+          self.data.blocks.category(id).is_some_and(|c| options.categories.contains(&c))
+        });
+        let scaled_calculated = self.calculated_cache.get_or_calculate(scaled.digest(), || scaled.calculate(&self.data));
+        ui.grid("Scale Comparison Grid", |ui| {
+          show_key_stats_comparison(ui, self.number_separator_policy, &self.calculated, &scaled_calculated);
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+          if ui.button("Apply").clicked() {
+            self.calculator = scaled;
+            self.calculated = scaled_calculated;
+            self.current_calculator_saved = false;
+            close = true;
+          }
+          if ui.button("Cancel").clicked() {
+            close = true;
+          }
+        });
+      });
+    if close {
+      self.enable_gui = true;
+    } else {
+      self.show_scale_window = Some(options);
+    }
+  }
+}
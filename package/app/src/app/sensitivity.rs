@@ -0,0 +1,96 @@
+use egui::{ComboBox, Context, Window};
+
+use secalc_core::grid::sweep::{sweep, SweepOption, SweepPoint, SweepResult};
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+use secalc_widgets::sparkline::Sparkline;
+
+use crate::App;
+
+/// Number of points a sensitivity sweep calculates.
+const SWEEP_POINTS: usize = 41;
+
+/// Options of the sensitivity window.
+#[derive(Default)]
+pub struct SensitivityOptions {
+  option: SweepOption,
+  result: SweepResult,
+  /// Sweep for the calculator digest, option, and result it was calculated for, or None if not calculated yet.
+  sweep: Option<(u64, SweepOption, SweepResult, Vec<SweepPoint>)>,
+}
+
+impl SensitivityOptions {
+  /// Invalidates the sweep, for example when the data changes.
+  pub fn invalidate(&mut self) {
+    self.sweep = None;
+  }
+}
+
+impl App {
+  pub fn show_sensitivity_window(&mut self, ctx: &Context) {
+    let mut show = self.show_sensitivity_window;
+    Window::new("Sensitivity")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        let options = &mut self.sensitivity;
+        ui.grid("Sensitivity Options Grid", |ui| {
+          ui.label("Result");
+          ComboBox::from_id_source("Sensitivity Result")
+            .selected_text(format!("{}", options.result))
+            .show_ui(ui, |ui| {
+              for result in SweepResult::items() {
+                ui.selectable_value(&mut options.result, result, format!("{}", result));
+              }
+            });
+          ui.end_row();
+          ui.label("Option");
+          ComboBox::from_id_source("Sensitivity Option")
+            .selected_text(format!("{}", options.option))
+            .show_ui(ui, |ui| {
+              for option in SweepOption::items() {
+                ui.selectable_value(&mut options.option, option, format!("{}", option));
+              }
+            });
+          ui.end_row();
+        });
+        ui.separator();
+
+        let digest = self.calculator.digest();
+        let up_to_date = options.sweep.as_ref().is_some_and(|(d, o, r, _)| *d == digest && *o == options.option && *r == options.result);
+        if !up_to_date {
+          let range = options.option.default_range(&self.calculator);
+          let points = sweep(&self.calculator, &self.data, options.option, options.result, range, SWEEP_POINTS);
+          options.sweep = Some((digest, options.option, options.result, points));
+        }
+        let Some((_, option, result, points)) = &options.sweep else { return; };
+
+        ui.label(format!("{} across the range of {}, all other options unchanged. The vertical line marks the current value.", result, option));
+        let line: Vec<_> = points.iter().map(|p| (p.option, p.result)).collect();
+        let response = ui.add(Sparkline::new(&line).marker(option.get(&self.calculator)).size([320.0, 80.0]));
+        if let Some(pos) = response.hover_pos() {
+          let fraction = ((pos.x - response.rect.left()) / response.rect.width()).clamp(0.0, 1.0);
+          let index = (fraction * (points.len() - 1) as f32).round() as usize;
+          if let Some(point) = points.get(index) {
+            let value = point.result.map_or_else(|| "-".to_string(), |r| format!("{:.2} {}", r, result.unit()));
+            response.on_hover_text_at_pointer(format!("{}: {:.2} {}\n{}: {}", option, point.option, option.unit(), result, value));
+          }
+        }
+        ui.grid("Sensitivity Range Grid", |ui| {
+          let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+          let values = points.iter().filter_map(|p| p.result);
+          let min = values.clone().fold(f64::INFINITY, f64::min);
+          let max = values.fold(f64::NEG_INFINITY, f64::max);
+          if min.is_finite() && max.is_finite() {
+            ui.show_row("Minimum", format!("{:.2}", min), result.unit());
+            ui.show_row("Maximum", format!("{:.2}", max), result.unit());
+          }
+          if let Some(current) = result.get(&self.calculated) {
+            ui.show_row("Current", format!("{:.2}", current), result.unit());
+          }
+        });
+      });
+    self.show_sensitivity_window = show;
+  }
+}
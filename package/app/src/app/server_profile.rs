@@ -0,0 +1,141 @@
+use egui::{Button, ComboBox, Context, DragValue, TextEdit, Ui, Window};
+
+use secalc_core::grid::server_profile::ServerProfile;
+use secalc_widgets::extensions::UiExtensions;
+
+use crate::App;
+
+/// Options of the server profile window.
+#[derive(Default)]
+pub struct ServerProfileOptions {
+  /// Server profile JSON to import.
+  import_text: String,
+  import_error: Option<String>,
+}
+
+impl App {
+  pub fn show_server_profile_window(&mut self, ctx: &Context) {
+    let mut show = self.show_server_profile_window;
+    let mut changed = false;
+    Window::new("Server Profile")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Limits that a server imposes on grids. Grids exceeding these limits are warned about in the results.");
+        ui.separator();
+        changed |= ui.checkbox(&mut self.server_profile_enabled, "Check grids against this profile").changed();
+        let profile = &mut self.server_profile;
+        ui.grid("Server Profile Grid", |ui| {
+          ui.label("Name");
+          ui.add(TextEdit::singleline(&mut profile.name).desired_width(200.0));
+          ui.end_row();
+          ui.label("Maximum PCU");
+          changed |= optional_limit(ui, &mut profile.max_pcu, 100.0);
+          ui.end_row();
+          ui.label("Maximum Blocks");
+          changed |= optional_limit(ui, &mut profile.max_block_count, 10.0);
+          ui.end_row();
+          ui.label("Welder Speed Multiplier").on_hover_text("Welder speed multiplier of the world, for estimating build times. Uses the default of 1 when disabled.");
+          changed |= optional_multiplier(ui, &mut profile.welder_speed_multiplier);
+          ui.end_row();
+        });
+        ui.separator();
+
+        ui.label("Maximum count per block type");
+        let mut remove = None;
+        ui.grid("Server Profile Block Limits Grid", |ui| {
+          for (id, limit) in profile.block_limits.iter_mut() {
+            ui.label(self.data.blocks.get_data(id).map_or(id.as_str(), |b| b.name(&self.data.localization)));
+            changed |= ui.add(DragValue::new(limit).speed(0.05)).changed();
+            if ui.danger_button("Remove").clicked() {
+              remove = Some(id.clone());
+            }
+            ui.end_row();
+          }
+        });
+        if let Some(id) = remove {
+          profile.block_limits.remove(&id);
+          changed = true;
+        }
+        let mut blocks: Vec<_> = self.data.blocks.iter_data()
+          .filter(|b| b.size == self.grid_size && !profile.block_limits.contains_key(&b.id))
+          .map(|b| (b.id.clone(), b.name(&self.data.localization).to_string()))
+          .collect();
+        blocks.sort_by(|(_, a), (_, b)| a.cmp(b));
+        ComboBox::from_id_source("Server Profile Add Block")
+          .selected_text("Add block limit…")
+          .show_ui(ui, |ui| {
+            for (id, name) in blocks {
+              if ui.selectable_label(false, name).clicked() {
+                profile.block_limits.insert(id, 1);
+                changed = true;
+              }
+            }
+          });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+          if ui.button("Copy JSON").on_hover_text("Copy this profile as JSON, for sharing it with other players.").clicked() {
+            let mut json = Vec::new();
+            if profile.to_json(&mut json).is_ok() {
+              ui.output_mut(|o| o.copied_text = String::from_utf8_lossy(&json).into_owned());
+            }
+          }
+          let options = &mut self.server_profile_options;
+          if ui.add_enabled(!options.import_text.trim().is_empty(), Button::new("Import JSON")).clicked() {
+            match ServerProfile::from_json(options.import_text.as_bytes()) {
+              Ok(imported) => {
+                *profile = imported;
+                options.import_text.clear();
+                options.import_error = None;
+                changed = true;
+              }
+              Err(e) => options.import_error = Some(format!("{}: {}", e, std::error::Error::source(&e).map(|s| s.to_string()).unwrap_or_default())),
+            }
+          }
+        });
+        let options = &mut self.server_profile_options;
+        ui.add(TextEdit::multiline(&mut options.import_text).hint_text("Paste server profile JSON to import").desired_rows(3).desired_width(300.0));
+        if let Some(error) = &options.import_error {
+          ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+      });
+    self.show_server_profile_window = show;
+    if changed {
+      self.calculate();
+    }
+  }
+}
+
+/// Shows a checkbox to enable `limit` and an editor of its value. Returns whether `limit` was changed.
+fn optional_limit(ui: &mut Ui, limit: &mut Option<u64>, speed: f64) -> bool {
+  let mut changed = false;
+  ui.horizontal(|ui| {
+    let mut enabled = limit.is_some();
+    if ui.checkbox(&mut enabled, "").changed() {
+      *limit = enabled.then_some(0);
+      changed = true;
+    }
+    if let Some(limit) = limit {
+      changed |= ui.add(DragValue::new(limit).speed(speed)).changed();
+    }
+  });
+  changed
+}
+
+/// Shows a checkbox to enable `multiplier` and an editor of its value. Returns whether `multiplier` was changed.
+fn optional_multiplier(ui: &mut Ui, multiplier: &mut Option<f64>) -> bool {
+  let mut changed = false;
+  ui.horizontal(|ui| {
+    let mut enabled = multiplier.is_some();
+    if ui.checkbox(&mut enabled, "").changed() {
+      *multiplier = enabled.then_some(1.0);
+      changed = true;
+    }
+    if let Some(multiplier) = multiplier {
+      changed |= ui.add(DragValue::new(multiplier).speed(0.01).clamp_range(0.01..=f64::INFINITY).suffix("x")).changed();
+    }
+  });
+  changed
+}
@@ -0,0 +1,75 @@
+use egui::{ComboBox, Ui};
+
+use secalc_core::grid::subgrid::Subgrid;
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+
+impl App {
+  /// Shows the subgrids of the grid, with buttons to add saved grids as subgrids and to remove subgrids. Returns whether
+  /// the subgrids were changed.
+  pub fn show_subgrids(&mut self, ui: &mut Ui) -> bool {
+    let mut changed = false;
+    ui.open_collapsing_header("Subgrids", |ui| {
+      ui.label("Grids attached to this grid, such as drill heads on pistons or docked ships, calculated together with this grid.");
+      let mut remove = None;
+      ui.grid("Subgrids Grid", |ui| {
+        for (index, subgrid) in self.calculator.subgrids.iter_mut().enumerate() {
+          changed |= ui.text_edit_singleline(&mut subgrid.name).changed();
+          let block_count: f64 = subgrid.blocks.values().sum::<f64>() + subgrid.directional_blocks.values().flat_map(|c| c.iter()).sum::<f64>();
+          ui.label(format!("{} blocks", block_count));
+          if ui.button("Remove").clicked() {
+            remove = Some(index);
+          }
+          ui.end_row();
+        }
+      });
+      if let Some(index) = remove {
+        self.calculator.subgrids.remove(index);
+        changed = true;
+      }
+      let mut names: Vec<_> = self.saved_calculators.keys().collect();
+      names.sort();
+      let mut add = None;
+      ComboBox::from_id_source("Add Subgrid")
+        .selected_text("Add Saved Grid")
+        .show_ui(ui, |ui| {
+          for name in names {
+            if ui.selectable_label(false, name).clicked() {
+              add = Some(name.clone());
+            }
+          }
+        }).response.on_hover_text("Add a copy of the blocks of a saved grid as a subgrid. Save grids with 'Grid -> Save As'.");
+      if let Some(calculator) = add.and_then(|name| self.saved_calculators.get(&name).map(|c| Subgrid::from_calculator(name, c))) {
+        self.calculator.subgrids.push(calculator);
+        changed = true;
+      }
+    });
+    changed
+  }
+
+  /// Shows the results of the main grid and each subgrid on their own, if the grid has subgrids.
+  pub fn show_grid_breakdown(&mut self, ui: &mut Ui) {
+    if self.calculated.grid_breakdown.is_empty() { return; }
+    ui.open_collapsing_header_with_grid("Grid Breakdown", |ui| {
+      let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+      ui.label("Grid");
+      for header in ["Blocks", "Mass (Empty)", "Mass (Filled)", "Power Generation", "Power Consumption", "Hydrogen Generation", "Hydrogen Consumption"] {
+        ui.right_align_label(header);
+      }
+      ui.end_row();
+      for grid in &self.calculated.grid_breakdown {
+        ui.label(grid.name.as_deref().unwrap_or("Main Grid"));
+        ui.right_align_value_with_unit(format!("{}", grid.block_count), "#");
+        ui.right_align_value_with_unit(format!("{}", grid.total_mass_empty.to_kilograms().round()), "kg");
+        ui.right_align_value_with_unit(format!("{}", grid.total_mass_filled.to_kilograms().round()), "kg");
+        ui.right_align_value_with_unit(format!("{:.2}", grid.power_generation.to_megawatts()), "MW");
+        ui.right_align_value_with_unit(format!("{:.2}", grid.power_consumption.to_megawatts()), "MW");
+        ui.right_align_value_with_unit(format!("{:.2}", grid.hydrogen_generation), "L/s");
+        ui.right_align_value_with_unit(format!("{:.2}", grid.hydrogen_consumption), "L/s");
+        ui.end_row();
+      }
+    });
+  }
+}
@@ -0,0 +1,155 @@
+use egui::{Align2, Button, ComboBox, Context, DragValue, RichText, Window};
+
+use secalc_core::grid::direction::{Direction, PerDirection};
+use secalc_core::grid::thruster_mix::{Environment, suggest_thruster_mix, ThrusterMix, ThrusterMixObjective, ThrusterMixProblem};
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+use crate::app::compare::show_key_stats_comparison;
+
+/// Options of the thruster mix wizard window.
+pub struct ThrusterMixOptions {
+  environments: [Environment; 2],
+  target_acceleration: PerDirection<f64>,
+  objective: ThrusterMixObjective,
+  /// Suggested mix, or None if not suggested yet. Some(None) if no mix reaches the target accelerations.
+  suggestion: Option<Option<ThrusterMix>>,
+}
+
+impl Default for ThrusterMixOptions {
+  fn default() -> Self {
+    Self {
+      environments: [Environment::PLANET_SURFACE, Environment::SPACE],
+      target_acceleration: PerDirection::splat(1.0),
+      objective: ThrusterMixObjective::default(),
+      suggestion: None,
+    }
+  }
+}
+
+impl App {
+  pub fn show_thruster_mix_window(&mut self, ctx: &Context) {
+    let Some(mut options) = self.show_thruster_mix_window.take() else { return; };
+    let mut close = false;
+    Window::new("Thruster Mix Wizard (Experimental)")
+      .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Suggests a mix of atmospheric, ion, and hydrogen thrusters that reaches the target accelerations in both environments. Thrusters of the grid are replaced when applied.");
+        ui.separator();
+        let mut changed = false;
+        ui.grid("Thruster Mix Options Grid", |ui| {
+          ui.label("");
+          ui.label("Planetary Influence");
+          ui.label("Gravity");
+          ui.end_row();
+          for (index, environment) in options.environments.iter_mut().enumerate() {
+            ui.label(format!("Environment {}", index + 1));
+            let mut planetary_influence = environment.planetary_influence * 100.0;
+            if ui.add(DragValue::new(&mut planetary_influence).speed(1.0).clamp_range(0.0..=100.0).suffix("%")).changed() {
+              environment.planetary_influence = planetary_influence / 100.0;
+              changed = true;
+            }
+            changed |= ui.add(DragValue::new(&mut environment.gravity_multiplier).speed(0.01).clamp_range(0.0..=f64::INFINITY).suffix("g")).changed();
+            ui.end_row();
+          }
+        });
+        ui.grid("Thruster Mix Targets Grid", |ui| {
+          for direction in Direction::items() {
+            ui.label(RichText::new(format!("{} Acceleration", self.calculator.direction_labels.get(direction))).underline())
+              .on_hover_text_at_pointer("Target acceleration in this direction. For up, this is on top of countering gravity.");
+            changed |= ui.add(DragValue::new(&mut options.target_acceleration[direction]).speed(0.1).clamp_range(0.0..=f64::INFINITY).suffix("m/s²")).changed();
+            ui.end_row();
+          }
+          ui.label("Objective");
+          ComboBox::from_id_source("Thruster Mix Objective")
+            .selected_text(format!("{}", options.objective))
+            .show_ui(ui, |ui| {
+              for objective in ThrusterMixObjective::items() {
+                changed |= ui.selectable_value(&mut options.objective, objective, format!("{}", objective)).changed();
+              }
+            });
+          ui.end_row();
+        });
+        if changed {
+          options.suggestion = None;
+        }
+        if ui.button("Suggest").clicked() {
+          let thruster_mass: f64 = self.calculator.directional_blocks.iter()
+            .filter_map(|(id, counts)| self.data.blocks.thrusters.get(id).map(|b| b.mass(&self.data.components) * counts.iter().sum::<f64>()))
+            .sum();
+          let problem = ThrusterMixProblem {
+            environments: options.environments.to_vec(),
+            target_acceleration: options.target_acceleration.clone(),
+            base_mass: self.calculated.total_mass_filled.to_kilograms() - thruster_mass,
+            objective: options.objective,
+          };
+          options.suggestion = Some(suggest_thruster_mix(&self.data, self.grid_size, &self.enabled_mod_ids, &problem));
+        }
+        ui.separator();
+
+        let mut applied = None;
+        match &options.suggestion {
+          Some(Some(mix)) => {
+            ui.grid("Thruster Mix Grid", |ui| {
+              let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+              ui.label("");
+              for direction in Direction::items() {
+                ui.right_align_label(self.calculator.direction_labels.get(direction));
+              }
+              ui.end_row();
+              for (id, counts) in mix.blocks.iter() {
+                let name = self.data.blocks.thrusters.get(id).map(|b| b.name(&self.data.localization)).unwrap_or(id);
+                ui.label(name);
+                for count in counts.iter() {
+                  ui.right_align_label(format!("{}", count));
+                }
+                ui.end_row();
+              }
+            });
+            ui.grid("Thruster Mix Totals Grid", |ui| {
+              let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+              ui.show_row("Thruster Mass", format!("{}", mix.thruster_mass.round()), "kg");
+              ui.show_row("Hydrogen Consumption", format!("{:.2}", mix.hydrogen_consumption), "L/s");
+            });
+            ui.separator();
+
+            let mut mixed = self.calculator.clone();
+            mixed.directional_blocks.retain(|id, _| !self.data.blocks.thrusters.contains_key(id));
+            mixed.directional_blocks.extend(mix.blocks.iter().map(|(id, counts)| (id.clone(), counts.clone())));
+            let mixed_calculated = self.calculated_cache.get_or_calculate(mixed.digest(), || mixed.calculate(&self.data));
+            ui.grid("Thruster Mix Comparison Grid", |ui| {
+              show_key_stats_comparison(ui, self.number_separator_policy, &self.calculated, &mixed_calculated);
+            });
+            applied = Some((mixed, mixed_calculated));
+          }
+          Some(None) => {
+            ui.colored_label(ui.visuals().warn_fg_color, "No mix of available thrusters reaches the target accelerations.");
+          }
+          None => {}
+        }
+        ui.separator();
+
+        ui.horizontal(|ui| {
+          if ui.add_enabled(applied.is_some(), Button::new("Apply")).clicked() {
+            if let Some((mixed, mixed_calculated)) = applied {
+              self.calculator = mixed;
+              self.calculated = mixed_calculated;
+              self.current_calculator_saved = false;
+            }
+            close = true;
+          }
+          if ui.button("Cancel").clicked() {
+            close = true;
+          }
+        });
+      });
+    if close {
+      self.enable_gui = true;
+    } else {
+      self.show_thruster_mix_window = Some(options);
+    }
+  }
+}
@@ -0,0 +1,56 @@
+use egui::{ComboBox, Context, RichText, ScrollArea, Window};
+
+use secalc_core::grid::thruster_ranking::{rank_thrusters, ThrusterRankOrder};
+use secalc_widgets::extensions::UiExtensions;
+use secalc_widgets::result::ResultUi;
+
+use crate::App;
+
+impl App {
+  pub fn show_thruster_ranking_window(&mut self, ctx: &Context) {
+    let mut show = self.show_thruster_ranking_window;
+    Window::new("Choose a Thruster")
+      .open(&mut show)
+      .collapsible(false)
+      .show(ctx, |ui| {
+        ui.horizontal(|ui| {
+          ui.label("Rank by");
+          ComboBox::from_id_source("Thruster Rank Order")
+            .selected_text(format!("{}", self.thruster_rank_order))
+            .show_ui(ui, |ui| {
+              for order in ThrusterRankOrder::items() {
+                ui.selectable_value(&mut self.thruster_rank_order, order, format!("{}", order));
+              }
+            });
+        });
+        ui.label(format!("{} grid thrusters at {:.0}% planetary influence, best first. Thrusters that produce no force at this planetary influence are not shown.", self.grid_size, self.calculator.planetary_influence * 100.0));
+        ui.separator();
+        let ranks = rank_thrusters(&self.data, self.grid_size, &self.enabled_mod_ids, self.calculator.planetary_influence, self.thruster_rank_order);
+        ScrollArea::vertical().show(ui, |ui| {
+          ui.grid("Thruster Ranking Grid", |ui| {
+            let mut ui = ResultUi::new(ui, self.number_separator_policy, self.duration_format);
+            ui.label(RichText::new("Thruster").strong());
+            ui.label(RichText::new("Type").strong());
+            ui.right_align_label(RichText::new("Force").strong());
+            ui.right_align_label(RichText::new("Consumption").strong());
+            ui.right_align_label(RichText::new("Force/Consumption").strong());
+            ui.right_align_label(RichText::new("Mass").strong());
+            ui.right_align_label(RichText::new("Force/Mass").strong());
+            ui.end_row();
+            for rank in ranks {
+              let unit = rank.resource.unit();
+              ui.label(rank.block.name(&self.data.localization));
+              ui.label(format!("{}", rank.block.details.ty));
+              ui.right_align_value_with_unit(format!("{:.2}", rank.force / 1000.0), "kN");
+              ui.right_align_value_with_unit(format!("{:.2}", rank.consumption), unit);
+              ui.right_align_value_with_unit(format!("{:.2}", rank.force_per_consumption / 1000.0), format!("kN/{}", unit));
+              ui.right_align_value_with_unit(format!("{:.0}", rank.mass), "kg");
+              ui.right_align_value_with_unit(format!("{:.2}", rank.force_per_mass), "N/kg");
+              ui.end_row();
+            }
+          });
+        });
+      });
+    self.show_thruster_ranking_window = show;
+  }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use egui::{Align2, CollapsingResponse, Context, Id, LayerId, Order, Rect, RichText, Stroke, Ui, vec2, Window};
+
+use crate::App;
+
+/// Part of the GUI that a tour step highlights.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TourTarget {
+  Options,
+  Blocks,
+  Results,
+  GridMenu,
+}
+
+/// Step of the onboarding tour: explanation `text` with `title`, shown next to the highlighted `target`.
+pub struct TourStep {
+  pub target: TourTarget,
+  pub title: &'static str,
+  pub text: &'static str,
+}
+
+/// Steps of the onboarding tour, in order. Add a step here to extend the tour; new targets must have their area set
+/// with [`Tour::set_target_rect`] where they are shown.
+pub const TOUR_STEPS: &[TourStep] = &[
+  TourStep {
+    target: TourTarget::Options,
+    title: "Options",
+    text: "Options describe the situation of your grid, such as gravity, planetary influence, and how full tanks and \
+      batteries are. Underlined options explain themselves when hovered.",
+  },
+  TourStep {
+    target: TourTarget::Blocks,
+    title: "Blocks",
+    text: "Enter how many of each block your grid has. Thrusters are entered per direction. Numbers are changed by \
+      dragging or by typing, and right-clicking a block shows more actions.",
+  },
+  TourStep {
+    target: TourTarget::Results,
+    title: "Results",
+    text: "Results update as you change options and blocks: acceleration per direction, power and hydrogen balances, \
+      and how long batteries and tanks last. Hover over a result for an explanation.",
+  },
+  TourStep {
+    target: TourTarget::GridMenu,
+    title: "Save and Load",
+    text: "Grids are saved and loaded through the 'Grid' menu. This tour can be shown again from the 'Help' menu.",
+  },
+];
+
+/// State of the onboarding tour.
+#[derive(Default)]
+pub struct Tour {
+  /// Index into [`TOUR_STEPS`] of the current step, or None if the tour is not running.
+  step: Option<usize>,
+  /// Area of each target, as shown in the last frame.
+  target_rects: HashMap<TourTarget, Rect>,
+}
+
+impl Tour {
+  /// Starts the tour at the first step.
+  pub fn start(&mut self) { self.step = Some(0); }
+
+  /// Sets the area of `target` to `rect`, for highlighting it.
+  pub fn set_target_rect(&mut self, target: TourTarget, rect: Rect) {
+    self.target_rects.insert(target, rect);
+  }
+}
+
+/// Gets the visible area of the collapsing header `response` and its body in `ui`, for use as a target area.
+pub fn collapsing_target_rect<R>(ui: &Ui, response: &CollapsingResponse<R>) -> Rect {
+  let rect = response.body_response.as_ref().map_or(response.header_response.rect, |body| response.header_response.rect.union(body.rect));
+  rect.intersect(ui.clip_rect())
+}
+
+impl App {
+  /// Shows the current step of the onboarding tour, if it is running: highlights its target and shows its explanation
+  /// with buttons to go to the next step or skip the tour. Starts the tour when the app is used for the first time.
+  pub fn show_tour(&mut self, ctx: &Context) {
+    if self.first_time {
+      self.tour.start();
+      self.first_time = false;
+    }
+    if !self.enable_gui { return; }
+    let Some(index) = self.tour.step else { return };
+    let Some(step) = TOUR_STEPS.get(index) else {
+      self.tour.step = None;
+      return;
+    };
+
+    let target_rect = self.tour.target_rects.get(&step.target).copied();
+    if let Some(rect) = target_rect {
+      let painter = ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("Tour Highlight")));
+      painter.rect_stroke(rect.shrink(1.0), 0.0, Stroke::new(3.0, ctx.style().visuals.selection.bg_fill));
+    }
+
+    let mut window = Window::new(format!("Tour ({}/{})", index + 1, TOUR_STEPS.len()))
+      .id(Id::new("Tour"))
+      .collapsible(false)
+      .resizable(false)
+      .default_width(300.0);
+    window = match target_rect {
+      // Show the explanation inside the top-left of the target, below the top edge so that the highlight stays visible.
+      Some(rect) => window.fixed_pos(rect.left_top() + vec2(16.0, 32.0)),
+      None => window.anchor(Align2::CENTER_CENTER, [0.0, 0.0]),
+    };
+    let mut next_step = Some(index);
+    window.show(ctx, |ui| {
+      ui.label(RichText::new(step.title).strong());
+      ui.label(step.text);
+      ui.separator();
+      ui.horizontal(|ui| {
+        if index > 0 && ui.button("Back").clicked() {
+          next_step = Some(index - 1);
+        }
+        let is_last = index + 1 == TOUR_STEPS.len();
+        if ui.button(if is_last { "Done" } else { "Next" }).clicked() {
+          next_step = (!is_last).then_some(index + 1);
+        }
+        if !is_last && ui.button("Skip").clicked() {
+          next_step = None;
+        }
+      });
+    });
+    self.tour.step = next_step;
+  }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use egui::{Context, Grid, Window};
+
+use secalc_core::data::blocks::BlockId;
+use secalc_core::grid::duration::Duration;
+
+use crate::App;
+
+/// Statistics of how the calculator is used. Only collected when enabled, and only stored locally with the app state;
+/// never sent anywhere.
+#[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct UsageStats {
+  /// Number of calculations, including calculations answered from the cache
+  pub calculations: u64,
+  /// Number of edits of the count of each block
+  pub block_edits: HashMap<BlockId, u64>,
+  /// Number of sessions (app starts)
+  pub sessions: u64,
+  /// Total time spent in the app across sessions (s)
+  pub session_seconds: f64,
+}
+
+impl UsageStats {
+  /// Gets the `count` most edited blocks with their edit count, most edited first.
+  pub fn most_edited_blocks(&self, count: usize) -> Vec<(&BlockId, u64)> {
+    let mut blocks: Vec<_> = self.block_edits.iter().map(|(id, edits)| (id, *edits)).collect();
+    blocks.sort_by(|(id_a, edits_a), (id_b, edits_b)| edits_b.cmp(edits_a).then_with(|| id_a.cmp(id_b)));
+    blocks.truncate(count);
+    blocks
+  }
+}
+
+impl App {
+  /// Records a calculation in the usage statistics, if enabled.
+  pub fn record_calculation(&mut self) {
+    if !self.usage_stats_enabled { return; }
+    self.usage_stats.calculations += 1;
+  }
+
+  /// Records edits of the counts of blocks `ids` in the usage statistics, if enabled.
+  pub fn record_block_edits(&mut self, ids: impl IntoIterator<Item=BlockId>) {
+    if !self.usage_stats_enabled { return; }
+    for id in ids {
+      *self.usage_stats.block_edits.entry(id).or_default() += 1;
+    }
+  }
+
+  /// Records the time passed since the previous frame in the usage statistics, if enabled. A new session is recorded on
+  /// the first frame.
+  pub fn record_session_time(&mut self, ctx: &Context) {
+    let time = ctx.input(|i| i.time);
+    let previous_time = self.usage_session_time.replace(time);
+    if !self.usage_stats_enabled { return; }
+    match previous_time {
+      Some(previous_time) => self.usage_stats.session_seconds += (time - previous_time).max(0.0),
+      None => self.usage_stats.sessions += 1,
+    }
+  }
+
+  pub fn show_usage_stats_window(&mut self, ctx: &Context) {
+    let mut show = self.show_usage_stats_window;
+    Window::new("Usage Statistics")
+      .open(&mut show)
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        ui.label("Statistics of how you use the calculator. Only collected when enabled, and only stored locally on this device; never sent anywhere.");
+        ui.checkbox(&mut self.usage_stats_enabled, "Collect Usage Statistics");
+        ui.separator();
+        let stats = &self.usage_stats;
+        Grid::new("Usage Statistics Grid").striped(true).show(ui, |ui| {
+          ui.label("Calculations");
+          ui.label(format!("{}", stats.calculations));
+          ui.end_row();
+          ui.label("Sessions");
+          ui.label(format!("{}", stats.sessions));
+          ui.end_row();
+          ui.label("Session Time");
+          ui.label(Duration::from_seconds(stats.session_seconds).format(self.duration_format, 0));
+          ui.end_row();
+        });
+        ui.separator();
+        ui.label("Most Edited Blocks");
+        let most_edited_blocks = stats.most_edited_blocks(10);
+        if most_edited_blocks.is_empty() {
+          ui.label("No blocks edited yet.");
+        } else {
+          Grid::new("Usage Statistics Blocks Grid").striped(true).show(ui, |ui| {
+            for (id, edits) in most_edited_blocks {
+              let name = self.data.blocks.get_data(id).map_or(id.as_str(), |b| b.name(&self.data.localization));
+              ui.label(name);
+              ui.label(format!("{}", edits));
+              ui.end_row();
+            }
+          });
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+          if ui.button("Copy as JSON").on_hover_text("Copy the usage statistics as JSON, for keeping or analysing them elsewhere.").clicked() {
+            let json = serde_json::to_string_pretty(&self.usage_stats).expect("Cannot serialize usage statistics");
+            ui.output_mut(|o| o.copied_text = json);
+          }
+          if ui.button("Reset").clicked() {
+            self.usage_stats = UsageStats::default();
+          }
+        });
+      });
+    self.show_usage_stats_window = show;
+  }
+}
@@ -1,8 +1,10 @@
 use eframe::App as AppT;
-use egui::{Align2, Context, DragValue, Grid, RichText, ScrollArea, Window};
+use egui::{Align2, ComboBox, Context, DragValue, Grid, RichText, ScrollArea, Window};
+
+use secalc_core::grid::duration::DurationFormat;
+use secalc_widgets::extensions::UiExtensions;
 
 use crate::App;
-use crate::widget::UiExtensions;
 
 impl App {
   pub fn show_settings_windows(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
@@ -12,6 +14,16 @@ impl App {
 
     self.show_settings_window(ctx, frame);
     self.show_about_window(ctx);
+    self.show_data_problems_window(ctx);
+    self.show_thruster_ranking_window(ctx);
+    self.show_sensitivity_window(ctx);
+    self.show_dock_charging_window(ctx);
+    self.show_fleet_window(ctx);
+    self.show_grid_search_window(ctx);
+    self.show_server_profile_window(ctx);
+    self.show_block_overrides_window(ctx);
+    self.show_usage_stats_window(ctx);
+    self.show_block_data_window(ctx);
 
     // EGUI Debug windows
     Window::new("GUI Settings")
@@ -23,6 +35,25 @@ impl App {
     Window::new("GUI Memory")
       .open(&mut self.show_debug_gui_memory_window)
       .show(ctx, |ui| { ctx.memory_ui(ui) });
+    let calculated_cache = &mut self.calculated_cache;
+    Window::new("Calculation Cache")
+      .open(&mut self.show_debug_calculation_cache_window)
+      .show(ctx, |ui| {
+        Grid::new("Calculation Cache Grid").striped(true).show(ui, |ui| {
+          ui.label("Entries");
+          ui.label(format!("{} / {}", calculated_cache.len(), calculated_cache.capacity()));
+          ui.end_row();
+          ui.label("Hits");
+          ui.label(format!("{}", calculated_cache.hits()));
+          ui.end_row();
+          ui.label("Misses");
+          ui.label(format!("{}", calculated_cache.misses()));
+          ui.end_row();
+        });
+        if ui.button("Clear").clicked() {
+          calculated_cache.clear();
+        }
+      });
   }
 
   fn show_settings_window(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
@@ -53,6 +84,15 @@ impl App {
                 self.apply_style(ctx);
               }
               ui.end_row();
+              ui.label("Duration format");
+              ComboBox::from_id_source("Duration Format")
+                .selected_text(format!("{}", self.duration_format))
+                .show_ui(ui, |ui| {
+                  for format in DurationFormat::items() {
+                    ui.selectable_value(&mut self.duration_format, format, format!("{}", format));
+                  }
+                });
+              ui.end_row();
             });
             ui.open_collapsing_header_with_grid("Mods", |ui| {
               for m in self.data.mods.iter() {
@@ -69,6 +109,7 @@ impl App {
                 ui.end_row();
               }
             });
+            self.show_key_bindings_settings(ui);
           });
         ui.separator();
         ui.horizontal(|ui| {
@@ -85,12 +126,31 @@ impl App {
     self.show_settings_window = show && !close;
   }
 
-  fn show_about_window(&mut self, ctx: &Context) {
-    if self.first_time {
-      self.show_about_window = true;
-      self.first_time = false;
-    }
+  fn show_data_problems_window(&mut self, ctx: &Context) {
+    let mut show = self.show_data_problems_window;
+    let mut close = false;
+    Window::new("Data Problems")
+      .open(&mut show)
+      .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+      .collapsible(false)
+      .fixed_size([600.0, 400.0])
+      .show(ctx, |ui| {
+        ui.colored_label(ui.visuals().warn_fg_color, format!("The game data has {} problem(s), and may be incomplete. Blocks with problems may be missing mass or produce wrong results. Extracting the data again may fix these problems.", self.data_validation.problems.len()));
+        ui.separator();
+        ScrollArea::vertical().max_height(300.0).auto_shrink([false; 2]).show(ui, |ui| {
+          for problem in &self.data_validation.problems {
+            ui.label(problem.to_string());
+          }
+        });
+        ui.separator();
+        if ui.button("Close").clicked() {
+          close = true;
+        }
+      });
+    self.show_data_problems_window = show && !close;
+  }
 
+  fn show_about_window(&mut self, ctx: &Context) {
     let mut show = self.show_about_window;
     let mut close = false;
     Window::new("About")
@@ -117,6 +177,12 @@ impl App {
         });
         ui.separator();
         Grid::new("Links Grid").show(ui, |ui| {
+          ui.label(RichText::new("Version").strong());
+          ui.label(env!("CARGO_PKG_VERSION"));
+          ui.end_row();
+          ui.label(RichText::new("Data Version").strong());
+          ui.label(self.data.version.as_deref().unwrap_or("unknown"));
+          ui.end_row();
           ui.label(RichText::new("Home").strong());
           ui.url_link("github.com/Gohla/space-engineers-calculator", "https://github.com/Gohla/space-engineers-calculator");
           ui.end_row();
@@ -141,7 +207,7 @@ impl App {
   }
 }
 
-const ABOUT_TEXT: &'static str = "Space Engineers Calculator is a handy app to calculate whether \
+const ABOUT_TEXT: &str = "Space Engineers Calculator is a handy app to calculate whether \
 your grid (ship) design has enough thrust, power generation, and hydrogen generation to keep up.\
 It also calculates charging durations, maximum jump distances, and more.
 
@@ -158,10 +224,10 @@ All numbers can be changed either by text editing, or by dragging the number. Wh
 Shift to make the number change slower.";
 
 #[cfg(target_arch = "wasm32")]
-const STORAGE_TEXT: &'static str = "The data in this calculator is stored whenever you press \
+const STORAGE_TEXT: &str = "The data in this calculator is stored whenever you press \
 'Save' anywhere, in the Local Storage of your browser. If you clean your Local Storage (for this \
 website), all data will be lost.";
 
 #[cfg(not(target_arch = "wasm32"))]
-const STORAGE_TEXT: &'static str = "The data in this calculator is stored whenever you press \
+const STORAGE_TEXT: &str = "The data in this calculator is stored whenever you press \
 'Save' anywhere, in a user-directory appropriate for your operating system.";
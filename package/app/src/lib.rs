@@ -0,0 +1,6 @@
+//! Space Engineers Calculator application: the state, persistence, and GUI of the calculator, independent of how it is
+//! run. The native and web binaries are thin shells that run [`App`] with eframe.
+
+pub use app::App;
+
+mod app;
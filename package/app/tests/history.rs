@@ -0,0 +1,54 @@
+use secalc_app::App;
+use secalc_core::grid::GridCalculator;
+
+fn calculator(additional_mass: f64) -> GridCalculator {
+  GridCalculator { additional_mass, ..GridCalculator::default() }
+}
+
+/// App with changes of the additional mass to 1000 kg and then 2000 kg, recorded 10 s apart.
+fn app() -> App {
+  let mut app = App::default();
+  app.record_history(0.0);
+  app.set_calculator(calculator(1000.0));
+  app.record_history(10.0);
+  app.set_calculator(calculator(2000.0));
+  app.record_history(20.0);
+  app
+}
+
+#[test]
+fn undo_and_redo() {
+  let mut app = app();
+  app.undo();
+  assert_eq!(app.calculator().additional_mass, 1000.0);
+  assert_eq!(app.calculated().total_mass_empty.to_kilograms(), 1000.0);
+  app.undo();
+  assert_eq!(app.calculator().additional_mass, 0.0);
+  assert!(!app.can_undo());
+  app.redo();
+  app.redo();
+  assert_eq!(app.calculator().additional_mass, 2000.0);
+  assert!(!app.can_redo());
+}
+
+#[test]
+fn changes_in_quick_succession_are_merged() {
+  let mut app = app();
+  app.set_calculator(calculator(3000.0));
+  app.record_history(20.5);
+  app.undo();
+  assert_eq!(app.calculator().additional_mass, 1000.0);
+}
+
+#[test]
+fn change_after_undo_clears_redo() {
+  let mut app = app();
+  app.undo();
+  app.record_history(30.0);
+  assert!(app.can_redo());
+  app.set_calculator(calculator(3000.0));
+  app.record_history(40.0);
+  assert!(!app.can_redo());
+  app.undo();
+  assert_eq!(app.calculator().additional_mass, 1000.0);
+}
@@ -0,0 +1,20 @@
+use secalc_app::App;
+
+/// ID of the 'More Batteries' mod, which adds power blocks.
+const MORE_BATTERIES: u64 = 2275681005;
+
+fn power_block_count(app: &App) -> usize {
+  app.data().blocks.power_blocks(app.grid_size(), app.enabled_mod_ids()).count()
+}
+
+#[test]
+fn mod_blocks_are_shown_only_when_enabled() {
+  let mut app = App::default();
+  let count = power_block_count(&app);
+  app.set_mod_enabled(MORE_BATTERIES, true);
+  assert!(app.enabled_mod_ids().contains(&MORE_BATTERIES));
+  assert!(power_block_count(&app) > count);
+  app.set_mod_enabled(MORE_BATTERIES, false);
+  assert!(app.enabled_mod_ids().is_empty());
+  assert_eq!(power_block_count(&app), count);
+}
@@ -0,0 +1,52 @@
+use secalc_app::App;
+use secalc_core::grid::GridCalculator;
+
+fn calculator(additional_mass: f64) -> GridCalculator {
+  GridCalculator { additional_mass, ..GridCalculator::default() }
+}
+
+#[test]
+fn save_as_makes_saved_calculator_current() {
+  let mut app = App::default();
+  app.set_calculator(calculator(1000.0));
+  assert!(!app.is_current_calculator_saved());
+  app.save_calculator_as("Miner".to_string());
+  assert_eq!(app.current_calculator(), Some("Miner"));
+  assert!(app.is_current_calculator_saved());
+  assert_eq!(app.saved_calculators()["Miner"].additional_mass, 1000.0);
+}
+
+#[test]
+fn load_replaces_current_calculator() {
+  let mut app = App::default();
+  app.set_calculator(calculator(1000.0));
+  app.save_calculator_as("Miner".to_string());
+  app.set_calculator(calculator(2000.0));
+  let saved = app.saved_calculators()["Miner"].clone();
+  app.load_calculator("Miner".to_string(), saved);
+  assert_eq!(app.calculator().additional_mass, 1000.0);
+  assert!(app.is_current_calculator_saved());
+  assert_eq!(app.calculated().total_mass_empty.to_kilograms(), 1000.0);
+}
+
+#[test]
+fn delete_current_calculator_makes_it_unsaved() {
+  let mut app = App::default();
+  app.save_calculator_as("Miner".to_string());
+  app.delete_calculator("Miner");
+  assert!(app.saved_calculators().is_empty());
+  assert_eq!(app.current_calculator(), None);
+  assert!(!app.is_current_calculator_saved());
+}
+
+#[test]
+fn reset_restores_defaults() {
+  let mut app = App::default();
+  app.set_calculator(calculator(1000.0));
+  app.save_calculator_as("Miner".to_string());
+  app.reset_calculator();
+  assert_eq!(app.calculator().additional_mass, 0.0);
+  assert_eq!(app.current_calculator(), None);
+  assert!(app.is_current_calculator_saved());
+  assert!(app.saved_calculators().contains_key("Miner"));
+}
@@ -1,12 +1,19 @@
 use std::fs::{File, OpenOptions};
-use std::path::PathBuf;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use steamlocate::SteamDir;
 
+use secalc_core::data::blocks::extract::RawDefinition;
 use secalc_core::data::Data;
-use secalc_core::data::extract::ExtractConfig;
+use secalc_core::data::extract::{CancellationToken, ExtractConfig};
+use secalc_core::grid::GridCalculator;
+use secalc_core::interop::{resource_chain, result_csv};
+use secalc_core::grid::server_profile::ServerProfile;
+use secalc_core::grid::snapshot::Snapshot;
+use secalc_core::schema::{data_schema, grid_calculator_schema, server_profile_schema};
 
 #[derive(Parser, Debug)]
 #[command(name = "SECalc", about = "Space Engineers Calculator")]
@@ -22,9 +29,12 @@ enum Command {
     #[arg(long, short, env = "SECALC_EXTRACT_SE_DIRECTORY")]
     /// Space Engineers directory to extract game data from. Automatically inferred if installed via Steam when not set
     se_directory: Option<PathBuf>,
-    #[arg(long, env = "SECALC_EXTRACT_SE_WORKSHOP_DIRECTORY")]
-    /// Space engineers workshop (mod) directory. Automatically inferred if installed via Steam when not set. No mods are extracted if this directory is not found
-    se_workshop_directory: Option<PathBuf>,
+    #[arg(long = "se-workshop-directory", env = "SECALC_EXTRACT_SE_WORKSHOP_DIRECTORY", value_delimiter = ',')]
+    /// Space engineers workshop (mod) directories, searched in order for each mod. Can be given multiple times, or comma-separated. Can also be a Steam library, SteamCMD installation, or dedicated server instance directory, from which the workshop directory is detected. Automatically inferred if installed via Steam when not set. Mods that are not found are not extracted
+    se_workshop_directories: Vec<PathBuf>,
+    #[arg(long, env = "SECALC_EXTRACT_DATA_VERSION")]
+    /// Version of the game data to store in the extracted data. Set to the Steam build ID of Space Engineers when not set and the Space Engineers directory is inferred
+    data_version: Option<String>,
     #[arg(env = "SECALC_EXTRACT_CONFIG_FILE")]
     /// Extract configuration file
     config_file: PathBuf,
@@ -32,6 +42,107 @@ enum Command {
     #[arg(env = "SECALC_EXTRACT_OUTPUT_FILE")]
     output_file: PathBuf,
   },
+  /// Calculates a grid and writes its result (`GridCalculated`) as JSON or CSV
+  Calculate {
+    #[arg(env = "SECALC_DATA_FILE")]
+    /// Extracted game data file to calculate with
+    data_file: PathBuf,
+    /// Grid file (`GridCalculator` JSON) to calculate
+    grid_file: PathBuf,
+    #[arg(long, value_enum, default_value_t = ResultFormat::Json)]
+    /// Format to write the result in
+    format: ResultFormat,
+    #[arg(long, env = "SECALC_SERVER_PROFILE_FILE")]
+    /// Server profile file (`ServerProfile` JSON) to check the grid against, adding a warning to the result for each
+    /// exceeded limit
+    server_profile_file: Option<PathBuf>,
+    /// File to write the result to. Written to standard output when not set
+    output_file: Option<PathBuf>,
+  },
+  /// Calculates grids in batch: reads one grid (`GridCalculator` JSON) per line from standard input, and writes one
+  /// result (`GridCalculated` JSON) per line to standard output
+  CalculateBatch {
+    #[arg(env = "SECALC_DATA_FILE")]
+    /// Extracted game data file to calculate with
+    data_file: PathBuf,
+    #[arg(long, env = "SECALC_SERVER_PROFILE_FILE")]
+    /// Server profile file (`ServerProfile` JSON) to check grids against, adding a warning to the result for each
+    /// exceeded limit
+    server_profile_file: Option<PathBuf>,
+  },
+  /// Inspects the extracted data of a block, for debugging extraction
+  Inspect {
+    #[arg(env = "SECALC_DATA_FILE")]
+    /// Extracted game data file to inspect
+    data_file: PathBuf,
+    /// ID of the block to inspect, as `<TypeId>.<SubtypeId>` or `<TypeId>.<SubtypeId>@<mod ID>` for blocks from mods
+    block_id: String,
+    #[arg(long)]
+    /// Also print the raw values of the block definitions in the SBC files the block was extracted from, with the file
+    /// and line of each definition
+    raw: bool,
+    #[arg(long, short, env = "SECALC_EXTRACT_SE_DIRECTORY")]
+    /// Space Engineers directory to find raw values in. Automatically inferred if installed via Steam when not set
+    se_directory: Option<PathBuf>,
+    #[arg(long = "se-workshop-directory", env = "SECALC_EXTRACT_SE_WORKSHOP_DIRECTORY", value_delimiter = ',')]
+    /// Space engineers workshop (mod) directories to find raw values in, in the same way as `extract-game-data`. Automatically inferred if installed via Steam when not set
+    se_workshop_directories: Vec<PathBuf>,
+  },
+  /// Writes the power and hydrogen resource chains of a grid as a Graphviz DOT graph, with the flows of the grid's
+  /// calculation
+  ResourceChainDot {
+    #[arg(env = "SECALC_DATA_FILE")]
+    /// Extracted game data file to calculate with
+    data_file: PathBuf,
+    /// Grid file (`GridCalculator` JSON) to calculate
+    grid_file: PathBuf,
+    /// File to write the DOT graph to. Written to standard output when not set
+    output_file: Option<PathBuf>,
+  },
+  /// Calculates all grids in a directory and writes their results to a snapshot file, or with `--check`, compares their
+  /// results against an earlier snapshot file, to detect when a data or formula change alters existing grids
+  Snapshot {
+    #[arg(env = "SECALC_DATA_FILE")]
+    /// Extracted game data file to calculate with
+    data_file: PathBuf,
+    /// Directory with grid files (`GridCalculator` JSON, with a `.json` extension) to calculate. Grids are named after
+    /// their file name without extension
+    grids_directory: PathBuf,
+    /// Snapshot file to write to, or to compare against with `--check`
+    snapshot_file: PathBuf,
+    #[arg(long)]
+    /// Compare results against the snapshot file instead of writing it, failing if any result differs
+    check: bool,
+    #[arg(long, default_value_t = 1e-9)]
+    /// Relative difference up to which numbers in results are considered equal when checking
+    tolerance: f64,
+  },
+  /// Writes the JSON Schema of a file format that SECalc reads
+  Schema {
+    #[arg(value_enum)]
+    /// File format to write the JSON Schema of
+    kind: SchemaKind,
+    /// File to write the JSON Schema to. Written to standard output when not set
+    output_file: Option<PathBuf>,
+  },
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum ResultFormat {
+  /// Pretty-printed JSON
+  Json,
+  /// CSV with a `path,value` row per value, with paths into the JSON result
+  Csv,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum SchemaKind {
+  /// Extracted game data (data.json)
+  Data,
+  /// Saved grid
+  Grid,
+  /// Server profile
+  ServerProfile,
 }
 
 fn main() -> Result<()> {
@@ -41,38 +152,242 @@ fn main() -> Result<()> {
   match cli.command {
     Command::ExtractGameData {
       se_directory,
-      se_workshop_directory,
+      se_workshop_directories,
+      mut data_version,
       config_file,
       output_file
     } => {
-      let se_directory = if let Some(se_directory) = se_directory {
-        se_directory
-      } else {
-        let steam_dir = SteamDir::locate()
-          .context("Space Engineers directory was not set, and could not be inferred due to no Steam installation being found")?;
-        let Some((space_engineers_app, library)) = steam_dir.find_app(244850)? else {
-          return Err(anyhow!("Space Engineers directory was not set, and could not be inferred due to it not being installed via Steam"));
-        };
-        library.resolve_app_dir(&space_engineers_app)
-      };
+      let (se_directory, build_id) = get_se_directory(se_directory)?;
+      if data_version.is_none() {
+        data_version = build_id.map(|build_id| format!("build {}", build_id));
+      }
 
-      let se_workshop_directory = se_workshop_directory.or(get_se_workshop_directory(&se_directory));
+      let se_workshop_directories = get_se_workshop_directories(se_workshop_directories, &se_directory);
 
       let config_reader = File::open(config_file)
         .context("Failed to open extract config file for reading")?;
       let extract_config: ExtractConfig = ron::de::from_reader(config_reader)
         .context("Failed to read extract configuration")?;
-      let data = Data::extract_from_se_dir(se_directory, se_workshop_directory, extract_config)
+      let mut data = Data::extract_from_se_dir_cancellable(se_directory, se_workshop_directories, extract_config, &CancellationToken::default(), |p| {
+        eprintln!("[{}/{}] {}", p.completed + 1, p.total, p.step);
+      })
         .context("Failed to read Space Engineers data")?;
+      data.version = data_version;
+      if !data.mods.overrides.is_empty() {
+        let source = |mod_id: Option<u64>| match mod_id {
+          Some(mod_id) => data.mods.get(&mod_id).map(|m| format!("mod '{}' ({})", m.1, mod_id)).unwrap_or_else(|| format!("mod {}", mod_id)),
+          None => "the game".to_string(),
+        };
+        eprintln!("Warning: {} block definition(s) override an earlier definition with the same TypeId and SubtypeId:", data.mods.overrides.len());
+        for o in &data.mods.overrides {
+          eprintln!("- '{}' from {} overrides the definition from {}", o.id, source(o.mod_id), source(o.overridden_mod_id));
+        }
+      }
+      if !data.localization.fallbacks.is_empty() {
+        eprintln!("Warning: {} localization key(s) could not be resolved in any language:", data.localization.fallbacks.len());
+        for (key, fallback) in &data.localization.fallbacks {
+          eprintln!("- '{}' is shown as '{}'", key, fallback);
+        }
+      }
       let data_writer = OpenOptions::new().write(true).create(true).truncate(true).open(output_file)
         .context("Failed to create a writer for writing game data to file")?;
       data.to_json(data_writer)
         .context("Failed to write game data to file")?;
     }
+    Command::Calculate { data_file, grid_file, format, server_profile_file, output_file } => {
+      let data_reader = File::open(data_file)
+        .context("Failed to open game data file for reading")?;
+      let data = Data::from_json(io::BufReader::new(data_reader))
+        .context("Failed to read game data")?;
+      let grid_reader = File::open(grid_file)
+        .context("Failed to open grid file for reading")?;
+      let calculator = GridCalculator::from_json(io::BufReader::new(grid_reader))
+        .context("Failed to read grid from file")?;
+      let mut calculated = calculator.calculate(&data);
+      if let Some(server_profile_file) = server_profile_file {
+        let server_profile_reader = File::open(server_profile_file)
+          .context("Failed to open server profile file for reading")?;
+        let server_profile = ServerProfile::from_json(io::BufReader::new(server_profile_reader))
+          .context("Failed to read server profile")?;
+        calculated.warnings.extend(server_profile.check(&calculator, &data));
+      }
+      let result = match format {
+        ResultFormat::Json => serde_json::to_string_pretty(&calculated)
+          .context("Failed to serialize result")? + "\n",
+        ResultFormat::Csv => result_csv::to_csv(&calculated),
+      };
+      if let Some(output_file) = output_file {
+        std::fs::write(output_file, result)
+          .context("Failed to write result to file")?;
+      } else {
+        print!("{}", result);
+      }
+    }
+    Command::CalculateBatch { data_file, server_profile_file } => {
+      let data_reader = File::open(data_file)
+        .context("Failed to open game data file for reading")?;
+      let data = Data::from_json(io::BufReader::new(data_reader))
+        .context("Failed to read game data")?;
+      let server_profile = if let Some(server_profile_file) = server_profile_file {
+        let server_profile_reader = File::open(server_profile_file)
+          .context("Failed to open server profile file for reading")?;
+        Some(ServerProfile::from_json(io::BufReader::new(server_profile_reader))
+          .context("Failed to read server profile")?)
+      } else {
+        None
+      };
+      let mut writer = BufWriter::new(io::stdout().lock());
+      for (index, line) in io::stdin().lock().lines().enumerate() {
+        let line = line.context("Failed to read grid from standard input")?;
+        if line.trim().is_empty() { continue; }
+        let calculator = GridCalculator::from_json(line.as_bytes())
+          .with_context(|| format!("Failed to read grid on line {}", index + 1))?;
+        let mut calculated = calculator.calculate(&data);
+        if let Some(server_profile) = &server_profile {
+          calculated.warnings.extend(server_profile.check(&calculator, &data));
+        }
+        serde_json::to_writer(&mut writer, &calculated)
+          .context("Failed to write result to standard output")?;
+        writer.write_all(b"\n")
+          .context("Failed to write result to standard output")?;
+      }
+      writer.flush()
+        .context("Failed to write result to standard output")?;
+    }
+    Command::Inspect { data_file, block_id, raw, se_directory, se_workshop_directories } => {
+      let data_reader = File::open(data_file)
+        .context("Failed to open game data file for reading")?;
+      let data = Data::from_json(io::BufReader::new(data_reader))
+        .context("Failed to read game data")?;
+      // Find the block in the serialized blocks, to print the extracted values of all kinds of blocks in the same way.
+      let blocks = serde_json::to_value(&data.blocks)
+        .context("Failed to serialize blocks")?;
+      let extracted = blocks.as_object()
+        .and_then(|kinds| kinds.iter().find_map(|(kind, blocks)| blocks.get(&block_id).map(|block| (kind, block))));
+      if let Some((kind, block)) = extracted {
+        println!("Extracted values ({}):", kind);
+        println!("{}", serde_json::to_string_pretty(block).context("Failed to serialize block")?);
+      } else {
+        println!("Block '{}' was not found in the extracted data", block_id);
+      }
+
+      if raw {
+        let (se_directory, _) = get_se_directory(se_directory)?;
+        let se_workshop_directories = get_se_workshop_directories(se_workshop_directories, &se_directory);
+        let definitions = RawDefinition::find(&se_directory, se_workshop_directories, &block_id)
+          .context("Failed to find raw block definitions")?;
+        if definitions.is_empty() {
+          println!("No raw definitions of block '{}' were found", block_id);
+        }
+        for definition in definitions {
+          println!();
+          println!("Raw values ({}:{}):", definition.file.display(), definition.line);
+          for (path, value) in definition.values {
+            println!("  {} = {}", path, value);
+          }
+        }
+      }
+    }
+    Command::ResourceChainDot { data_file, grid_file, output_file } => {
+      let data_reader = File::open(data_file)
+        .context("Failed to open data file for reading")?;
+      let data = Data::from_json(io::BufReader::new(data_reader))
+        .context("Failed to read data from file")?;
+      let grid_reader = File::open(grid_file)
+        .context("Failed to open grid file for reading")?;
+      let calculator = GridCalculator::from_json(io::BufReader::new(grid_reader))
+        .context("Failed to read grid from file")?;
+      let dot = resource_chain::to_dot(&calculator, &calculator.calculate(&data));
+      if let Some(output_file) = output_file {
+        std::fs::write(output_file, dot)
+          .context("Failed to write DOT graph to file")?;
+      } else {
+        print!("{}", dot);
+      }
+    }
+    Command::Snapshot { data_file, grids_directory, snapshot_file, check, tolerance } => {
+      let data_reader = File::open(data_file)
+        .context("Failed to open data file for reading")?;
+      let data = Data::from_json(io::BufReader::new(data_reader))
+        .context("Failed to read data from file")?;
+      let mut grid_files: Vec<PathBuf> = std::fs::read_dir(&grids_directory)
+        .with_context(|| format!("Failed to read grids directory '{}'", grids_directory.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "json"))
+        .collect();
+      grid_files.sort();
+      let mut grids = Vec::with_capacity(grid_files.len());
+      for grid_file in grid_files {
+        let grid_reader = File::open(&grid_file)
+          .with_context(|| format!("Failed to open grid file '{}' for reading", grid_file.display()))?;
+        let calculator = GridCalculator::from_json(io::BufReader::new(grid_reader))
+          .with_context(|| format!("Failed to read grid from file '{}'", grid_file.display()))?;
+        let name = grid_file.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        grids.push((name, calculator));
+      }
+      let snapshot = Snapshot::calculate(grids.iter().map(|(name, calculator)| (name.clone(), calculator)), &data);
+      if check {
+        let snapshot_reader = File::open(&snapshot_file)
+          .context("Failed to open snapshot file for reading")?;
+        let expected = Snapshot::from_json(io::BufReader::new(snapshot_reader))
+          .context("Failed to read snapshot from file")?;
+        let differences = expected.diff(&snapshot, tolerance);
+        if !differences.is_empty() {
+          for difference in &differences {
+            println!("{}", difference);
+          }
+          return Err(anyhow!("{} result(s) differ from snapshot '{}'", differences.len(), snapshot_file.display()));
+        }
+        println!("Results of {} grid(s) match snapshot '{}'", snapshot.grids.len(), snapshot_file.display());
+      } else {
+        let snapshot_writer = OpenOptions::new().write(true).create(true).truncate(true).open(&snapshot_file)
+          .context("Failed to create a writer for writing snapshot to file")?;
+        snapshot.to_json(BufWriter::new(snapshot_writer))
+          .context("Failed to write snapshot to file")?;
+      }
+    }
+    Command::Schema { kind, output_file } => {
+      let schema = match kind {
+        SchemaKind::Data => data_schema(),
+        SchemaKind::Grid => grid_calculator_schema(),
+        SchemaKind::ServerProfile => server_profile_schema(),
+      };
+      if let Some(output_file) = output_file {
+        let schema_writer = OpenOptions::new().write(true).create(true).truncate(true).open(output_file)
+          .context("Failed to create a writer for writing JSON Schema to file")?;
+        serde_json::to_writer_pretty(schema_writer, &schema)
+          .context("Failed to write JSON Schema to file")?;
+      } else {
+        serde_json::to_writer_pretty(io::stdout().lock(), &schema)
+          .context("Failed to write JSON Schema to standard output")?;
+        println!();
+      }
+    }
   }
   Ok(())
 }
 
-fn get_se_workshop_directory(se_directory: &PathBuf) -> Option<PathBuf> {
+/// Gets `se_directory`, or infers the Space Engineers directory from the Steam installation when None. Also returns
+/// the Steam build ID of Space Engineers when inferred.
+fn get_se_directory(se_directory: Option<PathBuf>) -> Result<(PathBuf, Option<u64>)> {
+  if let Some(se_directory) = se_directory {
+    return Ok((se_directory, None));
+  }
+  let steam_dir = SteamDir::locate()
+    .context("Space Engineers directory was not set, and could not be inferred due to no Steam installation being found")?;
+  let Some((space_engineers_app, library)) = steam_dir.find_app(244850)? else {
+    return Err(anyhow!("Space Engineers directory was not set, and could not be inferred due to it not being installed via Steam"));
+  };
+  Ok((library.resolve_app_dir(&space_engineers_app), space_engineers_app.build_id))
+}
+
+/// Gets `se_workshop_directories`, or infers the workshop directory from the `steamapps` directory that
+/// `se_directory` is installed in when empty. This also works for dedicated servers installed with SteamCMD.
+fn get_se_workshop_directories(se_workshop_directories: Vec<PathBuf>, se_directory: &Path) -> Vec<PathBuf> {
+  if !se_workshop_directories.is_empty() {
+    return se_workshop_directories;
+  }
   se_directory.parent().and_then(|common_dir| common_dir.parent().map(|steamapps_dir| steamapps_dir.join("workshop/content/244850")))
+    .into_iter()
+    .collect()
 }
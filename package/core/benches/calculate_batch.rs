@@ -0,0 +1,55 @@
+//! Measures the throughput of batch calculation as done by the `calculate-batch` CLI command: reading a grid from JSON,
+//! calculating it with a single loaded `Data`, and writing the result to JSON. Run with `cargo bench -p secalc_core`.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use secalc_core::data::Data;
+use secalc_core::grid::direction::Direction;
+use secalc_core::grid::GridCalculator;
+
+const CALCULATIONS: usize = 10_000;
+
+fn main() {
+  let data_file = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/data.json");
+  let data = Data::from_json(BufReader::new(File::open(data_file).expect("Cannot open data file"))).expect("Cannot read data");
+
+  // A grid with a mix of blocks, so that most of the calculation is exercised.
+  let mut calculator = GridCalculator::new();
+  for (id, count) in [
+    ("BatteryBlock.LargeBlockBatteryBlock", 4.0),
+    ("Reactor.LargeBlockSmallGenerator", 2.0),
+    ("OxygenGenerator.", 2.0),
+    ("OxygenTank.LargeHydrogenTank", 2.0),
+    ("CargoContainer.LargeBlockLargeContainer", 4.0),
+    ("Drill.LargeBlockDrill", 8.0),
+  ] {
+    calculator.blocks.insert(id.to_string(), count);
+  }
+  for direction in Direction::items() {
+    calculator.directional_blocks.entry("Thrust.LargeBlockLargeHydrogenThrust".to_string()).or_default()[direction] = 4.0;
+    calculator.directional_blocks.entry("Thrust.LargeBlockLargeThrust".to_string()).or_default()[direction] = 2.0;
+  }
+  let lines: Vec<String> = (0..CALCULATIONS).map(|i| {
+    calculator.additional_mass = i as f64 * 100.0;
+    serde_json::to_string(&calculator).unwrap()
+  }).collect();
+
+  let start = Instant::now();
+  let mut output_bytes = 0;
+  for line in &lines {
+    let calculator = GridCalculator::from_json(line.as_bytes()).unwrap();
+    let calculated = calculator.calculate(&data);
+    output_bytes += serde_json::to_string(&calculated).unwrap().len();
+  }
+  let elapsed = start.elapsed();
+  println!(
+    "{} calculations in {:.2?} ({:.0} calculations per second, {} bytes of output)",
+    CALCULATIONS,
+    elapsed,
+    CALCULATIONS as f64 / elapsed.as_secs_f64(),
+    output_bytes,
+  );
+}
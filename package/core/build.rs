@@ -1,7 +1,10 @@
 #[rustversion::nightly]
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(nightly)");
     println!("cargo:rustc-cfg=nightly");
 }
 
 #[rustversion::not(nightly)]
-fn main() {}
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(nightly)");
+}
@@ -1,5 +1,5 @@
 use std::backtrace::Backtrace;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use hashlink::LinkedHashMap;
@@ -9,11 +9,15 @@ use thiserror::Error;
 use walkdir::WalkDir;
 
 use crate::data::blocks::*;
+use crate::data::cube_sizes::CubeSizes;
+use crate::data::extract::{find_mod_workshop_directory, resolve_workshop_directory};
+use crate::data::mods::ModOverride;
 use crate::xml::{NodeExt, read_string_from_file, XmlError};
 
 // Block definition
 
 impl BlockData {
+  #[allow(clippy::too_many_arguments)]
   pub fn from_def(
     def: &Node,
     localization: &Localization,
@@ -35,6 +39,7 @@ impl BlockData {
       format!("{}.{}", type_id, subtype_id)
     };
     let name: String = def.parse_child_elem("DisplayName")?;
+    let description: Option<String> = def.parse_child_elem_opt("Description")?;
     let mut components = LinkedHashMap::new();
     let size = GridSize::from_def(def)?;
     for component in def.child_elem("Components")?.children_elems("Component") {
@@ -43,6 +48,8 @@ impl BlockData {
       *components.entry(component_id).or_insert(0.0) += count;
     }
     let has_physics = def.parse_child_elem_opt("HasPhysics")?.unwrap_or(true);
+    let pcu = Some(def.parse_child_elem_opt("PCU")?.unwrap_or(1)); // Blocks without PCU cost 1 PCU in the game.
+    let build_time = Some(def.parse_child_elem_opt("BuildTimeSeconds")?.unwrap_or(10.0)); // Default build time in the game.
 
     let localized_name = localization.get(&name);
     let public = def.child_elem_opt("Public").and_then(|n| n.text().map(|t| t.parse::<bool>().unwrap_or(true))).unwrap_or(true);
@@ -55,12 +62,12 @@ impl BlockData {
     };
     let rename = Self::rename(localized_name, rename_block_by_regex);
 
-    Ok(BlockData { id, name, size, components, has_physics, mod_id, hidden, rename })
+    Ok(BlockData { id, name, description, size, components, has_physics, pcu, build_time, mod_id, source: None, conveyor_ports: None, hidden, rename })
   }
 
   fn is_hidden(name: &str, hide_block_by_exact_name: &HashSet<String>, hide_block_by_regex_name: &RegexSet) -> bool {
     if hide_block_by_exact_name.contains(name) { return true; }
-    return hide_block_by_regex_name.is_match(name);
+    hide_block_by_regex_name.is_match(name)
   }
 
   fn rename(name: &str, rename_block_by_regex: &[(Regex, String)]) -> Option<String> {
@@ -75,12 +82,7 @@ impl BlockData {
 
 impl GridSize {
   pub fn from_def(def: &Node) -> Result<Self, XmlError> {
-    let size = match def.child_elem("CubeSize")?.text_or_err()? {
-      "Small" => GridSize::Small,
-      "Large" => GridSize::Large,
-      t => panic!("Unrecognized grid size {}", t),
-    };
-    Ok(size)
+    def.parse_child_elem("CubeSize")
   }
 }
 
@@ -113,6 +115,33 @@ impl JumpDrive {
   }
 }
 
+impl Gyroscope {
+  pub fn from_def(def: &Node) -> Result<Self, XmlError> {
+    let torque = def.parse_child_elem("ForceMagnitude")?;
+    let operational_power_consumption = def.parse_child_elem("RequiredPowerInput")?;
+    Ok(Self { torque, operational_power_consumption })
+  }
+}
+
+impl Refinery {
+  pub fn from_def(def: &Node) -> Result<Self, XmlError> {
+    let refine_speed = def.parse_child_elem_opt("RefineSpeed")?.unwrap_or(1.0);
+    let material_efficiency = def.parse_child_elem_opt("MaterialEfficiency")?.unwrap_or(1.0);
+    let operational_power_consumption = def.parse_child_elem("OperationalPowerConsumption")?;
+    let idle_power_consumption = def.parse_child_elem("StandbyPowerConsumption")?;
+    Ok(Self { refine_speed, material_efficiency, operational_power_consumption, idle_power_consumption })
+  }
+}
+
+impl Assembler {
+  pub fn from_def(def: &Node) -> Result<Self, XmlError> {
+    let assembly_speed = def.parse_child_elem_opt("AssemblySpeed")?.unwrap_or(1.0);
+    let operational_power_consumption = def.parse_child_elem("OperationalPowerConsumption")?;
+    let idle_power_consumption = def.parse_child_elem("StandbyPowerConsumption")?;
+    Ok(Self { assembly_speed, operational_power_consumption, idle_power_consumption })
+  }
+}
+
 impl Railgun {
   pub fn from_def(def: &Node, entity_components: &Node) -> Result<Self, XmlError> {
     let mut capacity = None;
@@ -245,11 +274,38 @@ impl HydrogenTank {
   }
 }
 
+impl OxygenTank {
+  fn from_def(def: &Node) -> Result<Self, XmlError> {
+    let capacity = def.parse_child_elem("Capacity")?;
+    let operational_power_consumption = def.parse_child_elem("OperationalPowerConsumption")?;
+    let idle_power_consumption = def.parse_child_elem("StandbyPowerConsumption")?;
+    Ok(Self { capacity, operational_power_consumption, idle_power_consumption })
+  }
+}
+
+impl OxygenFarm {
+  fn from_def(def: &Node) -> Result<Self, XmlError> {
+    let oxygen_generation = def.parse_child_elem("MaxGasOutput")?;
+    let operational_power_consumption = def.parse_child_elem("OperationalPowerConsumption")?;
+    Ok(Self { oxygen_generation, operational_power_consumption })
+  }
+}
+
+impl AirVent {
+  fn from_def(def: &Node) -> Result<Self, XmlError> {
+    let oxygen_consumption = def.parse_child_elem("VentilationCapacityPerSecond")?;
+    let operational_power_consumption = def.parse_child_elem("OperationalPowerConsumption")?;
+    let idle_power_consumption = def.parse_child_elem("StandbyPowerConsumption")?;
+    Ok(Self { oxygen_consumption, operational_power_consumption, idle_power_consumption })
+  }
+}
+
 impl Container {
   fn from_def(def: &Node, entity_components: &Node) -> Result<Self, XmlError> {
     let subtype_id: String = def.child_elem("Id")?.parse_child_elem("SubtypeId")?;
     let mut inventory_volume_any = None;
     let mut store_any = None;
+    let mut constraint = InventoryConstraint::default();
     for entity_component in entity_components.children_elems("EntityComponent") {
       if let Some("MyObjectBuilder_InventoryComponentDefinition") = entity_component.attribute(("http://www.w3.org/2001/XMLSchema-instance", "type")) {
         let entity_component_subtype_id: String = entity_component.child_elem("Id")?.parse_child_elem("SubtypeId")?;
@@ -259,25 +315,51 @@ impl Container {
         let y: f64 = size.parse_attribute("y")?;
         let z: f64 = size.parse_attribute("z")?;
         inventory_volume_any = Some(x * y * z * VOLUME_MULTIPLIER);
-        store_any = Some(entity_component.child_elem_opt("InputConstraint").map_or(true, |_| false));
+        let input_constraint = entity_component.child_elem_opt("InputConstraint");
+        store_any = Some(input_constraint.is_none());
+        if let Some(input_constraint) = input_constraint {
+          constraint = Self::classify_constraint(&input_constraint);
+        }
         break;
       }
     }
     if let (Some(inventory_volume_any), Some(store_any)) = (inventory_volume_any, store_any) {
-      Ok(Self { inventory_volume_any, store_any })
+      Ok(Self { inventory_volume_any, store_any, constraint })
     } else {
       Err(XmlError::StructureFail(Backtrace::capture()))
     }
   }
+
+  /// Classifies an `InputConstraint` node by the items its whitelist accepts. Blacklists, mixed whitelists, and empty
+  /// whitelists are classified as other.
+  fn classify_constraint(input_constraint: &Node) -> InventoryConstraint {
+    if input_constraint.attribute("IsWhitelist").is_some_and(|w| w.eq_ignore_ascii_case("false")) {
+      return InventoryConstraint::Other;
+    }
+    let entries: Vec<_> = input_constraint.children_elems("Entry")
+      .map(|e| (e.attribute("Type").unwrap_or_default(), e.attribute("Subtype")))
+      .collect();
+    if entries.is_empty() {
+      InventoryConstraint::Other
+    } else if entries.iter().all(|(ty, subtype)| *ty == "MyObjectBuilder_Ore" && *subtype == Some("Ice")) {
+      InventoryConstraint::Ice
+    } else if entries.iter().all(|(ty, _)| *ty == "MyObjectBuilder_Ore") {
+      InventoryConstraint::Ore
+    } else if entries.iter().all(|(ty, _)| *ty == "MyObjectBuilder_Component") {
+      InventoryConstraint::Components
+    } else {
+      InventoryConstraint::Other
+    }
+  }
 }
 
 impl Connector {
-  fn from_def(def: &Node, data: &BlockData) -> Result<Self, XmlError> {
+  fn from_def(def: &Node, cube_size: f64) -> Result<Self, XmlError> {
     let size = def.child_elem("Size")?;
     let x: f64 = size.parse_attribute("x")?;
     let y: f64 = size.parse_attribute("y")?;
     let z: f64 = size.parse_attribute("z")?;
-    let multiplier = data.size.size() * 0.8;
+    let multiplier = cube_size * 0.8;
     let inventory_volume_any = (x * multiplier) * (y * multiplier) * (z * multiplier) * VOLUME_MULTIPLIER; // Inventory capacity according to MyShipConnector.cs.
     Ok(Self { inventory_volume_any, })
   }
@@ -293,16 +375,35 @@ impl Cockpit {
 
 
 impl Drill {
-  fn from_def(def: &Node, data: &BlockData) -> Result<Self, XmlError> {
+  fn from_def(def: &Node, data: &BlockData, cube_size: f64) -> Result<Self, XmlError> {
     let size = def.child_elem("Size")?;
     let x: f64 = size.parse_attribute("x")?;
     let y: f64 = size.parse_attribute("y")?;
     let z: f64 = size.parse_attribute("z")?;
-    let cube_size = data.size.size();
     let inventory_volume_ore = x * y * z * cube_size * cube_size * cube_size * 0.5 * VOLUME_MULTIPLIER; // Inventory capacity according to MyShipDrill.cs.
     let operational_power_consumption = 1.0 / 500.0 * 1.0; // Maximum required power according to ComputeMaxRequiredPower in MyShipDrill.cs.
     let idle_power_consumption = 1e-06; // Idle power according to ComputeMaxRequiredPower in MyShipDrill.cs.
-    Ok(Self { inventory_volume_ore, operational_power_consumption, idle_power_consumption })
+    let cut_out_radius: f64 = def.parse_child_elem_opt("CutOutRadius")?.unwrap_or(match data.size {
+      GridSize::Small => 1.3,
+      GridSize::Large => 1.9,
+    });
+    // Best case: a full sphere of ore is cut out every drill interval of 90 frames (1.5 s) in MyDrillBase.cs.
+    let ore_rate = (4.0 / 3.0) * std::f64::consts::PI * cut_out_radius.powi(3) * VOLUME_MULTIPLIER / 1.5;
+    Ok(Self { inventory_volume_ore, operational_power_consumption, idle_power_consumption, ore_rate })
+  }
+}
+
+impl Weapon {
+  /// Required power of turrets according to MAX_REQUIRED_POWER_TURRET in MyEnergyConstants.cs.
+  const TURRET_POWER_CONSUMPTION: f64 = 0.002;
+  /// Required power of fixed weapons according to MAX_REQUIRED_POWER_SHIP_GUN in MyEnergyConstants.cs.
+  const FIXED_WEAPON_POWER_CONSUMPTION: f64 = 0.0002;
+
+  fn from_def(def: &Node, is_turret: bool) -> Result<Self, XmlError> {
+    let inventory_volume_ammo = def.parse_child_elem_opt::<f64>("InventoryMaxVolume")?.unwrap_or(0.0) * VOLUME_MULTIPLIER;
+    let power_consumption = if is_turret { Self::TURRET_POWER_CONSUMPTION } else { Self::FIXED_WEAPON_POWER_CONSUMPTION };
+    // Weapons draw their required power whenever they are on, whether they are shooting or not.
+    Ok(Self { inventory_volume_ammo, operational_power_consumption: power_consumption, idle_power_consumption: power_consumption })
   }
 }
 
@@ -327,10 +428,29 @@ pub struct BlocksBuilder {
   reactors: Vec<Block<Reactor>>,
   generators: Vec<Block<Generator>>,
   hydrogen_tanks: Vec<Block<HydrogenTank>>,
+  oxygen_tanks: Vec<Block<OxygenTank>>,
+  oxygen_farms: Vec<Block<OxygenFarm>>,
+  air_vents: Vec<Block<AirVent>>,
   containers: Vec<Block<Container>>,
   connectors: Vec<Block<Connector>>,
   cockpits: Vec<Block<Cockpit>>,
   drills: Vec<Block<Drill>>,
+  gyroscopes: Vec<Block<Gyroscope>>,
+  refineries: Vec<Block<Refinery>>,
+  assemblers: Vec<Block<Assembler>>,
+  turrets: Vec<Block<Weapon>>,
+  fixed_weapons: Vec<Block<Weapon>>,
+  interior_turrets: Vec<Block<Weapon>>,
+
+  /// Whether to record the source file and line of each block definition in its block data.
+  record_sources: bool,
+  /// Conveyor ports to assign to blocks by their exact ID.
+  conveyor_ports: HashMap<String, ConveyorPorts>,
+  /// Cube sizes to calculate size-dependent block properties with.
+  cube_sizes: CubeSizes,
+  /// Source of each TypeId.SubtypeId definition: None for the game itself, or the ID of the mod.
+  definition_sources: LinkedHashMap<String, Option<u64>>,
+  overrides: Vec<ModOverride>,
 }
 
 #[derive(Error, Debug)]
@@ -369,6 +489,10 @@ impl BlocksBuilder {
       hide_block_by_regex_id,
       rename_block_by_regex,
 
+      record_sources: false,
+      conveyor_ports: HashMap::new(),
+      cube_sizes: CubeSizes::default(),
+
       batteries: vec![],
       jump_drives: vec![],
       railguns: vec![],
@@ -378,10 +502,22 @@ impl BlocksBuilder {
       reactors: vec![],
       generators: vec![],
       hydrogen_tanks: vec![],
+      oxygen_tanks: vec![],
+      oxygen_farms: vec![],
+      air_vents: vec![],
       containers: vec![],
       connectors: vec![],
       cockpits: vec![],
-      drills: vec![]
+      drills: vec![],
+      gyroscopes: vec![],
+      refineries: vec![],
+      assemblers: vec![],
+      turrets: vec![],
+      fixed_weapons: vec![],
+      interior_turrets: vec![],
+
+      definition_sources: LinkedHashMap::new(),
+      overrides: vec![],
     })
   }
 }
@@ -396,6 +532,8 @@ pub enum ExtractError {
   ReadEntityComponentsFileFail { file: PathBuf, source: std::io::Error },
   #[error("Could not XML parse EntityComponents file '{file}'")]
   ParseEntityComponentsFileFail { file: PathBuf, source: roxmltree::Error },
+  #[error("Could not extract block definition in '{file}' at line {line}")]
+  ExtractDefinitionFail { file: PathBuf, line: u32, source: XmlError },
   #[error(transparent)]
   XmlFail {
     #[from]
@@ -403,6 +541,22 @@ pub enum ExtractError {
   },
 }
 
+/// Iterates over the paths of SBC files in `search_path` that pass `search_path_filter`.
+fn sbc_file_paths(search_path: impl AsRef<Path>, search_path_filter: impl Fn(&PathBuf) -> bool) -> impl Iterator<Item=PathBuf> {
+  WalkDir::new(search_path)
+    .into_iter()
+    .filter_map(move |de| {
+      if let Ok(de) = de {
+        let path = de.into_path();
+        if path.extension().is_none_or(|e| e != "sbc") { return None; }
+        if !search_path_filter(&path) { return None; }
+        Some(path)
+      } else {
+        None
+      }
+    })
+}
+
 impl BlocksBuilder {
   pub fn update_from_se_dir(
     &mut self,
@@ -411,7 +565,8 @@ impl BlocksBuilder {
   ) -> Result<(), ExtractError> {
     self.update_from_sbc_files(
       se_directory.as_ref().join("Content/Data/"),
-      |path| path.file_name().map_or(false, |n| n.to_string_lossy().contains("CubeBlocks")),
+      |path| path.file_name().is_some_and(|n| n.to_string_lossy().contains("CubeBlocks")),
+      se_directory.as_ref(),
       se_directory.as_ref().join("Content/Data/EntityComponents.sbc"),
       localization,
       None,
@@ -427,18 +582,40 @@ impl BlocksBuilder {
   ) -> Result<(), ExtractError> {
     let search_path = se_workshop_directory.as_ref().join(format!("{}", mod_id));
     self.update_from_sbc_files(
-      search_path,
+      &search_path,
       |_| true,
+      &search_path,
       se_directory.as_ref().join("Content/Data/EntityComponents.sbc"),
       localization,
       Some(mod_id),
     )
   }
 
+  /// Sets whether to record the source file and line of each block definition in its block data. Disabled by
+  /// default.
+  pub fn set_record_sources(&mut self, record_sources: bool) {
+    self.record_sources = record_sources;
+  }
+
+  /// Sets the conveyor ports to assign to blocks by their exact ID. No conveyor ports are assigned by default.
+  pub fn set_conveyor_ports(&mut self, conveyor_ports: impl Iterator<Item=(String, ConveyorPorts)>) {
+    self.conveyor_ports = HashMap::from_iter(conveyor_ports);
+  }
+
+  /// Sets the cube sizes to calculate size-dependent block properties, such as connector and drill inventory volumes,
+  /// with. Defaults to the default cube sizes of the game.
+  pub fn set_cube_sizes(&mut self, cube_sizes: CubeSizes) {
+    self.cube_sizes = cube_sizes;
+  }
+
+  /// Updates blocks from definitions in SBC files in `search_path` that pass `search_path_filter`. Recorded source
+  /// files are relative to `source_directory`.
+  #[allow(clippy::too_many_arguments)]
   pub fn update_from_sbc_files(
     &mut self,
     search_path: impl AsRef<Path>,
     search_path_filter: impl Fn(&PathBuf) -> bool,
+    source_directory: impl AsRef<Path>,
     entity_components_file: impl AsRef<Path>,
     localization: &Localization,
     mod_id: Option<u64>,
@@ -452,19 +629,7 @@ impl BlocksBuilder {
     let entity_components_root_node = entity_components_root.first_child_elem()?;
     let entity_components_node = entity_components_root_node.child_elem("EntityComponents")?;
 
-    let cube_blocks_file_paths = WalkDir::new(search_path)
-      .into_iter()
-      .filter_map(|de| {
-        if let Ok(de) = de {
-          let path = de.into_path();
-          if !path.extension().map_or(false, |e| e == "sbc") { return None; }
-          if !search_path_filter(&path) { return None; }
-          Some(path)
-        } else {
-          None
-        }
-      });
-    for cube_blocks_file_path in cube_blocks_file_paths {
+    for cube_blocks_file_path in sbc_file_paths(search_path, search_path_filter) {
       let cube_blocks_file_path = &cube_blocks_file_path;
       let cube_blocks_string = read_string_from_file(cube_blocks_file_path)
         .map_err(|source| ExtractError::ReadCubeBlocksFileFail { file: cube_blocks_file_path.to_path_buf(), source })?;
@@ -474,76 +639,130 @@ impl BlocksBuilder {
       let definitions_node = definitions_node.first_child_elem()?;
       let definitions_node = definitions_node.first_child_elem()?;
       for def in definitions_node.children_elems("Definition") {
-        let data = BlockData::from_def(
-          &def,
-          localization,
-          mod_id,
-          &self.hide_block_by_exact_name,
-          &self.hide_block_by_regex_name,
-          &self.hide_block_by_exact_subtype_id,
-          &self.hide_block_by_regex_subtype_id,
-          &self.hide_block_by_exact_id,
-          &self.hide_block_by_regex_id,
-          &self.rename_block_by_regex,
-        )?;
-        fn add_block<T>(details: T, data: BlockData, vec: &mut Vec<Block<T>>) {
-          let block = Block::new(data, details);
-          vec.push(block);
+        let line = cube_blocks_doc.text_pos_at(def.range().start).row;
+        let source = self.record_sources.then(|| {
+          let file = cube_blocks_file_path.strip_prefix(source_directory.as_ref()).unwrap_or(cube_blocks_file_path);
+          BlockSource { file: file.to_string_lossy().replace('\\', "/"), line }
+        });
+        self.add_definition(&def, &entity_components_node, localization, mod_id, source)
+          .map_err(|source| ExtractError::ExtractDefinitionFail { file: cube_blocks_file_path.to_path_buf(), line, source })?;
+      }
+    }
+    Ok(())
+  }
+
+  fn add_definition(
+    &mut self,
+    def: &Node,
+    entity_components_node: &Node,
+    localization: &Localization,
+    mod_id: Option<u64>,
+    source: Option<BlockSource>,
+  ) -> Result<(), XmlError> {
+    let mut data = BlockData::from_def(
+      def,
+      localization,
+      mod_id,
+      &self.hide_block_by_exact_name,
+      &self.hide_block_by_regex_name,
+      &self.hide_block_by_exact_subtype_id,
+      &self.hide_block_by_regex_subtype_id,
+      &self.hide_block_by_exact_id,
+      &self.hide_block_by_regex_id,
+      &self.rename_block_by_regex,
+    )?;
+    data.source = source;
+    data.conveyor_ports = self.conveyor_ports.get(&data.id).copied();
+    let type_subtype_id = data.type_subtype_id().to_string();
+    if let Some(overridden_mod_id) = self.definition_sources.insert(type_subtype_id.clone(), mod_id) {
+      self.overrides.push(ModOverride { id: type_subtype_id, mod_id, overridden_mod_id });
+    }
+    fn add_block<T>(details: T, data: BlockData, vec: &mut Vec<Block<T>>) {
+      let block = Block::new(data, details);
+      vec.push(block);
+    }
+    if let Some(ty) = def.attribute(("http://www.w3.org/2001/XMLSchema-instance", "type")) {
+      match ty {
+        "MyObjectBuilder_BatteryBlockDefinition" => {
+          add_block(Battery::from_def(def)?, data, &mut self.batteries);
+        }
+        "MyObjectBuilder_JumpDriveDefinition" => {
+          add_block(JumpDrive::from_def(def)?, data, &mut self.jump_drives);
+        }
+        "MyObjectBuilder_WeaponBlockDefinition" if data.id.contains("Railgun") => {
+          add_block(Railgun::from_def(def, entity_components_node)?, data, &mut self.railguns);
+        }
+        "MyObjectBuilder_ThrustDefinition" => {
+          add_block(Thruster::from_def(def)?, data, &mut self.thrusters);
+        }
+        "MyObjectBuilder_MotorSuspensionDefinition" => {
+          add_block(WheelSuspension::from_def(def)?, data, &mut self.wheel_suspensions);
         }
-        if let Some(ty) = def.attribute(("http://www.w3.org/2001/XMLSchema-instance", "type")) {
-          match ty {
-            "MyObjectBuilder_BatteryBlockDefinition" => {
-              add_block(Battery::from_def(&def)?, data, &mut self.batteries);
-            }
-            "MyObjectBuilder_JumpDriveDefinition" => {
-              add_block(JumpDrive::from_def(&def)?, data, &mut self.jump_drives);
-            }
-            "MyObjectBuilder_WeaponBlockDefinition" => {
-              if data.id.contains("Railgun") {
-                add_block(Railgun::from_def(&def, &entity_components_node)?, data, &mut self.railguns);
-              }
-            }
-            "MyObjectBuilder_ThrustDefinition" => {
-              add_block(Thruster::from_def(&def)?, data, &mut self.thrusters);
-            }
-            "MyObjectBuilder_MotorSuspensionDefinition" => {
-              add_block(WheelSuspension::from_def(&def)?, data, &mut self.wheel_suspensions);
-            }
-            "MyObjectBuilder_HydrogenEngineDefinition" => {
-              add_block(HydrogenEngine::from_def(&def)?, data, &mut self.hydrogen_engines);
-            }
-            "MyObjectBuilder_ReactorDefinition" => {
-              add_block(Reactor::from_def(&def)?, data, &mut self.reactors);
-            }
-            "MyObjectBuilder_OxygenGeneratorDefinition" => {
-              add_block(Generator::from_def(&def)?, data, &mut self.generators);
-            }
-            "MyObjectBuilder_GasTankDefinition" => {
-              if def.child_elem("StoredGasId")?.parse_child_elem::<String>("SubtypeId")? != "Hydrogen".to_owned() { continue }
-              add_block(HydrogenTank::from_def(&def)?, data, &mut self.hydrogen_tanks);
-            }
-            "MyObjectBuilder_CargoContainerDefinition" => {
-              add_block(Container::from_def(&def, &entity_components_node)?, data, &mut self.containers);
-            }
-            "MyObjectBuilder_ShipConnectorDefinition" => {
-              add_block(Connector::from_def(&def, &data)?, data, &mut self.connectors);
-            }
-            "MyObjectBuilder_CockpitDefinition" => {
-              add_block(Cockpit::from_def(&def)?, data, &mut self.cockpits);
-            }
-            "MyObjectBuilder_ShipDrillDefinition" => {
-              add_block(Drill::from_def(&def, &data)?, data, &mut self.drills);
-            }
+        "MyObjectBuilder_HydrogenEngineDefinition" => {
+          add_block(HydrogenEngine::from_def(def)?, data, &mut self.hydrogen_engines);
+        }
+        "MyObjectBuilder_ReactorDefinition" => {
+          add_block(Reactor::from_def(def)?, data, &mut self.reactors);
+        }
+        "MyObjectBuilder_OxygenGeneratorDefinition" => {
+          add_block(Generator::from_def(def)?, data, &mut self.generators);
+        }
+        "MyObjectBuilder_GasTankDefinition" => {
+          match def.child_elem("StoredGasId")?.parse_child_elem::<String>("SubtypeId")?.as_str() {
+            "Hydrogen" => add_block(HydrogenTank::from_def(def)?, data, &mut self.hydrogen_tanks),
+            "Oxygen" => add_block(OxygenTank::from_def(def)?, data, &mut self.oxygen_tanks),
             _ => {}
           }
         }
+        "MyObjectBuilder_OxygenFarmDefinition" => {
+          add_block(OxygenFarm::from_def(def)?, data, &mut self.oxygen_farms);
+        }
+        "MyObjectBuilder_AirVentDefinition" => {
+          add_block(AirVent::from_def(def)?, data, &mut self.air_vents);
+        }
+        "MyObjectBuilder_CargoContainerDefinition" => {
+          add_block(Container::from_def(def, entity_components_node)?, data, &mut self.containers);
+        }
+        "MyObjectBuilder_ShipConnectorDefinition" => {
+          add_block(Connector::from_def(def, self.cube_sizes.get(data.size))?, data, &mut self.connectors);
+        }
+        "MyObjectBuilder_CockpitDefinition" => {
+          add_block(Cockpit::from_def(def)?, data, &mut self.cockpits);
+        }
+        "MyObjectBuilder_ShipDrillDefinition" => {
+          add_block(Drill::from_def(def, &data, self.cube_sizes.get(data.size))?, data, &mut self.drills);
+        }
+        "MyObjectBuilder_GyroDefinition" => {
+          add_block(Gyroscope::from_def(def)?, data, &mut self.gyroscopes);
+        }
+        "MyObjectBuilder_RefineryDefinition" => {
+          add_block(Refinery::from_def(def)?, data, &mut self.refineries);
+        }
+        "MyObjectBuilder_AssemblerDefinition" => {
+          add_block(Assembler::from_def(def)?, data, &mut self.assemblers);
+        }
+        "MyObjectBuilder_WeaponBlockDefinition" => {
+          add_block(Weapon::from_def(def, false)?, data, &mut self.fixed_weapons);
+        }
+        "MyObjectBuilder_LargeTurretBaseDefinition" if data.type_subtype_id().starts_with("InteriorTurret.") => {
+          add_block(Weapon::from_def(def, true)?, data, &mut self.interior_turrets);
+        }
+        "MyObjectBuilder_LargeTurretBaseDefinition" => {
+          add_block(Weapon::from_def(def, true)?, data, &mut self.turrets);
+        }
+        _ => {}
       }
     }
     Ok(())
   }
 
+  /// Takes the block definitions that override an earlier definition with the same TypeId and SubtypeId.
+  pub fn take_overrides(&mut self) -> Vec<ModOverride> {
+    std::mem::take(&mut self.overrides)
+  }
+
   pub fn into_blocks(mut self, localization: &Localization) -> Blocks {
-    fn sort_block_vec<T>(vec: &mut Vec<Block<T>>, localization: &Localization) {
+    fn sort_block_vec<T>(vec: &mut [Block<T>], localization: &Localization) {
       vec.sort_by(|a, b| alphanumeric_sort::compare_str(a.name(localization), b.name(localization)));
     }
     sort_block_vec(&mut self.batteries, localization);
@@ -555,10 +774,19 @@ impl BlocksBuilder {
     sort_block_vec(&mut self.reactors, localization);
     sort_block_vec(&mut self.generators, localization);
     sort_block_vec(&mut self.hydrogen_tanks, localization);
+    sort_block_vec(&mut self.oxygen_tanks, localization);
+    sort_block_vec(&mut self.oxygen_farms, localization);
+    sort_block_vec(&mut self.air_vents, localization);
     sort_block_vec(&mut self.containers, localization);
     sort_block_vec(&mut self.connectors, localization);
     sort_block_vec(&mut self.cockpits, localization);
     sort_block_vec(&mut self.drills, localization);
+    sort_block_vec(&mut self.gyroscopes, localization);
+    sort_block_vec(&mut self.refineries, localization);
+    sort_block_vec(&mut self.assemblers, localization);
+    sort_block_vec(&mut self.turrets, localization);
+    sort_block_vec(&mut self.fixed_weapons, localization);
+    sort_block_vec(&mut self.interior_turrets, localization);
     fn create_map<T>(vec: Vec<Block<T>>) -> LinkedHashMap<BlockId, Block<T>> {
       LinkedHashMap::from_iter(vec.into_iter().map(|b| (b.data.id.clone(), b)))
     }
@@ -572,10 +800,117 @@ impl BlocksBuilder {
       reactors: create_map(self.reactors),
       generators: create_map(self.generators),
       hydrogen_tanks: create_map(self.hydrogen_tanks),
+      oxygen_tanks: create_map(self.oxygen_tanks),
+      oxygen_farms: create_map(self.oxygen_farms),
+      air_vents: create_map(self.air_vents),
       containers: create_map(self.containers),
       connectors: create_map(self.connectors),
       cockpits: create_map(self.cockpits),
       drills: create_map(self.drills),
+      gyroscopes: create_map(self.gyroscopes),
+      refineries: create_map(self.refineries),
+      assemblers: create_map(self.assemblers),
+      turrets: create_map(self.turrets),
+      fixed_weapons: create_map(self.fixed_weapons),
+      interior_turrets: create_map(self.interior_turrets),
     }
   }
 }
+
+
+// Raw definitions
+
+/// Raw values of a block definition in an SBC file, for debugging extraction.
+#[derive(Clone, Debug)]
+pub struct RawDefinition {
+  /// SBC file the definition was found in
+  pub file: PathBuf,
+  /// Line of the definition in the file, starting at 1
+  pub line: u32,
+  /// Values of the definition as (XML path, value) pairs, in document order. Attributes are suffixed to the path of
+  /// their element with `@`.
+  pub values: Vec<(String, String)>,
+}
+
+impl RawDefinition {
+  /// Finds the definitions of block `id` in the Space Engineers directory, or in the first of the workshop directories
+  /// that contains the mod if `id` is the ID of a block from a mod. Definitions are returned in extraction order, with
+  /// later definitions overriding earlier ones.
+  pub fn find(
+    se_directory: impl AsRef<Path>,
+    se_workshop_directories: impl IntoIterator<Item=impl AsRef<Path>>,
+    id: &str,
+  ) -> Result<Vec<Self>, ExtractError> {
+    match id.rsplit_once('@').and_then(|(_, mod_id)| mod_id.parse::<u64>().ok()) {
+      Some(mod_id) => {
+        let se_workshop_directories: Vec<PathBuf> = se_workshop_directories.into_iter().map(resolve_workshop_directory).collect();
+        let Some(se_workshop_directory) = find_mod_workshop_directory(&se_workshop_directories, mod_id) else { return Ok(Vec::new()) };
+        Self::find_in_sbc_files(se_workshop_directory.join(format!("{}", mod_id)), |_| true, Some(mod_id), id)
+      }
+      None => Self::find_in_sbc_files(
+        se_directory.as_ref().join("Content/Data/"),
+        |path| path.file_name().is_some_and(|n| n.to_string_lossy().contains("CubeBlocks")),
+        None,
+        id,
+      ),
+    }
+  }
+
+  /// Finds the definitions of block `id` in SBC files in `search_path` that pass `search_path_filter`, identifying
+  /// blocks in the same way as extraction does with `mod_id`.
+  pub fn find_in_sbc_files(
+    search_path: impl AsRef<Path>,
+    search_path_filter: impl Fn(&PathBuf) -> bool,
+    mod_id: Option<u64>,
+    id: &str,
+  ) -> Result<Vec<Self>, ExtractError> {
+    let mut definitions = Vec::new();
+    for file in sbc_file_paths(search_path, search_path_filter) {
+      let string = read_string_from_file(&file)
+        .map_err(|source| ExtractError::ReadCubeBlocksFileFail { file: file.clone(), source })?;
+      let doc = Document::parse(&string)
+        .map_err(|source| ExtractError::ParseCubeBlocksFileFail { file: file.clone(), source })?;
+      let Some(definitions_node) = doc.root().first_element_child().and_then(|n| n.first_element_child()) else { continue };
+      for def in definitions_node.children_elems("Definition") {
+        let Some(id_node) = def.child_elem_opt("Id") else { continue };
+        let type_id: String = id_node.parse_child_elem("TypeId")?;
+        let subtype_id: String = id_node.parse_child_elem_opt("SubtypeId")?.unwrap_or_default();
+        let def_id = match mod_id {
+          Some(mod_id) => format!("{}.{}@{}", type_id, subtype_id, mod_id),
+          None => format!("{}.{}", type_id, subtype_id),
+        };
+        if def_id != id { continue; }
+        let line = doc.text_pos_at(def.range().start).row;
+        let mut values = Vec::new();
+        collect_raw_values(def, "", &mut values);
+        definitions.push(Self { file: file.clone(), line, values });
+      }
+    }
+    Ok(definitions)
+  }
+}
+
+/// Collects the attributes and text of `node` and its descendants into `values`, with paths relative to `path`.
+fn collect_raw_values(node: Node, path: &str, values: &mut Vec<(String, String)>) {
+  for attribute in node.attributes() {
+    values.push((format!("{}@{}", path, attribute.name()), attribute.value().to_string()));
+  }
+  let children: Vec<Node> = node.children().filter(|n| n.is_element()).collect();
+  if children.is_empty() {
+    if let Some(text) = node.text().map(str::trim).filter(|t| !t.is_empty()) {
+      values.push((path.to_string(), text.to_string()));
+    }
+    return;
+  }
+  for child in &children {
+    let tag = child.tag_name().name();
+    let siblings = children.iter().filter(|c| c.tag_name().name() == tag);
+    let child_path = if siblings.clone().count() > 1 {
+      let index = siblings.take_while(|c| *c != child).count();
+      format!("{}/{}[{}]", path, tag, index)
+    } else {
+      format!("{}/{}", path, tag)
+    };
+    collect_raw_values(*child, &child_path, values);
+  }
+}
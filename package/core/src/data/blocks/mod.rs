@@ -2,10 +2,13 @@ use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Deref;
+use std::str::FromStr;
 
 use hashlink::LinkedHashMap;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{parse_enum, ParseEnumError};
+
 use super::components::Components;
 use super::gas_properties::GasProperties;
 use super::localization::Localization;
@@ -15,13 +18,22 @@ pub mod extract;
 
 /// Grid size.
 #[derive(Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug, )]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum GridSize {
   #[default] Small,
   Large
 }
 
 impl GridSize {
-  /// Cube size as defined by Configuration.sbc
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use GridSize::*;
+    const ITEMS: [GridSize; 2] = [Small, Large];
+    ITEMS.into_iter()
+  }
+
+  /// Default cube size as defined by Configuration.sbc of the game. Extracted data stores the actual cube sizes in
+  /// [Data::cube_sizes](crate::data::Data::cube_sizes).
   pub fn size(&self) -> f64 {
     match self {
       GridSize::Small => 0.5,
@@ -39,19 +51,95 @@ impl Display for GridSize {
   }
 }
 
+impl FromStr for GridSize {
+  type Err = ParseEnumError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_enum("grid size", s, Self::items())
+  }
+}
+
+
+/// Sizes of the conveyor ports of a block. Small grid blocks have small or large conveyor ports, which do not connect
+/// to each other. Large grid blocks only have large conveyor ports.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ConveyorPorts {
+  /// Only small conveyor ports, which large items cannot pass through
+  Small,
+  /// Only large conveyor ports
+  Large,
+  /// Both small and large conveyor ports, connecting them, such as conveyor converters
+  SmallAndLarge,
+}
+
+impl ConveyorPorts {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use ConveyorPorts::*;
+    const ITEMS: [ConveyorPorts; 3] = [Small, Large, SmallAndLarge];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for ConveyorPorts {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ConveyorPorts::Small => f.write_str("Small"),
+      ConveyorPorts::Large => f.write_str("Large"),
+      ConveyorPorts::SmallAndLarge => f.write_str("Small and Large"),
+    }
+  }
+}
+
+
+/// Source of a block definition.
+#[derive(Default, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BlockSource {
+  /// SBC file the block is defined in, relative to the Space Engineers directory or the directory of its mod
+  pub file: String,
+  /// Line of the definition in the file, starting at 1
+  pub line: u32,
+}
+
+impl Display for BlockSource {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}", self.file, self.line)
+  }
+}
+
 
 /// Alias for block identifiers.
 pub type BlockId = String;
 
 /// Common block data which can be created from a definition in a SBC XML file.
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BlockData {
   pub id: BlockId,
   pub name: String,
+  /// Localization key or text of the description of this block, or None if it has no description.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
   pub size: GridSize,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<String, f64>"))]
   pub components: LinkedHashMap<String, f64>,
   pub has_physics: bool,
+  /// Performance cost units (PCU), or None if unknown because the data was extracted before PCU was recorded.
+  #[serde(default)]
+  pub pcu: Option<u64>,
+  /// Time to build this block with a welder at a welder speed multiplier of 1 (s), or None if unknown because the data
+  /// was extracted before build times were recorded.
+  #[serde(default)]
+  pub build_time: Option<f64>,
   pub mod_id: Option<u64>,
+  /// Source of the definition this block was extracted from, or None if sources were not recorded during extraction.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub source: Option<BlockSource>,
+  /// Sizes of the conveyor ports of this block, or None if unknown or the block has no conveyor ports. Block definitions
+  /// do not contain conveyor ports, so these are assigned during extraction.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub conveyor_ports: Option<ConveyorPorts>,
 
   pub hidden: bool,
   pub rename: Option<String>,
@@ -61,26 +149,74 @@ impl BlockData {
   #[inline]
   pub fn id_cloned(&self) -> BlockId { self.id.clone() }
 
+  /// Gets the TypeId.SubtypeId of this block, which is the ID without the `@<mod ID>` suffix.
+  #[inline]
+  pub fn type_subtype_id(&self) -> &str {
+    if self.mod_id.is_some() {
+      self.id.rsplit_once('@').map_or(&self.id, |(id, _)| id)
+    } else {
+      &self.id
+    }
+  }
+
   #[inline]
   pub fn name<'a>(&'a self, localization: &'a Localization) -> &'a str {
     if let Some(rename) = &self.rename {
-      &rename
+      rename
     } else {
       localization.get(&self.name)
     }
   }
 
+  #[inline]
+  pub fn description<'a>(&'a self, localization: &'a Localization) -> Option<&'a str> {
+    self.description.as_deref().map(|d| localization.get(d))
+  }
+
   #[inline]
   pub fn mass(&self, components: &Components) -> f64 {
     let mut mass = 0.0;
     if !self.has_physics { return mass }
     for (component_id, count) in self.components.iter() {
-      if let Some(component) = components.get(&component_id) {
+      if let Some(component) = components.get(component_id) {
         mass += component.mass * *count;
       }
     }
     mass
   }
+
+  /// Gets the mass of each component this block is made of, in the order of its definition. Does not take
+  /// [`BlockData::has_physics`] into account.
+  pub fn component_masses(&self, components: &Components) -> Vec<ComponentMass> {
+    self.components.iter().map(|(component_id, count)| ComponentMass {
+      component_id: component_id.clone(),
+      count: *count,
+      mass: components.get(component_id).map(|c| c.mass),
+    }).collect()
+  }
+
+  /// Gets the IDs of components this block is made of that do not exist in `components`, which are not included in
+  /// [`BlockData::mass`].
+  #[inline]
+  pub fn unknown_component_ids<'a>(&'a self, components: &'a Components) -> impl Iterator<Item=&'a String> + 'a {
+    self.components.keys().filter(|id| components.get(id).is_none())
+  }
+}
+
+/// Mass of the components of one type in a block.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ComponentMass {
+  pub component_id: String,
+  /// Number of components
+  pub count: f64,
+  /// Mass of one component (kg), or None if the component does not exist
+  pub mass: Option<f64>,
+}
+
+impl ComponentMass {
+  /// Total mass of these components (kg), or None if the component does not exist
+  #[inline]
+  pub fn total_mass(&self) -> Option<f64> { self.mass.map(|m| m * self.count) }
 }
 
 impl PartialEq for BlockData {
@@ -92,7 +228,7 @@ impl Eq for BlockData {}
 
 impl PartialOrd for BlockData {
   #[inline]
-  fn partial_cmp(&self, other: &Self) -> Option<Ordering> { self.id.partial_cmp(&other.id) }
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 
 impl Ord for BlockData {
@@ -103,6 +239,7 @@ impl Ord for BlockData {
 
 /// Block with data and details.
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Block<T> {
   pub data: BlockData,
   pub details: T,
@@ -125,6 +262,11 @@ impl<T> Block<T> {
     self.data.name(localization)
   }
 
+  #[inline]
+  pub fn description<'a>(&'a self, localization: &'a Localization) -> Option<&'a str> {
+    self.data.description(localization)
+  }
+
   #[inline]
   pub fn mass(&self, components: &Components) -> f64 { self.data.mass(components) }
 }
@@ -138,7 +280,7 @@ impl<T> Eq for Block<T> {}
 
 impl<T> PartialOrd for Block<T> {
   #[inline]
-  fn partial_cmp(&self, other: &Self) -> Option<Ordering> { self.data.partial_cmp(&other.data) }
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 
 impl<T> Ord for Block<T> {
@@ -157,6 +299,7 @@ impl<T> Deref for Block<T> {
 
 /// Battery.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Battery {
   /// Power capacity (MWh)
   pub capacity: f64,
@@ -168,6 +311,7 @@ pub struct Battery {
 
 /// Jump Drive.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct JumpDrive {
   /// Power capacity (MWh)
   pub capacity: f64,
@@ -185,6 +329,7 @@ pub struct JumpDrive {
 
 /// Railgun.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Railgun {
   /// Power capacity (MWh)
   pub capacity: f64,
@@ -194,16 +339,65 @@ pub struct Railgun {
   pub idle_power_consumption: f64,
 }
 
+/// Gyroscope.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Gyroscope {
+  /// Maximum torque (N·m)
+  pub torque: f64,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: f64,
+}
+
+/// Refinery: refines ore into ingots.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Refinery {
+  /// Refine speed multiplier
+  pub refine_speed: f64,
+  /// Material efficiency multiplier of ingots produced from ore
+  pub material_efficiency: f64,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: f64,
+  /// Idle power consumption (MW)
+  pub idle_power_consumption: f64,
+}
+
+/// Assembler: assembles components from ingots.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Assembler {
+  /// Assembly speed multiplier
+  pub assembly_speed: f64,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: f64,
+  /// Idle power consumption (MW)
+  pub idle_power_consumption: f64,
+}
+
 /// Type of thruster
 #[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ThrusterType {
   Ion,
   Atmospheric,
   Hydrogen,
 }
 
+impl Display for ThrusterType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use ThrusterType::*;
+    match self {
+      Ion => f.write_str("Ion"),
+      Atmospheric => f.write_str("Atmospheric"),
+      Hydrogen => f.write_str("Hydrogen"),
+    }
+  }
+}
+
 /// Thruster.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Thruster {
   /// Thruster type
   pub ty: ThrusterType,
@@ -223,6 +417,19 @@ pub struct Thruster {
 }
 
 impl Thruster {
+  /// Gets the effectiveness of this thruster at `planetary_influence` 0-1, as a fraction of its force and consumption.
+  pub fn effectiveness(&self, planetary_influence: f64) -> f64 {
+    // Clamp planetary influence value.
+    let planetary_influence = planetary_influence.clamp(self.min_planetary_influence, self.max_planetary_influence);
+    // Slope-intercept form equation: y = mx + b
+    // Calculate m: m = (y2 - y1) / (x2 - x1)
+    let m = (self.effectiveness_at_min_influence - self.effectiveness_at_max_influence) / (self.min_planetary_influence - self.max_planetary_influence);
+    // Calculate b: b = y + -mx (choose x,y on the line)
+    let b = self.effectiveness_at_max_influence + (-m * self.max_planetary_influence);
+    // Calculate y: y = mx + b
+    m * planetary_influence + b
+  }
+
   pub fn actual_max_consumption(&self, gas_properties: &GasProperties) -> f64 {
     if let Some(id) = &self.fuel_gas_id {
       if let Some(gas_property) = gas_properties.get(id) {
@@ -250,6 +457,7 @@ impl Thruster {
 
 /// Wheel suspension.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WheelSuspension {
   /// Force (N)
   pub force: f64,
@@ -261,6 +469,7 @@ pub struct WheelSuspension {
 
 /// Hydrogen engine.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HydrogenEngine {
   /// Fuel capacity (L)
   pub fuel_capacity: f64,
@@ -272,6 +481,7 @@ pub struct HydrogenEngine {
 
 /// Reactor
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Reactor {
   /// Maximum power generation (MW)
   pub max_power_generation: f64,
@@ -281,6 +491,7 @@ pub struct Reactor {
 
 /// Generator (O2/H2)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Generator {
   /// Ice consumption (#/s)
   pub ice_consumption: f64,
@@ -298,6 +509,7 @@ pub struct Generator {
 
 /// Hydrogen tank
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HydrogenTank {
   /// Hydrogen capacity (L)
   pub capacity: f64,
@@ -307,17 +519,70 @@ pub struct HydrogenTank {
   pub idle_power_consumption: f64,
 }
 
+/// Oxygen tank
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OxygenTank {
+  /// Oxygen capacity (L)
+  pub capacity: f64,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: f64,
+  /// Idle power consumption (MW)
+  pub idle_power_consumption: f64,
+}
+
+/// Oxygen farm
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OxygenFarm {
+  /// Oxygen generation in full sunlight (L/s)
+  pub oxygen_generation: f64,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: f64,
+}
+
+/// Air vent
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AirVent {
+  /// Oxygen consumption when pressurizing (L/s)
+  pub oxygen_consumption: f64,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: f64,
+  /// Idle power consumption (MW)
+  pub idle_power_consumption: f64,
+}
+
+/// Items that a constrained inventory accepts.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum InventoryConstraint {
+  /// Only ore, including ice
+  Ore,
+  /// Only ice
+  Ice,
+  /// Only components
+  Components,
+  /// Other items, or an unknown constraint
+  #[default] Other,
+}
+
 /// Container
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Container {
   /// Inventory volume (L)
   pub inventory_volume_any: f64,
   /// Stores any item?
   pub store_any: bool,
+  /// Items the inventory accepts when it does not store any item
+  #[serde(default)]
+  pub constraint: InventoryConstraint,
 }
 
 /// Connector
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Connector {
   /// Inventory volume (L)
   pub inventory_volume_any: f64,
@@ -325,6 +590,7 @@ pub struct Connector {
 
 /// Cockpit
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Cockpit {
   /// Whether 'cockpit' has an inventory.
   pub has_inventory: bool,
@@ -334,6 +600,7 @@ pub struct Cockpit {
 
 /// Drill
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Drill {
   /// Inventory volume - ore only (L)
   pub inventory_volume_ore: f64,
@@ -341,62 +608,277 @@ pub struct Drill {
   pub operational_power_consumption: f64,
   /// Idle power consumption (MW)
   pub idle_power_consumption: f64,
+  /// Estimated best-case rate at which the drill collects ore when drilling into solid ore (L/s)
+  #[serde(default)]
+  pub ore_rate: f64,
+}
+
+/// Weapon: turret, fixed weapon, or interior turret.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Weapon {
+  /// Inventory volume - ammo only (L)
+  pub inventory_volume_ammo: f64,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: f64,
+  /// Idle power consumption (MW)
+  pub idle_power_consumption: f64,
+}
+
+/// Group of weapons, as shown in the armament results.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum WeaponGroup {
+  Turret,
+  FixedWeapon,
+  InteriorTurret,
+}
+
+impl WeaponGroup {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use WeaponGroup::*;
+    const ITEMS: [WeaponGroup; 3] = [Turret, FixedWeapon, InteriorTurret];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for WeaponGroup {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use WeaponGroup::*;
+    match self {
+      Turret => f.write_str("Turrets"),
+      FixedWeapon => f.write_str("Fixed Weapons"),
+      InteriorTurret => f.write_str("Interior Turrets"),
+    }
+  }
 }
 
 /// All blocks
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct Blocks {
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Battery>>"))]
   pub batteries: LinkedHashMap<BlockId, Block<Battery>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<JumpDrive>>"))]
   pub jump_drives: LinkedHashMap<BlockId, Block<JumpDrive>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Railgun>>"))]
   pub railguns: LinkedHashMap<BlockId, Block<Railgun>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Thruster>>"))]
   pub thrusters: LinkedHashMap<BlockId, Block<Thruster>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<WheelSuspension>>"))]
   pub wheel_suspensions: LinkedHashMap<BlockId, Block<WheelSuspension>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<HydrogenEngine>>"))]
   pub hydrogen_engines: LinkedHashMap<BlockId, Block<HydrogenEngine>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Reactor>>"))]
   pub reactors: LinkedHashMap<BlockId, Block<Reactor>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Generator>>"))]
   pub generators: LinkedHashMap<BlockId, Block<Generator>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<HydrogenTank>>"))]
   pub hydrogen_tanks: LinkedHashMap<BlockId, Block<HydrogenTank>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<OxygenTank>>"))]
+  pub oxygen_tanks: LinkedHashMap<BlockId, Block<OxygenTank>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<OxygenFarm>>"))]
+  pub oxygen_farms: LinkedHashMap<BlockId, Block<OxygenFarm>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<AirVent>>"))]
+  pub air_vents: LinkedHashMap<BlockId, Block<AirVent>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Container>>"))]
   pub containers: LinkedHashMap<BlockId, Block<Container>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Connector>>"))]
   pub connectors: LinkedHashMap<BlockId, Block<Connector>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Cockpit>>"))]
   pub cockpits: LinkedHashMap<BlockId, Block<Cockpit>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Drill>>"))]
   pub drills: LinkedHashMap<BlockId, Block<Drill>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Gyroscope>>"))]
+  pub gyroscopes: LinkedHashMap<BlockId, Block<Gyroscope>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Refinery>>"))]
+  pub refineries: LinkedHashMap<BlockId, Block<Refinery>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Assembler>>"))]
+  pub assemblers: LinkedHashMap<BlockId, Block<Assembler>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Weapon>>"))]
+  pub turrets: LinkedHashMap<BlockId, Block<Weapon>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Weapon>>"))]
+  pub fixed_weapons: LinkedHashMap<BlockId, Block<Weapon>>,
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, Block<Weapon>>"))]
+  pub interior_turrets: LinkedHashMap<BlockId, Block<Weapon>>,
+}
+
+/// Category of blocks, as shown in block lists.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum BlockCategory {
+  Thrusters,
+  Storage,
+  WheelSuspensions,
+  Power,
+  Hydrogen,
+  Oxygen,
+  Weapons,
+  Other,
+}
+
+impl BlockCategory {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use BlockCategory::*;
+    const ITEMS: [BlockCategory; 8] = [Thrusters, Storage, WheelSuspensions, Power, Hydrogen, Oxygen, Weapons, Other];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for BlockCategory {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use BlockCategory::*;
+    match self {
+      Thrusters => f.write_str("Thrusters"),
+      Storage => f.write_str("Storage"),
+      WheelSuspensions => f.write_str("Wheel Suspensions"),
+      Power => f.write_str("Power"),
+      Hydrogen => f.write_str("Hydrogen"),
+      Oxygen => f.write_str("Oxygen"),
+      Weapons => f.write_str("Weapons"),
+      Other => f.write_str("Other"),
+    }
+  }
 }
 
 impl Blocks {
+  /// Gets the category of the block with `id`, or None if there is no block with `id`.
+  pub fn category(&self, id: &BlockId) -> Option<BlockCategory> {
+    use BlockCategory::*;
+    if self.thrusters.contains_key(id) {
+      Some(Thrusters)
+    } else if self.containers.contains_key(id) || self.connectors.contains_key(id) || self.cockpits.contains_key(id) {
+      Some(Storage)
+    } else if self.wheel_suspensions.contains_key(id) {
+      Some(WheelSuspensions)
+    } else if self.hydrogen_engines.contains_key(id) || self.reactors.contains_key(id) || self.batteries.contains_key(id) {
+      Some(Power)
+    } else if self.generators.contains_key(id) || self.hydrogen_tanks.contains_key(id) {
+      Some(Hydrogen)
+    } else if self.oxygen_tanks.contains_key(id) || self.oxygen_farms.contains_key(id) || self.air_vents.contains_key(id) {
+      Some(Oxygen)
+    } else if self.turrets.contains_key(id) || self.fixed_weapons.contains_key(id) || self.interior_turrets.contains_key(id) {
+      Some(Weapons)
+    } else if self.drills.contains_key(id) || self.jump_drives.contains_key(id) || self.railguns.contains_key(id)
+      || self.gyroscopes.contains_key(id) || self.refineries.contains_key(id) || self.assemblers.contains_key(id) {
+      Some(Other)
+    } else {
+      None
+    }
+  }
+
+  /// Gets the data of the block with `id`, of any block kind.
+  pub fn get_data(&self, id: &BlockId) -> Option<&BlockData> {
+    None
+      .or_else(|| self.batteries.get(id).map(|b| &b.data))
+      .or_else(|| self.jump_drives.get(id).map(|b| &b.data))
+      .or_else(|| self.railguns.get(id).map(|b| &b.data))
+      .or_else(|| self.thrusters.get(id).map(|b| &b.data))
+      .or_else(|| self.wheel_suspensions.get(id).map(|b| &b.data))
+      .or_else(|| self.hydrogen_engines.get(id).map(|b| &b.data))
+      .or_else(|| self.reactors.get(id).map(|b| &b.data))
+      .or_else(|| self.generators.get(id).map(|b| &b.data))
+      .or_else(|| self.hydrogen_tanks.get(id).map(|b| &b.data))
+      .or_else(|| self.oxygen_tanks.get(id).map(|b| &b.data))
+      .or_else(|| self.oxygen_farms.get(id).map(|b| &b.data))
+      .or_else(|| self.air_vents.get(id).map(|b| &b.data))
+      .or_else(|| self.containers.get(id).map(|b| &b.data))
+      .or_else(|| self.connectors.get(id).map(|b| &b.data))
+      .or_else(|| self.cockpits.get(id).map(|b| &b.data))
+      .or_else(|| self.drills.get(id).map(|b| &b.data))
+      .or_else(|| self.gyroscopes.get(id).map(|b| &b.data))
+      .or_else(|| self.refineries.get(id).map(|b| &b.data))
+      .or_else(|| self.assemblers.get(id).map(|b| &b.data))
+      .or_else(|| self.turrets.get(id).map(|b| &b.data))
+      .or_else(|| self.fixed_weapons.get(id).map(|b| &b.data))
+      .or_else(|| self.interior_turrets.get(id).map(|b| &b.data))
+  }
+
+  /// Iterates over the data of all blocks, of any block kind, including hidden blocks.
+  pub fn iter_data(&self) -> impl Iterator<Item=&BlockData> {
+    self.batteries.values().map(|b| &b.data)
+      .chain(self.jump_drives.values().map(|b| &b.data))
+      .chain(self.railguns.values().map(|b| &b.data))
+      .chain(self.thrusters.values().map(|b| &b.data))
+      .chain(self.wheel_suspensions.values().map(|b| &b.data))
+      .chain(self.hydrogen_engines.values().map(|b| &b.data))
+      .chain(self.reactors.values().map(|b| &b.data))
+      .chain(self.generators.values().map(|b| &b.data))
+      .chain(self.hydrogen_tanks.values().map(|b| &b.data))
+      .chain(self.oxygen_tanks.values().map(|b| &b.data))
+      .chain(self.oxygen_farms.values().map(|b| &b.data))
+      .chain(self.air_vents.values().map(|b| &b.data))
+      .chain(self.containers.values().map(|b| &b.data))
+      .chain(self.connectors.values().map(|b| &b.data))
+      .chain(self.cockpits.values().map(|b| &b.data))
+      .chain(self.drills.values().map(|b| &b.data))
+      .chain(self.gyroscopes.values().map(|b| &b.data))
+      .chain(self.refineries.values().map(|b| &b.data))
+      .chain(self.assemblers.values().map(|b| &b.data))
+      .chain(self.turrets.values().map(|b| &b.data))
+      .chain(self.fixed_weapons.values().map(|b| &b.data))
+      .chain(self.interior_turrets.values().map(|b| &b.data))
+  }
+
+  /// Gets the weapon with `id` and its group, or None if there is no weapon with `id`.
+  pub fn weapon(&self, id: &BlockId) -> Option<(&Block<Weapon>, WeaponGroup)> {
+    None
+      .or_else(|| self.turrets.get(id).map(|b| (b, WeaponGroup::Turret)))
+      .or_else(|| self.fixed_weapons.get(id).map(|b| (b, WeaponGroup::FixedWeapon)))
+      .or_else(|| self.interior_turrets.get(id).map(|b| (b, WeaponGroup::InteriorTurret)))
+  }
+
   #[inline]
-  pub fn thruster_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&BlockData> + 'a {
+  pub fn thruster_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
     self.thrusters.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
   }
   #[inline]
-  pub fn storage_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&BlockData> + 'a {
+  pub fn storage_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
     self.containers.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
       .chain(self.connectors.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
       .chain(self.cockpits.values().filter(move |b| filter(b, grid_size, enabled_mod_ids) && b.has_inventory).map(|b| &b.data))
   }
   #[inline]
-  pub fn power_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&BlockData> + 'a {
+  pub fn power_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
     self.hydrogen_engines.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
       .chain(self.reactors.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
       .chain(self.batteries.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
   }
   #[inline]
-  pub fn hydrogen_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&BlockData> + 'a {
+  pub fn hydrogen_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
     self.generators.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
       .chain(self.hydrogen_tanks.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
   }
   #[inline]
-  pub fn wheel_suspension_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&BlockData> + 'a {
+  pub fn oxygen_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
+    self.oxygen_tanks.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
+      .chain(self.oxygen_farms.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
+      .chain(self.air_vents.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
+  }
+  #[inline]
+  pub fn wheel_suspension_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
     self.wheel_suspensions.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
   }
   #[inline]
-  pub fn other_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&BlockData> + 'a {
+  pub fn weapon_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
+    self.turrets.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
+      .chain(self.fixed_weapons.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
+      .chain(self.interior_turrets.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
+  }
+  #[inline]
+  pub fn other_blocks<'a>(&'a self, grid_size: GridSize, enabled_mod_ids: &'a HashSet<u64>) -> impl Iterator<Item=&'a BlockData> + 'a {
     self.drills.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data)
       .chain(self.jump_drives.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
       .chain(self.railguns.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
+      .chain(self.gyroscopes.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
+      .chain(self.refineries.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
+      .chain(self.assemblers.values().filter(move |b| filter(b, grid_size, enabled_mod_ids)).map(|b| &b.data))
   }
 }
 
 #[inline]
-fn filter<T>(b: &Block<T>, grid_size: GridSize, enabled_mod_ids: &HashSet<u64>) -> bool {
+pub(crate) fn filter<T>(b: &Block<T>, grid_size: GridSize, enabled_mod_ids: &HashSet<u64>) -> bool {
   !b.data.hidden && b.data.size == grid_size && b.data.mod_id.map(|i| enabled_mod_ids.contains(&i)).unwrap_or(true)
 }
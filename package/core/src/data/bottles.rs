@@ -0,0 +1,111 @@
+use hashlink::LinkedHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::localization::Localization;
+
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Bottles {
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<String, Bottle>"))]
+  pub bottles: LinkedHashMap<String, Bottle>,
+}
+
+impl Bottles {
+  #[inline]
+  pub fn get(&self, id: &str) -> Option<&Bottle> { self.bottles.get(id) }
+
+  /// Gets the first bottle that stores gas with `gas_id`.
+  #[inline]
+  pub fn get_by_gas(&self, gas_id: &str) -> Option<&Bottle> {
+    self.bottles.values().find(|b| b.gas_id == gas_id)
+  }
+}
+
+/// Item that stores gas, such as hydrogen and oxygen bottles.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Bottle {
+  pub name: String,
+  /// ID of the stored gas
+  pub gas_id: String,
+  /// Mass (kg)
+  pub mass: f64,
+  /// Volume (L)
+  pub volume: f64,
+  /// Gas capacity (L)
+  pub capacity: f64,
+}
+
+impl Bottle {
+  #[inline]
+  pub fn name<'a>(&'a self, localization: &'a Localization) -> &'a str {
+    localization.get(&self.name)
+  }
+}
+
+
+// Extraction
+
+#[cfg(feature = "extract")]
+pub mod extract {
+  use std::path::{Path, PathBuf};
+
+  use hashlink::LinkedHashMap;
+  use roxmltree::Document;
+  use thiserror::Error;
+
+  use crate::data::bottles::{Bottle, Bottles};
+  use crate::xml::{NodeExt, read_string_from_file, XmlError};
+
+  #[derive(Error, Debug)]
+  pub enum Error {
+    #[error("Could not read physical items file '{file}'")]
+    ReadFileFail { file: PathBuf, source: std::io::Error, },
+    #[error("Could not XML parse physical items file '{file}'")]
+    ParseFileFail { file: PathBuf, source: roxmltree::Error, },
+    #[error(transparent)]
+    XmlFail {
+      #[from]
+      source: XmlError
+    },
+  }
+
+  impl Bottles {
+    pub fn from_se_dir<P: AsRef<Path>>(se_directory: P) -> Result<Self, Error> {
+      Self::from_sbc_file(se_directory.as_ref().join("Content/Data/PhysicalItems.sbc"))
+    }
+
+    pub fn from_sbc_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+      let path = path.as_ref();
+      let string = read_string_from_file(path)
+        .map_err(|source| Error::ReadFileFail { file: path.to_path_buf(), source })?;
+      let doc = Document::parse(&string)
+        .map_err(|source| Error::ParseFileFail { file: path.to_path_buf(), source })?;
+
+      let mut bottles = LinkedHashMap::new();
+
+      let root_element = doc.root();
+      let root_element = root_element.first_child_elem()?;
+      let root_element = root_element.first_child_elem()?;
+      for item in root_element.children_elems("PhysicalItem") {
+        // Only gas containers store gas.
+        let stored_gas_id_node = match item.child_elem_opt("StoredGasId") {
+          Some(node) => node,
+          None => continue,
+        };
+        let gas_id = stored_gas_id_node.parse_child_elem("SubtypeId")?;
+        let id_node = item.child_elem("Id")?;
+        let id = id_node.parse_child_elem("SubtypeId")?;
+        let name = item.parse_child_elem("DisplayName")?;
+        let mass = item.parse_child_elem("Mass")?;
+        let volume = item.parse_child_elem("Volume")?;
+        let capacity = item.parse_child_elem("Capacity")?;
+        bottles.insert(id, Bottle { name, gas_id, mass, volume, capacity });
+      }
+
+      Ok(Self { bottles })
+    }
+  }
+}
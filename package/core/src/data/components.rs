@@ -3,9 +3,14 @@ use serde::{Deserialize, Serialize};
 
 use super::localization::Localization;
 
+/// ID of the steel plate component.
+pub const STEEL_PLATE_COMPONENT_ID: &str = "SteelPlate";
+
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct Components {
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<String, Component>"))]
   pub components: LinkedHashMap<String, Component>,
 }
 
@@ -15,11 +20,15 @@ impl Components {
 }
 
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct Component {
   pub name: String,
   pub mass: f64,
   pub volume: f64,
+  /// Maximum number of items in a single inventory stack, or None if unlimited
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub max_stack_amount: Option<f64>,
 }
 
 impl Component {
@@ -79,7 +88,8 @@ pub mod extract {
         let name = component.parse_child_elem("DisplayName")?;
         let mass = component.parse_child_elem("Mass")?;
         let volume = component.parse_child_elem("Volume")?;
-        components.insert(id, Component { name, mass, volume });
+        let max_stack_amount = component.parse_child_elem_opt("MaxStackAmount")?;
+        components.insert(id, Component { name, mass, volume, max_stack_amount });
       }
 
       Ok(Self { components })
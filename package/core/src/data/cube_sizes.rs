@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::GridSize;
+
+/// Cube sizes per grid size as defined by Configuration.sbc.
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct CubeSizes {
+  /// Size of a small grid cube (m)
+  pub small: f64,
+  /// Size of a large grid cube (m)
+  pub large: f64,
+}
+
+impl Default for CubeSizes {
+  fn default() -> Self {
+    Self { small: GridSize::Small.size(), large: GridSize::Large.size() }
+  }
+}
+
+impl CubeSizes {
+  /// Gets the cube size of `grid_size` (m).
+  #[inline]
+  pub fn get(&self, grid_size: GridSize) -> f64 {
+    match grid_size {
+      GridSize::Small => self.small,
+      GridSize::Large => self.large,
+    }
+  }
+}
+
+
+// Extraction
+
+#[cfg(feature = "extract")]
+pub mod extract {
+  use std::path::{Path, PathBuf};
+
+  use roxmltree::Document;
+  use thiserror::Error;
+
+  use crate::data::cube_sizes::CubeSizes;
+  use crate::xml::{NodeExt, read_string_from_file, XmlError};
+
+  #[derive(Error, Debug)]
+  pub enum Error {
+    #[error("Could not read configuration file '{file}'")]
+    ReadFileFail { file: PathBuf, source: std::io::Error, },
+    #[error("Could not XML parse configuration file '{file}'")]
+    ParseFileFail { file: PathBuf, source: roxmltree::Error, },
+    #[error(transparent)]
+    XmlFail {
+      #[from]
+      source: XmlError
+    },
+  }
+
+  impl CubeSizes {
+    /// Extracts cube sizes from Configuration.sbc in `se_directory`, falling back to the default cube sizes if that
+    /// file does not exist.
+    pub fn from_se_dir<P: AsRef<Path>>(se_directory: P) -> Result<Self, Error> {
+      let path = se_directory.as_ref().join("Content/Data/Configuration.sbc");
+      if !path.exists() { return Ok(Self::default()); }
+      Self::from_sbc_file(path)
+    }
+
+    /// Extracts cube sizes from configuration SBC file `path`, falling back to the default size for cube sizes that
+    /// are not defined.
+    pub fn from_sbc_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+      let path = path.as_ref();
+      let string = read_string_from_file(path)
+        .map_err(|source| Error::ReadFileFail { file: path.to_path_buf(), source })?;
+      let doc = Document::parse(&string)
+        .map_err(|source| Error::ParseFileFail { file: path.to_path_buf(), source })?;
+
+      let mut cube_sizes = Self::default();
+      if let Some(cube_sizes_node) = doc.descendants().find(|n| n.has_tag_name("CubeSizes")) {
+        if let Some(small) = cube_sizes_node.parse_child_elem_opt("Small")? {
+          cube_sizes.small = small;
+        }
+        if let Some(large) = cube_sizes_node.parse_child_elem_opt("Large")? {
+          cube_sizes.large = large;
+        }
+      }
+      Ok(cube_sizes)
+    }
+  }
+}
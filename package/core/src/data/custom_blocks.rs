@@ -0,0 +1,192 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::{Battery, Block, BlockData, BlockId, Container, GridSize, HydrogenTank, InventoryConstraint, Reactor, Thruster, ThrusterType};
+use crate::data::components::Component;
+use crate::data::Data;
+
+/// ID of the synthetic component of 1 kg that custom blocks are made of, to give them their mass.
+pub const CUSTOM_BLOCK_MASS_COMPONENT_ID: &str = "CustomBlockMass";
+
+/// Block defined by the user instead of extracted from the game, for theory-crafting blocks that do not exist.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CustomBlock {
+  pub name: String,
+  pub size: GridSize,
+  /// Mass (kg)
+  pub mass: f64,
+  pub details: CustomBlockDetails,
+}
+
+impl Default for CustomBlock {
+  fn default() -> Self {
+    Self { name: "Custom Block".to_string(), size: GridSize::default(), mass: 1000.0, details: CustomBlockDetails::default() }
+  }
+}
+
+impl CustomBlock {
+  /// Gets the ID of this custom block, which is unique per name and grid size.
+  pub fn id(&self) -> BlockId {
+    format!("Custom.{}.{}", self.size, self.name)
+  }
+}
+
+/// Kind of a custom block with its key stats.
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CustomBlockDetails {
+  Thruster {
+    ty: ThrusterType,
+    /// Force (N)
+    force: f64,
+    /// Maximum consumption (MW for ion and atmospheric thrusters, L/s for hydrogen thrusters)
+    max_consumption: f64,
+  },
+  Battery {
+    /// Power capacity (MWh)
+    capacity: f64,
+    /// Maximum power input (MW)
+    input: f64,
+    /// Maximum power output (MW)
+    output: f64,
+  },
+  Reactor {
+    /// Maximum power generation (MW)
+    max_power_generation: f64,
+  },
+  HydrogenTank {
+    /// Hydrogen capacity (L)
+    capacity: f64,
+  },
+  Container {
+    /// Inventory volume (L)
+    inventory_volume: f64,
+  },
+}
+
+impl Default for CustomBlockDetails {
+  fn default() -> Self { CustomBlockKind::default().default_details() }
+}
+
+impl CustomBlockDetails {
+  #[inline]
+  pub fn kind(&self) -> CustomBlockKind {
+    match self {
+      CustomBlockDetails::Thruster { .. } => CustomBlockKind::Thruster,
+      CustomBlockDetails::Battery { .. } => CustomBlockKind::Battery,
+      CustomBlockDetails::Reactor { .. } => CustomBlockKind::Reactor,
+      CustomBlockDetails::HydrogenTank { .. } => CustomBlockKind::HydrogenTank,
+      CustomBlockDetails::Container { .. } => CustomBlockKind::Container,
+    }
+  }
+}
+
+/// Kind of a custom block, without its stats.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CustomBlockKind {
+  #[default] Thruster,
+  Battery,
+  Reactor,
+  HydrogenTank,
+  Container,
+}
+
+impl CustomBlockKind {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use CustomBlockKind::*;
+    const ITEMS: [CustomBlockKind; 5] = [Thruster, Battery, Reactor, HydrogenTank, Container];
+    ITEMS.into_iter()
+  }
+
+  /// Gets details of this kind with reasonable default stats.
+  pub fn default_details(&self) -> CustomBlockDetails {
+    match self {
+      CustomBlockKind::Thruster => CustomBlockDetails::Thruster { ty: ThrusterType::Ion, force: 100000.0, max_consumption: 1.0 },
+      CustomBlockKind::Battery => CustomBlockDetails::Battery { capacity: 3.0, input: 12.0, output: 12.0 },
+      CustomBlockKind::Reactor => CustomBlockDetails::Reactor { max_power_generation: 15.0 },
+      CustomBlockKind::HydrogenTank => CustomBlockDetails::HydrogenTank { capacity: 15000000.0 },
+      CustomBlockKind::Container => CustomBlockDetails::Container { inventory_volume: 421875.0 },
+    }
+  }
+}
+
+impl Display for CustomBlockKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use CustomBlockKind::*;
+    match self {
+      Thruster => f.write_str("Thruster"),
+      Battery => f.write_str("Battery"),
+      Reactor => f.write_str("Reactor"),
+      HydrogenTank => f.write_str("Hydrogen Tank"),
+      Container => f.write_str("Container"),
+    }
+  }
+}
+
+impl Data {
+  /// Adds `custom_blocks` to this data, replacing existing blocks with the same ID.
+  pub fn add_custom_blocks<'a>(&mut self, custom_blocks: impl IntoIterator<Item=&'a CustomBlock>) {
+    self.components.components.entry(CUSTOM_BLOCK_MASS_COMPONENT_ID.to_string())
+      .or_insert_with(|| Component { name: "Custom Block Mass".to_string(), mass: 1.0, volume: 0.0, max_stack_amount: None });
+    for custom_block in custom_blocks {
+      let id = custom_block.id();
+      let mut data = BlockData {
+        id: id.clone(),
+        name: custom_block.name.clone(),
+        size: custom_block.size,
+        has_physics: true,
+        pcu: Some(1), // Default PCU of blocks in the game.
+        build_time: Some(10.0), // Default build time of blocks in the game.
+        rename: Some(custom_block.name.clone()),
+        ..BlockData::default()
+      };
+      data.components.insert(CUSTOM_BLOCK_MASS_COMPONENT_ID.to_string(), custom_block.mass);
+      let blocks = &mut self.blocks;
+      match custom_block.details {
+        CustomBlockDetails::Thruster { ty, force, max_consumption } => {
+          let (fuel_gas_id, max_consumption) = if ty == ThrusterType::Hydrogen {
+            // Thrusters store fuel consumption in energy, convert from L/s.
+            let energy_density = self.gas_properties.get("Hydrogen").map_or(1.0, |p| p.energy_density);
+            (Some("Hydrogen".to_string()), max_consumption * energy_density)
+          } else {
+            (None, max_consumption)
+          };
+          let (min_planetary_influence, effectiveness_at_min_influence, effectiveness_at_max_influence, needs_atmosphere_for_influence) = match ty {
+            ThrusterType::Ion => (0.0, 1.0, 0.2, false),
+            ThrusterType::Atmospheric => (0.3, 0.0, 1.0, true),
+            ThrusterType::Hydrogen => (0.0, 1.0, 1.0, false),
+          };
+          let details = Thruster {
+            ty,
+            fuel_gas_id,
+            force,
+            max_consumption,
+            min_consumption: 0.0,
+            min_planetary_influence,
+            max_planetary_influence: 1.0,
+            effectiveness_at_min_influence,
+            effectiveness_at_max_influence,
+            needs_atmosphere_for_influence,
+          };
+          blocks.thrusters.insert(id, Block::new(data, details));
+        }
+        CustomBlockDetails::Battery { capacity, input, output } => {
+          blocks.batteries.insert(id, Block::new(data, Battery { capacity, input, output }));
+        }
+        CustomBlockDetails::Reactor { max_power_generation } => {
+          blocks.reactors.insert(id, Block::new(data, Reactor { max_power_generation, max_fuel_consumption: 0.0 }));
+        }
+        CustomBlockDetails::HydrogenTank { capacity } => {
+          blocks.hydrogen_tanks.insert(id, Block::new(data, HydrogenTank { capacity, operational_power_consumption: 0.0, idle_power_consumption: 0.0 }));
+        }
+        CustomBlockDetails::Container { inventory_volume } => {
+          blocks.containers.insert(id, Block::new(data, Container { inventory_volume_any: inventory_volume, store_any: true, constraint: InventoryConstraint::Other }));
+        }
+      }
+    }
+  }
+}
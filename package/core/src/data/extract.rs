@@ -1,14 +1,22 @@
-use std::path::Path;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::data::{blocks, components, Data, gas_properties, localization};
+use crate::data::{blocks, bottles, components, cube_sizes, Data, gas_properties, localization};
+use crate::data::blocks::ConveyorPorts;
 use crate::data::blocks::extract::BlocksBuilder;
+use crate::data::bottles::Bottles;
 use crate::data::components::Components;
+use crate::data::cube_sizes::CubeSizes;
 use crate::data::gas_properties::GasProperties;
 use crate::data::localization::extract::LocalizationBuilder;
 use crate::data::mods::{Mod, Mods};
+use crate::data::power_groups::{PowerGroup, PowerGroups};
 
 #[derive(Default, Clone, Serialize, Deserialize)]
 pub struct ExtractConfig {
@@ -21,6 +29,26 @@ pub struct ExtractConfig {
   pub hide_block_by_exact_id: Vec<String>,
   pub hide_block_by_regex_id: Vec<String>,
   pub rename_block_by_regex: Vec<(String, String)>,
+
+  /// Power groups to assign to blocks by their exact ID, for modded blocks that function as a different group than
+  /// their block kind.
+  #[serde(default)]
+  pub assign_power_group_by_exact_id: Vec<(String, PowerGroup)>,
+
+  /// Conveyor ports to assign to blocks by their exact ID. Block definitions do not contain conveyor ports, as these are
+  /// part of block models.
+  #[serde(default)]
+  pub assign_conveyor_ports_by_exact_id: Vec<(String, ConveyorPorts)>,
+
+  /// Whether to record the source file and line of each block definition in the extracted data, for debugging
+  /// extraction. Disabled by default to keep extracted data small.
+  #[serde(default)]
+  pub record_block_sources: bool,
+
+  /// Language (e.g., `de` or `zh-CN`) to extract texts in, falling back to English for texts that are not translated.
+  /// None to only extract English texts.
+  #[serde(default)]
+  pub language: Option<String>,
 }
 
 #[derive(Error, Debug)]
@@ -35,11 +63,21 @@ pub enum ExtractError {
     #[from]
     source: blocks::extract::ExtractError
   },
+  #[error("Could not extract bottles")]
+  ExtractBottlesFail {
+    #[from]
+    source: bottles::extract::Error
+  },
   #[error("Could not extract components")]
   ExtractComponentsFail {
     #[from]
     source: components::extract::Error
   },
+  #[error("Could not extract cube sizes")]
+  ExtractCubeSizesFail {
+    #[from]
+    source: cube_sizes::extract::Error
+  },
   #[error("Could not extract gas properties")]
   ExtractGasPropertiesFail {
     #[from]
@@ -50,26 +88,188 @@ pub enum ExtractError {
     #[from]
     source: localization::extract::Error
   },
+  #[error("Extraction was cancelled")]
+  Cancelled,
+}
+
+/// Step of extraction.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ExtractStep {
+  Localization,
+  ModLocalization(u64),
+  Blocks,
+  ModBlocks(u64),
+  Components,
+  Bottles,
+  GasProperties,
+}
+
+impl Display for ExtractStep {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use ExtractStep::*;
+    match self {
+      Localization => f.write_str("Extracting localization"),
+      ModLocalization(id) => write!(f, "Extracting localization of mod {}", id),
+      Blocks => f.write_str("Extracting blocks"),
+      ModBlocks(id) => write!(f, "Extracting blocks of mod {}", id),
+      Components => f.write_str("Extracting components"),
+      Bottles => f.write_str("Extracting bottles"),
+      GasProperties => f.write_str("Extracting gas properties"),
+    }
+  }
+}
+
+/// Progress of extraction, reported before each step.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ExtractProgress {
+  /// Step that is about to start
+  pub step: ExtractStep,
+  /// Number of completed steps
+  pub completed: usize,
+  /// Total number of steps
+  pub total: usize,
+}
+
+impl ExtractProgress {
+  /// Gets the fraction of completed steps 0-1.
+  #[inline]
+  pub fn fraction(&self) -> f64 {
+    if self.total == 0 { 1.0 } else { self.completed as f64 / self.total as f64 }
+  }
+}
+
+/// Token for cancelling extraction, possibly from another thread. Clones share cancellation.
+#[derive(Default, Clone, Debug)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+  #[inline]
+  pub fn cancel(&self) { self.0.store(true, Ordering::Relaxed); }
+  #[inline]
+  pub fn is_cancelled(&self) -> bool { self.0.load(Ordering::Relaxed) }
+}
+
+/// Extraction running on a background thread, which can be cancelled and queried for progress without blocking.
+pub struct ExtractTask {
+  cancellation_token: CancellationToken,
+  progress: Arc<Mutex<Option<ExtractProgress>>>,
+  handle: JoinHandle<Result<Data, ExtractError>>,
+}
+
+impl ExtractTask {
+  /// Starts extracting data on a background thread. See [Data::extract_from_se_dir].
+  pub fn spawn(se_directory: PathBuf, se_workshop_directories: impl IntoIterator<Item=PathBuf>, extract_config: ExtractConfig) -> Self {
+    let se_workshop_directories: Vec<PathBuf> = se_workshop_directories.into_iter().collect();
+    let cancellation_token = CancellationToken::default();
+    let progress = Arc::new(Mutex::new(None));
+    let handle = {
+      let cancellation_token = cancellation_token.clone();
+      let progress = progress.clone();
+      std::thread::spawn(move || {
+        Data::extract_from_se_dir_cancellable(se_directory, se_workshop_directories, extract_config, &cancellation_token, |p| {
+          *progress.lock().unwrap() = Some(p);
+        })
+      })
+    };
+    Self { cancellation_token, progress, handle }
+  }
+
+  /// Requests cancellation. Extraction stops before its next step, after which joining returns
+  /// [ExtractError::Cancelled].
+  #[inline]
+  pub fn cancel(&self) { self.cancellation_token.cancel(); }
+
+  /// Gets the latest progress, or None if extraction has not reported progress yet.
+  #[inline]
+  pub fn progress(&self) -> Option<ExtractProgress> { *self.progress.lock().unwrap() }
+
+  /// Checks whether extraction has finished, after which joining does not block.
+  #[inline]
+  pub fn is_finished(&self) -> bool { self.handle.is_finished() }
+
+  /// Waits for extraction to finish and returns its result.
+  pub fn join(self) -> Result<Data, ExtractError> {
+    match self.handle.join() {
+      Ok(result) => result,
+      Err(payload) => std::panic::resume_unwind(payload),
+    }
+  }
+}
+
+/// Directories, relative to a workshop root, that contain a directory per Space Engineers mod, in detection order.
+const WORKSHOP_CONTENT_LAYOUTS: [&str; 4] = [
+  "steamapps/workshop/content/244850", // Steam library or SteamCMD installation
+  "workshop/content/244850", // steamapps directory
+  "content/244850", // Dedicated server instance, or workshop directory
+  "244850", // Workshop content directory
+];
+
+/// Resolves workshop root `directory` into the directory that contains a directory per mod ID. Detects the layouts of
+/// Steam libraries and SteamCMD installations, dedicated server instances, and directories in between. Returns
+/// `directory` as-is if none of these layouts are found, as it then presumably already contains the mod directories.
+pub fn resolve_workshop_directory(directory: impl AsRef<Path>) -> PathBuf {
+  let directory = directory.as_ref();
+  WORKSHOP_CONTENT_LAYOUTS.into_iter()
+    .map(|layout| directory.join(layout))
+    .find(|content_directory| content_directory.is_dir())
+    .unwrap_or_else(|| directory.to_path_buf())
+}
+
+/// Finds the first of `workshop_directories` (as resolved by [resolve_workshop_directory]) that contains mod `mod_id`,
+/// or None if no directory contains it.
+pub fn find_mod_workshop_directory(workshop_directories: &[PathBuf], mod_id: u64) -> Option<&Path> {
+  workshop_directories.iter()
+    .find(|directory| directory.join(mod_id.to_string()).is_dir())
+    .map(|directory| directory.as_path())
 }
 
 impl Data {
+  /// Extracts data from the game in `se_directory`, and from mods in `extract_config` found in
+  /// `se_workshop_directories`. Each workshop directory is resolved with [resolve_workshop_directory], and mods are
+  /// extracted from the first workshop directory that contains them. Mods that are not found are not extracted.
   pub fn extract_from_se_dir(
     se_directory: impl AsRef<Path>,
-    se_workshop_directory: Option<impl AsRef<Path>>,
+    se_workshop_directories: impl IntoIterator<Item=impl AsRef<Path>>,
+    extract_config: ExtractConfig,
+  ) -> Result<Self, ExtractError> {
+    Self::extract_from_se_dir_cancellable(se_directory, se_workshop_directories, extract_config, &CancellationToken::default(), |_| {})
+  }
+
+  /// Extracts data like [Self::extract_from_se_dir], calling `progress` before each step, and returning
+  /// [ExtractError::Cancelled] before the next step once `cancellation_token` is cancelled.
+  pub fn extract_from_se_dir_cancellable(
+    se_directory: impl AsRef<Path>,
+    se_workshop_directories: impl IntoIterator<Item=impl AsRef<Path>>,
     extract_config: ExtractConfig,
+    cancellation_token: &CancellationToken,
+    mut progress: impl FnMut(ExtractProgress),
   ) -> Result<Self, ExtractError> {
     let se_directory = se_directory.as_ref();
     // Mods
-    let mods = Mods::new(extract_config.extract_mods.into_iter());
+    let mut mods = Mods::new(extract_config.extract_mods.into_iter());
+    let se_workshop_directories: Vec<PathBuf> = se_workshop_directories.into_iter().map(resolve_workshop_directory).collect();
+    let mod_ids: Vec<(u64, &Path)> = mods.mods.keys()
+      .filter_map(|mod_id| find_mod_workshop_directory(&se_workshop_directories, *mod_id).map(|directory| (*mod_id, directory)))
+      .collect();
+    // Progress
+    let total = 5 + mod_ids.len() * 2;
+    let mut completed = 0;
+    let mut start_step = |step: ExtractStep| {
+      if cancellation_token.is_cancelled() { return Err(ExtractError::Cancelled); }
+      progress(ExtractProgress { step, completed, total });
+      completed += 1;
+      Ok(())
+    };
     // Localization
     let mut localization_builder = LocalizationBuilder::default();
+    localization_builder.set_language(extract_config.language);
+    start_step(ExtractStep::Localization)?;
     localization_builder.update_from_se_dir(se_directory)?;
-    if let Some(se_workshop_directory) = &se_workshop_directory {
-      for mod_id in mods.mods.keys() {
-        localization_builder.update_from_mod(&se_workshop_directory, *mod_id)?;
-      }
+    for (mod_id, se_workshop_directory) in &mod_ids {
+      start_step(ExtractStep::ModLocalization(*mod_id))?;
+      localization_builder.update_from_mod(se_workshop_directory, *mod_id)?;
     }
-    let localization = localization_builder.into_localization();
+    let mut localization = localization_builder.into_localization();
     // Blocks
     let mut blocks_builder = BlocksBuilder::new(
       extract_config.hide_block_by_exact_name.into_iter(),
@@ -80,18 +280,36 @@ impl Data {
       extract_config.hide_block_by_regex_id.into_iter(),
       extract_config.rename_block_by_regex.into_iter(),
     )?;
+    blocks_builder.set_record_sources(extract_config.record_block_sources);
+    blocks_builder.set_conveyor_ports(extract_config.assign_conveyor_ports_by_exact_id.into_iter());
+    start_step(ExtractStep::Blocks)?;
+    let cube_sizes = CubeSizes::from_se_dir(se_directory)?;
+    blocks_builder.set_cube_sizes(cube_sizes);
     blocks_builder.update_from_se_dir(se_directory, &localization)?;
-    if let Some(se_workshop_directory) = &se_workshop_directory {
-      for mod_id in mods.mods.keys() {
-        blocks_builder.update_from_mod(se_directory, &se_workshop_directory, *mod_id, &localization)?;
-      }
+    for (mod_id, se_workshop_directory) in &mod_ids {
+      start_step(ExtractStep::ModBlocks(*mod_id))?;
+      blocks_builder.update_from_mod(se_directory, se_workshop_directory, *mod_id, &localization)?;
     }
+    mods.overrides = blocks_builder.take_overrides();
     let blocks = blocks_builder.into_blocks(&localization);
     // Components
+    start_step(ExtractStep::Components)?;
     let components = Components::from_se_dir(se_directory)?;
+    // Bottles
+    start_step(ExtractStep::Bottles)?;
+    let bottles = Bottles::from_se_dir(se_directory)?;
+    // Fallbacks for localization keys that are referenced but not defined, such as by mods without localization files.
+    let block_keys = blocks.iter_data()
+      .flat_map(|data| data.rename.is_none().then_some(data.name.as_str()).into_iter().chain(data.description.as_deref()));
+    let component_keys = components.components.values().map(|c| c.name.as_str());
+    let bottle_keys = bottles.bottles.values().map(|b| b.name.as_str());
+    localization.add_fallbacks(block_keys.chain(component_keys).chain(bottle_keys));
     // Gas properties
+    start_step(ExtractStep::GasProperties)?;
     let gas_properties = GasProperties::from_se_dir(se_directory)?;
+    // Power groups
+    let power_groups = PowerGroups { power_groups: extract_config.assign_power_group_by_exact_id.into_iter().collect() };
     // Data
-    Ok(Self { blocks, components, gas_properties, localization, mods })
+    Ok(Self { version: None, blocks, components, gas_properties, bottles, cube_sizes, localization, mods, power_groups })
   }
 }
@@ -1,9 +1,16 @@
 use hashlink::LinkedHashMap;
 use serde::{Deserialize, Serialize};
 
+/// ID of the hydrogen gas.
+pub const HYDROGEN_GAS_ID: &str = "Hydrogen";
+/// ID of the oxygen gas.
+pub const OXYGEN_GAS_ID: &str = "Oxygen";
+
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct GasProperties {
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<String, GasProperty>"))]
   pub gas_properties: LinkedHashMap<String, GasProperty>,
 }
 
@@ -13,6 +20,7 @@ impl GasProperties {
 }
 
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct GasProperty {
   pub name: String,
@@ -2,26 +2,90 @@ use hashlink::LinkedHashMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct Localization {
+  /// English (or default language) texts by key
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<String, String>"))]
   pub localization: LinkedHashMap<String, String>,
+  /// Texts in the selected language by key, taking precedence over English texts. Empty if no language other than
+  /// English was selected during extraction.
+  #[serde(skip_serializing_if = "LinkedHashMap::is_empty")]
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<String, String>"))]
+  pub selected_language: LinkedHashMap<String, String>,
+  /// Humanized texts of keys that are not localized in any language, such as keys that mods reference but do not
+  /// define.
+  #[serde(skip_serializing_if = "LinkedHashMap::is_empty")]
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<String, String>"))]
+  pub fallbacks: LinkedHashMap<String, String>,
 }
 
 impl Localization {
+  /// Gets the text of `id` in the selected language, falling back to English, then to the humanized fallback of `id`,
+  /// and finally to `id` itself if it is not a localization key, such as a display name that is already text.
   #[inline]
   pub fn get<'a>(&'a self, id: &'a str) -> &'a str {
-    if let Some(name) = self.localization.get(id) {
-      &name
-    } else { // Some mods use {LOC:<name>} as DisplayName, remove those part and try again.
-      let len = id.len();
-      if len > 6 {
-        if let Some(name) = self.localization.get(&id[5..len - 1]) {
-          return name;
-        }
-      }
-      id // Otherwise, just return the id as name.
+    self.resolve(id)
+      .or_else(|| self.fallbacks.get(id).map(|t| t.as_str()))
+      .unwrap_or(id)
+  }
+
+  /// Gets the text of `id` in the selected language or in English, or None if `id` is not localized.
+  pub fn resolve(&self, id: &str) -> Option<&str> {
+    // Some mods use {LOC:<key>} as DisplayName, also try the key inside.
+    let key = strip_loc(id);
+    [&self.selected_language, &self.localization].into_iter()
+      .find_map(|texts| texts.get(id).or_else(|| key.and_then(|key| texts.get(key))))
+      .map(|t| t.as_str())
+  }
+
+  /// Checks whether `id` is a localization key that is not localized in any language.
+  pub fn is_unresolved(&self, id: &str) -> bool {
+    self.resolve(id).is_none() && is_key(id)
+  }
+
+  /// Adds humanized fallbacks for each of `ids` that is unresolved, returning the unresolved IDs.
+  pub fn add_fallbacks<'a>(&mut self, ids: impl IntoIterator<Item=&'a str>) -> Vec<String> {
+    let mut unresolved = Vec::new();
+    for id in ids {
+      if !self.is_unresolved(id) || self.fallbacks.contains_key(id) { continue; }
+      self.fallbacks.insert(id.to_string(), humanize_key(id));
+      unresolved.push(id.to_string());
+    }
+    unresolved
+  }
+}
+
+/// Gets the key inside `{LOC:<key>}`, or None if `id` is not of that form.
+fn strip_loc(id: &str) -> Option<&str> {
+  id.strip_prefix("{LOC:").and_then(|id| id.strip_suffix('}'))
+}
+
+/// Checks whether `id` looks like a localization key instead of text: `{LOC:<key>}`, or a single word with underscores,
+/// such as `DisplayName_Block_Foo`.
+fn is_key(id: &str) -> bool {
+  strip_loc(id).is_some() || (id.contains('_') && !id.contains(char::is_whitespace))
+}
+
+/// Turns localization key `id` into readable text by removing common key prefixes and splitting words at underscores
+/// and case changes, for example `DisplayName_Block_LargeFooBar` into `Large Foo Bar`.
+pub fn humanize_key(id: &str) -> String {
+  const PREFIXES: [&str; 4] = ["DisplayName_Block_", "DisplayName_Item_", "DisplayName_", "Description_"];
+  let key = strip_loc(id).unwrap_or(id);
+  let key = PREFIXES.into_iter().find_map(|prefix| key.strip_prefix(prefix)).unwrap_or(key);
+  let mut text = String::with_capacity(key.len() + 4);
+  let mut previous: Option<char> = None;
+  for c in key.chars() {
+    if c == '_' {
+      if !text.is_empty() && !text.ends_with(' ') { text.push(' '); }
+    } else {
+      let starts_word = previous.is_some_and(|p| p.is_lowercase() && c.is_uppercase());
+      if starts_word && !text.ends_with(' ') { text.push(' '); }
+      text.push(c);
     }
+    previous = Some(c);
   }
+  text.trim_end().to_string()
 }
 
 
@@ -42,6 +106,8 @@ pub mod extract {
   #[derive(Default)]
   pub struct LocalizationBuilder {
     pub localization: LinkedHashMap<String, String>,
+    pub selected_language: LinkedHashMap<String, String>,
+    language: Option<String>,
   }
 
   #[derive(Error, Debug)]
@@ -58,8 +124,22 @@ pub mod extract {
   }
 
   impl LocalizationBuilder {
+    /// Sets the language (e.g., `de` or `zh-CN`) to extract in addition to English, or None to only extract English.
+    pub fn set_language(&mut self, language: Option<String>) {
+      self.language = language.filter(|l| !l.is_empty() && l != "en" && l != "en-US");
+    }
+
     pub fn update_from_se_dir(&mut self, se_directory: impl AsRef<Path>) -> Result<(), Error> {
-      self.update_from_resx_file(se_directory.as_ref().join("Content/Data/Localization/MyTexts.resx"))
+      let localization_directory = se_directory.as_ref().join("Content/Data/Localization");
+      self.update_from_resx_file(localization_directory.join("MyTexts.resx"))?;
+      if let Some(language) = &self.language {
+        let path = localization_directory.join(format!("MyTexts.{}.resx", language));
+        // Missing language files are not an error: texts fall back to English.
+        if path.exists() {
+          read_resx_file(path, &mut self.selected_language)?;
+        }
+      }
+      Ok(())
     }
 
     pub fn update_from_mod(
@@ -73,7 +153,7 @@ pub mod extract {
         .filter_map(|de| {
           if let Ok(de) = de {
             let path = de.into_path();
-            if !path.extension().map_or(false, |e| e == "sbl") { return None; }
+            if path.extension().is_none_or(|e| e != "sbl") { return None; }
             Some(path)
           } else {
             None
@@ -91,7 +171,7 @@ pub mod extract {
           .filter_map(|de| {
             if let Ok(de) = de {
               let path = de.into_path();
-              if !path.file_name().map_or(false, |n| n == "MyTexts.resx") { return None; }
+              if path.file_name().is_none_or(|n| n != "MyTexts.resx") { return None; }
               Some(path)
             } else {
               None
@@ -115,37 +195,44 @@ pub mod extract {
       let resx_name: String = root_element.parse_child_elem("ResXName")?;
       let language: String = root_element.parse_child_elem("Language")?;
       let default: bool = root_element.parse_child_elem("Default")?;
+      let resx_path = path.parent().unwrap().join(resx_name); // Unwrap OK: path to file must have a parent directory.
       if language == "en-US" || default {
-        let resx_path = path.parent().unwrap().join(resx_name); // Unwrap OK: path to file must have a parent directory.
         self.update_from_resx_file(resx_path)?;
         Ok(true)
       } else {
+        if self.language.as_ref().is_some_and(|l| language.eq_ignore_ascii_case(l) || language.split('-').next().is_some_and(|p| p.eq_ignore_ascii_case(l))) {
+          read_resx_file(resx_path, &mut self.selected_language)?;
+        }
         Ok(false)
       }
     }
 
     pub fn update_from_resx_file(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
-      let path = path.as_ref();
-      let string = read_string_from_file(path)
-        .map_err(|source| Error::ReadFileFail { file: path.to_path_buf(), source })?;
-      let doc = Document::parse(&string)
-        .map_err(|source| Error::ParseFileFail { file: path.to_path_buf(), source })?;
-      let root_element = doc.root();
-      let root_element = root_element.first_child_elem()?;
-      for node in root_element.children_elems("data") {
-        if let Some(name) = node.attribute("name") {
-          if let Some(value_node) = node.first_element_child() {
-            if let Some(value) = value_node.text() {
-              self.localization.insert(name.to_string(), value.to_string());
-            }
-          }
-        }
-      }
-      Ok(())
+      read_resx_file(path, &mut self.localization)
     }
 
     pub fn into_localization(self) -> Localization {
-      Localization { localization: self.localization }
+      Localization { localization: self.localization, selected_language: self.selected_language, ..Localization::default() }
+    }
+  }
+
+  fn read_resx_file(path: impl AsRef<Path>, texts: &mut LinkedHashMap<String, String>) -> Result<(), Error> {
+    let path = path.as_ref();
+    let string = read_string_from_file(path)
+      .map_err(|source| Error::ReadFileFail { file: path.to_path_buf(), source })?;
+    let doc = Document::parse(&string)
+      .map_err(|source| Error::ParseFileFail { file: path.to_path_buf(), source })?;
+    let root_element = doc.root();
+    let root_element = root_element.first_child_elem()?;
+    for node in root_element.children_elems("data") {
+      if let Some(name) = node.attribute("name") {
+        if let Some(value_node) = node.first_element_child() {
+          if let Some(value) = value_node.text() {
+            texts.insert(name.to_string(), value.to_string());
+          }
+        }
+      }
     }
+    Ok(())
   }
 }
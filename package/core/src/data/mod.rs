@@ -4,35 +4,53 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::data::blocks::Blocks;
+use crate::data::bottles::Bottles;
 use crate::data::components::Components;
+use crate::data::cube_sizes::CubeSizes;
 use crate::data::gas_properties::GasProperties;
 use crate::data::localization::Localization;
 use crate::data::mods::Mods;
+use crate::data::power_groups::PowerGroups;
 
 pub mod blocks;
+pub mod bottles;
 pub mod components;
+pub mod cube_sizes;
+pub mod custom_blocks;
 pub mod gas_properties;
 pub mod localization;
 pub mod mods;
+pub mod overrides;
+pub mod power_groups;
+pub mod validate;
 #[cfg(feature = "extract")]
 pub mod extract;
 
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct Data {
+  /// Version of the game the data was extracted from, or None if unknown.
+  pub version: Option<String>,
   pub mods: Mods,
   pub localization: Localization,
   pub blocks: Blocks,
   pub components: Components,
   pub gas_properties: GasProperties,
+  /// Items that store gas, such as hydrogen and oxygen bottles.
+  pub bottles: Bottles,
+  /// Cube sizes per grid size.
+  pub cube_sizes: CubeSizes,
+  /// Power groups assigned to blocks by the extraction config.
+  pub power_groups: PowerGroups,
 }
 
 // From/to JSON
 
 #[derive(Error, Debug)]
 pub enum ReadError {
-  #[error("Could not read data from JSON")]
-  FromJSONFail(#[from] serde_json::Error),
+  #[error("Could not read data from JSON at '{path}'")]
+  FromJSONFail { path: String, source: serde_json::Error },
 }
 
 #[derive(Error, Debug)]
@@ -43,7 +61,9 @@ pub enum WriteError {
 
 impl Data {
   pub fn from_json<R: io::Read>(reader: R) -> Result<Self, ReadError> {
-    let data = serde_json::from_reader(reader)?;
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let data = serde_path_to_error::deserialize(&mut deserializer)
+      .map_err(|e| ReadError::FromJSONFail { path: e.path().to_string(), source: e.into_inner() })?;
     Ok(data)
   }
 
@@ -2,9 +2,13 @@ use hashlink::LinkedHashMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct Mods {
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<u64, Mod>"))]
   pub mods: LinkedHashMap<u64, Mod>,
+  /// Block definitions that override an earlier definition with the same TypeId and SubtypeId.
+  pub overrides: Vec<ModOverride>,
 }
 
 impl Mods {
@@ -14,7 +18,7 @@ impl Mods {
     for m in mods {
       map.insert(m.0, m);
     }
-    Self { mods: map }
+    Self { mods: map, overrides: Vec::new() }
   }
 
   #[inline]
@@ -25,4 +29,17 @@ impl Mods {
 }
 
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Mod(pub u64, pub String);
+
+/// Block definition of a mod that overrides an earlier definition of a block with the same TypeId and SubtypeId.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ModOverride {
+  /// TypeId.SubtypeId of the overridden block
+  pub id: String,
+  /// Mod that overrides the block, or None if the overriding definition is from the game itself.
+  pub mod_id: Option<u64>,
+  /// Mod that defined the overridden block, or None if the overridden definition is from the game itself.
+  pub overridden_mod_id: Option<u64>,
+}
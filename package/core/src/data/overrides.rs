@@ -0,0 +1,182 @@
+//! Overrides of block stats, for matching the data to servers that tweak blocks with mods, without extracting the data
+//! again.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::data::blocks::BlockId;
+use crate::data::Data;
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+  #[error("Could not read block overrides from JSON at '{path}'")]
+  FromJSONFail { path: String, source: serde_json::Error },
+}
+
+#[derive(Error, Debug)]
+pub enum WriteError {
+  #[error("Could not write block overrides to JSON")]
+  ToJSONFail(#[from] serde_json::Error),
+}
+
+/// Overrides of the stats of blocks. Serializable to JSON for sharing overrides between players of a server.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Overrides {
+  /// Override per block
+  pub blocks: BTreeMap<BlockId, BlockOverride>,
+}
+
+impl Overrides {
+  pub fn from_json<R: io::Read>(reader: R) -> Result<Self, ReadError> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let overrides = serde_path_to_error::deserialize(&mut deserializer)
+      .map_err(|e| ReadError::FromJSONFail { path: e.path().to_string(), source: e.into_inner() })?;
+    Ok(overrides)
+  }
+
+  pub fn to_json<W: io::Write>(&self, writer: W) -> Result<(), WriteError> {
+    serde_json::to_writer_pretty(writer, self)?;
+    Ok(())
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool { self.blocks.is_empty() }
+}
+
+/// Override of the stats of one block, where None keeps the stat of the data.
+#[derive(Default, Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct BlockOverride {
+  /// Force of thrusters and wheel suspensions (N)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub force: Option<f64>,
+  /// Capacity of batteries, jump drives, and railguns (MWh), or of hydrogen and oxygen tanks (L)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub capacity: Option<f64>,
+  /// Maximum power generation of reactors and hydrogen engines, or maximum output of batteries (MW)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub power_generation: Option<f64>,
+  /// Operational power consumption of blocks, maximum consumption of ion and atmospheric thrusters, or maximum input
+  /// of batteries (MW)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub power_consumption: Option<f64>,
+}
+
+impl BlockOverride {
+  /// Gets the names of the stats that are overridden.
+  fn fields(&self) -> impl Iterator<Item=&'static str> {
+    [
+      ("force", self.force),
+      ("capacity", self.capacity),
+      ("power_generation", self.power_generation),
+      ("power_consumption", self.power_consumption),
+    ].into_iter().filter_map(|(name, value)| value.map(|_| name))
+  }
+}
+
+/// Problem found when applying overrides.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum OverrideProblem {
+  /// Override is for a block that does not exist
+  UnknownBlock { block_id: BlockId },
+  /// Override sets a stat that the block does not have
+  UnsupportedField { block_id: BlockId, field: &'static str },
+}
+
+impl Display for OverrideProblem {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use OverrideProblem::*;
+    match self {
+      UnknownBlock { block_id } => write!(f, "Override is for unknown block '{}'", block_id),
+      UnsupportedField { block_id, field } => write!(f, "Block '{}' has no '{}' stat to override", block_id, field),
+    }
+  }
+}
+
+/// Replaces `value` with the override in `field`, taking it to mark it as applied.
+fn apply(value: &mut f64, field: &mut Option<f64>) {
+  if let Some(v) = field.take() {
+    *value = v;
+  }
+}
+
+/// Replaces the power generation `value` with the override in `field`, scaling `fuel_consumption` along with it, as
+/// fuel consumption is proportional to power generation in the game.
+fn apply_power_generation(value: &mut f64, fuel_consumption: &mut f64, field: &mut Option<f64>) {
+  if let Some(v) = field.take() {
+    if *value > 0.0 {
+      *fuel_consumption *= v / *value;
+    }
+    *value = v;
+  }
+}
+
+impl Data {
+  /// Applies `overrides` to the blocks of this data, returning the overrides that could not be applied.
+  pub fn apply_overrides(&mut self, overrides: &Overrides) -> Vec<OverrideProblem> {
+    let mut problems = Vec::new();
+    let blocks = &mut self.blocks;
+    for (id, block_override) in &overrides.blocks {
+      let mut o = *block_override;
+      if let Some(b) = blocks.batteries.get_mut(id) {
+        apply(&mut b.details.capacity, &mut o.capacity);
+        apply(&mut b.details.output, &mut o.power_generation);
+        apply(&mut b.details.input, &mut o.power_consumption);
+      } else if let Some(b) = blocks.jump_drives.get_mut(id) {
+        apply(&mut b.details.capacity, &mut o.capacity);
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.railguns.get_mut(id) {
+        apply(&mut b.details.capacity, &mut o.capacity);
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.thrusters.get_mut(id) {
+        apply(&mut b.details.force, &mut o.force);
+        if b.details.fuel_gas_id.is_none() {
+          apply(&mut b.details.max_consumption, &mut o.power_consumption);
+        }
+      } else if let Some(b) = blocks.wheel_suspensions.get_mut(id) {
+        apply(&mut b.details.force, &mut o.force);
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.hydrogen_engines.get_mut(id) {
+        let details = &mut b.details;
+        apply_power_generation(&mut details.max_power_generation, &mut details.max_fuel_consumption, &mut o.power_generation);
+      } else if let Some(b) = blocks.reactors.get_mut(id) {
+        let details = &mut b.details;
+        apply_power_generation(&mut details.max_power_generation, &mut details.max_fuel_consumption, &mut o.power_generation);
+      } else if let Some(b) = blocks.hydrogen_tanks.get_mut(id) {
+        apply(&mut b.details.capacity, &mut o.capacity);
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.oxygen_tanks.get_mut(id) {
+        apply(&mut b.details.capacity, &mut o.capacity);
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.generators.get_mut(id) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.oxygen_farms.get_mut(id) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.air_vents.get_mut(id) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.drills.get_mut(id) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.gyroscopes.get_mut(id) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.refineries.get_mut(id) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.assemblers.get_mut(id) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if let Some(b) = blocks.turrets.get_mut(id).or_else(|| blocks.fixed_weapons.get_mut(id)).or_else(|| blocks.interior_turrets.get_mut(id)) {
+        apply(&mut b.details.operational_power_consumption, &mut o.power_consumption);
+      } else if blocks.get_data(id).is_none() {
+        problems.push(OverrideProblem::UnknownBlock { block_id: id.clone() });
+        continue;
+      }
+      problems.extend(o.fields().map(|field| OverrideProblem::UnsupportedField { block_id: id.clone(), field }));
+    }
+    problems
+  }
+}
@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter};
+
+use hashlink::LinkedHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::BlockId;
+
+/// Group of power consumers in the power chain.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PowerGroup {
+  /// Defense, such as charging railguns
+  Defense,
+  /// Utility, such as drills and hydrogen tanks
+  Utility,
+  /// Production, such as O2/H2 generators
+  Production,
+}
+
+impl PowerGroup {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use PowerGroup::*;
+    const ITEMS: [PowerGroup; 3] = [Defense, Utility, Production];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for PowerGroup {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use PowerGroup::*;
+    match self {
+      Defense => f.write_str("Defense"),
+      Utility => f.write_str("Utility"),
+      Production => f.write_str("Production"),
+    }
+  }
+}
+
+/// Power groups assigned to blocks, overriding the power group of their block kind.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct PowerGroups {
+  #[cfg_attr(feature = "schema", schemars(with = "std::collections::HashMap<BlockId, PowerGroup>"))]
+  pub power_groups: LinkedHashMap<BlockId, PowerGroup>,
+}
+
+impl PowerGroups {
+  #[inline]
+  pub fn get(&self, id: &str) -> Option<PowerGroup> { self.power_groups.get(id).copied() }
+}
@@ -0,0 +1,144 @@
+//! Validation of cross-references in data, for finding incomplete or broken extractions, and of grids against data,
+//! for finding blocks that changed since the grid was saved.
+
+use std::fmt::{Display, Formatter};
+
+use crate::data::blocks::BlockId;
+use crate::data::Data;
+use crate::grid::GridCalculator;
+
+/// Problem found when validating data.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum DataProblem {
+  /// Block is made of a component that does not exist
+  UnknownComponent { block_id: BlockId, component_id: String },
+  /// Thruster uses a fuel gas that does not exist
+  UnknownFuelGas { block_id: BlockId, gas_id: String },
+  /// Block is from a mod that does not exist
+  UnknownMod { block_id: BlockId, mod_id: u64 },
+  /// Power group is assigned to a block that does not exist
+  UnknownPowerGroupBlock { block_id: BlockId },
+  /// Localization key is not localized in any language, and is shown as its humanized fallback
+  UnresolvedLocalizationKey { key: String, fallback: String },
+}
+
+impl Display for DataProblem {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use DataProblem::*;
+    match self {
+      UnknownComponent { block_id, component_id } => write!(f, "Block '{}' is made of unknown component '{}'", block_id, component_id),
+      UnknownFuelGas { block_id, gas_id } => write!(f, "Thruster '{}' uses unknown fuel gas '{}'", block_id, gas_id),
+      UnknownMod { block_id, mod_id } => write!(f, "Block '{}' is from unknown mod '{}'", block_id, mod_id),
+      UnknownPowerGroupBlock { block_id } => write!(f, "Power group is assigned to unknown block '{}'", block_id),
+      UnresolvedLocalizationKey { key, fallback } => write!(f, "Localization key '{}' is not localized, shown as '{}'", key, fallback),
+    }
+  }
+}
+
+/// Report of validating data.
+#[derive(Default, Clone, Debug)]
+pub struct DataValidation {
+  pub problems: Vec<DataProblem>,
+}
+
+impl DataValidation {
+  #[inline]
+  pub fn is_valid(&self) -> bool { self.problems.is_empty() }
+}
+
+impl Data {
+  /// Validates the cross-references in this data, such as components of blocks and fuel gases of thrusters, returning
+  /// a report of all references to things that do not exist.
+  pub fn validate(&self) -> DataValidation {
+    let mut problems = Vec::new();
+    for data in self.blocks.iter_data() {
+      for component_id in data.unknown_component_ids(&self.components) {
+        problems.push(DataProblem::UnknownComponent { block_id: data.id_cloned(), component_id: component_id.clone() });
+      }
+      if let Some(mod_id) = data.mod_id {
+        if !self.mods.mods.contains_key(&mod_id) {
+          problems.push(DataProblem::UnknownMod { block_id: data.id_cloned(), mod_id });
+        }
+      }
+    }
+    for block in self.blocks.thrusters.values() {
+      if let Some(gas_id) = &block.details.fuel_gas_id {
+        if self.gas_properties.get(gas_id).is_none() {
+          problems.push(DataProblem::UnknownFuelGas { block_id: block.data.id_cloned(), gas_id: gas_id.clone() });
+        }
+      }
+    }
+    for block_id in self.power_groups.power_groups.keys() {
+      if self.blocks.get_data(block_id).is_none() {
+        problems.push(DataProblem::UnknownPowerGroupBlock { block_id: block_id.clone() });
+      }
+    }
+    for (key, fallback) in &self.localization.fallbacks {
+      problems.push(DataProblem::UnresolvedLocalizationKey { key: key.clone(), fallback: fallback.clone() });
+    }
+    DataValidation { problems }
+  }
+}
+
+
+/// Problem found when validating a grid against data.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GridProblem {
+  /// Block has a non-zero count, but does not exist in the data
+  UnknownBlock { block_id: BlockId },
+  /// Block has a non-zero count, but is hidden by the extraction configuration; calculated unless `ignored`
+  HiddenBlock { block_id: BlockId, ignored: bool },
+  /// Block has a non-zero count, but is made of components that do not exist in the data, understating its mass
+  UnknownComponents { block_id: BlockId, component_ids: Vec<String> },
+}
+
+impl Display for GridProblem {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use GridProblem::*;
+    match self {
+      UnknownBlock { block_id } => write!(f, "Block '{}' does not exist in the game data and is ignored", block_id),
+      HiddenBlock { block_id, ignored: false } => write!(f, "Block '{}' is hidden by the game data, but is still calculated", block_id),
+      HiddenBlock { block_id, ignored: true } => write!(f, "Block '{}' is hidden by the game data and is ignored", block_id),
+      UnknownComponents { block_id, component_ids } => write!(f, "Block '{}' is made of unknown component(s) '{}', its mass is understated", block_id, component_ids.join("', '")),
+    }
+  }
+}
+
+/// Report of validating a grid against data.
+#[derive(Default, Clone, Debug)]
+pub struct GridValidation {
+  pub problems: Vec<GridProblem>,
+}
+
+impl GridValidation {
+  #[inline]
+  pub fn is_valid(&self) -> bool { self.problems.is_empty() }
+}
+
+impl GridCalculator {
+  /// Validates the blocks of this grid against `data`, returning a report of blocks with a non-zero count that do not
+  /// exist, are hidden, or are made of unknown components, sorted by block ID.
+  pub fn validate(&self, data: &Data) -> GridValidation {
+    let mut ids: Vec<_> = self.iter_total_block_counts().map(|(id, _)| id).collect();
+    ids.sort();
+    ids.dedup();
+    let mut problems = Vec::new();
+    for id in ids {
+      let block = match data.blocks.get_data(id) {
+        Some(block) => block,
+        None => {
+          problems.push(GridProblem::UnknownBlock { block_id: id.clone() });
+          continue;
+        }
+      };
+      if block.hidden {
+        problems.push(GridProblem::HiddenBlock { block_id: id.clone(), ignored: self.ignore_hidden_blocks });
+      }
+      let component_ids: Vec<_> = block.unknown_component_ids(&data.components).cloned().collect();
+      if block.has_physics && !component_ids.is_empty() {
+        problems.push(GridProblem::UnknownComponents { block_id: id.clone(), component_ids });
+      }
+    }
+    GridValidation { problems }
+  }
+}
@@ -1,5 +1,7 @@
 use std::error::Error;
 
+use thiserror::Error;
+
 pub trait ErrorExt {
   fn into_boxed(self) -> Box<dyn Error + Send + Sync + 'static>;
 }
@@ -9,3 +11,26 @@ impl<T: Error + Send + Sync + 'static> ErrorExt for T {
     Box::new(self) as Box<dyn Error + Send + Sync + 'static>
   }
 }
+
+/// Error while parsing an enum from its name.
+#[derive(Clone, Debug, Error)]
+#[error("Unrecognized {kind} '{name}'; expected one of: {expected}")]
+pub struct ParseEnumError {
+  pub kind: &'static str,
+  pub name: String,
+  pub expected: String,
+}
+
+/// Parses `name` into the item of `items` with the same (case-insensitive) display name, or returns an error naming
+/// `kind` if there is no such item.
+pub fn parse_enum<T: std::fmt::Display>(kind: &'static str, name: &str, items: impl IntoIterator<Item=T>) -> Result<T, ParseEnumError> {
+  let mut expected = Vec::new();
+  for item in items {
+    let item_name = item.to_string();
+    if item_name.eq_ignore_ascii_case(name.trim()) {
+      return Ok(item);
+    }
+    expected.push(item_name);
+  }
+  Err(ParseEnumError { kind, name: name.to_string(), expected: expected.join(", ") })
+}
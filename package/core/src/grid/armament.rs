@@ -0,0 +1,81 @@
+//! Armament: ammo storage and power consumption of weapons.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::{Weapon, WeaponGroup};
+use crate::grid::items::{AMMO_BOX, ItemSize, RIFLE_MAGAZINE};
+use crate::grid::units::{Mass, Power, Volume};
+
+/// Weapons of one group.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WeaponGroupCalculated {
+  pub group: WeaponGroup,
+  /// Number of weapons
+  pub count: f64,
+  /// Inventory volume for ammo (L)
+  pub ammo_volume: Volume,
+  /// Mass of the ammo at the ammo fill (kg)
+  pub ammo_mass: Mass,
+  /// Idle power consumption (MW)
+  pub idle_power_consumption: Power,
+  /// Operational power consumption (MW)
+  pub operational_power_consumption: Power,
+}
+
+impl WeaponGroupCalculated {
+  fn new(group: WeaponGroup) -> Self {
+    Self { group, count: 0.0, ammo_volume: Volume::default(), ammo_mass: Mass::default(), idle_power_consumption: Power::default(), operational_power_consumption: Power::default() }
+  }
+}
+
+/// Armament calculation: ammo storage and power consumption of weapons.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct ArmamentCalculated {
+  /// Weapon groups with weapons, in the order of [`WeaponGroup::items`].
+  pub groups: Vec<WeaponGroupCalculated>,
+  /// Inventory volume for ammo of all weapons (L)
+  pub ammo_volume: Volume,
+  /// Mass of the ammo of all weapons at the ammo fill (kg)
+  pub ammo_mass: Mass,
+  /// Idle power consumption of all weapons (MW)
+  pub idle_power_consumption: Power,
+  /// Operational power consumption of all weapons (MW)
+  pub operational_power_consumption: Power,
+}
+
+impl ArmamentCalculated {
+  /// Adds `count` weapons of `group` with `details`, with their inventories filled to `ammo_fill` 0-100%.
+  pub(crate) fn add_weapons(&mut self, group: WeaponGroup, details: &Weapon, count: f64, ammo_fill: f64) {
+    let index = match self.groups.binary_search_by_key(&group, |g| g.group) {
+      Ok(index) => index,
+      Err(index) => {
+        self.groups.insert(index, WeaponGroupCalculated::new(group));
+        index
+      }
+    };
+    let ammo = ammo_of(group);
+    let ammo_volume = Volume::from_liters(details.inventory_volume_ammo * count);
+    let ammo_mass = Mass::from_kilograms(ammo_volume.to_liters() * (ammo_fill / 100.0) * (ammo.mass / ammo.volume));
+    let idle_power_consumption = Power::from_megawatts(details.idle_power_consumption * count);
+    let operational_power_consumption = Power::from_megawatts(details.operational_power_consumption * count);
+
+    let weapon_group = &mut self.groups[index];
+    weapon_group.count += count;
+    weapon_group.ammo_volume += ammo_volume;
+    weapon_group.ammo_mass += ammo_mass;
+    weapon_group.idle_power_consumption += idle_power_consumption;
+    weapon_group.operational_power_consumption += operational_power_consumption;
+    self.ammo_volume += ammo_volume;
+    self.ammo_mass += ammo_mass;
+    self.idle_power_consumption += idle_power_consumption;
+    self.operational_power_consumption += operational_power_consumption;
+  }
+}
+
+/// Gets the ammo that weapons of `group` are filled with.
+fn ammo_of(group: WeaponGroup) -> ItemSize {
+  match group {
+    WeaponGroup::Turret | WeaponGroup::FixedWeapon => AMMO_BOX,
+    WeaponGroup::InteriorTurret => RIFLE_MAGAZINE,
+  }
+}
@@ -0,0 +1,258 @@
+//! Ascent-to-orbit planner: flies a grid straight up from the surface of a planet with its up thrusters, with gravity
+//! and atmosphere changing with altitude, to find out whether it reaches space, how long that takes, and how much power
+//! and hydrogen its thrusters use on the way.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::Data;
+use crate::grid::{GridCalculated, GridCalculator};
+use crate::grid::direction::Direction;
+
+/// Gravity below which a planet no longer pulls on grids (g).
+const GRAVITY_CUTOFF: f64 = 0.05;
+
+/// Planet with a gravity and atmosphere that change with altitude.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Planet {
+  pub name: String,
+  /// Radius at the surface (m)
+  pub radius: f64,
+  /// Gravity at the surface (g)
+  pub surface_gravity: f64,
+  /// Altitude above the surface up to which gravity is at full strength, the height of the highest hills (m)
+  pub hill_height: f64,
+  /// Exponent with which gravity falls off with the distance to the center of the planet, above the hills
+  pub gravity_falloff: f64,
+  /// Height of the atmosphere above the surface, or 0 if the planet has no atmosphere (m)
+  pub atmosphere_height: f64,
+  /// Exponent with which the density of the atmosphere falls off with altitude; 1 for a linear falloff
+  pub atmosphere_falloff: f64,
+}
+
+impl Default for Planet {
+  fn default() -> Self {
+    Self::new("Earth-like", 60000.0, 1.0, 6000.0, 30000.0)
+  }
+}
+
+impl Planet {
+  fn new(name: &str, radius: f64, surface_gravity: f64, hill_height: f64, atmosphere_height: f64) -> Self {
+    Self { name: name.to_string(), radius, surface_gravity, hill_height, gravity_falloff: 7.0, atmosphere_height, atmosphere_falloff: 1.0 }
+  }
+
+  /// Approximations of the vanilla planets and moons.
+  pub fn presets() -> Vec<Planet> {
+    vec![
+      Self::default(),
+      Self::new("Mars", 60000.0, 0.9, 7000.0, 28000.0),
+      Self::new("Alien", 60000.0, 1.1, 7000.0, 30000.0),
+      Self::new("Pertam", 30000.0, 1.2, 2500.0, 18000.0),
+      Self::new("Triton", 40000.0, 1.0, 8000.0, 24000.0),
+      Self::new("Moon", 9500.0, 0.25, 300.0, 0.0),
+    ]
+  }
+
+  /// Gets the gravity at `altitude` (m) above the surface (g).
+  pub fn gravity(&self, altitude: f64) -> f64 {
+    if altitude <= self.hill_height { return self.surface_gravity; }
+    let hill_radius = self.radius + self.hill_height;
+    let gravity = self.surface_gravity * (hill_radius / (self.radius + altitude)).powf(self.gravity_falloff);
+    if gravity < GRAVITY_CUTOFF { 0.0 } else { gravity }
+  }
+
+  /// Gets the planetary influence 0-1 at `altitude` (m) above the surface.
+  pub fn planetary_influence(&self, altitude: f64) -> f64 {
+    if self.atmosphere_height <= 0.0 { return 0.0; }
+    (1.0 - altitude.max(0.0) / self.atmosphere_height).clamp(0.0, 1.0).powf(self.atmosphere_falloff)
+  }
+
+  /// Gets the altitude above the surface (m) at which the planet has neither gravity nor atmosphere.
+  pub fn space_altitude(&self) -> f64 {
+    let gravity_altitude = if self.surface_gravity > GRAVITY_CUTOFF && self.gravity_falloff > 0.0 {
+      let hill_radius = self.radius + self.hill_height;
+      hill_radius * (self.surface_gravity / GRAVITY_CUTOFF).powf(1.0 / self.gravity_falloff) - self.radius
+    } else {
+      0.0
+    };
+    gravity_altitude.max(self.atmosphere_height)
+  }
+}
+
+/// Options of an ascent.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct AscentOptions {
+  pub planet: Planet,
+  /// Altitude above the surface to ascend to, or None to ascend to space (m)
+  pub target_altitude: Option<f64>,
+  /// Speed that the grid does not exceed (m/s)
+  pub speed_limit: f64,
+  /// Duration of a single step (s)
+  pub time_step: f64,
+  /// Duration after which the ascent is given up (s)
+  pub maximum_time: f64,
+}
+
+impl Default for AscentOptions {
+  fn default() -> Self {
+    Self {
+      planet: Planet::default(),
+      target_altitude: None,
+      speed_limit: 100.0,
+      time_step: 1.0,
+      maximum_time: 3600.0,
+    }
+  }
+}
+
+impl AscentOptions {
+  /// Gets the altitude above the surface to ascend to (m).
+  pub fn target_altitude(&self) -> f64 {
+    self.target_altitude.unwrap_or_else(|| self.planet.space_altitude())
+  }
+}
+
+/// State of a grid at a point in time of an ascent.
+#[derive(Default, Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct AscentSample {
+  /// Time since lift-off (s)
+  pub time: f64,
+  /// Altitude above the surface (m)
+  pub altitude: f64,
+  /// Upwards speed (m/s)
+  pub speed: f64,
+  /// Gravity (g)
+  pub gravity: f64,
+  /// Planetary influence 0-1
+  pub planetary_influence: f64,
+  /// Thrust of up thrusters 0-100%, relative to their maximum force at this planetary influence
+  pub thrust: f64,
+}
+
+/// Plan of an ascent.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct AscentPlan {
+  /// Altitude above the surface that was ascended to (m)
+  pub target_altitude: f64,
+  /// Time to reach the target altitude, or None if it is not reached (s)
+  pub time: Option<f64>,
+  /// Highest altitude reached (m)
+  pub maximum_altitude: f64,
+  /// Altitude at which the grid stops climbing because up thrusters no longer overcome gravity, or None if it does not
+  /// stop before the target altitude (m)
+  pub stall_altitude: Option<f64>,
+  /// Power used by thrusters during the ascent (MWh)
+  pub power_consumed: f64,
+  /// Hydrogen used by thrusters during the ascent (L)
+  pub hydrogen_consumed: f64,
+  /// Samples at lift-off, and after each step
+  pub samples: Vec<AscentSample>,
+}
+
+impl AscentPlan {
+  /// Returns whether the target altitude is reached.
+  pub fn reaches_target(&self) -> bool { self.time.is_some() }
+}
+
+impl GridCalculator {
+  /// Plans an ascent of this grid straight up from the surface of the planet of `options`, using up thrusters with
+  /// their force and consumption at the gravity and planetary influence of each altitude, as in [`Self::calculate`].
+  ///
+  /// Thrusters thrust enough to accelerate up to the speed limit, and then to hold it. Batteries and hydrogen tanks
+  /// start at their fill levels in this calculator, and drain when consumption of thrusters and the consumers before them
+  /// exceeds power and hydrogen generation, limiting the force of thrusters when they run out. Hydrogen engines are
+  /// assumed to be refilled. Stops when the target altitude is reached, when thrusters no longer overcome gravity, or
+  /// after the maximum time.
+  pub fn plan_ascent(&self, data: &Data, options: &AscentOptions) -> AscentPlan {
+    let planet = &options.planet;
+    let target_altitude = options.target_altitude();
+    let mut plan = AscentPlan { target_altitude, ..AscentPlan::default() };
+    let mut sample = AscentSample {
+      gravity: planet.gravity(0.0),
+      planetary_influence: planet.planetary_influence(0.0),
+      ..AscentSample::default()
+    };
+    plan.samples.push(sample);
+    if options.time_step <= 0.0 { return plan; }
+
+    // Thruster force and consumption only change with planetary influence, so calculate once per percent of influence.
+    let mut calculated_per_influence: HashMap<u64, GridCalculated> = HashMap::new();
+    let mut calculate = |planetary_influence: f64| {
+      let percent = (planetary_influence * 100.0).round() as u64;
+      calculated_per_influence.entry(percent).or_insert_with(|| {
+        let mut calculator = self.clone();
+        calculator.planetary_influence = percent as f64 / 100.0;
+        calculator.calculate(data)
+      }).clone()
+    };
+
+    let initial = calculate(sample.planetary_influence);
+    let battery = initial.battery.clone().filter(|_| self.battery_mode.is_discharging()).unwrap_or_default();
+    let tank = initial.hydrogen_tank.clone().filter(|_| self.hydrogen_tank_mode.is_providing()).unwrap_or_default();
+    let mut battery_energy = battery.capacity.to_megawatt_hours() * (self.battery_fill / 100.0);
+    let battery_output = battery.maximum_output.to_megawatts();
+    let mut tank_volume = tank.capacity.to_liters() * (self.hydrogen_tank_fill / 100.0);
+
+    let dt = options.time_step;
+    let hours = dt / 3600.0;
+    while sample.altitude < target_altitude {
+      if sample.time >= options.maximum_time { return plan; }
+      let c = calculate(sample.planetary_influence);
+      let mass = c.total_mass_filled.to_kilograms();
+      if mass <= 0.0 { return plan; }
+
+      // Power and hydrogen left for up thrusters, with batteries and tanks covering what generation does not.
+      let power_generation = c.power_generation.to_megawatts() - battery_output;
+      let power_available = power_generation - c.power_upto_generator.total_consumption.to_megawatts() + battery_output.min(battery_energy / hours);
+      let hydrogen_idle: f64 = Direction::items().into_iter().map(|d| c.hydrogen_thruster_idle[d]).sum();
+      let hydrogen_supply = c.hydrogen_generation + tank.maximum_output.min(tank_volume / dt);
+      let hydrogen_available = hydrogen_supply - hydrogen_idle;
+      let supplied = |available: f64, consumption: f64| if consumption > 0.0 { (available / consumption).clamp(0.0, 1.0) } else { 1.0 };
+      let power_consumption = c.power_thruster_consumption[Direction::Up].to_megawatts();
+      let hydrogen_consumption = (c.hydrogen_thruster_consumption[Direction::Up] - c.hydrogen_thruster_idle[Direction::Up]).max(0.0);
+      let power_supplied = supplied(power_available, power_consumption);
+      let hydrogen_supplied = supplied(hydrogen_available, hydrogen_consumption);
+      let hydrogen_force = c.hydrogen_thruster_force[Direction::Up].to_newtons();
+      let power_force = c.forces.thrusters[Direction::Up].to_newtons() - hydrogen_force;
+      let maximum_force = power_force * power_supplied + hydrogen_force * hydrogen_supplied;
+
+      // Thrust to reach the speed limit in this step, but no more than available.
+      let weight = (mass * 9.81 * sample.gravity - self.external_lift_force).max(0.0);
+      let desired_force = weight + mass * (options.speed_limit - sample.speed) / dt;
+      let force = desired_force.clamp(0.0, maximum_force);
+      let thrust = if maximum_force > 0.0 { force / maximum_force } else { 0.0 };
+      if force < weight && sample.speed <= 0.0 {
+        plan.stall_altitude = Some(sample.altitude);
+        return plan;
+      }
+
+      let power_used = power_consumption * power_supplied * thrust;
+      let hydrogen_used = (hydrogen_idle + hydrogen_consumption * hydrogen_supplied * thrust).min(hydrogen_supply);
+      plan.power_consumed += power_used * hours;
+      plan.hydrogen_consumed += hydrogen_used * dt;
+      let power_deficit = power_used + c.power_upto_generator.total_consumption.to_megawatts() - power_generation;
+      battery_energy = (battery_energy - power_deficit.max(0.0) * hours).max(0.0);
+      let hydrogen_deficit = hydrogen_used - c.hydrogen_generation;
+      tank_volume = (tank_volume - hydrogen_deficit.max(0.0) * dt).max(0.0);
+
+      let acceleration = (force - weight) / mass;
+      let speed = (sample.speed + acceleration * dt).min(options.speed_limit);
+      let altitude = (sample.altitude + (sample.speed + speed) / 2.0 * dt).max(0.0);
+      sample = AscentSample {
+        time: sample.time + dt,
+        altitude,
+        speed: if altitude > 0.0 { speed } else { 0.0 },
+        gravity: planet.gravity(altitude),
+        planetary_influence: planet.planetary_influence(altitude),
+        thrust: thrust * 100.0,
+      };
+      plan.maximum_altitude = plan.maximum_altitude.max(altitude);
+      plan.samples.push(sample);
+    }
+    plan.time = Some(sample.time);
+    plan
+  }
+}
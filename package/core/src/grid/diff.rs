@@ -0,0 +1,82 @@
+//! Differences between the results of two grids, for comparing design variants.
+
+use hashlink::LinkedHashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::grid::GridCalculated;
+use crate::interop::result_csv::for_each_value;
+
+/// Difference of a numeric result value between two grids.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct FieldDiff {
+  /// Path to the value, with field names separated by dots and array elements by index, as in
+  /// [CSV results](crate::interop::result_csv).
+  pub path: String,
+  /// Value of the first grid, or None if it is not calculated.
+  pub before: Option<f64>,
+  /// Value of the second grid, or None if it is not calculated.
+  pub after: Option<f64>,
+}
+
+impl FieldDiff {
+  /// Gets the change from `before` to `after`, or None if either is not calculated.
+  #[inline]
+  pub fn delta(&self) -> Option<f64> {
+    self.before.zip(self.after).map(|(before, after)| after - before)
+  }
+}
+
+/// Differences between the results of two grids: each numeric value that differs, in order of the results.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct GridCalculatedDiff {
+  pub fields: Vec<FieldDiff>,
+}
+
+impl GridCalculatedDiff {
+  /// Gets the difference of the value at `path`, or None if it did not change.
+  pub fn get(&self, path: &str) -> Option<&FieldDiff> {
+    self.fields.iter().find(|f| f.path == path)
+  }
+
+  /// Checks whether no numeric values differ.
+  #[inline]
+  pub fn is_empty(&self) -> bool { self.fields.is_empty() }
+}
+
+impl GridCalculated {
+  /// Compares the numeric values of these results with those of `other`. The calculation stamp, and values that are not
+  /// numeric such as warnings and block IDs, are not compared.
+  pub fn diff(&self, other: &GridCalculated) -> GridCalculatedDiff {
+    let before = numeric_values(self);
+    let mut after = numeric_values(other);
+    let mut fields = Vec::new();
+    for (path, before) in before {
+      let after = after.remove(&path).flatten();
+      if before != after {
+        fields.push(FieldDiff { path, before, after });
+      }
+    }
+    for (path, after) in after {
+      if after.is_some() {
+        fields.push(FieldDiff { path, before: None, after });
+      }
+    }
+    GridCalculatedDiff { fields }
+  }
+}
+
+/// Gets the numeric and not calculated (None) values of `calculated` by their path.
+fn numeric_values(calculated: &GridCalculated) -> LinkedHashMap<String, Option<f64>> {
+  let mut values = LinkedHashMap::new();
+  let Ok(Value::Object(mut map)) = serde_json::to_value(calculated) else { return values };
+  map.remove("stamp");
+  for_each_value(String::new(), &Value::Object(map), &mut |path, value| {
+    match value {
+      Value::Number(number) => { values.insert(path, number.as_f64()); }
+      Value::Null => { values.insert(path, None); }
+      _ => {}
+    }
+  });
+  values
+}
@@ -35,18 +35,25 @@ impl Direction {
       Right => 5,
     }
   }
+
+  /// Gets the default name of this direction.
+  #[inline]
+  pub const fn name(self) -> &'static str {
+    use Direction::*;
+    match self {
+      Up => "Up",
+      Down => "Down",
+      Front => "Front",
+      Back => "Back",
+      Left => "Left",
+      Right => "Right",
+    }
+  }
 }
 
 impl Display for Direction {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-    match self {
-      Direction::Up => f.write_str("Up"),
-      Direction::Down => f.write_str("Down"),
-      Direction::Front => f.write_str("Front"),
-      Direction::Back => f.write_str("Back"),
-      Direction::Left => f.write_str("Left"),
-      Direction::Right => f.write_str("Right"),
-    }
+    f.write_str(self.name())
   }
 }
 
@@ -55,8 +62,19 @@ impl Display for Direction {
 
 #[repr(transparent)]
 #[derive(Default, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PerDirection<T>([T; 6]);
 
+impl<T: Copy> PerDirection<T> {
+  /// Creates a per-direction with `value` for every direction.
+  #[inline]
+  pub const fn splat(value: T) -> Self { Self([value; 6]) }
+
+  /// Creates a per-direction with `f` applied to the value of every direction.
+  #[inline]
+  pub fn map<U>(&self, f: impl FnMut(T) -> U) -> PerDirection<U> { PerDirection(self.0.map(f)) }
+}
+
 impl<T> PerDirection<T> {
   #[inline]
   pub const fn get(&self, direction: Direction) -> &T { &self.0[direction.into_index()] }
@@ -118,4 +136,34 @@ impl<T> IndexMut<Direction> for PerDirection<T> {
 
 // Count-per-direction
 
-pub type CountPerDirection = PerDirection<u64>;
\ No newline at end of file
+pub type CountPerDirection = PerDirection<f64>;
+
+
+// Direction labels
+
+/// Custom labels of directions, for players that use different direction conventions, such as "Forward" instead of
+/// "Front". Directions without a custom label are labelled with their default name.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DirectionLabels(PerDirection<String>);
+
+impl DirectionLabels {
+  /// Gets the label of `direction`: its custom label, or its default name if it has no custom label.
+  #[inline]
+  pub fn get(&self, direction: Direction) -> &str {
+    let label = self.0[direction].trim();
+    if label.is_empty() { direction.name() } else { label }
+  }
+
+  /// Gets the custom label of `direction` for editing, where an empty label means no custom label.
+  #[inline]
+  pub fn custom_mut(&mut self, direction: Direction) -> &mut String { &mut self.0[direction] }
+
+  /// Gets the label of the pair of directions `a` and `b`, such as "Up/Down".
+  #[inline]
+  pub fn pair(&self, a: Direction, b: Direction) -> String { format!("{}/{}", self.get(a), self.get(b)) }
+
+  /// Returns whether no direction has a custom label.
+  #[inline]
+  pub fn is_default(&self) -> bool { self.0.iter().all(|l| l.trim().is_empty()) }
+}
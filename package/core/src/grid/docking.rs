@@ -0,0 +1,100 @@
+//! Charging and refueling a grid that is docked to a station via a connector.
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::{CHARGE_EFFICIENCY, GridCalculated, GridCalculator};
+use crate::grid::duration::Duration;
+
+/// Power and hydrogen a station supplies to a docked grid.
+#[derive(Default, Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct DockSupply {
+  /// Surplus power of the station (MW)
+  pub power: f64,
+  /// Surplus hydrogen production of the station (L/s)
+  pub hydrogen: f64,
+}
+
+impl DockSupply {
+  /// Creates the supply of a station from its calculated results: the power balance without charging its own
+  /// batteries, and the hydrogen balance without filling its own tanks, when thrusters are at full burn.
+  pub fn from_station(station: &GridCalculated) -> Self {
+    Self {
+      power: station.power_upto_left_right_thruster.balance.to_megawatts().max(0.0),
+      hydrogen: station.hydrogen_upto_left_right_thruster.balance_without_tank.max(0.0),
+    }
+  }
+}
+
+#[derive(Default, Copy, Clone, Serialize, Debug)]
+pub struct DockChargingCalculated {
+  /// Duration until batteries are full (min), or None if there are no batteries.
+  pub battery_charge_duration: Option<Duration>,
+  /// Duration until hydrogen tanks are full (min), or None if there are no hydrogen tanks.
+  pub hydrogen_tank_fill_duration: Option<Duration>,
+  /// Duration until hydrogen engines are full (min), or None if there are no hydrogen engines.
+  pub hydrogen_engine_fill_duration: Option<Duration>,
+  /// Duration until batteries, hydrogen tanks, and hydrogen engines are all full (min), or None if there is none of
+  /// them.
+  pub total_duration: Option<Duration>,
+}
+
+impl GridCalculated {
+  /// Calculates how long it takes to charge batteries and fill hydrogen tanks and engines to full when docked to a
+  /// station that provides `supply`, starting at the fill levels of `calculator`. Only the supply of the station is
+  /// taken into account; the grid's own generation is assumed to cover its idle consumption. Batteries charge at most
+  /// at their maximum input, and hydrogen tanks and engines fill at the same time, sharing the hydrogen supply in
+  /// proportion to their maximum input. Durations are infinite when there is no supply, and zero when already full.
+  pub fn dock_charging(&self, calculator: &GridCalculator, supply: &DockSupply) -> DockChargingCalculated {
+    let battery_charge_duration = self.battery.as_ref().map(|battery| {
+      let energy = battery.capacity.to_megawatt_hours() * (1.0 - calculator.battery_fill / 100.0);
+      let input = supply.power.min(battery.maximum_input.to_megawatts()) * CHARGE_EFFICIENCY;
+      Duration::from_hours(fill_hours(energy, input))
+    });
+
+    let tank = self.hydrogen_tank.as_ref().map(|tank| (tank.capacity.to_liters() * (1.0 - calculator.hydrogen_tank_fill / 100.0), tank.maximum_input));
+    let engine = self.hydrogen_engine.as_ref().map(|engine| (engine.capacity.to_liters() * (1.0 - calculator.hydrogen_engine_fill / 100.0), engine.maximum_refilling_input));
+    let [tank_seconds, engine_seconds] = shared_fill_seconds([tank.unwrap_or_default(), engine.unwrap_or_default()], supply.hydrogen);
+    let hydrogen_tank_fill_duration = tank.map(|_| Duration::from_seconds(tank_seconds));
+    let hydrogen_engine_fill_duration = engine.map(|_| Duration::from_seconds(engine_seconds));
+
+    let total_duration = [battery_charge_duration, hydrogen_tank_fill_duration, hydrogen_engine_fill_duration].into_iter()
+      .flatten()
+      .reduce(|a, b| if b.to_minutes() > a.to_minutes() { b } else { a });
+    DockChargingCalculated { battery_charge_duration, hydrogen_tank_fill_duration, hydrogen_engine_fill_duration, total_duration }
+  }
+}
+
+/// Gets the time it takes to fill `amount` at `rate`: zero if there is nothing to fill, infinite if `rate` is zero.
+fn fill_hours(amount: f64, rate: f64) -> f64 {
+  if amount <= 0.0 { 0.0 } else { amount / rate }
+}
+
+/// Gets the seconds until each consumer, given as (amount to fill (L), maximum input (L/s)), is full, when sharing
+/// `supply` (L/s) in proportion to their maximum input. Supply freed up by full consumers goes to the others.
+fn shared_fill_seconds<const N: usize>(consumers: [(f64, f64); N], supply: f64) -> [f64; N] {
+  let mut remaining = consumers.map(|(amount, _)| amount.max(0.0));
+  let mut seconds = [0.0; N];
+  let mut elapsed = 0.0;
+  loop {
+    let active: Vec<usize> = (0..N).filter(|i| remaining[*i] > 0.0).collect();
+    if active.is_empty() { break; }
+    let total_input: f64 = active.iter().map(|i| consumers[*i].1).sum();
+    let share = if total_input > 0.0 { (supply / total_input).min(1.0) } else { 0.0 };
+    if share <= 0.0 {
+      for i in active { seconds[i] = f64::INFINITY; }
+      break;
+    }
+    // Advance until the first active consumer is full.
+    let step = active.iter().map(|i| remaining[*i] / (consumers[*i].1 * share)).fold(f64::INFINITY, f64::min);
+    elapsed += step;
+    for i in active {
+      remaining[i] -= consumers[i].1 * share * step;
+      if remaining[i] <= 1e-9 {
+        remaining[i] = 0.0;
+        seconds[i] = elapsed;
+      }
+    }
+  }
+  seconds
+}
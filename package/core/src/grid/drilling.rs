@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::Drill;
+use crate::grid::duration::Duration;
+use crate::grid::warning::Warning;
+
+/// Drilling calculation: how fast drills fill inventories with ore.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct DrillingCalculated {
+  /// Number of drills
+  pub count: f64,
+  /// Estimated best-case rate at which all drills collect ore (L/s)
+  pub ore_rate: f64,
+  /// Inventory volume for ore in drills (L)
+  pub drill_volume: f64,
+  /// Inventory volume for ore in storage blocks such as containers, excluding drills (L)
+  pub storage_volume: f64,
+  /// Duration until all inventories for ore are full when drilling (min), or None if the ore rate is 0.
+  pub fill_duration: Option<Duration>,
+}
+
+impl DrillingCalculated {
+  /// Adds `count` drills with `details`.
+  pub(crate) fn add_drills(&mut self, details: &Drill, count: f64) {
+    self.count += count;
+    self.ore_rate += details.ore_rate * count;
+    self.drill_volume += details.inventory_volume_ore * count;
+  }
+
+  /// Completes the calculation with `ore_volume`: the inventory volume for ore of all blocks, including drills.
+  pub(crate) fn complete(&mut self, ore_volume: f64, warnings: &mut Vec<Warning>) {
+    self.storage_volume = (ore_volume - self.drill_volume).max(0.0);
+    self.fill_duration = (self.ore_rate > 0.0).then(|| Duration::from_seconds(ore_volume / self.ore_rate));
+    if self.count > 0.0 && self.storage_volume <= 0.0 {
+      warnings.push(Warning::DrillsWithoutStorage);
+    }
+  }
+}
@@ -1,9 +1,14 @@
 use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{Error, Unexpected, Visitor};
 
+/// Duration, stored in minutes. Serializes as a number of minutes, or as `"inf"` when infinite (such as a tank that
+/// never drains), because formats like JSON have no infinite numbers.
 #[repr(transparent)]
-#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Debug)]
 pub struct Duration(f64);
 
 impl Duration {
@@ -14,6 +19,14 @@ impl Duration {
   #[inline]
   pub fn from_hours(hours: f64) -> Self { Self::from_minutes(hours * HOURS_TO_MINUTES) }
   #[inline]
+  pub fn to_minutes(&self) -> f64 { self.0 }
+  #[inline]
+  pub fn to_seconds(&self) -> f64 { self.0 / SECONDS_TO_MINUTES }
+  #[inline]
+  pub fn to_hours(&self) -> f64 { self.0 / HOURS_TO_MINUTES }
+  #[inline]
+  pub fn is_infinite(&self) -> bool { self.0.is_infinite() }
+  #[inline]
   pub fn to_f64_and_unit(&self) -> (f64, &str) {
     let d = self.0;
     if d.is_infinite() {
@@ -33,17 +46,154 @@ impl Duration {
     }
   }
 
+  /// Formats this duration as `h:mm:ss`, or None if this duration is negative, infinite, or too long to be read as a
+  /// clock (more than 999 hours).
+  pub fn to_clock_string(&self) -> Option<String> {
+    if !self.0.is_finite() || self.0 < 0.0 || self.to_hours() >= 1000.0 { return None; }
+    let seconds = self.to_seconds().round() as u64;
+    Some(format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60))
+  }
+
+  /// Formats this duration with `format`, with `precision` decimal places in the unit format.
+  pub fn format(&self, format: DurationFormat, precision: usize) -> String {
+    match format {
+      DurationFormat::Unit => format!("{:.*}", precision, self),
+      DurationFormat::Clock => format!("{:#.*}", precision, self),
+    }
+  }
+
   pub const DEFAULT_UNIT: &'static str = "mins";
 }
 
 
 impl Display for Duration {
+  /// Formats as a value with a unit, such as `1.5 hours`. The alternate flag (`{:#}`) formats as `h:mm:ss` instead,
+  /// falling back to a value with a unit when the duration cannot be read as a clock.
   #[inline]
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    if f.alternate() {
+      if let Some(clock) = self.to_clock_string() {
+        return f.write_str(&clock);
+      }
+    }
     let (d, unit) = self.to_f64_and_unit();
-    d.fmt(f)?;
-    f.write_str(" ")?;
-    f.write_str(unit)
+    if let Some(precision) = f.precision() {
+      write!(f, "{:.*}", precision, d)?;
+    } else {
+      write!(f, "{}", d)?;
+    }
+    if !unit.is_empty() {
+      f.write_str(" ")?;
+      f.write_str(unit)?;
+    }
+    Ok(())
+  }
+}
+
+
+impl Serialize for Duration {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    if self.0.is_finite() {
+      serializer.serialize_f64(self.0)
+    } else if self.0 == f64::INFINITY {
+      serializer.serialize_str("inf")
+    } else if self.0 == f64::NEG_INFINITY {
+      serializer.serialize_str("-inf")
+    } else {
+      serializer.serialize_str("NaN")
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct DurationVisitor;
+    impl<'de> Visitor<'de> for DurationVisitor {
+      type Value = Duration;
+      fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("a number of minutes, \"inf\", \"-inf\", or \"NaN\"")
+      }
+      fn visit_f64<E: Error>(self, v: f64) -> Result<Duration, E> { Ok(Duration(v)) }
+      fn visit_i64<E: Error>(self, v: i64) -> Result<Duration, E> { Ok(Duration(v as f64)) }
+      fn visit_u64<E: Error>(self, v: u64) -> Result<Duration, E> { Ok(Duration(v as f64)) }
+      fn visit_str<E: Error>(self, v: &str) -> Result<Duration, E> {
+        match v {
+          "inf" => Ok(Duration(f64::INFINITY)),
+          "-inf" => Ok(Duration(f64::NEG_INFINITY)),
+          "NaN" => Ok(Duration(f64::NAN)),
+          _ => Err(E::invalid_value(Unexpected::Str(v), &self)),
+        }
+      }
+    }
+    deserializer.deserialize_any(DurationVisitor)
+  }
+}
+
+
+impl Add for Duration {
+  type Output = Self;
+  #[inline]
+  fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) }
+}
+impl AddAssign for Duration {
+  #[inline]
+  fn add_assign(&mut self, rhs: Self) { self.0 += rhs.0 }
+}
+impl Sub for Duration {
+  type Output = Self;
+  #[inline]
+  fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) }
+}
+impl SubAssign for Duration {
+  #[inline]
+  fn sub_assign(&mut self, rhs: Self) { self.0 -= rhs.0 }
+}
+impl Mul<f64> for Duration {
+  type Output = Self;
+  #[inline]
+  fn mul(self, rhs: f64) -> Self { Self(self.0 * rhs) }
+}
+impl Div<f64> for Duration {
+  type Output = Self;
+  #[inline]
+  fn div(self, rhs: f64) -> Self { Self(self.0 / rhs) }
+}
+impl Div for Duration {
+  type Output = f64;
+  #[inline]
+  fn div(self, rhs: Self) -> f64 { self.0 / rhs.0 }
+}
+impl Sum for Duration {
+  #[inline]
+  fn sum<I: Iterator<Item=Self>>(iter: I) -> Self { Self(iter.map(|d| d.0).sum()) }
+}
+
+
+/// Format of durations.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum DurationFormat {
+  /// Value with a unit, such as `1.50 hours`
+  #[default] Unit,
+  /// Clock, such as `1:30:00`
+  Clock,
+}
+
+impl DurationFormat {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use DurationFormat::*;
+    const ITEMS: [DurationFormat; 2] = [Unit, Clock];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for DurationFormat {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use DurationFormat::*;
+    match self {
+      Unit => f.write_str("Value with unit"),
+      Clock => f.write_str("Clock (h:mm:ss)"),
+    }
   }
 }
 
@@ -0,0 +1,40 @@
+//! Efficiency: power, energy, hydrogen, and thrust normalized by mass and power, for comparing grids of different sizes.
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::direction::Direction;
+use crate::grid::GridCalculated;
+
+/// Efficiency calculation: stats of a grid normalized by its mass and power.
+#[derive(Default, Copy, Clone, Serialize, Deserialize, Debug)]
+pub struct EfficiencyCalculated {
+  /// Power generation per tonne of empty mass (MW/t), or None if the grid has no mass when empty.
+  pub power_generation_per_mass: Option<f64>,
+  /// Battery capacity per tonne of empty mass (MWh/t), or None if there are no batteries or the grid has no mass when
+  /// empty.
+  pub battery_capacity_per_mass: Option<f64>,
+  /// Hydrogen tank capacity per tonne of empty mass (L/t), or None if there are no hydrogen tanks or the grid has no
+  /// mass when empty.
+  pub hydrogen_capacity_per_mass: Option<f64>,
+  /// Thrust of all directions per MW of thruster power consumption of all directions (N/MW), or None if thrusters consume no power, or if
+  /// the grid has hydrogen thrusters whose thrust does not consume power.
+  pub thrust_per_power: Option<f64>,
+}
+
+impl GridCalculated {
+  /// Calculates the efficiency of this grid.
+  pub fn efficiency(&self) -> EfficiencyCalculated {
+    let mass_in_tonnes = self.total_mass_empty.to_tonnes();
+    let per_mass = |value: f64| (mass_in_tonnes > 0.0).then(|| value / mass_in_tonnes);
+    // Force and consumption of all six directions, instead of the peak consumption per axis of the power chain.
+    let thruster_power_consumption = Direction::items().into_iter().map(|d| self.power_thruster_consumption[d].to_megawatts()).sum::<f64>();
+    let has_hydrogen_thrusters = Direction::items().into_iter().any(|d| self.hydrogen_thruster_consumption[d] > 0.0);
+    let thrust = Direction::items().into_iter().map(|d| self.thruster_acceleration[d].force.to_newtons()).sum::<f64>();
+    EfficiencyCalculated {
+      power_generation_per_mass: per_mass(self.power_generation.to_megawatts()),
+      battery_capacity_per_mass: self.battery.as_ref().and_then(|b| per_mass(b.capacity.to_megawatt_hours())),
+      hydrogen_capacity_per_mass: self.hydrogen_tank.as_ref().and_then(|t| per_mass(t.capacity.to_liters())),
+      thrust_per_power: (thruster_power_consumption > 0.0 && !has_hydrogen_thrusters).then(|| thrust / thruster_power_consumption),
+    }
+  }
+}
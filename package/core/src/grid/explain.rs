@@ -0,0 +1,102 @@
+//! Short explanations of how key results are calculated, with the main inputs used, for showing as tooltips.
+
+use crate::grid::{GridCalculated, GridCalculator, HydrogenCalculated, PowerCalculated};
+use crate::grid::direction::Direction;
+use crate::grid::units::{Force, Mass};
+
+/// Explanations of a power calculation.
+#[derive(Default, Clone, Debug)]
+pub struct PowerExplanation {
+  pub balance: String,
+  pub budget: String,
+  /// Explanation of the battery duration, or None if there is no battery duration.
+  pub battery_duration: Option<String>,
+}
+
+/// Explanations of a hydrogen calculation.
+#[derive(Default, Clone, Debug)]
+pub struct HydrogenExplanation {
+  pub balance_without_tank: String,
+  pub balance_with_tank: String,
+  pub budget: String,
+  /// Explanation of the tank duration, or None if there is no tank duration.
+  pub tank_duration: Option<String>,
+}
+
+impl GridCalculated {
+  /// Explains the acceleration in `direction` when `filled` or empty, and inside of `gravity` or outside, or returns
+  /// None if there is no such acceleration. `calculator` must be the calculator these results were calculated with.
+  pub fn explain_acceleration(&self, calculator: &GridCalculator, direction: Direction, filled: bool, gravity: bool) -> Option<String> {
+    let a = &self.thruster_acceleration[direction];
+    let (mass, acceleration) = if filled {
+      (self.total_mass_filled, if gravity { a.acceleration_filled_gravity } else { a.acceleration_filled_no_gravity })
+    } else {
+      (self.total_mass_empty, if gravity { a.acceleration_empty_gravity } else { a.acceleration_empty_no_gravity })
+    };
+    let acceleration = acceleration?;
+    let mut explanation = String::new();
+    if a.nominal_force != Force::default() && a.force != a.nominal_force {
+      explanation.push_str(&format!("force {} × effectiveness {:.2} = {}\n", format_force(a.nominal_force), a.force / a.nominal_force, format_force(a.force)));
+    }
+    if gravity {
+      explanation.push_str(&format!("(force {} − weight {} × 9.81 m/s² × {:.2} g", format_force(a.force), format_mass(mass), calculator.gravity_multiplier));
+      if calculator.external_lift_force != 0.0 {
+        explanation.push_str(&format!(" + lift {}", format_force(Force::from_newtons(calculator.external_lift_force))));
+      }
+      explanation.push_str(&format!(") / mass {} = {:.2} m/s²", format_mass(mass), acceleration));
+    } else {
+      explanation.push_str(&format!("force {} / mass {} = {:.2} m/s²", format_force(a.force), format_mass(mass), acceleration));
+    }
+    Some(explanation)
+  }
+
+  /// Explains `power`, which must be one of the power calculations of these results. `calculator` must be the
+  /// calculator these results were calculated with.
+  pub fn explain_power(&self, calculator: &GridCalculator, power: &PowerCalculated) -> PowerExplanation {
+    let balance = format!("generation {:.2} MW − total consumption {:.2} MW = {:.2} MW", self.power_generation.to_megawatts(), power.total_consumption.to_megawatts(), power.balance.to_megawatts());
+    let budget = format!("balance {:.2} MW − safety margin {:.0}% × total consumption {:.2} MW = {:.2} MW", power.balance.to_megawatts(), calculator.safety_margin, power.total_consumption.to_megawatts(), power.budget.to_megawatts());
+    let battery_duration = power.battery_duration.zip(self.battery.as_ref()).map(|(duration, battery)| {
+      format!("stored {:.2} MWh ({:.0}% of {:.2} MWh) / drain {:.2} MW = {:.2}", battery.capacity.to_megawatt_hours() * calculator.battery_fill / 100.0, calculator.battery_fill, battery.capacity.to_megawatt_hours(), power.battery_drain.to_megawatts(), duration)
+    });
+    PowerExplanation { balance, budget, battery_duration }
+  }
+
+  /// Explains `hydrogen`, which must be one of the hydrogen calculations of these results. `calculator` must be the
+  /// calculator these results were calculated with.
+  pub fn explain_hydrogen(&self, calculator: &GridCalculator, hydrogen: &HydrogenCalculated) -> HydrogenExplanation {
+    let balance_without_tank = format!("generation {:.2} L/s − total consumption {:.2} L/s = {:.2} L/s", self.hydrogen_generation, hydrogen.total_consumption, hydrogen.balance_without_tank);
+    let tank_output = hydrogen.balance_with_tank - hydrogen.balance_without_tank;
+    let balance_with_tank = if tank_output != 0.0 {
+      format!("generation {:.2} L/s + tank output {:.2} L/s − total consumption {:.2} L/s = {:.2} L/s", self.hydrogen_generation, tank_output, hydrogen.total_consumption, hydrogen.balance_with_tank)
+    } else {
+      format!("{}; tanks are not providing hydrogen", balance_without_tank)
+    };
+    let budget = format!("balance {:.2} L/s − safety margin {:.0}% × total consumption {:.2} L/s = {:.2} L/s", hydrogen.balance_with_tank, calculator.safety_margin, hydrogen.total_consumption, hydrogen.budget);
+    let tank_duration = hydrogen.tank_duration.zip(self.hydrogen_tank.as_ref()).map(|(duration, tank)| {
+      let stored = tank.capacity.to_liters() * calculator.hydrogen_tank_fill / 100.0;
+      let drain = (hydrogen.total_consumption - self.hydrogen_generation).min(tank.maximum_output);
+      if drain > 0.0 {
+        format!("stored {:.0} L ({:.0}% of {:.0} L) / drain {:.2} L/s = {:.2}", stored, calculator.hydrogen_tank_fill, tank.capacity.to_liters(), drain, duration)
+      } else {
+        "generation covers all consumption, so tanks are not drained".to_string()
+      }
+    });
+    HydrogenExplanation { balance_without_tank, balance_with_tank, budget, tank_duration }
+  }
+}
+
+fn format_force(force: Force) -> String {
+  if force.to_meganewtons().abs() >= 1.0 {
+    format!("{:.2} MN", force.to_meganewtons())
+  } else {
+    format!("{:.2} kN", force.to_kilonewtons())
+  }
+}
+
+fn format_mass(mass: Mass) -> String {
+  if mass.to_tonnes().abs() >= 1.0 {
+    format!("{:.2} t", mass.to_tonnes())
+  } else {
+    format!("{:.0} kg", mass.to_kilograms())
+  }
+}
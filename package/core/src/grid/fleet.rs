@@ -0,0 +1,102 @@
+//! Aggregate statistics of several grids, for planning fleets such as carriers with drones.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::data::blocks::BlockId;
+use crate::data::Data;
+use crate::grid::{GridCalculated, GridCalculator};
+use crate::grid::units::{Mass, Power};
+
+impl GridCalculator {
+  /// Iterates over the total count of each block with a non-zero count, summing counts over all directions.
+  pub fn iter_total_block_counts(&self) -> impl Iterator<Item=(&BlockId, f64)> {
+    self.blocks.iter()
+      .map(|(id, count)| (id, *count))
+      .chain(self.directional_blocks.iter().map(|(id, count_per_direction)| (id, count_per_direction.iter().sum())))
+      .filter(|(_, count)| *count != 0.0)
+  }
+
+  /// Gets the total PCU of all blocks, rounded up for fractional block counts, or None if the PCU of a block is unknown.
+  /// Blocks not in `data` are ignored.
+  pub fn pcu(&self, data: &Data) -> Option<u64> {
+    let mut pcu = 0.0;
+    for (id, count) in self.iter_total_block_counts() {
+      let Some(block) = data.blocks.get_data(id) else { continue };
+      pcu += block.pcu? as f64 * count;
+    }
+    Some(pcu.ceil() as u64)
+  }
+
+  /// Gets the time to build all blocks with a single welder at `welder_speed_multiplier` (s), or None if the build time of
+  /// a block is unknown. Blocks not in `data` are ignored.
+  pub fn build_time(&self, data: &Data, welder_speed_multiplier: f64) -> Option<f64> {
+    let mut build_time = 0.0;
+    for (id, count) in self.iter_total_block_counts() {
+      let Some(block) = data.blocks.get_data(id) else { continue };
+      build_time += block.build_time? * count;
+    }
+    Some(build_time / welder_speed_multiplier)
+  }
+
+  /// Gets the components needed to build all blocks, by component ID. Blocks not in `data` are ignored.
+  pub fn build_cost(&self, data: &Data) -> BTreeMap<String, f64> {
+    let mut components = BTreeMap::new();
+    for (id, count) in self.iter_total_block_counts() {
+      let Some(block) = data.blocks.get_data(id) else { continue };
+      for (component_id, component_count) in block.components.iter() {
+        *components.entry(component_id.clone()).or_default() += component_count * count;
+      }
+    }
+    components
+  }
+}
+
+/// Aggregate statistics of a fleet of grids.
+#[derive(Default, Clone, Serialize, Debug)]
+pub struct FleetCalculated {
+  /// Number of grids
+  pub grid_count: usize,
+  /// Total number of blocks
+  pub block_count: f64,
+  /// Total PCU, or None if the PCU of a block is unknown
+  pub pcu: Option<u64>,
+  /// Total mass when empty (kg)
+  pub total_mass_empty: Mass,
+  /// Total mass when filled (kg)
+  pub total_mass_filled: Mass,
+  /// Total power generation (MW)
+  pub power_generation: Power,
+  /// Total hydrogen generation (L/s)
+  pub hydrogen_generation: f64,
+  /// Combined hydrogen consumption with thrusters at full burn, without filling tanks (L/s)
+  pub hydrogen_consumption: f64,
+  /// Components needed to build all grids, by component ID
+  pub build_cost: BTreeMap<String, f64>,
+  /// Time to build all grids with a single welder (s), or None if the build time of a block is unknown
+  pub build_time: Option<f64>,
+}
+
+impl FleetCalculated {
+  /// Aggregates `grids`, given as calculators with the results calculated from them with `data`. Build times are
+  /// calculated at `welder_speed_multiplier`.
+  pub fn calculate<'a>(grids: impl IntoIterator<Item=(&'a GridCalculator, &'a GridCalculated)>, data: &Data, welder_speed_multiplier: f64) -> Self {
+    let mut fleet = Self { pcu: Some(0), build_time: Some(0.0), ..Self::default() };
+    for (calculator, calculated) in grids {
+      fleet.grid_count += 1;
+      fleet.block_count += calculator.iter_total_block_counts().map(|(_, count)| count).sum::<f64>();
+      fleet.pcu = fleet.pcu.zip(calculator.pcu(data)).map(|(a, b)| a + b);
+      fleet.build_time = fleet.build_time.zip(calculator.build_time(data, welder_speed_multiplier)).map(|(a, b)| a + b);
+      fleet.total_mass_empty += calculated.total_mass_empty;
+      fleet.total_mass_filled += calculated.total_mass_filled;
+      fleet.power_generation += calculated.power_generation;
+      fleet.hydrogen_generation += calculated.hydrogen_generation;
+      fleet.hydrogen_consumption += calculated.hydrogen_upto_left_right_thruster.total_consumption;
+      for (component_id, count) in calculator.build_cost(data) {
+        *fleet.build_cost.entry(component_id).or_default() += count;
+      }
+    }
+    fleet
+  }
+}
@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::direction::{Direction, DirectionLabels, PerDirection};
+use crate::grid::units::Force;
+
+/// Unit to show forces in.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum ForceUnit {
+  Newton,
+  #[default] Kilonewton,
+  Meganewton,
+}
+
+impl ForceUnit {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use ForceUnit::*;
+    const ITEMS: [ForceUnit; 3] = [Newton, Kilonewton, Meganewton];
+    ITEMS.into_iter()
+  }
+
+  /// Converts `force` into a value in this unit.
+  #[inline]
+  pub fn convert(&self, force: Force) -> f64 {
+    use ForceUnit::*;
+    match self {
+      Newton => force.to_newtons(),
+      Kilonewton => force.to_kilonewtons(),
+      Meganewton => force.to_meganewtons(),
+    }
+  }
+
+  #[inline]
+  pub fn symbol(&self) -> &'static str {
+    use ForceUnit::*;
+    match self {
+      Newton => "N",
+      Kilonewton => "kN",
+      Meganewton => "MN",
+    }
+  }
+}
+
+impl Display for ForceUnit {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.symbol())
+  }
+}
+
+/// Source of a force.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ForceSource {
+  /// Thrusters in a direction
+  Thrusters(Direction),
+  Wheels,
+}
+
+impl ForceSource {
+  /// Gets the label of this source, with thruster directions labelled with `labels`.
+  pub fn label(&self, labels: &DirectionLabels) -> String {
+    match self {
+      ForceSource::Thrusters(direction) => format!("{} Thrusters", labels.get(*direction)),
+      ForceSource::Wheels => self.to_string(),
+    }
+  }
+}
+
+impl Display for ForceSource {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ForceSource::Thrusters(direction) => write!(f, "{} Thrusters", direction),
+      ForceSource::Wheels => f.write_str("Wheels"),
+    }
+  }
+}
+
+/// Forces a grid can exert, independent of its mass and gravity.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct ForcesCalculated {
+  /// Thruster force per direction (N)
+  pub thrusters: PerDirection<Force>,
+  /// Wheel force (N)
+  pub wheels: Force,
+}
+
+impl ForcesCalculated {
+  /// Iterates over all forces with their source, thrusters first in direction order.
+  pub fn iter(&self) -> impl Iterator<Item=(ForceSource, Force)> + '_ {
+    Direction::items().into_iter()
+      .map(|d| (ForceSource::Thrusters(d), self.thrusters[d]))
+      .chain(std::iter::once((ForceSource::Wheels, self.wheels)))
+  }
+}
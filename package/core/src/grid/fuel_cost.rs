@@ -0,0 +1,53 @@
+//! Fuel cost: fuel needed to fully charge batteries and jump drives from empty.
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::{HydrogenEngineCalculated, ReactorCalculated};
+use crate::grid::items::{ICE, URANIUM_INGOT};
+use crate::grid::units::{Energy, Mass, Volume};
+
+/// Fuel cost calculation: fuel needed to fully charge batteries and jump drives from empty, when the charge is
+/// generated by reactors, or by hydrogen engines burning hydrogen from generators.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct FuelCostCalculated {
+  /// Energy needed to fully charge batteries from empty, including charging losses (MWh)
+  pub battery_energy: Energy,
+  /// Energy needed to fully charge jump drives from empty, including charging losses (MWh)
+  pub jump_drive_energy: Energy,
+  /// Mass of uranium ingots that reactors consume to generate the energy (kg), or None if there are no reactors or
+  /// they consume no fuel.
+  pub uranium_mass: Option<Mass>,
+  /// Hydrogen that hydrogen engines consume to generate the energy (L), or None if there are no hydrogen engines or
+  /// they consume no fuel.
+  pub hydrogen: Option<Volume>,
+  /// Mass of ice that generators consume to generate that hydrogen (kg), or None if there is no hydrogen or no
+  /// generators producing hydrogen.
+  pub ice_mass: Option<Mass>,
+}
+
+impl FuelCostCalculated {
+  /// Calculates the fuel needed to generate `battery_energy` and `jump_drive_energy` (MWh), where
+  /// `generator_ice_consumption` (#/s) is converted into `hydrogen_generation` (L/s) by generators.
+  pub(crate) fn new(
+    battery_energy: Energy,
+    jump_drive_energy: Energy,
+    reactor: Option<&ReactorCalculated>,
+    hydrogen_engine: Option<&HydrogenEngineCalculated>,
+    generator_ice_consumption: f64,
+    hydrogen_generation: f64,
+  ) -> Self {
+    let energy_seconds = (battery_energy + jump_drive_energy).to_megawatt_hours() * 3600.0; // MWh to MW*s.
+    // Custom blocks may not consume fuel at all, in which case there is no fuel cost rather than a cost of 0.
+    let uranium_mass = reactor.filter(|r| r.maximum_output.to_megawatts() > 0.0 && r.maximum_fuel_consumption > 0.0)
+      .map(|r| Mass::from_kilograms(energy_seconds * (r.maximum_fuel_consumption / r.maximum_output.to_megawatts()) * URANIUM_INGOT.mass));
+    let hydrogen = hydrogen_engine.filter(|e| e.maximum_output.to_megawatts() > 0.0 && e.maximum_fuel_consumption > 0.0)
+      .map(|e| Volume::from_liters(energy_seconds * (e.maximum_fuel_consumption / e.maximum_output.to_megawatts())));
+    let ice_mass = hydrogen.filter(|_| hydrogen_generation > 0.0)
+      .map(|h| Mass::from_kilograms(h.to_liters() * (generator_ice_consumption / hydrogen_generation) * ICE.mass));
+    Self { battery_energy, jump_drive_energy, uranium_mass, hydrogen, ice_mass }
+  }
+
+  /// Total energy needed to fully charge batteries and jump drives from empty, including charging losses (MWh)
+  #[inline]
+  pub fn energy(&self) -> Energy { self.battery_energy + self.jump_drive_energy }
+}
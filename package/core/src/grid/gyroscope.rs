@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::Gyroscope;
+
+/// Axis of rotation of a grid.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum RotationAxis {
+  /// Rotation around the left-right axis
+  Pitch,
+  /// Rotation around the up-down axis
+  Yaw,
+  /// Rotation around the front-back axis
+  Roll,
+}
+
+impl RotationAxis {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use RotationAxis::*;
+    const ITEMS: [RotationAxis; 3] = [Pitch, Yaw, Roll];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for RotationAxis {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use RotationAxis::*;
+    match self {
+      Pitch => f.write_str("Pitch"),
+      Yaw => f.write_str("Yaw"),
+      Roll => f.write_str("Roll"),
+    }
+  }
+}
+
+/// Outer dimensions of a grid (m), for estimating its rotational inertia.
+#[derive(Default, Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct GridDimensions {
+  /// Size along the left-right axis (m)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub width: f64,
+  /// Size along the up-down axis (m)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub height: f64,
+  /// Size along the front-back axis (m)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub length: f64,
+}
+
+impl GridDimensions {
+  /// Gets the sum of the squares of the two dimensions perpendicular to `axis` (m^2).
+  fn perpendicular_squared(&self, axis: RotationAxis) -> f64 {
+    use RotationAxis::*;
+    match axis {
+      Pitch => self.height.powi(2) + self.length.powi(2),
+      Yaw => self.width.powi(2) + self.length.powi(2),
+      Roll => self.width.powi(2) + self.height.powi(2),
+    }
+  }
+
+  /// Gets the moment of inertia around `axis` of a solid cuboid with these dimensions and `mass` (kg·m^2).
+  pub fn moment_of_inertia(&self, axis: RotationAxis, mass: f64) -> f64 {
+    mass * self.perpendicular_squared(axis) / 12.0
+  }
+}
+
+/// Rotational acceleration around an axis.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RotationCalculated {
+  pub axis: RotationAxis,
+  /// Estimated rotational acceleration when empty (deg/s^2), or None if the grid has no mass when empty or no
+  /// dimensions perpendicular to the axis.
+  pub acceleration_empty: Option<f64>,
+  /// Estimated rotational acceleration when filled (deg/s^2), or None if the grid has no mass when filled or no
+  /// dimensions perpendicular to the axis.
+  pub acceleration_filled: Option<f64>,
+}
+
+/// Gyroscope calculation: how fast gyroscopes can rotate the grid.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct GyroscopeCalculated {
+  /// Number of gyroscopes
+  pub count: f64,
+  /// Total maximum torque (N·m)
+  pub torque: f64,
+  /// Maximum power consumption (MW)
+  pub maximum_input: f64,
+  /// Rotational acceleration per axis, in the order of [RotationAxis::items], estimated by treating the grid as a
+  /// solid cuboid of uniform density with the dimensions of the calculator. Empty if the grid has no dimensions.
+  pub rotation: Vec<RotationCalculated>,
+}
+
+impl GyroscopeCalculated {
+  /// Adds `count` gyroscopes with `details`.
+  pub(crate) fn add_gyroscopes(&mut self, details: &Gyroscope, count: f64) {
+    self.count += count;
+    self.torque += details.torque * count;
+    self.maximum_input += details.operational_power_consumption * count;
+  }
+
+  /// Completes the calculation with the `dimensions` of the grid and its `mass_empty` and `mass_filled` (kg).
+  pub(crate) fn complete(&mut self, dimensions: &GridDimensions, mass_empty: f64, mass_filled: f64) {
+    if *dimensions == GridDimensions::default() { return; }
+    let acceleration = |axis: RotationAxis, mass: f64| {
+      let moment_of_inertia = dimensions.moment_of_inertia(axis, mass);
+      (moment_of_inertia > 0.0).then(|| (self.torque / moment_of_inertia).to_degrees())
+    };
+    self.rotation = RotationAxis::items().into_iter()
+      .map(|axis| RotationCalculated {
+        axis,
+        acceleration_empty: acceleration(axis, mass_empty),
+        acceleration_filled: acceleration(axis, mass_filled),
+      })
+      .collect();
+  }
+
+  /// Gets the rotation calculation around `axis`, or None if rotation was not estimated.
+  pub fn rotation(&self, axis: RotationAxis) -> Option<&RotationCalculated> {
+    self.rotation.iter().find(|r| r.axis == axis)
+  }
+}
@@ -0,0 +1,99 @@
+//! Items that inventories are filled with, and their counts in a filled grid.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::Data;
+use crate::data::gas_properties::{HYDROGEN_GAS_ID, OXYGEN_GAS_ID};
+use crate::grid::GridCalculator;
+use crate::grid::units::Volume;
+
+/// Volume (L) and mass (kg) of one item.
+pub(crate) struct ItemSize {
+  pub volume: f64,
+  pub mass: f64,
+}
+
+pub(crate) const ORE: ItemSize = ItemSize { volume: 0.37, mass: 1.0 };
+pub(crate) const ICE: ItemSize = ItemSize { volume: 0.37, mass: 1.0 };
+pub(crate) const STEEL_PLATE: ItemSize = ItemSize { volume: 3.0, mass: 20.0 };
+/// Gatling ammo box, used as an estimate for the ammo of turrets and fixed weapons.
+pub(crate) const AMMO_BOX: ItemSize = ItemSize { volume: 16.0, mass: 35.0 };
+/// Rifle magazine, the ammo of interior turrets.
+pub(crate) const RIFLE_MAGAZINE: ItemSize = ItemSize { volume: 0.2, mass: 0.45 };
+/// Uranium ingot, the fuel of reactors.
+pub(crate) const URANIUM_INGOT: ItemSize = ItemSize { volume: 0.052, mass: 1.0 };
+
+impl ItemSize {
+  /// Gets the size of the bottle that stores `gas_id` in `data`, or None if there is no such bottle.
+  pub(crate) fn bottle(data: &Data, gas_id: &str) -> Option<Self> {
+    data.bottles.get_by_gas(gas_id).map(|b| Self { volume: b.volume, mass: b.mass })
+  }
+}
+
+/// Item that inventories of any type are filled with.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct FillItem {
+  pub name: String,
+  /// Volume of one item (L)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub volume: f64,
+  /// Mass of one item (kg)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub mass: f64,
+  /// Fill of inventories of any type with this item 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub fill: f64,
+}
+
+impl FillItem {
+  pub fn new(name: impl Into<String>, volume: f64, mass: f64, fill: f64) -> Self {
+    Self { name: name.into(), volume, mass, fill }
+  }
+
+  /// Gets the number of these items in `volume_any` (L) of inventories of any type.
+  #[inline]
+  pub fn count(&self, volume_any: f64) -> f64 {
+    if self.volume > 0.0 { volume_any * (self.fill / 100.0) / self.volume } else { 0.0 }
+  }
+}
+
+impl GridCalculator {
+  /// Gets the items of the any fills with ice, ore, steel plates, and bottles of this calculator, as a starting point for
+  /// overriding them with [`GridCalculator::any_fill_items`]. Bottle sizes are taken from `data`, leaving out bottles
+  /// that are missing from it.
+  pub fn any_fill_items_from_options(&self, data: &Data) -> Vec<FillItem> {
+    let mut items = vec![
+      FillItem::new("Ice", ICE.volume, ICE.mass, self.any_fill_with_ice),
+      FillItem::new("Ore", ORE.volume, ORE.mass, self.any_fill_with_ore),
+      FillItem::new("Steel Plate", STEEL_PLATE.volume, STEEL_PLATE.mass, self.any_fill_with_steel_plates),
+    ];
+    if let Some(bottle) = ItemSize::bottle(data, HYDROGEN_GAS_ID) {
+      items.push(FillItem::new("H2 Bottle", bottle.volume, bottle.mass, self.any_fill_with_hydrogen_bottles));
+    }
+    if let Some(bottle) = ItemSize::bottle(data, OXYGEN_GAS_ID) {
+      items.push(FillItem::new("O2 Bottle", bottle.volume, bottle.mass, self.any_fill_with_oxygen_bottles));
+    }
+    items
+  }
+}
+
+/// Number of items of one type in a filled grid.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct ItemCalculated {
+  pub name: String,
+  /// Number of items
+  pub count: f64,
+  /// Volume of one item (L)
+  pub volume: Volume,
+  /// Volume of one item relative to inventory volumes without the container multiplier (L)
+  pub effective_volume: Volume,
+}
+
+impl ItemCalculated {
+  pub(crate) fn new(name: impl Into<String>, count: f64, volume: f64, container_multiplier: f64) -> Self {
+    let effective_volume = if container_multiplier > 0.0 { volume / container_multiplier } else { volume };
+    Self { name: name.into(), count, volume: Volume::from_liters(volume), effective_volume: Volume::from_liters(effective_volume) }
+  }
+}
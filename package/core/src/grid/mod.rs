@@ -1,21 +1,70 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{Display, Formatter};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use direction::PerDirection;
 
-use crate::data::blocks::{BlockId, ThrusterType};
+use crate::data::blocks::{BlockId, ConveyorPorts, GridSize, InventoryConstraint, ThrusterType};
+use crate::data::components::STEEL_PLATE_COMPONENT_ID;
 use crate::data::Data;
-use crate::grid::direction::{CountPerDirection, Direction};
+use crate::data::gas_properties::{HYDROGEN_GAS_ID, OXYGEN_GAS_ID};
+use crate::data::power_groups::PowerGroup;
+use crate::error::{parse_enum, ParseEnumError};
+use crate::grid::armament::ArmamentCalculated;
+use crate::grid::fuel_cost::FuelCostCalculated;
+use crate::grid::direction::{CountPerDirection, Direction, DirectionLabels};
+use crate::grid::drilling::DrillingCalculated;
 use crate::grid::duration::Duration;
-
+use crate::grid::forces::ForcesCalculated;
+use crate::grid::gyroscope::{GridDimensions, GyroscopeCalculated};
+use crate::grid::items::{FillItem, ICE, ItemCalculated, ItemSize, ORE, STEEL_PLATE};
+use crate::grid::oxygen::OxygenCalculated;
+use crate::grid::production::ProductionCalculated;
+use crate::grid::scenario::Scenario;
+use crate::grid::subgrid::{GridBreakdownCalculated, Subgrid};
+use crate::grid::units::{Energy, Force, Mass, Power, Volume};
+use crate::grid::warning::Warning;
+
+pub mod armament;
+pub mod fuel_cost;
+pub mod ascent;
+pub mod diff;
 pub mod direction;
+pub mod docking;
+pub mod drilling;
 pub mod duration;
+pub mod efficiency;
+pub mod explain;
+pub mod fleet;
+pub mod forces;
+pub mod gyroscope;
+pub mod items;
+pub mod oxygen;
+pub mod production;
+pub mod preset;
+pub mod reference;
+pub mod scenario;
+pub mod search;
+pub mod server_profile;
+pub mod simulation;
+pub mod snapshot;
+pub mod subgrid;
+pub mod summary;
+pub mod sweep;
+pub mod thruster_mix;
+pub mod thruster_ranking;
+pub mod units;
+pub mod warning;
 
 // Battery mode
 
 #[derive(Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BatteryMode {
   Auto,
   Recharge,
@@ -34,13 +83,13 @@ impl BatteryMode {
   #[inline]
   pub fn is_charging(&self) -> bool {
     use BatteryMode::*;
-    match self { Auto => true, Recharge => true, _ => false }
+    matches!(self, Auto | Recharge)
   }
 
   #[inline]
   pub fn is_discharging(&self) -> bool {
     use BatteryMode::*;
-    match self { Auto => true, Discharge => true, _ => false }
+    matches!(self, Auto | Discharge)
   }
 }
 
@@ -56,10 +105,18 @@ impl Display for BatteryMode {
   }
 }
 
+impl FromStr for BatteryMode {
+  type Err = ParseEnumError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_enum("battery mode", s, Self::items())
+  }
+}
+
 
 // Hydrogen tank mode
 
 #[derive(Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum HydrogenTankMode {
   #[default] On,
   Stockpile,
@@ -77,13 +134,20 @@ impl HydrogenTankMode {
   #[inline]
   pub fn is_refilling(&self) -> bool {
     use HydrogenTankMode::*;
-    match self { On => true, Stockpile => true, _ => false }
+    matches!(self, On | Stockpile)
   }
 
   #[inline]
   pub fn is_providing(&self) -> bool {
     use HydrogenTankMode::*;
-    match self { On => true, _ => false }
+    matches!(self, On)
+  }
+
+  /// Can tanks in this mode with `fill` 0-100% transfer hydrogen in either direction? Enabled tanks draw operational
+  /// power when transferring, and idle power otherwise, according to ComputeRequiredPower in MyGasTank.cs.
+  #[inline]
+  pub fn is_transferring(&self, fill: f64) -> bool {
+    (self.is_refilling() && fill < 100.0) || (self.is_providing() && fill > 0.0)
   }
 }
 
@@ -98,24 +162,285 @@ impl Display for HydrogenTankMode {
   }
 }
 
+impl FromStr for HydrogenTankMode {
+  type Err = ParseEnumError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_enum("hydrogen tank mode", s, Self::items())
+  }
+}
+
+// Power source priority
+
+/// Which power sources take load first, for attributing power consumption to batteries and hydrogen engines.
+#[derive(Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PowerSourcePriority {
+  /// Batteries take load first, engines take the remaining load
+  BatteriesFirst,
+  /// Engines take load first, batteries take the remaining load
+  EnginesFirst,
+  /// Batteries and engines share load proportional to their maximum output
+  #[default] Proportional,
+}
+
+impl PowerSourcePriority {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use PowerSourcePriority::*;
+    const ITEMS: [PowerSourcePriority; 3] = [BatteriesFirst, EnginesFirst, Proportional];
+    ITEMS.into_iter()
+  }
+
+  /// Attributes `load` (MW) to batteries with `battery_output` (MW) and engines with `engine_output` (MW), returning
+  /// the drain on batteries and on engines (MW). Load exceeding the combined output is not attributed.
+  pub fn attribute(&self, load: f64, battery_output: f64, engine_output: f64) -> (f64, f64) {
+    use PowerSourcePriority::*;
+    let load = load.max(0.0);
+    match self {
+      BatteriesFirst => {
+        let battery_drain = load.min(battery_output);
+        (battery_drain, (load - battery_drain).min(engine_output))
+      }
+      EnginesFirst => {
+        let engine_drain = load.min(engine_output);
+        ((load - engine_drain).min(battery_output), engine_drain)
+      }
+      Proportional => {
+        let output = battery_output + engine_output;
+        if output <= 0.0 { return (0.0, 0.0); }
+        let share = load.min(output);
+        (share * battery_output / output, share * engine_output / output)
+      }
+    }
+  }
+}
+
+impl Display for PowerSourcePriority {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use PowerSourcePriority::*;
+    match self {
+      BatteriesFirst => f.write_str("Batteries First"),
+      EnginesFirst => f.write_str("Engines First"),
+      Proportional => f.write_str("Proportional"),
+    }
+  }
+}
+
+// Scale rounding
+
+/// How to round block counts after scaling them.
+#[derive(Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum ScaleRounding {
+  #[default] Nearest,
+  Up,
+  Down,
+}
+
+impl ScaleRounding {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use ScaleRounding::*;
+    const ITEMS: [ScaleRounding; 3] = [Nearest, Up, Down];
+    ITEMS.into_iter()
+  }
+
+  #[inline]
+  pub fn round(&self, value: f64) -> f64 {
+    use ScaleRounding::*;
+    match self {
+      Nearest => value.round(),
+      Up => value.ceil(),
+      Down => value.floor(),
+    }
+  }
+}
+
+impl Display for ScaleRounding {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use ScaleRounding::*;
+    match self {
+      Nearest => f.write_str("Nearest"),
+      Up => f.write_str("Up"),
+      Down => f.write_str("Down"),
+    }
+  }
+}
+
+// Power chain group
+
+/// Group of power consumers in the power chain of calculated results, in order of priority.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum PowerChainGroup {
+  RailgunCharge,
+  Utility,
+  WheelSuspension,
+  JumpDriveCharge,
+  Generator,
+  UpDownThruster,
+  FrontBackThruster,
+  LeftRightThruster,
+  BatteryCharge,
+}
+
+impl PowerChainGroup {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use PowerChainGroup::*;
+    const ITEMS: [PowerChainGroup; 9] = [RailgunCharge, Utility, WheelSuspension, JumpDriveCharge, Generator, UpDownThruster, FrontBackThruster, LeftRightThruster, BatteryCharge];
+    ITEMS.into_iter()
+  }
+
+  /// Gets the label of this group, with thruster directions labelled with `labels`.
+  pub fn label(&self, labels: &DirectionLabels) -> String {
+    use PowerChainGroup::*;
+    match self {
+      UpDownThruster => format!("{} Thrusters", labels.pair(Direction::Up, Direction::Down)),
+      FrontBackThruster => format!("{} Thrusters", labels.pair(Direction::Front, Direction::Back)),
+      LeftRightThruster => format!("{} Thrusters", labels.pair(Direction::Left, Direction::Right)),
+      _ => self.to_string(),
+    }
+  }
+}
+
+impl Display for PowerChainGroup {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use PowerChainGroup::*;
+    match self {
+      RailgunCharge => f.write_str("Charge Railguns"),
+      Utility => f.write_str("Utility"),
+      WheelSuspension => f.write_str("Wheel Suspensions"),
+      JumpDriveCharge => f.write_str("Charge Jump Drives"),
+      Generator => f.write_str("O2/H2 Generators"),
+      UpDownThruster => f.write_str("Up/Down Thrusters"),
+      FrontBackThruster => f.write_str("Front/Back Thrusters"),
+      LeftRightThruster => f.write_str("Left/Right Thrusters"),
+      BatteryCharge => f.write_str("Charge Batteries"),
+    }
+  }
+}
+
+/// Group of hydrogen consumers in the hydrogen chain of calculated results, in order of priority.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum HydrogenChainGroup {
+  EngineFill,
+  UpDownThruster,
+  FrontBackThruster,
+  LeftRightThruster,
+  TankFill,
+}
+
+impl HydrogenChainGroup {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use HydrogenChainGroup::*;
+    const ITEMS: [HydrogenChainGroup; 5] = [EngineFill, UpDownThruster, FrontBackThruster, LeftRightThruster, TankFill];
+    ITEMS.into_iter()
+  }
+
+  /// Gets the label of this group, with thruster directions labelled with `labels`.
+  pub fn label(&self, labels: &DirectionLabels) -> String {
+    use HydrogenChainGroup::*;
+    match self {
+      UpDownThruster => format!("{} Thrusters", labels.pair(Direction::Up, Direction::Down)),
+      FrontBackThruster => format!("{} Thrusters", labels.pair(Direction::Front, Direction::Back)),
+      LeftRightThruster => format!("{} Thrusters", labels.pair(Direction::Left, Direction::Right)),
+      _ => self.to_string(),
+    }
+  }
+}
+
+impl Display for HydrogenChainGroup {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use HydrogenChainGroup::*;
+    match self {
+      EngineFill => f.write_str("Fill Engines"),
+      UpDownThruster => f.write_str("Up/Down Thrusters"),
+      FrontBackThruster => f.write_str("Front/Back Thrusters"),
+      LeftRightThruster => f.write_str("Left/Right Thrusters"),
+      TankFill => f.write_str("Fill Tanks"),
+    }
+  }
+}
+
+
 // Calculator
 
+#[derive(Error, Debug)]
+pub enum ReadError {
+  #[error("Could not read grid calculator from JSON at '{path}'")]
+  FromJSONFail { path: String, source: serde_json::Error },
+}
+
+#[derive(Error, Debug)]
+pub enum WriteError {
+  #[error("Could not write grid calculator to JSON")]
+  ToJSONFail(#[from] serde_json::Error),
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(default)]
 pub struct GridCalculator {
   /// Gravity multiplier 0-* (g)
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub gravity_multiplier: f64,
   /// Container multiplier 0-*
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub container_multiplier: f64,
   /// Planetary influence 0-1
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub planetary_influence: f64,
   /// Additional mass (kg)
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub additional_mass: f64,
+  /// External force lifting the grid against gravity, for example from balloon or anti-gravity mods (N)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub external_lift_force: f64,
+  /// Number of engineers (crew and passengers) on board
+  pub crew_count: u64,
+  /// Mass per engineer, including suit and tools (kg)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub crew_mass_per_engineer: f64,
+
+  /// Pitch of the grid relative to the horizon when hovering, positive is nose-down, -90-90 (deg)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub hover_pitch: f64,
+  /// Rate at which drills fill inventories with ore when mining while hovering, or 0 to use the estimated ore rate of
+  /// drills (L/s)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub mining_rate: f64,
+  /// Ore that a refinery with a refine speed of 1 processes, depending on the type of ore; 20 for iron ore (kg/s)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub refinery_ore_rate: f64,
+  /// Mass of ingots that refineries produce from ore with a material efficiency of 1, depending on the type of ore; 70
+  /// for iron ore 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub refinery_ingot_yield: f64,
+  /// Altitude above the ground at which the grid starts braking its descent, for calculating the maximum safe descent
+  /// speed (m)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub descent_altitude: f64,
+  /// Time between deciding to brake and up thrusters firing when descending (s)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub descent_reaction_time: f64,
+  /// Speed from which the grid brakes to a stop with thrusters, for calculating stopping distances and times (m/s)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub braking_speed: f64,
+  /// Outer dimensions of the grid, for estimating how fast gyroscopes can rotate it. All 0 if unknown.
+  pub dimensions: GridDimensions,
 
   /// Thruster power 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub thruster_power: f64,
   /// Wheel power 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub wheel_power: f64,
+  /// Are thrusters enabled, per direction? Thrusters in disabled directions still have mass, but provide no force and
+  /// have no maximum consumption.
+  pub thruster_direction_enabled: PerDirection<bool>,
+  /// Are thrusters in disabled directions turned off? When false, they are turned on but unused, and still have idle
+  /// consumption.
+  pub disabled_thrusters_off: bool,
 
   /// Are railguns charging?
   pub railgun_charging: bool,
@@ -124,32 +449,93 @@ pub struct GridCalculator {
   /// Battery mode
   pub battery_mode: BatteryMode,
   /// Fill level of batteries 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub battery_fill: f64,
 
   /// Hydrogen tanks mode?
   pub hydrogen_tank_mode: HydrogenTankMode,
   /// Fill level of hydrogen tanks 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub hydrogen_tank_fill: f64,
+  /// Do hydrogen tanks that are on or stockpiling always draw operational power, even when they cannot transfer
+  /// hydrogen because they are stockpiling and full? Off to follow the game.
+  pub hydrogen_tank_always_operational: bool,
   /// Hydrogen engines enabled?
   pub hydrogen_engine_enabled: bool,
   /// Fill level of hydrogen engines 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub hydrogen_engine_fill: f64,
+  /// Which power sources take load first, when attributing power consumption to batteries and engines
+  pub power_source_priority: PowerSourcePriority,
+  /// Safety margin 0-100% of total consumption that power and hydrogen balances must exceed to be within budget, and
+  /// that generator requirements account for.
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub safety_margin: f64,
+  /// Whether oxygen demand throttles generators in addition to hydrogen demand. When disabled, generators only run to
+  /// cover hydrogen demand, and produced oxygen exceeding the oxygen demand is vented.
+  pub generator_oxygen_throttling: bool,
+  /// Oxygen demand of consumers other than air vents, such as engineers refilling their suits (L/s)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub oxygen_demand: f64,
+  /// Are air vents pressurizing rooms, consuming oxygen?
+  pub air_vents_pressurizing: bool,
+  /// Fill level of oxygen tanks 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub oxygen_tank_fill: f64,
 
   /// Ice only fill 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub ice_only_fill: f64,
   /// Ore only fill 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub ore_only_fill: f64,
   /// Any fill with ice 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub any_fill_with_ice: f64,
   /// Any fill with ore 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub any_fill_with_ore: f64,
   /// Any fill with steel plates 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
   pub any_fill_with_steel_plates: f64,
-
-  /// Block counts
-  pub blocks: HashMap<BlockId, u64>,
-  /// Block counts per direction.
+  /// Any fill with (filled) hydrogen bottles 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub any_fill_with_hydrogen_bottles: f64,
+  /// Any fill with (filled) oxygen bottles 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub any_fill_with_oxygen_bottles: f64,
+  /// Items that inventories of any type are filled with instead of the any fills with ice, ore, steel plates, and
+  /// bottles above, or None to fill with those.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub any_fill_items: Option<Vec<FillItem>>,
+  /// Round item counts down to whole items, and calculate the number of inventory stacks?
+  pub round_items_to_stacks: bool,
+  /// Ammo fill of weapons 0-100%
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub ammo_fill: f64,
+
+  /// Block counts, which may be fractional for modelling blocks that are only active part of the time
+  #[serde(serialize_with = "crate::precision::serialize_counts")]
+  pub blocks: HashMap<BlockId, f64>,
+  /// Block counts per direction, which may be fractional
+  #[serde(serialize_with = "crate::precision::serialize_counts_per_direction")]
   pub directional_blocks: HashMap<BlockId, CountPerDirection>,
+  /// Custom labels of directions, shown instead of the default direction names.
+  pub direction_labels: DirectionLabels,
+  /// Blocks hidden from block lists for this grid, in addition to blocks hidden by the extraction configuration.
+  /// Excluded blocks with a non-zero count are still shown.
+  pub excluded_blocks: BTreeSet<BlockId>,
+  /// Build progress 0-100% per block, for estimating partially built grids. Blocks without an entry are fully built.
+  pub build_progress: HashMap<BlockId, f64>,
+  /// Build progress 0-100% at or above which blocks are functional. Blocks below this progress only contribute the
+  /// mass of their placed components.
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub functional_build_progress: f64,
+  /// Exclude blocks that are hidden by the extraction configuration from calculations, with a warning? Hidden blocks can
+  /// have a non-zero count in grids saved before the data was extracted with stricter hide rules.
+  pub ignore_hidden_blocks: bool,
+  /// Subgrids attached to this grid, calculated together with this grid.
+  pub subgrids: Vec<Subgrid>,
 }
 
 impl Default for GridCalculator {
@@ -159,9 +545,23 @@ impl Default for GridCalculator {
       container_multiplier: 1.0,
       planetary_influence: 1.0,
       additional_mass: 0.0,
+      external_lift_force: 0.0,
+      crew_count: 0,
+      crew_mass_per_engineer: 100.0,
+
+      hover_pitch: 0.0,
+      descent_altitude: 1000.0,
+      descent_reaction_time: 1.0,
+      braking_speed: 100.0,
+      dimensions: GridDimensions::default(),
+      mining_rate: 0.0,
+      refinery_ore_rate: 20.0,
+      refinery_ingot_yield: 70.0,
 
       thruster_power: 100.0,
       wheel_power: 100.0,
+      thruster_direction_enabled: PerDirection::splat(true),
+      disabled_thrusters_off: true,
 
       railgun_charging: true,
       jump_drive_charging: true,
@@ -170,17 +570,35 @@ impl Default for GridCalculator {
 
       hydrogen_tank_mode: Default::default(),
       hydrogen_tank_fill: 100.0,
+      hydrogen_tank_always_operational: false,
       hydrogen_engine_enabled: true,
       hydrogen_engine_fill: 100.0,
+      power_source_priority: Default::default(),
+      safety_margin: 0.0,
+      generator_oxygen_throttling: false,
+      oxygen_demand: 0.0,
+      air_vents_pressurizing: true,
+      oxygen_tank_fill: 100.0,
 
       ice_only_fill: 100.0,
       ore_only_fill: 100.0,
       any_fill_with_ice: 0.0,
       any_fill_with_ore: 0.0,
       any_fill_with_steel_plates: 0.0,
+      any_fill_with_hydrogen_bottles: 0.0,
+      any_fill_with_oxygen_bottles: 0.0,
+      any_fill_items: None,
+      round_items_to_stacks: false,
+      ammo_fill: 100.0,
 
       blocks: Default::default(),
       directional_blocks: Default::default(),
+      direction_labels: Default::default(),
+      excluded_blocks: Default::default(),
+      build_progress: Default::default(),
+      functional_build_progress: 100.0,
+      ignore_hidden_blocks: false,
+      subgrids: Vec::new(),
     }
   }
 }
@@ -190,19 +608,126 @@ impl GridCalculator {
     Self::default()
   }
 
-  pub fn iter_block_counts(&self) -> impl Iterator<Item=(&BlockId, &u64)> {
+  pub fn iter_block_counts(&self) -> impl Iterator<Item=(&BlockId, &f64)> {
     self.blocks.iter()
   }
 
+  /// Returns whether block `id` has a non-zero count, in any direction.
+  pub fn has_block(&self, id: &BlockId) -> bool {
+    self.blocks.get(id).is_some_and(|c| *c != 0.0) || self.directional_blocks.get(id).is_some_and(|c| c.iter().any(|c| *c != 0.0))
+  }
+
+  /// Returns whether block `id` should be shown in block lists: when it is not excluded, or when it has a non-zero
+  /// count.
+  pub fn is_block_shown(&self, id: &BlockId) -> bool {
+    !self.excluded_blocks.contains(id) || self.has_block(id)
+  }
+
+  /// Gets the IDs of blocks with a non-zero count that are hidden by the extraction configuration of `data`, sorted.
+  pub fn hidden_blocks(&self, data: &Data) -> Vec<BlockId> {
+    let mut ids: Vec<_> = self.iter_total_block_counts()
+      .filter(|(id, _)| data.blocks.get_data(id).is_some_and(|b| b.hidden))
+      .map(|(id, _)| id.clone())
+      .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+  }
+
+  /// Gets the build progress 0-100% of block `id`.
+  pub fn block_build_progress(&self, id: &BlockId) -> f64 {
+    self.build_progress.get(id).copied().unwrap_or(100.0)
+  }
+
+  /// Returns whether block `id` is built far enough to be functional.
+  pub fn is_block_functional(&self, id: &BlockId) -> bool {
+    self.block_build_progress(id) >= self.functional_build_progress
+  }
+
+  /// Gets a digest of the contents of this calculator, which is equal for calculators with equal contents, independent
+  /// of the order of block counts. Digests are not stable across versions, and should not be persisted.
+  pub fn digest(&self) -> u64 {
+    // Serialize to a JSON value, which sorts map keys, making the result independent of hash map ordering.
+    let value = serde_json::to_value(self).expect("serializing grid calculator to JSON value should not fail");
+    let mut hasher = DefaultHasher::new();
+    value.to_string().hash(&mut hasher);
+    hasher.finish()
+  }
+
+  /// Multiplies the counts of blocks for which `should_scale` returns true by `factor`, rounding the scaled counts
+  /// with `rounding`. Directional blocks are scaled per direction.
+  pub fn scale_block_counts(&mut self, factor: f64, rounding: ScaleRounding, should_scale: impl Fn(&BlockId) -> bool) {
+    let scale = |count: &mut f64| *count = rounding.round(*count * factor.max(0.0));
+    for (id, count) in self.blocks.iter_mut() {
+      if should_scale(id) { scale(count); }
+    }
+    for (id, count_per_direction) in self.directional_blocks.iter_mut() {
+      if should_scale(id) { count_per_direction.iter_mut().for_each(scale); }
+    }
+  }
+
+  pub fn from_json<R: io::Read>(reader: R) -> Result<Self, ReadError> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let calculator = serde_path_to_error::deserialize(&mut deserializer)
+      .map_err(|e| ReadError::FromJSONFail { path: e.path().to_string(), source: e.into_inner() })?;
+    Ok(calculator)
+  }
+
+  pub fn to_json<W: io::Write>(&self, writer: W) -> Result<(), WriteError> {
+    serde_json::to_writer_pretty(writer, self)?;
+    Ok(())
+  }
+
   pub fn calculate(&self, data: &Data) -> GridCalculated {
-    let ice_weight_per_volume = 1.0 / 0.37; // TODO: derive from data
-    let ice_items_per_volume = 1.0 / 0.37; // TODO: derive from data
-    let ore_weight_per_volume = 1.0 / 0.37; // TODO: derive from data
-    let ore_items_per_volume = 1.0 / 0.37; // TODO: derive from data
-    let steel_plate_weight_per_volume = 20.0 / 3.0; // TODO: derive from data
-    let steel_plate_items_per_volume = 1.0 / 3.0; // TODO: derive from data
+    let mut c = self.calculate_scenario(data, &Scenario::default());
+    if c.battery.is_some() {
+      let silent_running = self.calculate_scenario(data, &Scenario::SILENT_RUNNING);
+      c.silent_running = Some(SilentRunningCalculated {
+        idle: silent_running.power_idle.battery_duration,
+        utility: silent_running.power_upto_utility.battery_duration,
+        full_thrust: silent_running.power_upto_left_right_thruster.battery_duration,
+      });
+    }
+    c
+  }
 
-    let mut c = GridCalculated::default();
+  /// Calculates this grid in `scenario`, which overrides settings of this calculator.
+  pub fn calculate_scenario(&self, data: &Data, scenario: &Scenario) -> GridCalculated {
+    if !self.subgrids.is_empty() {
+      return self.calculate_with_subgrids(data, scenario);
+    }
+    let hydrogen_engine_enabled = scenario.hydrogen_engine_enabled(self);
+    let battery_mode = scenario.battery_mode(self);
+    let railgun_charging = scenario.railgun_charging(self);
+    let jump_drive_charging = scenario.jump_drive_charging(self);
+
+    let ice_weight_per_volume = ICE.mass / ICE.volume; // TODO: derive from data
+    let ice_items_per_volume = 1.0 / ICE.volume; // TODO: derive from data
+    let ore_weight_per_volume = ORE.mass / ORE.volume; // TODO: derive from data
+    let ore_items_per_volume = 1.0 / ORE.volume; // TODO: derive from data
+    let steel_plate_weight_per_volume = STEEL_PLATE.mass / STEEL_PLATE.volume; // TODO: derive from data
+    let steel_plate_items_per_volume = 1.0 / STEEL_PLATE.volume; // TODO: derive from data
+    // Bottles missing from the data are not counted, and store no gas.
+    let bottle_gas_capacity = |gas_id: &str| data.bottles.get_by_gas(gas_id).map_or(0.0, |b| b.capacity);
+    let hydrogen_bottle = ItemSize::bottle(data, HYDROGEN_GAS_ID);
+    let oxygen_bottle = ItemSize::bottle(data, OXYGEN_GAS_ID);
+    let steel_plate_max_stack_amount = data.components.get(STEEL_PLATE_COMPONENT_ID).and_then(|c| c.max_stack_amount);
+    let bottle_max_stack_amount = Some(1.0); // Bottles do not stack, as each bottle has its own fill level.
+
+    let mut c = GridCalculated { stamp: CalculationStamp::new(data), ..GridCalculated::default() };
+
+    // Totals are accumulated as plain numbers, and stored in the result as typed units once final.
+    let mut total_mass_empty = 0.0;
+    let mut total_volume_any = 0.0;
+    let mut total_volume_ore = 0.0;
+    let mut total_volume_ice = 0.0;
+    let mut total_volume_ore_only = 0.0;
+    let mut total_volume_ice_only = 0.0;
+    let mut total_volume_components_only = 0.0;
+    let mut power_generation = 0.0;
+    let mut wheel_force = 0.0;
+    let mut thruster_force: PerDirection<f64> = PerDirection::default();
+    let mut thruster_nominal_force: PerDirection<f64> = PerDirection::default();
 
     let mut power_consumption_idle = 0.0;
     let mut power_consumption_railgun = 0.0;
@@ -218,51 +743,99 @@ impl GridCalculator {
     let mut hydrogen_consumption_thruster: PerDirection<f64> = PerDirection::default();
     let mut hydrogen_consumption_tank = 0.0;
 
+    // Thruster force of thrusters that need power or hydrogen, for limiting force when those are lacking.
+    let mut power_thruster_force: PerDirection<f64> = PerDirection::default();
+    let mut hydrogen_thruster_force: PerDirection<f64> = PerDirection::default();
+
+    let mut generator_count = 0.0;
+    let mut generator_ice_consumption = 0.0;
+
     let mut jump_strength = 0.0; // Divide by mass to get max jump distance.
     let mut max_jump_distance = 0.0; // Cap on max jump distance.
 
-    c.total_mass_empty += self.additional_mass;
+    total_mass_empty += self.additional_mass;
+    let crew_mass = self.crew_count as f64 * self.crew_mass_per_engineer;
+    total_mass_empty += crew_mass;
+
+    // Sum of all power consumption so far, used to track power draw per block type.
+    macro_rules! power_consumption_sum {
+      () => {
+        power_consumption_idle + power_consumption_railgun + power_consumption_utility + power_consumption_wheel_suspension
+          + power_consumption_jump_drive + power_consumption_generator + power_consumption_thruster.iter().sum::<f64>()
+          + power_consumption_battery
+      }
+    }
+    // Adds operational power consumption of the block with `id` to the power group assigned to that block in the data,
+    // or to `default` if no power group is assigned.
+    macro_rules! add_power_consumption {
+      ($id:expr, $default:expr, $consumption:expr) => {
+        match data.power_groups.get($id).unwrap_or($default) {
+          PowerGroup::Defense => power_consumption_railgun += $consumption,
+          PowerGroup::Utility => power_consumption_utility += $consumption,
+          PowerGroup::Production => power_consumption_generator += $consumption,
+        }
+      }
+    }
+    let mut block_mass: Vec<ContributorCalculated> = Vec::new();
+    let mut block_power_consumption: Vec<ContributorCalculated> = Vec::new();
+    let mut non_functional_blocks = Vec::new();
 
     // Non-directional blocks
     let wheel_power_ratio = self.wheel_power / 100.0;
-    for (id, count) in self.blocks.iter().filter(|(_, c)| **c != 0) {
-      let count = *count as f64;
-      if let Some(block) = data.blocks.containers.get(id) { // Containers.
-        c.total_mass_empty += block.mass(&data.components) * count;
+    let ignored_blocks = if self.ignore_hidden_blocks { self.hidden_blocks(data) } else { Vec::new() };
+    for (id, count) in self.blocks.iter().filter(|(id, c)| **c != 0.0 && !ignored_blocks.contains(id)) {
+      let mass_before = total_mass_empty;
+      let power_consumption_before = power_consumption_sum!();
+      let count = *count;
+      let build_ratio = self.block_build_progress(id) / 100.0;
+      if !self.is_block_functional(id) { // Non-functional blocks only contribute the mass of their placed components.
+        if let Some(block) = data.blocks.get_data(id) {
+          total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        }
+        non_functional_blocks.push(id.clone());
+      } else if let Some(block) = data.blocks.containers.get(id) { // Containers.
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        let volume = block.details.inventory_volume_any * count * self.container_multiplier;
         if block.store_any {
-          let volume = block.details.inventory_volume_any * count * self.container_multiplier;
-          c.total_volume_any += volume;
-          c.total_volume_ore += volume;
-          c.total_volume_ice += volume;
+          total_volume_any += volume;
+          total_volume_ore += volume;
+          total_volume_ice += volume;
+        } else {
+          match block.constraint {
+            InventoryConstraint::Ore => total_volume_ore_only += volume,
+            InventoryConstraint::Ice => total_volume_ice_only += volume,
+            InventoryConstraint::Components => total_volume_components_only += volume,
+            InventoryConstraint::Other => {}
+          }
         }
       } else if let Some(block) = data.blocks.connectors.get(id) { // Connectors.
-        c.total_mass_empty += block.mass(&data.components) * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
         let volume = block.details.inventory_volume_any * count * self.container_multiplier;
-        c.total_volume_any += volume;
-        c.total_volume_ore += volume;
-        c.total_volume_ice += volume;
+        total_volume_any += volume;
+        total_volume_ore += volume;
+        total_volume_ice += volume;
       } else if let Some(block) = data.blocks.cockpits.get(id) { // Cockpits.
-        c.total_mass_empty += block.mass(&data.components) * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
         if block.has_inventory {
           let volume = block.details.inventory_volume_any * count * self.container_multiplier;
-          c.total_volume_any += volume;
-          c.total_volume_ore += volume;
-          c.total_volume_ice += volume;
+          total_volume_any += volume;
+          total_volume_ore += volume;
+          total_volume_ice += volume;
         }
       } else if let Some(block) = data.blocks.wheel_suspensions.get(id) { // Wheel suspensions
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
-        c.wheel_force += details.force * count * wheel_power_ratio;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        wheel_force += details.force * count * wheel_power_ratio;
         power_consumption_idle += details.idle_power_consumption * count;
         power_consumption_wheel_suspension += details.operational_power_consumption * count * wheel_power_ratio;
       } else if let Some(block) = data.blocks.hydrogen_engines.get(id) { // Hydrogen Engines.
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
         let maximum_fuel_consumption = details.max_fuel_consumption * count;
         let maximum_power_output = details.max_power_generation * count;
         let maximum_refilling_input = maximum_fuel_consumption * 60.0; // Hydrogen engine input is multiplied by 60 when not full in MyFueledPowerProducer.cs
-        if self.hydrogen_engine_enabled {
-          c.power_generation += maximum_power_output;
+        if hydrogen_engine_enabled {
+          power_generation += maximum_power_output;
           hydrogen_consumption_engine += if self.hydrogen_engine_fill != 100.0 {
             maximum_refilling_input
           } else {
@@ -270,156 +843,331 @@ impl GridCalculator {
           };
         }
         let hydrogen_engine = c.hydrogen_engine.get_or_insert(HydrogenEngineCalculated::default());
-        hydrogen_engine.capacity += details.fuel_capacity * count;
+        hydrogen_engine.capacity += Volume::from_liters(details.fuel_capacity * count);
         hydrogen_engine.maximum_fuel_consumption += maximum_fuel_consumption;
-        hydrogen_engine.maximum_output += maximum_power_output;
+        hydrogen_engine.maximum_output += Power::from_megawatts(maximum_power_output);
         hydrogen_engine.maximum_refilling_input += maximum_refilling_input;
       } else if let Some(block) = data.blocks.reactors.get(id) { // Reactors.
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
-        c.power_generation += details.max_power_generation * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        let maximum_output = details.max_power_generation * count;
+        if scenario.reactors_enabled() {
+          power_generation += maximum_output;
+        }
+        let reactor = c.reactor.get_or_insert(ReactorCalculated::default());
+        reactor.maximum_output += Power::from_megawatts(maximum_output);
+        reactor.maximum_fuel_consumption += details.max_fuel_consumption * count;
         // TODO: inventory - uranium ingot only
-        // TODO: fuel capacity/use
       } else if let Some(block) = data.blocks.batteries.get(id) { // Batteries.
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
         let input = details.input * count;
         let output = details.output * count;
-        if self.battery_mode.is_charging() {
+        if battery_mode.is_charging() {
           power_consumption_battery += input;
         }
-        if self.battery_mode.is_discharging() {
-          c.power_generation += output;
+        if battery_mode.is_discharging() {
+          power_generation += output;
         }
         let battery = c.battery.get_or_insert(BatteryCalculated::default());
-        battery.capacity += details.capacity * count;
-        battery.maximum_input += input;
-        battery.maximum_output += output;
+        battery.capacity += Energy::from_megawatt_hours(details.capacity * count);
+        battery.maximum_input += Power::from_megawatts(input);
+        battery.maximum_output += Power::from_megawatts(output);
       } else if let Some(block) = data.blocks.jump_drives.get(id) { // Jump drives
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
         let input = details.operational_power_consumption * count;
-        if self.jump_drive_charging {
+        if jump_drive_charging {
           power_consumption_jump_drive += input;
         }
         let jump_drive = c.jump_drive.get_or_insert(JumpDriveCalculated::default());
-        jump_drive.capacity += block.capacity * count;
-        jump_drive.maximum_input += input;
+        jump_drive.capacity += Energy::from_megawatt_hours(block.capacity * count);
+        jump_drive.maximum_input += Power::from_megawatts(input);
         // Formula based on https://www.spaceengineerswiki.com/Jump_drive
         let max_jump_drive_distance = details.max_jump_distance / 1000.0; // Convert from m to km.
         jump_strength += max_jump_drive_distance * details.max_jump_mass * count;
         max_jump_distance += max_jump_drive_distance * count;
       } else if let Some(block) = data.blocks.railguns.get(id) { // Railguns
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
         let input = details.operational_power_consumption * count;
         power_consumption_idle += details.idle_power_consumption * count;
-        if self.railgun_charging {
+        if railgun_charging {
           power_consumption_railgun += input;
         }
         let railgun = c.railgun.get_or_insert(RailgunCalculated::default());
-        railgun.capacity += block.capacity * count;
-        railgun.maximum_input += input;
+        railgun.capacity += Energy::from_megawatt_hours(block.capacity * count);
+        railgun.maximum_input += Power::from_megawatts(input);
       } else if let Some(block) = data.blocks.generators.get(id) { // Hydrogen Generators.
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
-        c.total_volume_ice_only += details.inventory_volume_ice * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        total_volume_ice_only += details.inventory_volume_ice * count;
         power_consumption_idle += details.idle_power_consumption * count;
-        power_consumption_generator += details.operational_power_consumption * count;
+        add_power_consumption!(id, PowerGroup::Production, details.operational_power_consumption * count);
         c.hydrogen_generation += details.hydrogen_generation * count;
-        // TODO: ice consumption
+        c.oxygen_generation += details.oxygen_generation * count;
+        generator_count += count;
+        generator_ice_consumption += details.ice_consumption * count;
       } else if let Some(block) = data.blocks.hydrogen_tanks.get(id) { // Hydrogen Tanks.
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
-        let maximum_input_output = details.capacity * count * 0.05; // Hydrogen tank consumption is capacity * 0.05 when not full according to MyGasTank.cs
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        let maximum_input_output = details.capacity * count * 0.05; // Hydrogen tank input and output is capacity * 0.05 according to MyGasTank.cs
         if self.hydrogen_tank_mode.is_refilling() {
           power_consumption_idle += details.idle_power_consumption * count;
-          power_consumption_utility += details.operational_power_consumption * count;
-          hydrogen_consumption_tank = if self.hydrogen_tank_fill != 100.0 {
-            maximum_input_output
-          } else {
-            0.0
-          };
+          if self.hydrogen_tank_always_operational || self.hydrogen_tank_mode.is_transferring(self.hydrogen_tank_fill) {
+            add_power_consumption!(id, PowerGroup::Utility, details.operational_power_consumption * count);
+          }
+          if self.hydrogen_tank_fill != 100.0 {
+            hydrogen_consumption_tank += maximum_input_output;
+          }
         }
         let hydrogen_tank = c.hydrogen_tank.get_or_insert(HydrogenTankCalculated::default());
-        hydrogen_tank.capacity += details.capacity * count;
+        hydrogen_tank.capacity += Volume::from_liters(details.capacity * count);
         hydrogen_tank.maximum_input += maximum_input_output;
         hydrogen_tank.maximum_output += maximum_input_output;
       } else if let Some(block) = data.blocks.drills.get(id) { // Drills
         let details = &block.details;
-        c.total_mass_empty += block.mass(&data.components) * count;
-        c.total_volume_ore_only += details.inventory_volume_ore * count;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        total_volume_ore_only += details.inventory_volume_ore * count;
         power_consumption_idle += details.idle_power_consumption * count;
-        power_consumption_utility += details.operational_power_consumption * count;
+        add_power_consumption!(id, PowerGroup::Utility, details.operational_power_consumption * count);
+        c.drilling.get_or_insert_with(DrillingCalculated::default).add_drills(details, count);
+      } else if let Some(block) = data.blocks.gyroscopes.get(id) { // Gyroscopes
+        let details = &block.details;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        add_power_consumption!(id, PowerGroup::Utility, details.operational_power_consumption * count);
+        c.gyroscope.get_or_insert_with(GyroscopeCalculated::default).add_gyroscopes(details, count);
+      } else if let Some(block) = data.blocks.refineries.get(id) { // Refineries
+        let details = &block.details;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        power_consumption_idle += details.idle_power_consumption * count;
+        add_power_consumption!(id, PowerGroup::Production, details.operational_power_consumption * count);
+        c.production.get_or_insert_with(ProductionCalculated::default)
+          .add_refineries(details, count, self.refinery_ore_rate, self.refinery_ingot_yield / 100.0);
+      } else if let Some(block) = data.blocks.assemblers.get(id) { // Assemblers
+        let details = &block.details;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        power_consumption_idle += details.idle_power_consumption * count;
+        add_power_consumption!(id, PowerGroup::Production, details.operational_power_consumption * count);
+        c.production.get_or_insert_with(ProductionCalculated::default).add_assemblers(details, count);
+      } else if let Some((block, group)) = data.blocks.weapon(id) { // Weapons
+        let details = &block.details;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        power_consumption_idle += details.idle_power_consumption * count;
+        add_power_consumption!(id, PowerGroup::Utility, details.operational_power_consumption * count);
+        c.armament.get_or_insert_with(ArmamentCalculated::default).add_weapons(group, details, count, self.ammo_fill);
+      } else if let Some(block) = data.blocks.oxygen_tanks.get(id) { // Oxygen Tanks
+        let details = &block.details;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        power_consumption_idle += details.idle_power_consumption * count;
+        add_power_consumption!(id, PowerGroup::Utility, details.operational_power_consumption * count);
+        c.oxygen.get_or_insert_with(OxygenCalculated::default).add_oxygen_tanks(details, count);
+      } else if let Some(block) = data.blocks.oxygen_farms.get(id) { // Oxygen Farms
+        let details = &block.details;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        add_power_consumption!(id, PowerGroup::Utility, details.operational_power_consumption * count);
+        c.oxygen.get_or_insert_with(OxygenCalculated::default).add_oxygen_farms(details, count);
+      } else if let Some(block) = data.blocks.air_vents.get(id) { // Air Vents
+        let details = &block.details;
+        total_mass_empty += block.mass(&data.components) * count * build_ratio;
+        power_consumption_idle += details.idle_power_consumption * count;
+        if self.air_vents_pressurizing {
+          add_power_consumption!(id, PowerGroup::Utility, details.operational_power_consumption * count);
+        }
+        c.oxygen.get_or_insert_with(OxygenCalculated::default).add_air_vents(details, count, self.air_vents_pressurizing);
       }
+      Self::add_contributor(&mut block_mass, id, total_mass_empty - mass_before);
+      Self::add_contributor(&mut block_power_consumption, id, power_consumption_sum!() - power_consumption_before);
+      Self::update_heaviest_block(&mut c.heaviest_block, id, data);
     }
     // Directional blocks
     let thruster_power_ratio = self.thruster_power / 100.0;
+    let mut atmospheric_thrusters_without_force = Vec::new();
     for (id, count_per_direction) in self.directional_blocks.iter() {
+      if count_per_direction.iter().all(|c| *c == 0.0) || ignored_blocks.contains(id) { continue; }
+      if let Some(block) = data.blocks.thrusters.get(id) {
+        if block.ty == ThrusterType::Atmospheric && block.effectiveness(self.planetary_influence) <= 0.0 && self.is_block_functional(id) {
+          atmospheric_thrusters_without_force.push(id.clone());
+        }
+      }
+      let power_consumption_before = power_consumption_sum!();
+      let build_ratio = self.block_build_progress(id) / 100.0;
+      let functional = self.is_block_functional(id);
+      if !functional {
+        non_functional_blocks.push(id.clone());
+      }
       for (direction, count) in count_per_direction.iter_with_direction() {
         if let Some(block) = data.blocks.thrusters.get(id) { // Thrusters
-          let count = *count as f64;
+          let count = *count;
           let details = &block.details;
-          c.total_mass_empty += block.mass(&data.components) * count;
-          // Clamp planetary influence value.
-          let planetary_influence = self.planetary_influence.clamp(details.min_planetary_influence, details.max_planetary_influence);
-          // Slope-intercept form equation: y = mx + b
-          // Calculate m: m = (y2 - y1) / (x2 - x1)
-          let m = (details.effectiveness_at_min_influence - details.effectiveness_at_max_influence) / (details.min_planetary_influence - details.max_planetary_influence);
-          // Calculate b: b = y + -mx (choose x,y on the line)
-          let b = details.effectiveness_at_max_influence + (-1.0 * m * details.max_planetary_influence);
-          // Calculate y: y = mx + b
-          let effectiveness = m * planetary_influence + b;
-          c.thruster_acceleration[direction].force += details.force * thruster_power_ratio * effectiveness * count;
+          let mass = block.mass(&data.components) * count * build_ratio;
+          total_mass_empty += mass;
+          c.thruster_mass[direction] += Mass::from_kilograms(mass);
+          Self::add_directed_contributor(&mut block_mass, id, direction, mass);
+          if !functional { continue; }
+          let enabled = self.thruster_direction_enabled[direction];
+          if !enabled && self.disabled_thrusters_off { continue; }
+          let idle_consumption = details.actual_min_consumption(&data.gas_properties) * count;
+          match details.ty {
+            ThrusterType::Hydrogen => {
+              hydrogen_consumption_idle += idle_consumption;
+              c.hydrogen_thruster_idle[direction] += idle_consumption;
+            },
+            _ => power_consumption_idle += idle_consumption,
+          }
+          if !enabled { continue; }
+          let effectiveness = details.effectiveness(self.planetary_influence);
+          let force = details.force * thruster_power_ratio * effectiveness * count;
+          thruster_force[direction] += force;
+          thruster_nominal_force[direction] += details.force * count;
           match details.ty {
             ThrusterType::Hydrogen => {
-              hydrogen_consumption_idle += details.actual_min_consumption(&data.gas_properties) * count;
               let max_consumption = details.actual_max_consumption(&data.gas_properties) * thruster_power_ratio * effectiveness * count;
               hydrogen_consumption_thruster[direction] += max_consumption;
+              hydrogen_thruster_force[direction] += force;
             },
             _ => {
-              power_consumption_idle += details.actual_min_consumption(&data.gas_properties) * count;
               let max_consumption = details.actual_max_consumption(&data.gas_properties) * thruster_power_ratio * effectiveness * count;
               power_consumption_thruster[direction] += max_consumption;
+              power_thruster_force[direction] += force;
+              if details.ty == ThrusterType::Atmospheric {
+                c.atmospheric_thruster_consumption[direction] += Power::from_megawatts(max_consumption);
+              } else {
+                c.ion_thruster_consumption[direction] += Power::from_megawatts(max_consumption);
+              }
             },
           }
         }
       }
+      Self::add_contributor(&mut block_power_consumption, id, power_consumption_sum!() - power_consumption_before);
+      Self::update_heaviest_block(&mut c.heaviest_block, id, data);
+    }
+    if !atmospheric_thrusters_without_force.is_empty() {
+      atmospheric_thrusters_without_force.sort();
+      c.warnings.push(Warning::AtmosphericThrustersWithoutForce { ids: atmospheric_thrusters_without_force });
+    }
+    if !non_functional_blocks.is_empty() {
+      non_functional_blocks.sort();
+      c.warnings.push(Warning::NonFunctionalBlocks { ids: non_functional_blocks });
     }
+    if !ignored_blocks.is_empty() {
+      c.warnings.push(Warning::HiddenBlocksIgnored { ids: ignored_blocks });
+    }
+    if let Some(warning) = self.conveyor_port_size_mismatch(data) {
+      c.warnings.push(warning);
+    }
+    c.top_mass_contributors = Self::top_contributors(block_mass);
+    c.top_power_consumption_contributors = Self::top_contributors(block_power_consumption);
 
     // Calculate filled volumes.
-    let ice_only_volume = c.total_volume_ice_only * (self.ice_only_fill / 100.0);
-    let ore_only_volume = c.total_volume_ore_only * (self.ore_only_fill / 100.0);
-    let ice_in_any_volume = c.total_volume_any * (self.any_fill_with_ice / 100.0);
-    let ore_in_any_volume = c.total_volume_any * (self.any_fill_with_ore / 100.0);
-    let steel_plates_in_any_volume = c.total_volume_any * (self.any_fill_with_steel_plates / 100.0);
+    let ice_only_volume = total_volume_ice_only * (self.ice_only_fill / 100.0);
+    let ore_only_volume = total_volume_ore_only * (self.ore_only_fill / 100.0);
+    // Items that override the any fills take their place.
+    let any_fill = |fill: f64| if self.any_fill_items.is_some() { 0.0 } else { total_volume_any * (fill / 100.0) };
+    let ice_in_any_volume = any_fill(self.any_fill_with_ice);
+    let ore_in_any_volume = any_fill(self.any_fill_with_ore);
+    let steel_plates_in_any_volume = any_fill(self.any_fill_with_steel_plates);
+    let hydrogen_bottles_in_any_volume = any_fill(self.any_fill_with_hydrogen_bottles);
+    let oxygen_bottles_in_any_volume = any_fill(self.any_fill_with_oxygen_bottles);
+    let any_fill_items = self.any_fill_items.as_deref().unwrap_or_default();
 
     // Calculate filled mass.
     // TODO: container multiplier increases volume but keeps mass the same!
     let ice_only_mass = ice_only_volume * ice_weight_per_volume;
     let ore_only_mass = ore_only_volume * ore_weight_per_volume;
-    let any_mass = (ice_in_any_volume * ice_weight_per_volume) + (ore_in_any_volume * ore_weight_per_volume) + (steel_plates_in_any_volume * steel_plate_weight_per_volume);
-    c.total_mass_filled = c.total_mass_empty + ice_only_mass + ore_only_mass + any_mass;
+    let any_mass = (ice_in_any_volume * ice_weight_per_volume) + (ore_in_any_volume * ore_weight_per_volume) + (steel_plates_in_any_volume * steel_plate_weight_per_volume)
+      + hydrogen_bottle.as_ref().map_or(0.0, |b| hydrogen_bottles_in_any_volume * b.mass / b.volume)
+      + oxygen_bottle.as_ref().map_or(0.0, |b| oxygen_bottles_in_any_volume * b.mass / b.volume)
+      + any_fill_items.iter().map(|item| item.count(total_volume_any) * item.mass).sum::<f64>();
+    let ammo_mass = c.armament.as_ref().map_or(0.0, |a| a.ammo_mass.to_kilograms());
+    let total_mass_filled = total_mass_empty + ice_only_mass + ore_only_mass + any_mass + ammo_mass;
+    c.total_mass_empty = Mass::from_kilograms(total_mass_empty);
+    c.total_mass_filled = Mass::from_kilograms(total_mass_filled);
+    c.crew_mass = Mass::from_kilograms(crew_mass);
+    c.total_volume_any = Volume::from_liters(total_volume_any);
+    c.total_volume_ore = Volume::from_liters(total_volume_ore);
+    c.total_volume_ice = Volume::from_liters(total_volume_ice);
+    c.total_volume_ore_only = Volume::from_liters(total_volume_ore_only);
+    c.total_volume_ice_only = Volume::from_liters(total_volume_ice_only);
+    c.total_volume_components_only = Volume::from_liters(total_volume_components_only);
+    c.power_generation = Power::from_megawatts(power_generation);
+    c.power_thruster_consumption = power_consumption_thruster.map(Power::from_megawatts);
+    c.hydrogen_thruster_consumption = hydrogen_consumption_thruster.clone();
+    c.hydrogen_thruster_force = hydrogen_thruster_force.map(Force::from_newtons);
+    c.wheel_force = Force::from_newtons(wheel_force);
+    if let Some(production) = &mut c.production {
+      production.complete((ore_only_volume + ore_in_any_volume) * ore_weight_per_volume);
+    }
+    if let Some(gyroscope) = &mut c.gyroscope {
+      gyroscope.complete(&self.dimensions, total_mass_empty, total_mass_filled);
+    }
 
     // Calculate filled items.
     c.total_items_ore = (ore_only_volume + ore_in_any_volume) * ore_items_per_volume;
     c.total_items_ice = (ice_only_volume + ice_in_any_volume) * ice_items_per_volume;
     c.total_items_steel_plate = steel_plates_in_any_volume * steel_plate_items_per_volume;
+    c.total_items_hydrogen_bottle = hydrogen_bottle.as_ref().map_or(0.0, |b| hydrogen_bottles_in_any_volume / b.volume);
+    c.total_items_oxygen_bottle = oxygen_bottle.as_ref().map_or(0.0, |b| oxygen_bottles_in_any_volume / b.volume);
+    if self.round_items_to_stacks {
+      c.total_items_ore = c.total_items_ore.floor();
+      c.total_items_ice = c.total_items_ice.floor();
+      c.total_items_steel_plate = c.total_items_steel_plate.floor();
+      c.total_items_hydrogen_bottle = c.total_items_hydrogen_bottle.floor();
+      c.total_items_oxygen_bottle = c.total_items_oxygen_bottle.floor();
+      let stacks = |count: f64, max_stack_amount: Option<f64>| max_stack_amount.filter(|m| *m > 0.0).map(|m| (count / m).ceil());
+      c.total_stacks_steel_plate = stacks(c.total_items_steel_plate, steel_plate_max_stack_amount);
+      c.total_stacks_hydrogen_bottle = stacks(c.total_items_hydrogen_bottle, bottle_max_stack_amount);
+      c.total_stacks_oxygen_bottle = stacks(c.total_items_oxygen_bottle, bottle_max_stack_amount);
+    }
+    c.items = vec![
+      ItemCalculated::new("Ore", c.total_items_ore, ORE.volume, self.container_multiplier),
+      ItemCalculated::new("Ice", c.total_items_ice, ICE.volume, self.container_multiplier),
+      ItemCalculated::new("Steel Plate", c.total_items_steel_plate, STEEL_PLATE.volume, self.container_multiplier),
+    ];
+    if let Some(bottle) = &hydrogen_bottle {
+      c.items.push(ItemCalculated::new("H2 Bottle", c.total_items_hydrogen_bottle, bottle.volume, self.container_multiplier));
+    }
+    if let Some(bottle) = &oxygen_bottle {
+      c.items.push(ItemCalculated::new("O2 Bottle", c.total_items_oxygen_bottle, bottle.volume, self.container_multiplier));
+    }
+    for item in any_fill_items {
+      let count = item.count(total_volume_any);
+      let count = if self.round_items_to_stacks { count.floor() } else { count };
+      c.items.push(ItemCalculated::new(&item.name, count, item.volume, self.container_multiplier));
+    }
+    c.hydrogen_bottle_capacity = Volume::from_liters(c.total_items_hydrogen_bottle * bottle_gas_capacity(HYDROGEN_GAS_ID));
+    c.oxygen_bottle_capacity = Volume::from_liters(c.total_items_oxygen_bottle * bottle_gas_capacity(OXYGEN_GAS_ID));
+
+    // Calculate Acceleration. Not applicable to grids without (positive) mass.
+    let has_mass_empty = total_mass_empty > 0.0;
+    let has_mass_filled = total_mass_filled > 0.0;
+    for direction in Direction::items() {
+      let force = thruster_force[direction];
+      let a = &mut c.thruster_acceleration[direction];
+      a.force = Force::from_newtons(force);
+      a.nominal_force = Force::from_newtons(thruster_nominal_force[direction]);
+      a.acceleration_empty_no_gravity = has_mass_empty.then(|| force / total_mass_empty);
+      a.acceleration_filled_no_gravity = has_mass_filled.then(|| force / total_mass_filled);
+      a.acceleration_empty_gravity = has_mass_empty.then(|| (force - (total_mass_empty * 9.81 * self.gravity_multiplier - self.external_lift_force)) / total_mass_empty);
+      a.acceleration_filled_gravity = has_mass_filled.then(|| (force - (total_mass_filled * 9.81 * self.gravity_multiplier - self.external_lift_force)) / total_mass_filled);
+      a.calculate_braking(self.braking_speed);
+    }
 
-    // Calculate Acceleration
-    let has_mass_empty = c.total_mass_empty != 0.0;
-    let has_mass_filled = c.total_mass_filled != 0.0;
-    for a in c.thruster_acceleration.iter_mut() {
-      a.acceleration_empty_no_gravity = has_mass_empty.then(|| a.force / c.total_mass_empty);
-      a.acceleration_filled_no_gravity = has_mass_filled.then(|| a.force / c.total_mass_filled);
-      a.acceleration_empty_gravity = has_mass_empty.then(|| (a.force - (c.total_mass_empty * 9.81 * self.gravity_multiplier)) / c.total_mass_empty);
-      a.acceleration_filled_gravity = has_mass_filled.then(|| (a.force - (c.total_mass_filled * 9.81 * self.gravity_multiplier)) / c.total_mass_filled);
+    // Calculate forces
+    for direction in Direction::items() {
+      c.forces.thrusters[direction] = c.thruster_acceleration[direction].force;
     }
+    c.forces.wheels = c.wheel_force;
+
+    // Calculate hovering at a pitch
+    c.pitched_hover_empty = c.pitched_hover(self.hover_pitch, self.gravity_multiplier, self.external_lift_force, total_mass_empty);
+    c.pitched_hover_filled = c.pitched_hover(self.hover_pitch, self.gravity_multiplier, self.external_lift_force, total_mass_filled);
 
     // Calculate power
     let (actual_power_consumption_railgun, actual_power_consumption_jump_drive, actual_power_consumption_battery) = {
       struct PowerCalculatedBuilder {
         generation: f64,
+        other_generation: f64,
         battery_capacity: Option<f64>,
         battery_fill: f64,
         battery_generation: f64,
@@ -428,38 +1176,62 @@ impl GridCalculator {
         engine_fill: f64,
         engine_fuel_consumption: f64,
         engine_generation: f64,
-        engine_is_generating_power: bool
+        engine_is_generating_power: bool,
+        priority: PowerSourcePriority,
+        safety_margin: f64,
       }
       impl PowerCalculatedBuilder {
         fn power_resource(&self, consumption: f64, total_consumption: f64) -> PowerCalculated {
           let balance = self.generation - total_consumption;
-          let battery_duration = if total_consumption != 0.0 && self.battery_discharging {
-            self.battery_capacity.map(|c| Duration::from_hours(c * (self.battery_fill / 100.0) / total_consumption.min(self.battery_generation)))
+          let budget = balance - total_consumption * (self.safety_margin / 100.0);
+          let battery_output = if self.battery_discharging { self.battery_generation } else { 0.0 };
+          let engine_output = if self.engine_is_generating_power { self.engine_generation } else { 0.0 };
+          // Reactors and other sources take load first. The remaining deficit is divided between batteries and
+          // hydrogen engines according to the power source priority.
+          let (battery_drain, engine_drain) = self.priority.attribute((total_consumption - self.other_generation).max(0.0), battery_output, engine_output);
+          let battery_duration = if battery_drain != 0.0 {
+            self.battery_capacity.map(|c| Duration::from_hours(c * (self.battery_fill / 100.0) / battery_drain))
           } else {
             None
           };
-          let engine_duration = if total_consumption != 0.0 && self.engine_is_generating_power {
+          let engine_duration = if engine_drain != 0.0 {
             self.engine_capacity.map(|c| {
               let capacity = c * (self.engine_fill / 100.0);
-              Duration::from_seconds((capacity / self.engine_fuel_consumption) * (self.engine_generation / total_consumption.min(self.engine_generation)))
+              Duration::from_seconds((capacity / self.engine_fuel_consumption) * (self.engine_generation / engine_drain))
             })
           } else {
             None
           };
-          PowerCalculated { consumption, total_consumption, balance, battery_duration, engine_duration }
+          PowerCalculated {
+            consumption: Power::from_megawatts(consumption),
+            total_consumption: Power::from_megawatts(total_consumption),
+            balance: Power::from_megawatts(balance),
+            budget: Power::from_megawatts(budget),
+            battery_drain: Power::from_megawatts(battery_drain),
+            engine_drain: Power::from_megawatts(engine_drain),
+            battery_duration,
+            engine_duration,
+          }
         }
       }
+      let battery_generation = c.battery.as_ref().map_or(0.0, |b| b.maximum_output.to_megawatts());
+      let engine_generation = c.hydrogen_engine.as_ref().map_or(0.0, |e| e.maximum_output.to_megawatts());
       let b = PowerCalculatedBuilder {
-        generation: c.power_generation,
-        battery_capacity: c.battery.as_ref().map(|b| b.capacity),
+        generation: power_generation,
+        other_generation: power_generation
+          - if battery_mode.is_discharging() { battery_generation } else { 0.0 }
+          - if hydrogen_engine_enabled { engine_generation } else { 0.0 },
+        battery_capacity: c.battery.as_ref().map(|b| b.capacity.to_megawatt_hours()),
         battery_fill: self.battery_fill,
-        battery_generation: c.battery.as_ref().map(|b| b.maximum_output).unwrap_or(0.0),
-        battery_discharging: self.battery_mode.is_discharging() && self.battery_fill != 0.0,
-        engine_capacity: c.hydrogen_engine.as_ref().map(|e| e.capacity),
+        battery_generation,
+        battery_discharging: battery_mode.is_discharging() && self.battery_fill != 0.0,
+        engine_capacity: c.hydrogen_engine.as_ref().map(|e| e.capacity.to_liters()),
         engine_fill: self.hydrogen_engine_fill,
         engine_fuel_consumption: c.hydrogen_engine.as_ref().map(|e| e.maximum_fuel_consumption).unwrap_or(0.0),
-        engine_generation: c.hydrogen_engine.as_ref().map(|e| e.maximum_output).unwrap_or(0.0),
-        engine_is_generating_power: self.hydrogen_engine_enabled && self.hydrogen_engine_fill != 0.0,
+        engine_generation,
+        engine_is_generating_power: hydrogen_engine_enabled && self.hydrogen_engine_fill != 0.0,
+        priority: self.power_source_priority,
+        safety_margin: self.safety_margin,
       };
 
       // Idle
@@ -467,7 +1239,7 @@ impl GridCalculator {
 
       // Non-idle
       // Defense (railgun)
-      let actual_power_consumption_railgun = power_consumption_railgun.min(c.power_generation).max(0.0);
+      let actual_power_consumption_railgun = power_consumption_railgun.min(power_generation).max(0.0);
       let mut total_consumption = power_consumption_railgun;
       c.power_railgun_charge = b.power_resource(power_consumption_railgun, total_consumption);
       // Utility
@@ -477,7 +1249,7 @@ impl GridCalculator {
       total_consumption += power_consumption_wheel_suspension;
       c.power_upto_wheel_suspension = b.power_resource(power_consumption_wheel_suspension, total_consumption);
       // Charge jump drive
-      let actual_power_consumption_jump_drive = power_consumption_jump_drive.min(c.power_upto_wheel_suspension.balance).max(0.0);
+      let actual_power_consumption_jump_drive = power_consumption_jump_drive.min(c.power_upto_wheel_suspension.balance.to_megawatts()).max(0.0);
       total_consumption += power_consumption_jump_drive;
       c.power_upto_jump_drive_charge = b.power_resource(power_consumption_jump_drive, total_consumption);
       // Generator
@@ -495,32 +1267,72 @@ impl GridCalculator {
       let left_right_consumption = Self::thruster_consumption_peak(&power_consumption_thruster, Direction::Left, Direction::Right);
       total_consumption += left_right_consumption;
       c.power_upto_left_right_thruster = b.power_resource(left_right_consumption, total_consumption);
-      // Charge battery
-      let actual_power_consumption_battery = power_consumption_battery.min(c.power_upto_left_right_thruster.balance).max(0.0);
+      // Worst thruster direction pair: only the pair with the highest consumption, on top of all non-thruster groups.
+      let worst_pair = Self::worst_thruster_pair([
+        (PowerChainGroup::UpDownThruster, up_down_consumption),
+        (PowerChainGroup::FrontBackThruster, front_back_consumption),
+        (PowerChainGroup::LeftRightThruster, left_right_consumption),
+      ]);
+      c.power_worst_thruster_group = worst_pair.map(|(group, _)| group);
+      let worst_pair_consumption = worst_pair.map_or(0.0, |(_, consumption)| consumption);
+      c.power_worst_thruster = b.power_resource(worst_pair_consumption, c.power_upto_generator.total_consumption.to_megawatts() + worst_pair_consumption);
+      // Charge battery: consumption is capped by both the maximum input of batteries and the surplus.
+      let actual_power_consumption_battery = power_consumption_battery.min(c.power_upto_left_right_thruster.balance.to_megawatts()).max(0.0);
       total_consumption += power_consumption_battery;
       c.power_upto_battery_charge = b.power_resource(power_consumption_battery, total_consumption);
 
       (actual_power_consumption_railgun, actual_power_consumption_jump_drive, actual_power_consumption_battery)
     };
+    c.first_unpowered_group = PowerChainGroup::items().into_iter()
+      .filter(|group| *group != PowerChainGroup::BatteryCharge)
+      .find(|group| c.power_upto(*group).balance.to_megawatts() < 0.0);
+    c.first_over_budget_group = PowerChainGroup::items().into_iter()
+      .filter(|group| *group != PowerChainGroup::BatteryCharge)
+      .find(|group| c.power_upto(*group).budget.to_megawatts() < 0.0);
+    let power_total_consumption = c.power_upto_battery_charge.total_consumption.to_megawatts();
+    c.power_shares = PowerChainGroup::items().into_iter()
+      .map(|group| (group, Self::consumption_share(c.power_upto(group).consumption.to_megawatts(), power_total_consumption)))
+      .collect();
 
     if let Some(railgun) = &mut c.railgun { // TODO: is this also 80% efficient?
-      railgun.charge_duration = self.railgun_charging.then(|| Duration::from_hours(railgun.capacity / actual_power_consumption_railgun));
+      railgun.charge_duration = railgun_charging.then(|| Duration::from_hours(railgun.capacity.to_megawatt_hours() / actual_power_consumption_railgun));
+      railgun.wasted_surplus = railgun_charging.then(|| c.power_railgun_charge.balance.max(Power::default()));
     }
 
-    const CHARGE_EFFICIENCY: f64 = 0.8;
-
     if let Some(jump_drive) = &mut c.jump_drive {
       // TODO: use efficiency from jump drive data, instead of hardcoded 80% efficiency!
-      let should_charge = self.jump_drive_charging;
-      jump_drive.charge_duration = should_charge.then(|| Duration::from_hours(jump_drive.capacity / (actual_power_consumption_jump_drive * CHARGE_EFFICIENCY)));
-      jump_drive.max_distance_empty = (jump_strength / c.total_mass_empty).min(max_jump_distance);
-      jump_drive.max_distance_filled = (jump_strength / c.total_mass_filled).min(max_jump_distance);
+      let should_charge = jump_drive_charging;
+      jump_drive.charge_duration = should_charge.then(|| Duration::from_hours(jump_drive.capacity.to_megawatt_hours() / (actual_power_consumption_jump_drive * CHARGE_EFFICIENCY)));
+      jump_drive.wasted_surplus = should_charge.then(|| c.power_upto_jump_drive_charge.balance.max(Power::default()));
+      if let (Some(charge_duration), Some(battery)) = (jump_drive.charge_duration, &c.battery) {
+        // Jump drives that receive no power never finish charging, so no battery fill is sufficient.
+        if battery_mode.is_discharging() && battery.capacity.to_megawatt_hours() > 0.0 && actual_power_consumption_jump_drive > 0.0 {
+          // Reactors and other sources take load first. The remaining deficit, upto and including jump drive
+          // charging, is divided between batteries and hydrogen engines according to the power source priority.
+          let engine_output = if hydrogen_engine_enabled { c.hydrogen_engine.as_ref().map_or(0.0, |e| e.maximum_output.to_megawatts()) } else { 0.0 };
+          let other_generation = power_generation - battery.maximum_output.to_megawatts() - engine_output;
+          let engine_output = if self.hydrogen_engine_fill != 0.0 { engine_output } else { 0.0 };
+          let consumption = c.power_upto_wheel_suspension.total_consumption.to_megawatts() + actual_power_consumption_jump_drive;
+          let (battery_drain, _) = self.power_source_priority.attribute((consumption - other_generation).max(0.0), battery.maximum_output.to_megawatts(), engine_output);
+          let battery_energy = battery_drain * (charge_duration.to_minutes() / 60.0);
+          jump_drive.minimum_battery_fill = Some((battery_energy / battery.capacity.to_megawatt_hours()) * 100.0);
+        }
+      }
+      jump_drive.max_distance_empty = (total_mass_empty > 0.0).then(|| (jump_strength / total_mass_empty).min(max_jump_distance));
+      jump_drive.max_distance_filled = (total_mass_filled > 0.0).then(|| (jump_strength / total_mass_filled).min(max_jump_distance));
     }
 
     if let Some(battery) = &mut c.battery {
       let anti_fill = 1.0 - self.battery_fill / 100.0;
-      let should_charge = self.battery_mode.is_charging() && self.battery_fill != 100.0;
-      battery.charge_duration = should_charge.then(|| Duration::from_hours((battery.capacity * anti_fill) / (actual_power_consumption_battery * CHARGE_EFFICIENCY)));
+      let should_charge = battery_mode.is_charging() && self.battery_fill != 100.0;
+      battery.charge_duration = should_charge.then(|| Duration::from_hours((battery.capacity.to_megawatt_hours() * anti_fill) / (actual_power_consumption_battery * CHARGE_EFFICIENCY)));
+      battery.wasted_surplus = should_charge.then(|| c.power_upto_battery_charge.balance.max(Power::default()));
+    }
+
+    if c.battery.is_some() || c.jump_drive.is_some() {
+      let battery_energy = c.battery.as_ref().map_or(Energy::default(), |b| b.capacity / CHARGE_EFFICIENCY);
+      let jump_drive_energy = c.jump_drive.as_ref().map_or(Energy::default(), |j| j.capacity / CHARGE_EFFICIENCY);
+      c.fuel_cost = Some(FuelCostCalculated::new(battery_energy, jump_drive_energy, c.reactor.as_ref(), c.hydrogen_engine.as_ref(), generator_ice_consumption, c.hydrogen_generation));
     }
 
     // Calculate Hydrogen
@@ -531,6 +1343,8 @@ impl GridCalculator {
         tank_fill: f64,
         tank_generation: f64,
         tank_is_providing_hydrogen: bool,
+        bottle_capacity: f64,
+        safety_margin: f64,
       }
       impl HydrogenCalculatedBuilder {
         fn hydrogen_resource(&self, consumption: f64, total_consumption: f64) -> HydrogenCalculated {
@@ -540,21 +1354,32 @@ impl GridCalculator {
           } else {
             balance_without_tank
           };
+          let budget = balance_with_tank - total_consumption * (self.safety_margin / 100.0);
           let has_consumption = total_consumption != 0.0;
           let tank_duration = if has_consumption && self.tank_is_providing_hydrogen {
-            self.tank_capacity.map(|c| Duration::from_seconds((c * (self.tank_fill / 100.0)) / total_consumption.min(self.tank_generation)))
+            // Tanks only provide the hydrogen that generators cannot, limited by their maximum output, according
+            // to MyGasTank.cs. When generators cover all consumption, tanks are not drained. Hydrogen bottles refill
+            // the tanks, extending their duration.
+            let tank_drain = (total_consumption - self.generation).min(self.tank_generation);
+            self.tank_capacity.map(|c| if tank_drain > 0.0 {
+              Duration::from_seconds((c * (self.tank_fill / 100.0) + self.bottle_capacity) / tank_drain)
+            } else {
+              Duration::from_seconds(f64::INFINITY)
+            })
           } else {
             None
           };
-          HydrogenCalculated { consumption, total_consumption, balance_without_tank, balance_with_tank, tank_duration }
+          HydrogenCalculated { consumption, total_consumption, balance_without_tank, balance_with_tank, budget, tank_duration }
         }
       }
       let mut b = HydrogenCalculatedBuilder {
         generation: c.hydrogen_generation,
-        tank_capacity: c.hydrogen_tank.as_ref().map(|t| t.capacity),
+        tank_capacity: c.hydrogen_tank.as_ref().map(|t| t.capacity.to_liters()),
         tank_fill: self.hydrogen_tank_fill,
         tank_generation: c.hydrogen_tank.as_ref().map(|t| t.maximum_output).unwrap_or(0.0),
         tank_is_providing_hydrogen: self.hydrogen_tank_mode.is_providing() && self.hydrogen_tank_fill != 0.0,
+        bottle_capacity: c.hydrogen_bottle_capacity.to_liters(),
+        safety_margin: self.safety_margin,
       };
 
       // Idle
@@ -576,68 +1401,342 @@ impl GridCalculator {
       let left_right_consumption = Self::thruster_consumption_peak(&hydrogen_consumption_thruster, Direction::Left, Direction::Right);
       total_consumption += left_right_consumption;
       c.hydrogen_upto_left_right_thruster = b.hydrogen_resource(left_right_consumption, total_consumption);
-      // Tank
-      let actual_hydrogen_consumption_tank = hydrogen_consumption_tank.min(c.hydrogen_generation).max(0.0);
+      // Worst thruster direction pair: only the pair with the highest consumption, on top of filling engines.
+      let worst_pair = Self::worst_thruster_pair([
+        (HydrogenChainGroup::UpDownThruster, up_down_consumption),
+        (HydrogenChainGroup::FrontBackThruster, front_back_consumption),
+        (HydrogenChainGroup::LeftRightThruster, left_right_consumption),
+      ]);
+      c.hydrogen_worst_thruster_group = worst_pair.map(|(group, _)| group);
+      let worst_pair_consumption = worst_pair.map_or(0.0, |(_, consumption)| consumption);
+      c.hydrogen_worst_thruster = b.hydrogen_resource(worst_pair_consumption, hydrogen_consumption_engine + worst_pair_consumption);
+      // Generators needed for engines and the worst direction pair of thrusters, with the safety margin on top.
+      c.hydrogen_generator_requirement = Self::generator_requirement(
+        (hydrogen_consumption_engine + worst_pair_consumption) * (1.0 + self.safety_margin / 100.0),
+        generator_count,
+        c.hydrogen_generation,
+        generator_ice_consumption,
+      );
+      // Tank. Tanks are only filled with the hydrogen that is left over after all other consumers, both in On and
+      // Stockpile mode. In On mode, a tank that is providing hydrogen to cover a deficit is not filled at the same time.
+      let actual_hydrogen_consumption_tank = hydrogen_consumption_tank.min(c.hydrogen_upto_left_right_thruster.balance_without_tank).max(0.0);
       total_consumption += hydrogen_consumption_tank;
       b.tank_is_providing_hydrogen = false; // Disable tank duration for tanks.
       c.hydrogen_upto_tank_fill = b.hydrogen_resource(hydrogen_consumption_tank, total_consumption);
 
       (actual_hydrogen_consumption_tank, actual_hydrogen_consumption_engine)
     };
+    let hydrogen_total_consumption = c.hydrogen_upto_tank_fill.total_consumption;
+    c.hydrogen_shares = HydrogenChainGroup::items().into_iter()
+      .map(|group| (group, Self::consumption_share(c.hydrogen_upto(group).consumption, hydrogen_total_consumption)))
+      .collect();
+
+    // Calculate drilling and mining while hovering, until inventories are full of ore.
+    let ore_volume = total_volume_ore_only + total_volume_any;
+    if let Some(drilling) = &mut c.drilling {
+      drilling.complete(ore_volume, &mut c.warnings);
+    }
+    let mining_rate = if self.mining_rate > 0.0 {
+      self.mining_rate
+    } else {
+      c.drilling.as_ref().map(|d| d.ore_rate).unwrap_or(0.0)
+    };
+    if mining_rate > 0.0 && ore_volume > 0.0 {
+      let fill_duration = Duration::from_seconds(ore_volume / mining_rate);
+      let mass_full = total_mass_empty + ore_volume * ore_weight_per_volume;
+      // Mass increases linearly while mining, so average consumption is the consumption at the average mass.
+      let average_mass = (total_mass_empty + mass_full) / 2.0;
+      let up_force = thruster_force[Direction::Up];
+      let required_force = (average_mass * 9.81 * self.gravity_multiplier - self.external_lift_force).max(0.0);
+      let hover_ratio = if up_force > 0.0 { (required_force / up_force).min(1.0) } else { 0.0 };
+
+      let power_consumption = power_consumption_idle + power_consumption_utility + power_consumption_thruster[Direction::Up] * hover_ratio;
+      let battery = c.battery.as_ref().filter(|_| battery_mode.is_discharging());
+      let power_generation_without_battery = power_generation - battery.map_or(0.0, |b| b.maximum_output.to_megawatts());
+      let power_deficit = power_consumption - power_generation_without_battery;
+      let power_duration = (power_deficit > 0.0).then(|| match battery {
+        Some(b) if b.maximum_output.to_megawatts() >= power_deficit => Duration::from_hours(b.capacity.to_megawatt_hours() * (self.battery_fill / 100.0) / power_deficit),
+        _ => Duration::from_minutes(0.0),
+      });
+
+      let hydrogen_consumption = hydrogen_consumption_idle + hydrogen_consumption_thruster[Direction::Up] * hover_ratio;
+      let hydrogen_tank = c.hydrogen_tank.as_ref().filter(|_| self.hydrogen_tank_mode.is_providing());
+      let hydrogen_deficit = hydrogen_consumption - c.hydrogen_generation;
+      let hydrogen_duration = (hydrogen_deficit > 0.0).then(|| match hydrogen_tank {
+        Some(t) if t.maximum_output >= hydrogen_deficit => Duration::from_seconds(t.capacity.to_liters() * (self.hydrogen_tank_fill / 100.0) / hydrogen_deficit),
+        _ => Duration::from_minutes(0.0),
+      });
+
+      let ascent_acceleration_full = (mass_full > 0.0).then(|| (up_force - (mass_full * 9.81 * self.gravity_multiplier - self.external_lift_force)) / mass_full);
+      c.mining = Some(MiningCalculated {
+        fill_duration,
+        power_consumption: Power::from_megawatts(power_consumption),
+        power_duration,
+        hydrogen_consumption,
+        hydrogen_duration,
+        mass_full: Mass::from_kilograms(mass_full),
+        ascent_acceleration_full,
+      });
+    }
+
+    if total_mass_filled > 0.0 {
+      // Up thrusters only get the fraction of their power and hydrogen that is left after consumers before them in the
+      // power and hydrogen chains, which limits their force.
+      let supplied = |consumption: f64, balance: f64| if consumption > 0.0 { ((consumption + balance.min(0.0)) / consumption).clamp(0.0, 1.0) } else { 1.0 };
+      let power_supplied = supplied(c.power_upto_up_down_thruster.consumption.to_megawatts(), c.power_upto_up_down_thruster.balance.to_megawatts());
+      let hydrogen_supplied = supplied(c.hydrogen_upto_up_down_thruster.consumption, c.hydrogen_upto_up_down_thruster.balance_with_tank);
+      let up_force = thruster_force[Direction::Up];
+      let braking_force = up_force
+        - power_thruster_force[Direction::Up] * (1.0 - power_supplied)
+        - hydrogen_thruster_force[Direction::Up] * (1.0 - hydrogen_supplied);
+      c.descent = Some(DescentCalculated::new(
+        braking_force,
+        total_mass_filled,
+        9.81 * self.gravity_multiplier - self.external_lift_force / total_mass_filled,
+        self.descent_altitude,
+        self.descent_reaction_time,
+      ));
+    }
+
+    // Oxygen demand on generators is the consumption of air vents and other consumers that oxygen farms do not cover.
+    if c.oxygen.is_none() && (self.oxygen_demand > 0.0 || c.oxygen_generation > 0.0) {
+      c.oxygen = Some(OxygenCalculated::default());
+    }
+    if let Some(oxygen) = &mut c.oxygen {
+      oxygen.demand = self.oxygen_demand;
+    }
+    let oxygen_generator_demand = c.oxygen.as_ref().map(|o| o.generator_demand()).unwrap_or(0.0);
+    if generator_count > 0.0 {
+      // Generators run only as much as needed to cover demand, consuming ice proportionally.
+      let utilization = |demand: f64, generation: f64| if generation > 0.0 { (demand / generation).clamp(0.0, 1.0) } else { 0.0 };
+      let hydrogen_utilization = utilization(c.hydrogen_upto_tank_fill.total_consumption, c.hydrogen_generation);
+      let utilization = if self.generator_oxygen_throttling {
+        hydrogen_utilization.max(utilization(oxygen_generator_demand, c.oxygen_generation))
+      } else {
+        hydrogen_utilization
+      };
+      let ice_consumption = generator_ice_consumption * utilization;
+      let oxygen_output = c.oxygen_generation * utilization;
+      c.generator = Some(GeneratorCalculated {
+        utilization: utilization * 100.0,
+        ice_consumption,
+        ice_duration: (ice_consumption > 0.0).then(|| Duration::from_seconds(c.total_items_ice / ice_consumption)),
+        hydrogen_output: c.hydrogen_generation * utilization,
+        oxygen_output,
+        oxygen_balance: oxygen_output - oxygen_generator_demand,
+      });
+    }
+    if let Some(oxygen) = &mut c.oxygen {
+      oxygen.complete(c.generator.as_ref().map(|g| g.oxygen_output).unwrap_or(0.0), self.oxygen_tank_fill / 100.0, c.oxygen_bottle_capacity.to_liters());
+    }
 
     if let Some(hydrogen_tank) = &mut c.hydrogen_tank {
       let anti_fill = 1.0 - self.hydrogen_tank_fill / 100.0;
       let should_refill = self.hydrogen_tank_mode.is_refilling() && self.hydrogen_tank_fill != 100.0;
-      hydrogen_tank.fill_duration = should_refill.then(|| Duration::from_seconds((hydrogen_tank.capacity * anti_fill) / actual_hydrogen_consumption_tank));
+      hydrogen_tank.fill_duration = should_refill.then(|| Duration::from_seconds((hydrogen_tank.capacity.to_liters() * anti_fill) / actual_hydrogen_consumption_tank));
     }
 
     if let Some(hydrogen_engine) = &mut c.hydrogen_engine {
       let anti_fill = 1.0 - self.hydrogen_engine_fill / 100.0;
-      let should_refill = self.hydrogen_engine_enabled && self.hydrogen_engine_fill != 100.0;
-      hydrogen_engine.fill_duration = should_refill.then(|| Duration::from_seconds((hydrogen_engine.capacity * anti_fill) / actual_hydrogen_consumption_engine));
+      let should_refill = hydrogen_engine_enabled && self.hydrogen_engine_fill != 100.0;
+      hydrogen_engine.fill_duration = should_refill.then(|| Duration::from_seconds((hydrogen_engine.capacity.to_liters() * anti_fill) / actual_hydrogen_consumption_engine));
     }
 
     c
   }
 
+  /// Solves for the number of generators needed to generate `consumption` L/s of hydrogen, assuming all generators
+  /// have the average hydrogen generation and ice consumption of the `generator_count` generators on the grid. Returns
+  /// None if there are no generators on the grid, or they do not generate hydrogen.
+  fn generator_requirement(consumption: f64, generator_count: f64, hydrogen_generation: f64, ice_consumption: f64) -> Option<HydrogenGeneratorRequirementCalculated> {
+    if generator_count == 0.0 || hydrogen_generation <= 0.0 { return None; }
+    let hydrogen_generation_per_generator = hydrogen_generation / generator_count;
+    let ice_consumption_per_generator = ice_consumption / generator_count;
+    let required_generator_count = (consumption / hydrogen_generation_per_generator).ceil().max(0.0);
+    Some(HydrogenGeneratorRequirementCalculated {
+      consumption,
+      generator_count: required_generator_count as u64,
+      additional_generator_count: (required_generator_count - generator_count).ceil().max(0.0) as u64,
+      ice_consumption: required_generator_count * ice_consumption_per_generator,
+    })
+  }
+
+  /// Fraction 0-1 of `consumption` in `total_consumption`, or 0 if there is no total consumption.
+  fn consumption_share(consumption: f64, total_consumption: f64) -> f64 {
+    if total_consumption > 0.0 { consumption / total_consumption } else { 0.0 }
+  }
+
+  /// Gets the thruster group with the highest consumption in `pairs` along with that consumption, preferring earlier
+  /// groups on ties, or None if no group has consumption.
+  fn worst_thruster_pair<G: Copy>(pairs: [(G, f64); 3]) -> Option<(G, f64)> {
+    pairs.into_iter()
+      .filter(|(_, consumption)| *consumption > 0.0)
+      .fold(None, |worst, pair| match worst {
+        Some((_, worst_consumption)) if worst_consumption >= pair.1 => worst,
+        _ => Some(pair),
+      })
+  }
+
   fn thruster_consumption_peak(per_direction: &PerDirection<f64>, direction_a: Direction, direction_b: Direction) -> f64 {
     per_direction[direction_a].max(per_direction[direction_b])
   }
+
+  fn add_contributor(contributors: &mut Vec<ContributorCalculated>, id: &BlockId, value: f64) {
+    if value > 0.0 {
+      contributors.push(ContributorCalculated { id: id.clone(), direction: None, value });
+    }
+  }
+
+  fn add_directed_contributor(contributors: &mut Vec<ContributorCalculated>, id: &BlockId, direction: Direction, value: f64) {
+    if value > 0.0 {
+      contributors.push(ContributorCalculated { id: id.clone(), direction: Some(direction), value });
+    }
+  }
+
+  fn top_contributors(mut contributors: Vec<ContributorCalculated>) -> Vec<ContributorCalculated> {
+    contributors.sort_by(|a, b| b.value.total_cmp(&a.value).then_with(|| a.id.cmp(&b.id)));
+    contributors.truncate(TOP_CONTRIBUTORS_COUNT);
+    contributors
+  }
+
+  /// Checks whether small grid blocks with a non-zero count only have small or only have large conveyor ports, without
+  /// any blocks connecting both port sizes. Blocks with unknown conveyor ports are ignored.
+  fn conveyor_port_size_mismatch(&self, data: &Data) -> Option<Warning> {
+    let mut small_port_ids = Vec::new();
+    let mut large_port_ids = Vec::new();
+    for (id, _) in self.iter_total_block_counts() {
+      let Some(block) = data.blocks.get_data(id) else { continue };
+      if block.size != GridSize::Small { continue; }
+      match block.conveyor_ports {
+        Some(ConveyorPorts::Small) => small_port_ids.push(id.clone()),
+        Some(ConveyorPorts::Large) => large_port_ids.push(id.clone()),
+        Some(ConveyorPorts::SmallAndLarge) => return None,
+        None => {}
+      }
+    }
+    if small_port_ids.is_empty() || large_port_ids.is_empty() { return None; }
+    small_port_ids.sort();
+    large_port_ids.sort();
+    Some(Warning::ConveyorPortSizeMismatch { small_port_ids, large_port_ids })
+  }
+
+  fn update_heaviest_block(heaviest_block: &mut Option<ContributorCalculated>, id: &BlockId, data: &Data) {
+    let Some(mass) = data.blocks.get_data(id).map(|b| b.mass(&data.components)) else { return };
+    if heaviest_block.as_ref().is_none_or(|h| mass > h.value || (mass == h.value && *id < h.id)) {
+      *heaviest_block = Some(ContributorCalculated { id: id.clone(), direction: None, value: mass });
+    }
+  }
 }
 
+/// Fraction of input power that is stored when charging batteries and jump drives.
+const CHARGE_EFFICIENCY: f64 = 0.8;
+
+/// Number of block types in [`GridCalculated::top_mass_contributors`] and
+/// [`GridCalculated::top_power_consumption_contributors`].
+pub const TOP_CONTRIBUTORS_COUNT: usize = 5;
+
 
 // Calculated data
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct CalculationStamp {
+  /// Version of the game data used in the calculation, or None if unknown.
+  pub data_version: Option<String>,
+  /// Version of the calculator that performed the calculation.
+  pub app_version: String,
+  /// Time of the calculation (seconds since the Unix epoch), or None if the time is unavailable on this platform.
+  pub calculated_at: Option<u64>,
+}
+
+impl CalculationStamp {
+  pub fn new(data: &Data) -> Self {
+    Self {
+      data_version: data.version.clone(),
+      app_version: env!("CARGO_PKG_VERSION").to_string(),
+      calculated_at: Self::now(),
+    }
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  fn now() -> Option<u64> {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).ok().map(|d| d.as_secs())
+  }
+  #[cfg(target_arch = "wasm32")]
+  fn now() -> Option<u64> {
+    None // `SystemTime::now` panics on wasm32-unknown-unknown.
+  }
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct GridCalculated {
+  /// Data and app version, and time of the calculation.
+  pub stamp: CalculationStamp,
+  /// Warnings about likely mistakes in the calculator configuration.
+  pub warnings: Vec<Warning>,
   /// Total volume available in inventories that accept any item (L)
-  pub total_volume_any: f64,
+  pub total_volume_any: Volume,
   /// Total volume available for ore in inventories that accept any item (L)
-  pub total_volume_ore: f64,
+  pub total_volume_ore: Volume,
   /// Total volume available for ice in inventories that accept any item (L)
-  pub total_volume_ice: f64,
+  pub total_volume_ice: Volume,
   /// Total volume available for ore in inventories that accept only ore (L)
-  pub total_volume_ore_only: f64,
+  pub total_volume_ore_only: Volume,
   /// Total volume available for ore in inventories that accept only ice (L)
-  pub total_volume_ice_only: f64,
+  pub total_volume_ice_only: Volume,
+  /// Total volume available for components in inventories that accept only components (L)
+  pub total_volume_components_only: Volume,
+  /// Mass of the crew, included in the total masses (kg)
+  pub crew_mass: Mass,
+  /// Heaviest single block (kg), or None if there are no blocks
+  pub heaviest_block: Option<ContributorCalculated>,
+  /// Block types with the highest total empty mass, heaviest first (kg)
+  pub top_mass_contributors: Vec<ContributorCalculated>,
+  /// Block types with the highest total power consumption, including idle consumption, highest first (MW)
+  pub top_power_consumption_contributors: Vec<ContributorCalculated>,
   /// Total mass without items (kg)
-  pub total_mass_empty: f64,
+  pub total_mass_empty: Mass,
   /// Total mass when fully filled with items (kg)
-  pub total_mass_filled: f64,
+  pub total_mass_filled: Mass,
   /// Total number of ore that can are stored
   pub total_items_ore: f64,
   /// Total number of ice that can are stored
   pub total_items_ice: f64,
   /// Total number of steel plates that can are stored
   pub total_items_steel_plate: f64,
+  /// Total number of hydrogen bottles that can are stored
+  pub total_items_hydrogen_bottle: f64,
+  /// Total number of oxygen bottles that can are stored
+  pub total_items_oxygen_bottle: f64,
+  /// Number of inventory stacks of steel plates, or None if items are not rounded to stacks or the stack size is unlimited
+  pub total_stacks_steel_plate: Option<f64>,
+  /// Number of inventory stacks of hydrogen bottles, or None if items are not rounded to stacks
+  pub total_stacks_hydrogen_bottle: Option<f64>,
+  /// Number of inventory stacks of oxygen bottles, or None if items are not rounded to stacks
+  pub total_stacks_oxygen_bottle: Option<f64>,
+  /// Number of items per item type, including items that override the any fills
+  pub items: Vec<ItemCalculated>,
 
   /// Thruster force (N) and acceleration (m/s^2)
   pub thruster_acceleration: PerDirection<ThrusterAccelerationCalculated>,
+  /// Mass of thrusters per direction they thrust in (kg)
+  pub thruster_mass: PerDirection<Mass>,
   /// Wheel force (N)
-  pub wheel_force: f64,
+  pub wheel_force: Force,
+  /// Thruster and wheel forces
+  pub forces: ForcesCalculated,
+  /// Hovering at a pitch when empty
+  pub pitched_hover_empty: PitchedHoverCalculated,
+  /// Hovering at a pitch when filled
+  pub pitched_hover_filled: PitchedHoverCalculated,
 
   /// Total power generation (MW)
-  pub power_generation: f64,
+  pub power_generation: Power,
+  /// Maximum power consumption of non-hydrogen thrusters, per direction (MW)
+  pub power_thruster_consumption: PerDirection<Power>,
+  /// Maximum power consumption of ion thrusters, per direction (MW)
+  pub ion_thruster_consumption: PerDirection<Power>,
+  /// Maximum power consumption of atmospheric thrusters, per direction (MW)
+  pub atmospheric_thruster_consumption: PerDirection<Power>,
   /// Idle power calculation
   pub power_idle: PowerCalculated,
   /// Railgun (charging) power calculation
@@ -658,6 +1757,21 @@ pub struct GridCalculated {
   pub power_upto_left_right_thruster: PowerCalculated,
   /// + Battery (charging) power calculation
   pub power_upto_battery_charge: PowerCalculated,
+  /// Thruster group of the direction pair with the highest power consumption, or None if no thrusters consume power.
+  pub power_worst_thruster_group: Option<PowerChainGroup>,
+  /// Power calculation of all non-thruster groups upto O2/H2 generators plus only the direction pair of thrusters with
+  /// the highest power consumption, which is the binding constraint when thrusting in one direction at a time.
+  pub power_worst_thruster: PowerCalculated,
+  /// First group in the power chain that goes unpowered because the total consumption upto and including that group
+  /// exceeds the power generation (including battery output), or None if all groups are powered. Charging batteries
+  /// is never unpowered, as batteries only charge from surplus power.
+  pub first_unpowered_group: Option<PowerChainGroup>,
+  /// First group in the power chain whose budget is negative, because the total consumption upto and including that
+  /// group plus the safety margin exceeds the power generation, or None if all groups are within budget.
+  pub first_over_budget_group: Option<PowerChainGroup>,
+  /// Fraction 0-1 of the total power consumption upto charging batteries per group in the power chain. Idle
+  /// consumption is not included, as it is replaced by the consumption of groups when they are active.
+  pub power_shares: Vec<(PowerChainGroup, f64)>,
 
   /// Railgun calculation, or None if there are no railguns.
   pub railgun: Option<RailgunCalculated>,
@@ -665,11 +1779,25 @@ pub struct GridCalculated {
   pub jump_drive: Option<JumpDriveCalculated>,
   /// Battery calculation, or None if there are no batteries.
   pub battery: Option<BatteryCalculated>,
+  /// Reactor calculation, or None if there are no reactors.
+  pub reactor: Option<ReactorCalculated>,
 
   /// Total hydrogen generation (L/s)
   pub hydrogen_generation: f64,
+  /// Total hydrogen stored in hydrogen bottles, as reserve capacity that refills hydrogen tanks (L)
+  pub hydrogen_bottle_capacity: Volume,
+  /// Total oxygen stored in oxygen bottles, as reserve capacity that refills oxygen tanks (L)
+  pub oxygen_bottle_capacity: Volume,
+  /// Total oxygen generation (L/s)
+  pub oxygen_generation: f64,
   /// Idle hydrogen calculation
   pub hydrogen_idle: HydrogenCalculated,
+  /// Idle hydrogen consumption of hydrogen thrusters, per direction (L/s)
+  pub hydrogen_thruster_idle: PerDirection<f64>,
+  /// Maximum hydrogen consumption of hydrogen thrusters, per direction (L/s)
+  pub hydrogen_thruster_consumption: PerDirection<f64>,
+  /// Force of hydrogen thrusters, per direction (N)
+  pub hydrogen_thruster_force: PerDirection<Force>,
   /// + Engine (filling) hydrogen calculation
   pub hydrogen_engine_fill: HydrogenCalculated,
   /// + Up/down thruster hydrogen calculation
@@ -680,35 +1808,202 @@ pub struct GridCalculated {
   pub hydrogen_upto_left_right_thruster: HydrogenCalculated,
   /// + Tank (filling) hydrogen calculation
   pub hydrogen_upto_tank_fill: HydrogenCalculated,
+  /// Thruster group of the direction pair with the highest hydrogen consumption, or None if no thrusters consume
+  /// hydrogen.
+  pub hydrogen_worst_thruster_group: Option<HydrogenChainGroup>,
+  /// Hydrogen calculation of filling engines plus only the direction pair of thrusters with the highest hydrogen
+  /// consumption, which is the binding constraint when thrusting in one direction at a time.
+  pub hydrogen_worst_thruster: HydrogenCalculated,
+  /// Fraction 0-1 of the total hydrogen consumption upto filling tanks per group in the hydrogen chain. Idle
+  /// consumption is not included, as it is replaced by the consumption of groups when they are active.
+  pub hydrogen_shares: Vec<(HydrogenChainGroup, f64)>,
+
+  /// Generators required to sustain a full burn, or None if there are no generators.
+  pub hydrogen_generator_requirement: Option<HydrogenGeneratorRequirementCalculated>,
 
   /// Hydrogen tank calculation, or None if there are no hydrogen tanks.
   pub hydrogen_tank: Option<HydrogenTankCalculated>,
   /// Hydrogen engine calculation, or None if there are no hydrogen engines.
   pub hydrogen_engine: Option<HydrogenEngineCalculated>,
+  /// Generator calculation, or None if there are no generators.
+  pub generator: Option<GeneratorCalculated>,
+  /// Oxygen calculation, or None if there are no oxygen blocks, no generators producing oxygen, and no oxygen demand.
+  pub oxygen: Option<OxygenCalculated>,
+
+  /// Silent running calculation, or None if there are no batteries.
+  pub silent_running: Option<SilentRunningCalculated>,
+  /// Drilling calculation, or None if there are no drills.
+  pub drilling: Option<DrillingCalculated>,
+  /// Gyroscope calculation, or None if there are no gyroscopes.
+  pub gyroscope: Option<GyroscopeCalculated>,
+  /// Production calculation, or None if there are no refineries or assemblers.
+  pub production: Option<ProductionCalculated>,
+  /// Armament calculation, or None if there are no weapons.
+  pub armament: Option<ArmamentCalculated>,
+  /// Fuel cost calculation, or None if there are no batteries and no jump drives.
+  pub fuel_cost: Option<FuelCostCalculated>,
+  /// Mining calculation, or None if both the mining rate and the ore rate of drills are 0, or if there is no inventory
+  /// volume for ore.
+  pub mining: Option<MiningCalculated>,
+  /// Descending and braking with up thrusters when filled and inside of gravity, or None if the grid has no mass when
+  /// filled
+  pub descent: Option<DescentCalculated>,
+  /// Results of the main grid and each subgrid on their own, or empty if there are no subgrids.
+  pub grid_breakdown: Vec<GridBreakdownCalculated>,
+}
+
+impl GridCalculated {
+  /// Gets the power calculation upto and including `group` in the power chain.
+  pub fn power_upto(&self, group: PowerChainGroup) -> &PowerCalculated {
+    use PowerChainGroup::*;
+    match group {
+      RailgunCharge => &self.power_railgun_charge,
+      Utility => &self.power_upto_utility,
+      WheelSuspension => &self.power_upto_wheel_suspension,
+      JumpDriveCharge => &self.power_upto_jump_drive_charge,
+      Generator => &self.power_upto_generator,
+      UpDownThruster => &self.power_upto_up_down_thruster,
+      FrontBackThruster => &self.power_upto_front_back_thruster,
+      LeftRightThruster => &self.power_upto_left_right_thruster,
+      BatteryCharge => &self.power_upto_battery_charge,
+    }
+  }
+
+  /// Gets the hydrogen calculation upto and including `group` in the hydrogen chain.
+  pub fn hydrogen_upto(&self, group: HydrogenChainGroup) -> &HydrogenCalculated {
+    use HydrogenChainGroup::*;
+    match group {
+      EngineFill => &self.hydrogen_engine_fill,
+      UpDownThruster => &self.hydrogen_upto_up_down_thruster,
+      FrontBackThruster => &self.hydrogen_upto_front_back_thruster,
+      LeftRightThruster => &self.hydrogen_upto_left_right_thruster,
+      TankFill => &self.hydrogen_upto_tank_fill,
+    }
+  }
+
+  /// Calculates the thrust required to hover a grid of `mass` (kg) that is pitched `pitch` degrees (positive is
+  /// nose-down) relative to the horizon, in `gravity_multiplier` g. Gravity is decomposed into a component along the
+  /// down axis of the grid, which must be countered by up thrusters, and a component along the front (nose-down) or
+  /// back (nose-up) axis, which must be countered by back or front thrusters respectively.
+  pub fn pitched_hover(&self, pitch: f64, gravity_multiplier: f64, external_lift_force: f64, mass: f64) -> PitchedHoverCalculated {
+    let pitch = pitch.clamp(-90.0, 90.0).to_radians();
+    let weight = (mass * 9.81 * gravity_multiplier - external_lift_force).max(0.0); // Lift exceeding weight needs no thrust to hover.
+    let required_up_force = weight * pitch.cos();
+    let required_horizontal_force = weight * pitch.sin().abs();
+    let horizontal_direction = if pitch >= 0.0 { Direction::Back } else { Direction::Front };
+    let utilization = |required: f64, direction: Direction| {
+      let available = self.thruster_acceleration[direction].force.to_newtons();
+      (available != 0.0).then(|| required / available)
+    };
+    let up_utilization = utilization(required_up_force, Direction::Up);
+    let horizontal_utilization = utilization(required_horizontal_force, horizontal_direction);
+    let can_counter = |required: f64, utilization: Option<f64>| required <= 0.0 || utilization.is_some_and(|u| u <= 1.0);
+    let can_hover = can_counter(required_up_force, up_utilization) && can_counter(required_horizontal_force, horizontal_utilization);
+    PitchedHoverCalculated {
+      required_up_force: Force::from_newtons(required_up_force),
+      required_horizontal_force: Force::from_newtons(required_horizontal_force),
+      horizontal_direction,
+      up_utilization,
+      horizontal_utilization,
+      can_hover,
+    }
+  }
 }
 
-#[derive(Default, Copy, Clone)]
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct PitchedHoverCalculated {
+  /// Force required from up thrusters (N)
+  pub required_up_force: Force,
+  /// Force required from `horizontal_direction` thrusters (N)
+  pub required_horizontal_force: Force,
+  /// Direction of thrusters countering the horizontal component of gravity: back when nose-down, front when nose-up.
+  pub horizontal_direction: Direction,
+  /// Fraction of up thruster force required, or None if there is no up thruster force.
+  pub up_utilization: Option<f64>,
+  /// Fraction of `horizontal_direction` thruster force required, or None if there is no such thruster force.
+  pub horizontal_utilization: Option<f64>,
+  /// Whether the thrusters can hover the grid at this pitch.
+  pub can_hover: bool,
+}
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
 pub struct ThrusterAccelerationCalculated {
   /// Force (N)
-  pub force: f64,
-  /// Acceleration when empty and outside of gravity (m/s^2)
+  pub force: Force,
+  /// Force at full effectiveness and thruster power (N)
+  pub nominal_force: Force,
+  /// Acceleration when empty and outside of gravity (m/s^2), or None if the grid has no mass when empty
   pub acceleration_empty_no_gravity: Option<f64>,
-  /// Acceleration when empty and inside of gravity (m/s^2)
+  /// Acceleration when empty and inside of gravity (m/s^2), or None if the grid has no mass when empty
   pub acceleration_empty_gravity: Option<f64>,
-  /// Acceleration when filled and outside of gravity (m/s^2)
+  /// Acceleration when filled and outside of gravity (m/s^2), or None if the grid has no mass when filled
   pub acceleration_filled_no_gravity: Option<f64>,
-  /// Acceleration when filled and outside of gravity (m/s^2)
+  /// Acceleration when filled and inside of gravity (m/s^2), or None if the grid has no mass when filled
   pub acceleration_filled_gravity: Option<f64>,
+  /// Braking when empty and outside of gravity, or None if the grid has no mass when empty or cannot decelerate
+  pub braking_empty_no_gravity: Option<BrakingCalculated>,
+  /// Braking when empty and inside of gravity, or None if the grid has no mass when empty or cannot decelerate
+  pub braking_empty_gravity: Option<BrakingCalculated>,
+  /// Braking when filled and outside of gravity, or None if the grid has no mass when filled or cannot decelerate
+  pub braking_filled_no_gravity: Option<BrakingCalculated>,
+  /// Braking when filled and inside of gravity, or None if the grid has no mass when filled or cannot decelerate
+  pub braking_filled_gravity: Option<BrakingCalculated>,
+}
+
+impl ThrusterAccelerationCalculated {
+  /// Calculates braking from `speed` (m/s) to a stop, decelerating with the accelerations of these thrusters. Inside of
+  /// gravity, gravity works against the thrusters, as when braking a descent with up thrusters.
+  fn calculate_braking(&mut self, speed: f64) {
+    let braking = |acceleration: Option<f64>| acceleration.and_then(|a| BrakingCalculated::new(speed, a));
+    self.braking_empty_no_gravity = braking(self.acceleration_empty_no_gravity);
+    self.braking_empty_gravity = braking(self.acceleration_empty_gravity);
+    self.braking_filled_no_gravity = braking(self.acceleration_filled_no_gravity);
+    self.braking_filled_gravity = braking(self.acceleration_filled_gravity);
+  }
+}
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct BrakingCalculated {
+  /// Distance travelled until stopped (m)
+  pub distance: f64,
+  /// Time until stopped
+  pub time: Duration,
+}
+
+impl BrakingCalculated {
+  /// Creates braking from `speed` (m/s) to a stop at a constant `deceleration` (m/s^2), or None if `deceleration` is
+  /// not positive.
+  pub fn new(speed: f64, deceleration: f64) -> Option<Self> {
+    (deceleration > 0.0).then(|| Self { distance: speed * speed / (2.0 * deceleration), time: Duration::from_seconds(speed / deceleration) })
+  }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ContributorCalculated {
+  /// Block type
+  pub id: BlockId,
+  /// Direction of the blocks for directional blocks such as thrusters, or None if the contribution is not attributed to
+  /// a direction.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub direction: Option<Direction>,
+  /// Contributed value, unit depends on what is contributed to
+  pub value: f64,
 }
 
-#[derive(Default, Copy, Clone)]
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
 pub struct PowerCalculated {
   /// Power consumption of this group (MW)
-  pub consumption: f64,
+  pub consumption: Power,
   /// Total power consumption upto this group (MW)
-  pub total_consumption: f64,
+  pub total_consumption: Power,
   /// Power balance upto this group (+-MW)
-  pub balance: f64,
+  pub balance: Power,
+  /// Power balance upto this group, minus the safety margin of the total consumption (+-MW)
+  pub budget: Power,
+  /// Power consumption upto this group attributed to discharging batteries (MW)
+  pub battery_drain: Power,
+  /// Power consumption upto this group attributed to hydrogen engines (MW)
+  pub engine_drain: Power,
   /// Duration until batteries are empty when discharging (min), or None if there are no batteries
   /// or they are not discharging.
   pub battery_duration: Option<Duration>,
@@ -717,44 +2012,66 @@ pub struct PowerCalculated {
   pub engine_duration: Option<Duration>,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct RailgunCalculated {
   /// Total power capacity in railguns (MWh)
-  pub capacity: f64,
+  pub capacity: Energy,
   /// Maximum power input (MW)
-  pub maximum_input: f64,
+  pub maximum_input: Power,
   /// Duration until railguns are full when charging (min), or None if railguns are not charging.
   pub charge_duration: Option<Duration>,
+  /// Surplus power left after charging railguns at their maximum input, which railguns cannot absorb (MW), or None if
+  /// railguns are not charging. Consumers later in the power chain may still use it.
+  pub wasted_surplus: Option<Power>,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct JumpDriveCalculated {
   /// Total power capacity in jump drives (MWh)
-  pub capacity: f64,
+  pub capacity: Energy,
   /// Maximum power input (MW)
-  pub maximum_input: f64,
+  pub maximum_input: Power,
   /// Duration until jump drives are full when charging (min), or None if jump drives are not 
   /// charging.
   pub charge_duration: Option<Duration>,
-  /// Maximum jump distance when empty (km)
-  pub max_distance_empty: f64,
-  /// Maximum jump distance when filled (km)
-  pub max_distance_filled: f64,
+  /// Surplus power left after charging jump drives at their maximum input, which jump drives cannot absorb (MW), or
+  /// None if jump drives are not charging. Consumers later in the power chain may still use it.
+  pub wasted_surplus: Option<Power>,
+  /// Minimum battery fill needed to fully charge jump drives without batteries running empty 0-100%, where 0 means
+  /// batteries are not drained, and >100 means batteries cannot provide enough power. None if jump drives are not
+  /// charging, if jump drives receive no power, or if there are no batteries or they are not discharging. Consumption
+  /// and generation do not change while jump drives charge, so batteries are drained at a constant rate.
+  pub minimum_battery_fill: Option<f64>,
+  /// Maximum jump distance when empty (km), or None if the grid has no mass when empty.
+  pub max_distance_empty: Option<f64>,
+  /// Maximum jump distance when filled (km), or None if the grid has no mass when filled.
+  pub max_distance_filled: Option<f64>,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct BatteryCalculated {
   /// Total power capacity in batteries (MWh)
-  pub capacity: f64,
+  pub capacity: Energy,
   /// Maximum power input (MW)
-  pub maximum_input: f64,
+  pub maximum_input: Power,
   /// Maximum power output (MW)
-  pub maximum_output: f64,
+  pub maximum_output: Power,
   /// Duration until batteries are full when charging (min), or None if batteries are not charging.
   pub charge_duration: Option<Duration>,
+  /// Surplus power left after charging batteries at their maximum input, which batteries cannot absorb (MW), or None
+  /// if batteries are not charging. Batteries are last in the power chain, so this surplus is wasted.
+  pub wasted_surplus: Option<Power>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct ReactorCalculated {
+  /// Maximum power output (MW)
+  pub maximum_output: Power,
+  /// Maximum uranium ingot consumption (#/s)
+  pub maximum_fuel_consumption: f64,
 }
 
-#[derive(Default, Copy, Clone)]
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
 pub struct HydrogenCalculated {
   /// Hydrogen consumption of this group (L/s)
   pub consumption: f64,
@@ -764,33 +2081,139 @@ pub struct HydrogenCalculated {
   pub balance_without_tank: f64,
   /// Hydrogen balance upto this group, with hydrogen provided by tanks (+-L/s)
   pub balance_with_tank: f64,
-  /// Duration until hydrogen tanks are empty when discharging (min), or None if there are no 
-  /// hydrogen tanks or they are stockpiling.
+  /// Hydrogen balance upto this group with hydrogen provided by tanks, minus the safety margin of the total
+  /// consumption (+-L/s)
+  pub budget: f64,
+  /// Duration until hydrogen tanks, and the hydrogen bottles that refill them, are empty when discharging (min), or
+  /// None if there are no hydrogen tanks or they are stockpiling. Infinite when generators cover all consumption.
   pub tank_duration: Option<Duration>,
 }
 
-#[derive(Default)]
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct HydrogenGeneratorRequirementCalculated {
+  /// Hydrogen consumption of filling engines and the worst direction pair of thrusters at full burn, plus the safety
+  /// margin (L/s)
+  pub consumption: f64,
+  /// Number of generators needed to generate the consumed hydrogen
+  pub generator_count: u64,
+  /// Number of generators needed in addition to the generators on the grid
+  pub additional_generator_count: u64,
+  /// Ice consumption of the needed generators (#/s)
+  pub ice_consumption: f64,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct HydrogenTankCalculated {
   /// Total hydrogen capacity in hydrogen tanks (L)
-  pub capacity: f64,
+  pub capacity: Volume,
   /// Maximum hydrogen input (L/s)
   pub maximum_input: f64,
   /// Maximum hydrogen output (L/s)
   pub maximum_output: f64,
-  /// Duration until hydrogen tanks are full(min), or None if hydrogen tanks are disabled.
+  /// Duration until hydrogen tanks are full (min), or None if hydrogen tanks are disabled. Infinite
+  /// when there is no hydrogen left over after all other consumers to fill the tanks with.
   pub fill_duration: Option<Duration>,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct HydrogenEngineCalculated {
   /// Total hydrogen capacity in hydrogen engines (L)
-  pub capacity: f64,
+  pub capacity: Volume,
   /// Maximum fuel consumption (L/s)
   pub maximum_fuel_consumption: f64,
   /// Maximum power output (MW)
-  pub maximum_output: f64,
+  pub maximum_output: Power,
   /// Maximum hydrogen input when refilling (L/s)
   pub maximum_refilling_input: f64,
   /// Duration until hydrogen engines are full (min), or None if hydrogen engines are disabled.
   pub fill_duration: Option<Duration>,
-}
\ No newline at end of file
+}
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct MiningCalculated {
+  /// Duration until inventories are full of ore, starting empty (min)
+  pub fill_duration: Duration,
+  /// Average power consumption while mining and hovering, including idle consumption (MW)
+  pub power_consumption: Power,
+  /// Duration until power runs out while mining (min), or None if power generation without batteries covers
+  /// consumption. Zero if batteries cannot cover the deficit.
+  pub power_duration: Option<Duration>,
+  /// Average hydrogen consumption while mining and hovering, including idle consumption (L/s)
+  pub hydrogen_consumption: f64,
+  /// Duration until hydrogen runs out while mining (min), or None if generators cover consumption. Zero if tanks
+  /// cannot cover the deficit.
+  pub hydrogen_duration: Option<Duration>,
+  /// Mass when inventories are full of ore (kg)
+  pub mass_full: Mass,
+  /// Upwards acceleration in gravity when inventories are full of ore (m/s^2), or None if the grid has no mass when
+  /// full of ore.
+  pub ascent_acceleration_full: Option<f64>,
+}
+
+impl MiningCalculated {
+  /// Whether power lasts until inventories are full.
+  pub fn power_lasts(&self) -> bool { self.power_duration.is_none_or(|d| d >= self.fill_duration) }
+  /// Whether hydrogen lasts until inventories are full.
+  pub fn hydrogen_lasts(&self) -> bool { self.hydrogen_duration.is_none_or(|d| d >= self.fill_duration) }
+}
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct DescentCalculated {
+  /// Up thruster force available for braking, limited by the power and hydrogen left for up thrusters (N)
+  pub braking_force: Force,
+  /// Deceleration when braking (m/s^2), or None if the braking force cannot overcome gravity
+  pub deceleration: Option<f64>,
+  /// Maximum descent speed from which the grid can stop before reaching the ground, accounting for falling freely
+  /// during the reaction time (m/s), or None if the braking force cannot overcome gravity. Zero if the grid cannot even
+  /// stop from hovering.
+  pub max_safe_speed: Option<f64>,
+}
+
+impl DescentCalculated {
+  /// Calculates descending with `braking_force` (N) and `mass` (kg), in net `gravity` (m/s^2), starting to brake at
+  /// `altitude` (m) after `reaction_time` (s).
+  pub fn new(braking_force: f64, mass: f64, gravity: f64, altitude: f64, reaction_time: f64) -> Self {
+    let deceleration = braking_force / mass - gravity;
+    if deceleration <= 0.0 {
+      return Self { braking_force: Force::from_newtons(braking_force), deceleration: None, max_safe_speed: None };
+    }
+    // During the reaction time the grid falls freely, reaching speed `u = v + g * t` while covering
+    // `u * t - g * t^2 / 2`. Braking from `u` then covers `u^2 / (2 * a)`. Solving for the `u` where these distances
+    // add up to the altitude gives the maximum safe speed `v`.
+    let t = reaction_time.max(0.0);
+    let a = deceleration;
+    let distance = altitude.max(0.0) + gravity * t * t / 2.0;
+    let u = a * (-t + (t * t + 2.0 * distance / a).sqrt());
+    let max_safe_speed = (u - gravity * t).max(0.0);
+    Self { braking_force: Force::from_newtons(braking_force), deceleration: Some(deceleration), max_safe_speed: Some(max_safe_speed) }
+  }
+}
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct SilentRunningCalculated {
+  /// Duration until batteries are empty at idle (min), or None if there is no consumption or batteries are empty.
+  pub idle: Option<Duration>,
+  /// Duration until batteries are empty at utility load (min), or None if there is no consumption or batteries are
+  /// empty.
+  pub utility: Option<Duration>,
+  /// Duration until batteries are empty at utility load and full thrust in all direction pairs (min), or None if there
+  /// is no consumption or batteries are empty.
+  pub full_thrust: Option<Duration>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct GeneratorCalculated {
+  /// Fraction of time that generators run to cover hydrogen demand, and oxygen demand when oxygen throttles
+  /// generators 0-100%
+  pub utilization: f64,
+  /// Ice consumption (#/s)
+  pub ice_consumption: f64,
+  /// Duration until stored ice runs out (min), or None if generators consume no ice.
+  pub ice_duration: Option<Duration>,
+  /// Hydrogen output (L/s)
+  pub hydrogen_output: f64,
+  /// Oxygen output (L/s)
+  pub oxygen_output: f64,
+  /// Oxygen output minus the oxygen demand that oxygen farms do not cover, where a positive balance is vented (+-L/s)
+  pub oxygen_balance: f64,
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::{AirVent, OxygenFarm, OxygenTank};
+use crate::grid::duration::Duration;
+use crate::grid::units::Volume;
+
+/// Oxygen calculation: oxygen generation of generators and farms against consumption of air vents and other demand.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct OxygenCalculated {
+  /// Oxygen output of O2/H2 generators at their utilization (L/s)
+  pub generator_generation: f64,
+  /// Oxygen generation of oxygen farms in full sunlight (L/s)
+  pub farm_generation: f64,
+  /// Total oxygen generation (L/s)
+  pub generation: f64,
+  /// Oxygen consumption of air vents when pressurizing (L/s)
+  pub air_vent_consumption: f64,
+  /// Oxygen demand of other consumers, such as engineers refilling their suits (L/s)
+  pub demand: f64,
+  /// Total oxygen consumption (L/s)
+  pub consumption: f64,
+  /// Oxygen generation minus consumption, without oxygen provided by tanks (+-L/s)
+  pub balance: f64,
+  /// Total oxygen capacity in oxygen tanks (L)
+  pub tank_capacity: Volume,
+  /// Maximum oxygen output of oxygen tanks (L/s)
+  pub tank_maximum_output: f64,
+  /// Duration until oxygen tanks, and the oxygen bottles that refill them, are empty (min), or None if there are no
+  /// oxygen tanks. Infinite when generation covers consumption.
+  pub tank_duration: Option<Duration>,
+}
+
+impl OxygenCalculated {
+  /// Adds `count` oxygen tanks with `details`.
+  pub(crate) fn add_oxygen_tanks(&mut self, details: &OxygenTank, count: f64) {
+    self.tank_capacity += Volume::from_liters(details.capacity * count);
+    self.tank_maximum_output += details.capacity * count * 0.05; // Tank output is capacity * 0.05 according to MyGasTank.cs
+  }
+
+  /// Adds `count` oxygen farms with `details`.
+  pub(crate) fn add_oxygen_farms(&mut self, details: &OxygenFarm, count: f64) {
+    self.farm_generation += details.oxygen_generation * count;
+  }
+
+  /// Adds `count` air vents with `details`, consuming oxygen if they are `pressurizing`.
+  pub(crate) fn add_air_vents(&mut self, details: &AirVent, count: f64, pressurizing: bool) {
+    if pressurizing {
+      self.air_vent_consumption += details.oxygen_consumption * count;
+    }
+  }
+
+  /// Gets the oxygen demand on O2/H2 generators: the consumption that oxygen farms do not cover (L/s).
+  pub(crate) fn generator_demand(&self) -> f64 {
+    (self.air_vent_consumption + self.demand - self.farm_generation).max(0.0)
+  }
+
+  /// Completes the calculation with the `generator_generation` (L/s) of O2/H2 generators, the `tank_fill` (0-1) of
+  /// oxygen tanks, and the `bottle_capacity` (L) of oxygen bottles that refill the tanks.
+  pub(crate) fn complete(&mut self, generator_generation: f64, tank_fill: f64, bottle_capacity: f64) {
+    self.generator_generation = generator_generation;
+    self.generation = generator_generation + self.farm_generation;
+    self.consumption = self.air_vent_consumption + self.demand;
+    self.balance = self.generation - self.consumption;
+    if self.tank_capacity.to_liters() > 0.0 {
+      // Tanks only provide the oxygen that generation cannot, limited by their maximum output.
+      let tank_drain = (-self.balance).min(self.tank_maximum_output);
+      self.tank_duration = Some(if tank_drain > 0.0 {
+        Duration::from_seconds((self.tank_capacity.to_liters() * tank_fill + bottle_capacity) / tank_drain)
+      } else {
+        Duration::from_seconds(f64::INFINITY)
+      });
+    }
+  }
+}
@@ -0,0 +1,114 @@
+//! Named bundles of calculator options for common scenarios, such as charging while docked or silent running.
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::{BatteryMode, GridCalculator, HydrogenTankMode};
+
+/// Named bundle of calculator options that can be applied to a calculator in one go. Options that are None are left
+/// unchanged when applying the preset.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct OptionPreset {
+  pub name: String,
+  /// Battery mode
+  pub battery_mode: Option<BatteryMode>,
+  /// Fill level of batteries 0-100%
+  pub battery_fill: Option<f64>,
+  /// Hydrogen tanks mode
+  pub hydrogen_tank_mode: Option<HydrogenTankMode>,
+  /// Fill level of hydrogen tanks 0-100%
+  pub hydrogen_tank_fill: Option<f64>,
+  /// Hydrogen engines enabled?
+  pub hydrogen_engine_enabled: Option<bool>,
+  /// Fill level of hydrogen engines 0-100%
+  pub hydrogen_engine_fill: Option<f64>,
+  /// Are railguns charging?
+  pub railgun_charging: Option<bool>,
+  /// Are jump drives charging?
+  pub jump_drive_charging: Option<bool>,
+}
+
+impl OptionPreset {
+  /// Creates a preset named `name` with all options of `calculator` that presets can set.
+  pub fn from_calculator(name: impl Into<String>, calculator: &GridCalculator) -> Self {
+    Self {
+      name: name.into(),
+      battery_mode: Some(calculator.battery_mode),
+      battery_fill: Some(calculator.battery_fill),
+      hydrogen_tank_mode: Some(calculator.hydrogen_tank_mode),
+      hydrogen_tank_fill: Some(calculator.hydrogen_tank_fill),
+      hydrogen_engine_enabled: Some(calculator.hydrogen_engine_enabled),
+      hydrogen_engine_fill: Some(calculator.hydrogen_engine_fill),
+      railgun_charging: Some(calculator.railgun_charging),
+      jump_drive_charging: Some(calculator.jump_drive_charging),
+    }
+  }
+
+  /// Gets the built-in presets.
+  pub fn built_in() -> Vec<Self> {
+    vec![
+      // Docked: batteries and tanks fill up from the station, engines are off to save hydrogen.
+      Self {
+        name: "Docked Charging".to_string(),
+        battery_mode: Some(BatteryMode::Recharge),
+        hydrogen_tank_mode: Some(HydrogenTankMode::Stockpile),
+        hydrogen_engine_enabled: Some(false),
+        railgun_charging: Some(true),
+        jump_drive_charging: Some(true),
+        ..Self::default()
+      },
+      Self {
+        name: "Cruise".to_string(),
+        battery_mode: Some(BatteryMode::Auto),
+        hydrogen_tank_mode: Some(HydrogenTankMode::On),
+        hydrogen_engine_enabled: Some(true),
+        railgun_charging: Some(false),
+        jump_drive_charging: Some(true),
+        ..Self::default()
+      },
+      Self {
+        name: "Combat".to_string(),
+        battery_mode: Some(BatteryMode::Discharge),
+        hydrogen_tank_mode: Some(HydrogenTankMode::On),
+        hydrogen_engine_enabled: Some(true),
+        railgun_charging: Some(true),
+        jump_drive_charging: Some(false),
+        ..Self::default()
+      },
+      // Silent running: batteries are the only power source, and nothing charges.
+      Self {
+        name: "Silent Running".to_string(),
+        battery_mode: Some(BatteryMode::Discharge),
+        hydrogen_tank_mode: Some(HydrogenTankMode::On),
+        hydrogen_engine_enabled: Some(false),
+        railgun_charging: Some(false),
+        jump_drive_charging: Some(false),
+        ..Self::default()
+      },
+    ]
+  }
+
+  /// Sets the options of `calculator` that are set in this preset.
+  pub fn apply(&self, calculator: &mut GridCalculator) {
+    if let Some(battery_mode) = self.battery_mode { calculator.battery_mode = battery_mode; }
+    if let Some(battery_fill) = self.battery_fill { calculator.battery_fill = battery_fill; }
+    if let Some(hydrogen_tank_mode) = self.hydrogen_tank_mode { calculator.hydrogen_tank_mode = hydrogen_tank_mode; }
+    if let Some(hydrogen_tank_fill) = self.hydrogen_tank_fill { calculator.hydrogen_tank_fill = hydrogen_tank_fill; }
+    if let Some(hydrogen_engine_enabled) = self.hydrogen_engine_enabled { calculator.hydrogen_engine_enabled = hydrogen_engine_enabled; }
+    if let Some(hydrogen_engine_fill) = self.hydrogen_engine_fill { calculator.hydrogen_engine_fill = hydrogen_engine_fill; }
+    if let Some(railgun_charging) = self.railgun_charging { calculator.railgun_charging = railgun_charging; }
+    if let Some(jump_drive_charging) = self.jump_drive_charging { calculator.jump_drive_charging = jump_drive_charging; }
+  }
+
+  /// Returns whether all options set in this preset are equal to the options of `calculator`.
+  pub fn is_applied_to(&self, calculator: &GridCalculator) -> bool {
+    self.battery_mode.is_none_or(|v| v == calculator.battery_mode)
+      && self.battery_fill.is_none_or(|v| v == calculator.battery_fill)
+      && self.hydrogen_tank_mode.is_none_or(|v| v == calculator.hydrogen_tank_mode)
+      && self.hydrogen_tank_fill.is_none_or(|v| v == calculator.hydrogen_tank_fill)
+      && self.hydrogen_engine_enabled.is_none_or(|v| v == calculator.hydrogen_engine_enabled)
+      && self.hydrogen_engine_fill.is_none_or(|v| v == calculator.hydrogen_engine_fill)
+      && self.railgun_charging.is_none_or(|v| v == calculator.railgun_charging)
+      && self.jump_drive_charging.is_none_or(|v| v == calculator.jump_drive_charging)
+  }
+}
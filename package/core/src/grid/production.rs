@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::{Assembler, Refinery};
+use crate::grid::duration::Duration;
+
+/// Production calculation: how fast refineries refine ore into ingots, and how fast assemblers assemble.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct ProductionCalculated {
+  /// Number of refineries
+  pub refinery_count: f64,
+  /// Number of assemblers
+  pub assembler_count: f64,
+  /// Rate at which all refineries process ore (kg/s)
+  pub ore_rate: f64,
+  /// Rate at which all refineries produce ingots (kg/s)
+  pub ingot_rate: f64,
+  /// Total assembly speed multiplier of all assemblers
+  pub assembly_speed: f64,
+  /// Maximum power consumption of all refineries and assemblers (MW)
+  pub maximum_input: f64,
+  /// Mass of ore in inventories at the configured fill (kg)
+  pub ore_mass: f64,
+  /// Duration until refineries have processed all ore in inventories (min), or None if the ore rate is 0.
+  pub process_duration: Option<Duration>,
+}
+
+impl ProductionCalculated {
+  /// Adds `count` refineries with `details`, that process `ore_rate` (kg/s) of ore at a refine speed of 1 and produce
+  /// `ingot_yield` (0-1) of the processed ore mass as ingots at a material efficiency of 1.
+  pub(crate) fn add_refineries(&mut self, details: &Refinery, count: f64, ore_rate: f64, ingot_yield: f64) {
+    let refinery_ore_rate = details.refine_speed * ore_rate * count;
+    self.refinery_count += count;
+    self.ore_rate += refinery_ore_rate;
+    self.ingot_rate += refinery_ore_rate * ingot_yield * details.material_efficiency;
+    self.maximum_input += details.operational_power_consumption * count;
+  }
+
+  /// Adds `count` assemblers with `details`.
+  pub(crate) fn add_assemblers(&mut self, details: &Assembler, count: f64) {
+    self.assembler_count += count;
+    self.assembly_speed += details.assembly_speed * count;
+    self.maximum_input += details.operational_power_consumption * count;
+  }
+
+  /// Completes the calculation with `ore_mass`: the mass of ore in all inventories (kg).
+  pub(crate) fn complete(&mut self, ore_mass: f64) {
+    self.ore_mass = ore_mass;
+    self.process_duration = (self.ore_rate > 0.0).then(|| Duration::from_seconds(ore_mass / self.ore_rate));
+  }
+}
@@ -0,0 +1,35 @@
+//! Reference grids approximating vanilla ships, for judging whether the results of a grid are reasonable by comparing
+//! them against ships that are known to work.
+
+use crate::data::blocks::GridSize;
+use crate::grid::GridCalculator;
+
+/// Grid approximating a vanilla ship, with the blocks that the calculator simulates and the mass of other blocks as
+/// additional mass.
+pub struct ReferenceGrid {
+  pub name: &'static str,
+  pub grid_size: GridSize,
+  /// Grid (`GridCalculator` JSON)
+  json: &'static str,
+}
+
+impl ReferenceGrid {
+  /// Gets the calculator of this reference grid.
+  pub fn calculator(&self) -> GridCalculator {
+    GridCalculator::from_json(self.json.as_bytes()).expect("reference grid should be a valid grid")
+  }
+}
+
+/// All reference grids.
+pub const REFERENCE_GRIDS: &[ReferenceGrid] = &[
+  ReferenceGrid {
+    name: "Respawn Pod",
+    grid_size: GridSize::Small,
+    json: include_str!("../../../../data/reference/respawn_pod.json"),
+  },
+  ReferenceGrid {
+    name: "Basic Miner",
+    grid_size: GridSize::Small,
+    json: include_str!("../../../../data/reference/basic_miner.json"),
+  },
+];
@@ -0,0 +1,54 @@
+use crate::grid::{BatteryMode, GridCalculator};
+
+/// Overrides of calculator settings, for calculating alternative scenarios without modifying the calculator. Settings
+/// that are None are taken from the calculator.
+#[derive(Default, Copy, Clone, PartialEq, Debug)]
+pub struct Scenario {
+  /// Whether reactors generate power. Reactors generate power when None, as the calculator has no such setting.
+  pub reactors_enabled: Option<bool>,
+  /// Whether hydrogen engines are enabled
+  pub hydrogen_engine_enabled: Option<bool>,
+  /// Battery mode
+  pub battery_mode: Option<BatteryMode>,
+  /// Whether to charge railguns
+  pub railgun_charging: Option<bool>,
+  /// Whether to charge jump drives
+  pub jump_drive_charging: Option<bool>,
+}
+
+impl Scenario {
+  /// Silent running: reactors and hydrogen engines are off, batteries are discharging, and railguns and jump drives
+  /// are not charging, leaving batteries as the only power source.
+  pub const SILENT_RUNNING: Self = Self {
+    reactors_enabled: Some(false),
+    hydrogen_engine_enabled: Some(false),
+    battery_mode: Some(BatteryMode::Discharge),
+    railgun_charging: Some(false),
+    jump_drive_charging: Some(false),
+  };
+
+  #[inline]
+  pub fn reactors_enabled(&self) -> bool {
+    self.reactors_enabled.unwrap_or(true)
+  }
+
+  #[inline]
+  pub fn hydrogen_engine_enabled(&self, calculator: &GridCalculator) -> bool {
+    self.hydrogen_engine_enabled.unwrap_or(calculator.hydrogen_engine_enabled)
+  }
+
+  #[inline]
+  pub fn battery_mode(&self, calculator: &GridCalculator) -> BatteryMode {
+    self.battery_mode.unwrap_or(calculator.battery_mode)
+  }
+
+  #[inline]
+  pub fn railgun_charging(&self, calculator: &GridCalculator) -> bool {
+    self.railgun_charging.unwrap_or(calculator.railgun_charging)
+  }
+
+  #[inline]
+  pub fn jump_drive_charging(&self, calculator: &GridCalculator) -> bool {
+    self.jump_drive_charging.unwrap_or(calculator.jump_drive_charging)
+  }
+}
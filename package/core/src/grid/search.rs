@@ -0,0 +1,50 @@
+//! Searching saved grids for the blocks they contain.
+
+use crate::data::blocks::BlockId;
+use crate::data::Data;
+use crate::grid::GridCalculator;
+
+/// Returns whether all characters of `query` appear in `text` in the same order, ignoring case and whitespace in
+/// `query`. An empty query matches any text.
+pub fn fuzzy_matches(query: &str, text: &str) -> bool {
+  let mut text = text.chars().flat_map(char::to_lowercase);
+  query.chars()
+    .filter(|c| !c.is_whitespace())
+    .flat_map(char::to_lowercase)
+    .all(|q| text.any(|t| t == q))
+}
+
+/// Grid containing blocks that match a search.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GridSearchMatch<'a> {
+  /// Name of the grid
+  pub grid_name: &'a str,
+  /// Matching blocks with their total count, by descending count.
+  pub blocks: Vec<(&'a BlockId, f64)>,
+}
+
+impl GridSearchMatch<'_> {
+  /// Gets the total count of all matching blocks.
+  #[inline]
+  pub fn count(&self) -> f64 { self.blocks.iter().map(|(_, count)| count).sum() }
+}
+
+/// Searches `grids`, given as (name, calculator) pairs, for blocks with a non-zero count whose ID or name (in `data`)
+/// fuzzy matches `query`. Returns the grids with matching blocks, ordered by name. An empty query matches nothing.
+pub fn search_grids<'a>(grids: impl IntoIterator<Item=(&'a str, &'a GridCalculator)>, data: &Data, query: &str) -> Vec<GridSearchMatch<'a>> {
+  if query.trim().is_empty() { return Vec::new(); }
+  let mut matches: Vec<_> = grids.into_iter()
+    .filter_map(|(grid_name, calculator)| {
+      let mut blocks: Vec<_> = calculator.iter_total_block_counts()
+        .filter(|(id, _)| {
+          fuzzy_matches(query, id) || data.blocks.get_data(id).is_some_and(|b| fuzzy_matches(query, b.name(&data.localization)))
+        })
+        .collect();
+      if blocks.is_empty() { return None; }
+      blocks.sort_by(|(id_a, count_a), (id_b, count_b)| count_b.total_cmp(count_a).then_with(|| id_a.cmp(id_b)));
+      Some(GridSearchMatch { grid_name, blocks })
+    })
+    .collect();
+  matches.sort_by(|a, b| a.grid_name.cmp(b.grid_name));
+  matches
+}
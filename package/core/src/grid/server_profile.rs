@@ -0,0 +1,87 @@
+//! Limits that servers impose on grids, such as a maximum number of jump drives per grid, for validating grids against
+//! the rules of a server.
+
+use std::collections::BTreeMap;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::data::blocks::BlockId;
+use crate::data::Data;
+use crate::grid::GridCalculator;
+use crate::grid::warning::Warning;
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+  #[error("Could not read server profile from JSON at '{path}'")]
+  FromJSONFail { path: String, source: serde_json::Error },
+}
+
+#[derive(Error, Debug)]
+pub enum WriteError {
+  #[error("Could not write server profile to JSON")]
+  ToJSONFail(#[from] serde_json::Error),
+}
+
+/// Limits of a server on grids. Serializable to JSON for sharing profiles between players.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ServerProfile {
+  pub name: String,
+  /// Maximum count per block type in a grid, summed over all directions
+  pub block_limits: BTreeMap<BlockId, u64>,
+  /// Maximum PCU of a grid, or None if unlimited
+  pub max_pcu: Option<u64>,
+  /// Maximum number of blocks in a grid, or None if unlimited
+  pub max_block_count: Option<u64>,
+  /// Welder speed multiplier of the world, or None for the default of 1
+  pub welder_speed_multiplier: Option<f64>,
+}
+
+impl ServerProfile {
+  pub fn from_json<R: io::Read>(reader: R) -> Result<Self, ReadError> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let profile = serde_path_to_error::deserialize(&mut deserializer)
+      .map_err(|e| ReadError::FromJSONFail { path: e.path().to_string(), source: e.into_inner() })?;
+    Ok(profile)
+  }
+
+  pub fn to_json<W: io::Write>(&self, writer: W) -> Result<(), WriteError> {
+    serde_json::to_writer_pretty(writer, self)?;
+    Ok(())
+  }
+
+  /// Gets the welder speed multiplier of the world.
+  #[inline]
+  pub fn welder_speed_multiplier(&self) -> f64 { self.welder_speed_multiplier.unwrap_or(1.0) }
+
+  /// Checks `calculator` against the limits of this profile, returning a warning for each exceeded limit. PCU is not
+  /// checked when the PCU of a block in `data` is unknown.
+  pub fn check(&self, calculator: &GridCalculator, data: &Data) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let mut counts: BTreeMap<&BlockId, f64> = BTreeMap::new();
+    for (id, count) in calculator.iter_total_block_counts() {
+      *counts.entry(id).or_default() += count;
+    }
+    for (id, limit) in &self.block_limits {
+      let count = counts.get(id).copied().unwrap_or(0.0);
+      if count > *limit as f64 {
+        warnings.push(Warning::BlockLimitExceeded { id: id.clone(), count, limit: *limit });
+      }
+    }
+    if let Some(limit) = self.max_block_count {
+      let count = counts.values().sum();
+      if count > limit as f64 {
+        warnings.push(Warning::BlockCountLimitExceeded { count, limit });
+      }
+    }
+    if let (Some(limit), Some(pcu)) = (self.max_pcu, calculator.pcu(data)) {
+      if pcu > limit {
+        warnings.push(Warning::PcuLimitExceeded { pcu, limit });
+      }
+    }
+    warnings
+  }
+}
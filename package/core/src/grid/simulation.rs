@@ -0,0 +1,192 @@
+//! Time-domain simulation of power and hydrogen: steps the power and hydrogen balance over time with a thruster usage
+//! profile, tracking how batteries, hydrogen tanks, and hydrogen engines charge and drain. Static balances only show
+//! whether a grid can sustain a situation indefinitely, while simulating shows whether it lasts long enough, for
+//! example to reach orbit.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::Data;
+use crate::grid::{CHARGE_EFFICIENCY, GridCalculator};
+use crate::grid::direction::{Direction, PerDirection};
+
+/// Phase of a thruster usage profile, using thrusters at `thrust` for `duration`.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct ThrustPhase {
+  /// Duration of this phase (s)
+  pub duration: f64,
+  /// Thrust per direction 0-100%, relative to the maximum consumption of thrusters in that direction
+  pub thrust: PerDirection<f64>,
+}
+
+impl ThrustPhase {
+  /// Creates a phase using thrusters at `thrust` 0-100% in `direction` for `duration` (s), and not in other directions.
+  pub fn new(duration: f64, direction: Direction, thrust: f64) -> Self {
+    let mut phase = Self { duration, thrust: PerDirection::default() };
+    phase.thrust[direction] = thrust;
+    phase
+  }
+}
+
+/// Options of a simulation.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct SimulationOptions {
+  /// Duration of a single step (s)
+  pub time_step: f64,
+  /// Thruster usage profile, in order. The simulation ends after the last phase.
+  pub phases: Vec<ThrustPhase>,
+}
+
+impl Default for SimulationOptions {
+  fn default() -> Self {
+    Self { time_step: 1.0, phases: Vec::new() }
+  }
+}
+
+/// State of a grid at a point in time of a simulation.
+#[derive(Default, Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct SimulationSample {
+  /// Time since the start of the simulation (s)
+  pub time: f64,
+  /// Charge of batteries 0-100%, or None if there are no batteries.
+  pub battery_charge: Option<f64>,
+  /// Fill of hydrogen tanks 0-100%, or None if there are no hydrogen tanks.
+  pub hydrogen_tank_fill: Option<f64>,
+  /// Fill of hydrogen engines 0-100%, or None if there are no hydrogen engines.
+  pub hydrogen_engine_fill: Option<f64>,
+  /// Power consumption that could not be supplied in the step ending at this time (MW)
+  pub power_shortage: f64,
+  /// Hydrogen consumption of thrusters that could not be supplied in the step ending at this time (L/s)
+  pub hydrogen_shortage: f64,
+}
+
+/// Result of a simulation.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct SimulationResult {
+  /// Samples at the start of the simulation, and after each step
+  pub samples: Vec<SimulationSample>,
+}
+
+impl SimulationResult {
+  /// Gets the time at the end of the first step with a power shortage (s), or None if power never runs short.
+  pub fn first_power_shortage(&self) -> Option<f64> {
+    self.samples.iter().find(|s| s.power_shortage > SHORTAGE_EPSILON).map(|s| s.time)
+  }
+
+  /// Gets the time at the end of the first step with a hydrogen shortage (s), or None if hydrogen never runs short.
+  pub fn first_hydrogen_shortage(&self) -> Option<f64> {
+    self.samples.iter().find(|s| s.hydrogen_shortage > SHORTAGE_EPSILON).map(|s| s.time)
+  }
+}
+
+/// Shortages at or below this value are rounding errors.
+const SHORTAGE_EPSILON: f64 = 1e-9;
+
+impl GridCalculator {
+  /// Simulates the power and hydrogen of this grid over time, with thrusters used according to `options`, starting at
+  /// the fill levels of this calculator.
+  ///
+  /// Each step, non-thruster consumers use power as in the static calculation up to and including generators, and
+  /// thrusters use power and hydrogen in proportion to their thrust. Power generation other than batteries and hydrogen
+  /// engines covers consumption first, and batteries and engines cover the deficit according to the power source
+  /// priority, or are charged with the surplus. Hydrogen generation refills engines first, then supplies thrusters,
+  /// with tanks covering the deficit or being filled with the surplus according to the hydrogen tank mode.
+  pub fn simulate(&self, data: &Data, options: &SimulationOptions) -> SimulationResult {
+    let calculated = self.calculate(data);
+    let battery = calculated.battery.clone().unwrap_or_default();
+    let tank = calculated.hydrogen_tank.clone().unwrap_or_default();
+    let engine = calculated.hydrogen_engine.clone().unwrap_or_default();
+    let battery_maximum_input = battery.maximum_input.to_megawatts();
+    let battery_maximum_output = battery.maximum_output.to_megawatts();
+    let tank_capacity = tank.capacity.to_liters();
+    let engine_capacity = engine.capacity.to_liters();
+    let engine_maximum_output = engine.maximum_output.to_megawatts();
+    let battery_charging = self.battery_mode.is_charging();
+    let battery_discharging = self.battery_mode.is_discharging();
+    let engine_enabled = self.hydrogen_engine_enabled;
+    let base_generation = calculated.power_generation.to_megawatts()
+      - if battery_discharging { battery_maximum_output } else { 0.0 }
+      - if engine_enabled { engine_maximum_output } else { 0.0 };
+    let base_consumption = calculated.power_upto_generator.total_consumption.to_megawatts();
+
+    let mut battery_energy = battery.capacity.to_megawatt_hours() * (self.battery_fill / 100.0);
+    let mut tank_volume = tank_capacity * (self.hydrogen_tank_fill / 100.0);
+    let mut engine_fuel = engine_capacity * (self.hydrogen_engine_fill / 100.0);
+    let sample = |time: f64, battery_energy: f64, tank_volume: f64, engine_fuel: f64, power_shortage: f64, hydrogen_shortage: f64| SimulationSample {
+      time,
+      battery_charge: calculated.battery.as_ref().map(|_| percentage(battery_energy, battery.capacity.to_megawatt_hours())),
+      hydrogen_tank_fill: calculated.hydrogen_tank.as_ref().map(|_| percentage(tank_volume, tank_capacity)),
+      hydrogen_engine_fill: calculated.hydrogen_engine.as_ref().map(|_| percentage(engine_fuel, engine_capacity)),
+      power_shortage,
+      hydrogen_shortage,
+    };
+
+    let mut samples = vec![sample(0.0, battery_energy, tank_volume, engine_fuel, 0.0, 0.0)];
+    if options.time_step <= 0.0 { return SimulationResult { samples }; }
+    let mut time = 0.0;
+    for phase in &options.phases {
+      let phase_end = time + phase.duration.max(0.0);
+      let thrust = phase.thrust.map(|t| t.clamp(0.0, 100.0) / 100.0);
+      while phase_end - time > SHORTAGE_EPSILON {
+        let dt = options.time_step.min(phase_end - time);
+        let hours = dt / 3600.0;
+
+        // Power
+        let thruster_consumption: f64 = Direction::items().into_iter()
+          .map(|d| calculated.power_thruster_consumption[d].to_megawatts() * thrust[d])
+          .sum();
+        let deficit = base_consumption + thruster_consumption - base_generation;
+        let battery_output = if battery_discharging { battery_maximum_output.min(battery_energy / hours) } else { 0.0 };
+        let engine_output = if engine_enabled && engine.maximum_fuel_consumption > 0.0 {
+          engine_maximum_output * (engine_fuel / (engine.maximum_fuel_consumption * dt)).min(1.0)
+        } else {
+          0.0
+        };
+        let (battery_drain, engine_drain) = self.power_source_priority.attribute(deficit.max(0.0), battery_output, engine_output);
+        let power_shortage = (deficit - battery_drain - engine_drain).max(0.0);
+        battery_energy -= battery_drain * hours;
+        if engine_maximum_output > 0.0 {
+          engine_fuel -= (engine_drain / engine_maximum_output) * engine.maximum_fuel_consumption * dt;
+        }
+        if deficit < 0.0 && battery_charging {
+          let input = (-deficit).min(battery_maximum_input).min((battery.capacity.to_megawatt_hours() - battery_energy).max(0.0) / (hours * CHARGE_EFFICIENCY));
+          battery_energy += input * CHARGE_EFFICIENCY * hours;
+        }
+
+        // Hydrogen
+        let thruster_consumption: f64 = Direction::items().into_iter()
+          .map(|d| {
+            let idle = calculated.hydrogen_thruster_idle[d];
+            idle + (calculated.hydrogen_thruster_consumption[d] - idle).max(0.0) * thrust[d]
+          })
+          .sum();
+        let tank_output = if self.hydrogen_tank_mode.is_providing() { tank.maximum_output.min(tank_volume / dt) } else { 0.0 };
+        let mut available = calculated.hydrogen_generation + tank_output;
+        let engine_refill = if engine_enabled {
+          engine.maximum_refilling_input.min((engine_capacity - engine_fuel).max(0.0) / dt).min(available)
+        } else {
+          0.0
+        };
+        engine_fuel += engine_refill * dt;
+        available -= engine_refill;
+        let hydrogen_shortage = (thruster_consumption - available).max(0.0);
+        let surplus = calculated.hydrogen_generation - engine_refill - thruster_consumption;
+        if surplus < 0.0 {
+          tank_volume -= (-surplus).min(tank_output) * dt;
+        } else if self.hydrogen_tank_mode.is_refilling() {
+          tank_volume += surplus.min(tank.maximum_input).min((tank_capacity - tank_volume).max(0.0) / dt) * dt;
+        }
+
+        time += dt;
+        samples.push(sample(time, battery_energy, tank_volume, engine_fuel, power_shortage, hydrogen_shortage));
+      }
+    }
+    SimulationResult { samples }
+  }
+}
+
+#[inline]
+fn percentage(amount: f64, capacity: f64) -> f64 {
+  if capacity > 0.0 { (amount / capacity * 100.0).clamp(0.0, 100.0) } else { 0.0 }
+}
@@ -0,0 +1,142 @@
+//! Snapshots of the results of many grids, for detecting when a data or formula change alters established builds.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::data::Data;
+use crate::grid::GridCalculator;
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+  #[error("Could not read snapshot from JSON at '{path}'")]
+  FromJSONFail { path: String, source: serde_json::Error },
+}
+
+#[derive(Error, Debug)]
+pub enum WriteError {
+  #[error("Could not write snapshot to JSON")]
+  ToJSONFail(#[from] serde_json::Error),
+}
+
+/// Results of grids by name. Deterministic: grids are sorted by name, and the calculation stamp is left out of results,
+/// so that calculating the same grids with the same data and formulas gives the same snapshot.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Snapshot {
+  /// Version of the game data the snapshot was calculated with, or None if unknown.
+  pub data_version: Option<String>,
+  /// Results (`GridCalculated` JSON) by grid name.
+  pub grids: BTreeMap<String, Value>,
+}
+
+impl Snapshot {
+  /// Calculates all `grids`, given as pairs of name and calculator, with `data`.
+  pub fn calculate<'a>(grids: impl IntoIterator<Item=(String, &'a GridCalculator)>, data: &Data) -> Self {
+    let grids = grids.into_iter()
+      .map(|(name, calculator)| {
+        let mut value = serde_json::to_value(calculator.calculate(data)).unwrap_or_default();
+        if let Value::Object(map) = &mut value {
+          map.remove("stamp");
+        }
+        (name, value)
+      })
+      .collect();
+    Self { data_version: data.version.clone(), grids }
+  }
+
+  /// Compares `actual` against this snapshot, returning all differences in grid name and result order. Numbers differing
+  /// by a relative `tolerance` or less are considered equal.
+  pub fn diff(&self, actual: &Snapshot, tolerance: f64) -> Vec<SnapshotDifference> {
+    let mut differences = Vec::new();
+    for (name, expected) in &self.grids {
+      match actual.grids.get(name) {
+        Some(actual) => diff_values(name, String::new(), expected, actual, tolerance, &mut differences),
+        None => differences.push(SnapshotDifference::new(name, String::new(), Some(expected), None)),
+      }
+    }
+    for (name, actual) in &actual.grids {
+      if !self.grids.contains_key(name) {
+        differences.push(SnapshotDifference::new(name, String::new(), None, Some(actual)));
+      }
+    }
+    differences
+  }
+
+  pub fn from_json<R: io::Read>(reader: R) -> Result<Self, ReadError> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let snapshot = serde_path_to_error::deserialize(&mut deserializer)
+      .map_err(|e| ReadError::FromJSONFail { path: e.path().to_string(), source: e.into_inner() })?;
+    Ok(snapshot)
+  }
+
+  pub fn to_json<W: io::Write>(&self, writer: W) -> Result<(), WriteError> {
+    serde_json::to_writer_pretty(writer, self)?;
+    Ok(())
+  }
+}
+
+/// Difference between an expected (snapshot) and actual result value.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SnapshotDifference {
+  /// Name of the grid
+  pub grid: String,
+  /// Path to the value in the results of the grid, such as `thruster_acceleration.0.force`, or empty for the results
+  /// as a whole.
+  pub path: String,
+  /// Expected value, or None if it is not in the snapshot.
+  pub expected: Option<Value>,
+  /// Actual value, or None if it is no longer calculated.
+  pub actual: Option<Value>,
+}
+
+impl SnapshotDifference {
+  fn new(grid: &str, path: String, expected: Option<&Value>, actual: Option<&Value>) -> Self {
+    Self { grid: grid.to_string(), path, expected: expected.cloned(), actual: actual.cloned() }
+  }
+}
+
+impl Display for SnapshotDifference {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let format = |value: &Option<Value>| value.as_ref().map_or_else(|| "(missing)".to_string(), |v| v.to_string());
+    let path = if self.path.is_empty() { String::new() } else { format!(" {}", self.path) };
+    write!(f, "{}{}: expected {}, got {}", self.grid, path, format(&self.expected), format(&self.actual))
+  }
+}
+
+fn diff_values(grid: &str, path: String, expected: &Value, actual: &Value, tolerance: f64, differences: &mut Vec<SnapshotDifference>) {
+  let child_path = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+  match (expected, actual) {
+    (Value::Object(expected), Value::Object(actual)) => {
+      for (key, expected_value) in expected {
+        match actual.get(key) {
+          Some(actual_value) => diff_values(grid, child_path(key), expected_value, actual_value, tolerance, differences),
+          None => differences.push(SnapshotDifference::new(grid, child_path(key), Some(expected_value), None)),
+        }
+      }
+      for (key, actual_value) in actual {
+        if !expected.contains_key(key) {
+          differences.push(SnapshotDifference::new(grid, child_path(key), None, Some(actual_value)));
+        }
+      }
+    }
+    (Value::Array(expected_values), Value::Array(actual_values)) if expected_values.len() == actual_values.len() => {
+      for (index, (expected, actual)) in expected_values.iter().zip(actual_values).enumerate() {
+        diff_values(grid, child_path(&index.to_string()), expected, actual, tolerance, differences);
+      }
+    }
+    (Value::Number(expected_number), Value::Number(actual_number)) => {
+      let (e, a) = (expected_number.as_f64().unwrap_or_default(), actual_number.as_f64().unwrap_or_default());
+      if (e - a).abs() > tolerance * e.abs().max(a.abs()) {
+        differences.push(SnapshotDifference::new(grid, path, Some(expected), Some(actual)));
+      }
+    }
+    _ => if expected != actual {
+      differences.push(SnapshotDifference::new(grid, path, Some(expected), Some(actual)));
+    }
+  }
+}
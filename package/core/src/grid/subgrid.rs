@@ -0,0 +1,121 @@
+//! Subgrids attached to a grid, such as drill heads mounted on pistons or rotors, or ships docked with connectors.
+//! Subgrids are calculated together with their main grid as one combined entity: their mass is moved by the thrusters
+//! of all grids, and they share power and hydrogen.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::BlockId;
+use crate::data::Data;
+use crate::grid::{GridCalculated, GridCalculator};
+use crate::grid::direction::CountPerDirection;
+use crate::grid::scenario::Scenario;
+use crate::grid::units::{Mass, Power};
+
+/// Subgrid with its own blocks. Other settings, such as gravity and fill levels, are those of the main grid.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Subgrid {
+  /// Name of the subgrid
+  pub name: String,
+  /// Block counts, which may be fractional
+  #[serde(serialize_with = "crate::precision::serialize_counts")]
+  pub blocks: HashMap<BlockId, f64>,
+  /// Block counts per direction relative to the main grid, which may be fractional
+  #[serde(serialize_with = "crate::precision::serialize_counts_per_direction")]
+  pub directional_blocks: HashMap<BlockId, CountPerDirection>,
+  /// Additional mass (kg)
+  #[serde(serialize_with = "crate::precision::serialize")]
+  pub additional_mass: f64,
+}
+
+impl Subgrid {
+  /// Creates a subgrid named `name` with the blocks and additional mass of `calculator`.
+  pub fn from_calculator(name: impl Into<String>, calculator: &GridCalculator) -> Self {
+    Self {
+      name: name.into(),
+      blocks: calculator.blocks.clone(),
+      directional_blocks: calculator.directional_blocks.clone(),
+      additional_mass: calculator.additional_mass,
+    }
+  }
+}
+
+/// Results of a single grid of a main grid with subgrids.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct GridBreakdownCalculated {
+  /// Name of the subgrid, or None for the main grid
+  pub name: Option<String>,
+  /// Number of blocks
+  pub block_count: f64,
+  /// Mass when empty (kg)
+  pub total_mass_empty: Mass,
+  /// Mass when filled (kg)
+  pub total_mass_filled: Mass,
+  /// Power generation (MW)
+  pub power_generation: Power,
+  /// Power consumption with thrusters at full power, without charging batteries (MW)
+  pub power_consumption: Power,
+  /// Hydrogen generation (L/s)
+  pub hydrogen_generation: f64,
+  /// Hydrogen consumption with thrusters at full burn, without filling tanks (L/s)
+  pub hydrogen_consumption: f64,
+}
+
+impl GridBreakdownCalculated {
+  fn new(name: Option<String>, calculator: &GridCalculator, calculated: &GridCalculated) -> Self {
+    Self {
+      name,
+      block_count: calculator.iter_total_block_counts().map(|(_, count)| count).sum(),
+      total_mass_empty: calculated.total_mass_empty,
+      total_mass_filled: calculated.total_mass_filled,
+      power_generation: calculated.power_generation,
+      power_consumption: calculated.power_upto_left_right_thruster.total_consumption,
+      hydrogen_generation: calculated.hydrogen_generation,
+      hydrogen_consumption: calculated.hydrogen_upto_left_right_thruster.total_consumption,
+    }
+  }
+}
+
+impl GridCalculator {
+  /// Creates a copy of this grid without subgrids, with the blocks and additional mass of all subgrids added to it.
+  pub fn combined_with_subgrids(&self) -> GridCalculator {
+    let mut combined = self.clone();
+    for subgrid in std::mem::take(&mut combined.subgrids) {
+      for (id, count) in subgrid.blocks {
+        *combined.blocks.entry(id).or_default() += count;
+      }
+      for (id, count_per_direction) in subgrid.directional_blocks {
+        let combined_count_per_direction = combined.directional_blocks.entry(id).or_default();
+        for (direction, count) in count_per_direction.iter_with_direction() {
+          combined_count_per_direction[direction] += *count;
+        }
+      }
+      combined.additional_mass += subgrid.additional_mass;
+    }
+    combined
+  }
+
+  /// Calculates this grid combined with its subgrids in `scenario`, with a breakdown of the main grid and each
+  /// subgrid calculated on its own.
+  pub(crate) fn calculate_with_subgrids(&self, data: &Data, scenario: &Scenario) -> GridCalculated {
+    let mut c = self.combined_with_subgrids().calculate_scenario(data, scenario);
+    let main = GridCalculator { subgrids: Vec::new(), ..self.clone() };
+    c.grid_breakdown.push(GridBreakdownCalculated::new(None, &main, &main.calculate_scenario(data, scenario)));
+    for subgrid in &self.subgrids {
+      let calculator = GridCalculator {
+        blocks: subgrid.blocks.clone(),
+        directional_blocks: subgrid.directional_blocks.clone(),
+        additional_mass: subgrid.additional_mass,
+        crew_count: 0,
+        subgrids: Vec::new(),
+        ..self.clone()
+      };
+      let calculated = calculator.calculate_scenario(data, scenario);
+      c.grid_breakdown.push(GridBreakdownCalculated::new(Some(subgrid.name.clone()), &calculator, &calculated));
+    }
+    c
+  }
+}
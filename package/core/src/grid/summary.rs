@@ -0,0 +1,80 @@
+//! Compact summaries of key results, for tracking how a design changes over time, such as between saves.
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::direction::Direction;
+use crate::grid::GridCalculated;
+
+/// Summary of key results of a grid.
+#[derive(Default, Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct StatSummary {
+  /// Total mass when empty (kg)
+  pub mass_empty: f64,
+  /// Total mass when filled (kg)
+  pub mass_filled: f64,
+  /// Up acceleration when filled and inside of gravity (m/s^2), or None if not applicable
+  pub up_acceleration: Option<f64>,
+  /// Power generation (MW)
+  pub power_generation: f64,
+  /// Power balance with thrusters at full burn (+-MW)
+  pub power_balance: f64,
+  /// Hydrogen balance with thrusters at full burn, without tanks (+-L/s)
+  pub hydrogen_balance: f64,
+  /// Maximum jump distance when filled (km), or None if not applicable
+  pub jump_range: Option<f64>,
+}
+
+impl StatSummary {
+  pub fn from_calculated(calculated: &GridCalculated) -> Self {
+    Self {
+      mass_empty: calculated.total_mass_empty.to_kilograms(),
+      mass_filled: calculated.total_mass_filled.to_kilograms(),
+      up_acceleration: calculated.thruster_acceleration[Direction::Up].acceleration_filled_gravity,
+      power_generation: calculated.power_generation.to_megawatts(),
+      power_balance: calculated.power_upto_left_right_thruster.balance.to_megawatts(),
+      hydrogen_balance: calculated.hydrogen_upto_left_right_thruster.balance_without_tank,
+      jump_range: calculated.jump_drive.as_ref().and_then(|j| j.max_distance_filled),
+    }
+  }
+
+  /// Gets the stats that changed from this summary to `newer`, in the order of the fields of this summary. Stats that
+  /// are not applicable in either summary are not included.
+  pub fn changes(&self, newer: &Self) -> Vec<StatChange> {
+    let mut changes = Vec::new();
+    let mut push = |stat: &'static str, unit: &'static str, older: Option<f64>, newer: Option<f64>| {
+      if let (Some(older), Some(newer)) = (older, newer) {
+        let delta = newer - older;
+        // Ignore changes that are not visible at the precision of the changes.
+        if delta.abs() >= 0.005 {
+          changes.push(StatChange { stat, delta, unit });
+        }
+      }
+    };
+    push("mass", "t", Some(self.mass_empty / 1000.0), Some(newer.mass_empty / 1000.0));
+    push("mass filled", "t", Some(self.mass_filled / 1000.0), Some(newer.mass_filled / 1000.0));
+    push("up accel", "m/s²", self.up_acceleration, newer.up_acceleration);
+    push("power generation", "MW", Some(self.power_generation), Some(newer.power_generation));
+    push("power balance", "MW", Some(self.power_balance), Some(newer.power_balance));
+    push("hydrogen balance", "L/s", Some(self.hydrogen_balance), Some(newer.hydrogen_balance));
+    push("jump range", "km", self.jump_range, newer.jump_range);
+    changes
+  }
+}
+
+/// Change of a stat between two summaries.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct StatChange {
+  pub stat: &'static str,
+  pub delta: f64,
+  pub unit: &'static str,
+}
+
+impl Display for StatChange {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let sign = if self.delta < 0.0 { '−' } else { '+' };
+    write!(f, "{} {}{:.2} {}", self.stat, sign, self.delta.abs(), self.unit)
+  }
+}
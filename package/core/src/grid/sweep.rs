@@ -0,0 +1,200 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::Data;
+use crate::grid::direction::Direction;
+use crate::grid::{GridCalculated, GridCalculator};
+
+/// Numeric calculator option that can be swept over a range of values.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum SweepOption {
+  GravityMultiplier,
+  #[default] PlanetaryInfluence,
+  AdditionalMass,
+  ContainerMultiplier,
+  ThrusterPower,
+  BatteryFill,
+  HydrogenTankFill,
+  HoverPitch,
+}
+
+impl SweepOption {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use SweepOption::*;
+    const ITEMS: [SweepOption; 8] = [GravityMultiplier, PlanetaryInfluence, AdditionalMass, ContainerMultiplier, ThrusterPower, BatteryFill, HydrogenTankFill, HoverPitch];
+    ITEMS.into_iter()
+  }
+
+  /// Gets the unit of this option's values.
+  pub fn unit(&self) -> &'static str {
+    use SweepOption::*;
+    match self {
+      GravityMultiplier => "g",
+      PlanetaryInfluence => "",
+      AdditionalMass => "kg",
+      ContainerMultiplier => "x",
+      ThrusterPower | BatteryFill | HydrogenTankFill => "%",
+      HoverPitch => "°",
+    }
+  }
+
+  /// Gets the default range of values this option is swept over, given the current `calculator`.
+  pub fn default_range(&self, calculator: &GridCalculator) -> RangeInclusive<f64> {
+    use SweepOption::*;
+    match self {
+      GravityMultiplier => 0.0..=1.5,
+      PlanetaryInfluence => 0.0..=1.0,
+      AdditionalMass => 0.0..=(calculator.additional_mass * 2.0).max(100000.0),
+      ContainerMultiplier => 1.0..=10.0,
+      ThrusterPower | BatteryFill | HydrogenTankFill => 0.0..=100.0,
+      HoverPitch => -90.0..=90.0,
+    }
+  }
+
+  /// Gets the value of this option from `calculator`.
+  pub fn get(&self, calculator: &GridCalculator) -> f64 {
+    use SweepOption::*;
+    match self {
+      GravityMultiplier => calculator.gravity_multiplier,
+      PlanetaryInfluence => calculator.planetary_influence,
+      AdditionalMass => calculator.additional_mass,
+      ContainerMultiplier => calculator.container_multiplier,
+      ThrusterPower => calculator.thruster_power,
+      BatteryFill => calculator.battery_fill,
+      HydrogenTankFill => calculator.hydrogen_tank_fill,
+      HoverPitch => calculator.hover_pitch,
+    }
+  }
+
+  /// Sets the value of this option in `calculator` to `value`.
+  pub fn set(&self, calculator: &mut GridCalculator, value: f64) {
+    use SweepOption::*;
+    let option = match self {
+      GravityMultiplier => &mut calculator.gravity_multiplier,
+      PlanetaryInfluence => &mut calculator.planetary_influence,
+      AdditionalMass => &mut calculator.additional_mass,
+      ContainerMultiplier => &mut calculator.container_multiplier,
+      ThrusterPower => &mut calculator.thruster_power,
+      BatteryFill => &mut calculator.battery_fill,
+      HydrogenTankFill => &mut calculator.hydrogen_tank_fill,
+      HoverPitch => &mut calculator.hover_pitch,
+    };
+    *option = value;
+  }
+}
+
+impl Display for SweepOption {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use SweepOption::*;
+    match self {
+      GravityMultiplier => f.write_str("Gravity Multiplier"),
+      PlanetaryInfluence => f.write_str("Planetary Influence"),
+      AdditionalMass => f.write_str("Additional Mass"),
+      ContainerMultiplier => f.write_str("Container Multiplier"),
+      ThrusterPower => f.write_str("Thruster Power"),
+      BatteryFill => f.write_str("Battery Fill"),
+      HydrogenTankFill => f.write_str("Hydrogen Tank Fill"),
+      HoverPitch => f.write_str("Hover Pitch"),
+    }
+  }
+}
+
+/// Result value that can be observed while sweeping an option.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum SweepResult {
+  /// Up acceleration when filled and inside of gravity
+  #[default] UpAcceleration,
+  /// Front acceleration when filled and inside of gravity
+  FrontAcceleration,
+  /// Total mass when filled
+  TotalMass,
+  /// Power balance of all power consumers
+  PowerBalance,
+  /// Hydrogen balance of all hydrogen consumers, with hydrogen provided by tanks
+  HydrogenBalance,
+  /// Duration until batteries are empty with all power consumers
+  BatteryDuration,
+}
+
+impl SweepResult {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use SweepResult::*;
+    const ITEMS: [SweepResult; 6] = [UpAcceleration, FrontAcceleration, TotalMass, PowerBalance, HydrogenBalance, BatteryDuration];
+    ITEMS.into_iter()
+  }
+
+  /// Gets the unit of this result's values.
+  pub fn unit(&self) -> &'static str {
+    use SweepResult::*;
+    match self {
+      UpAcceleration | FrontAcceleration => "m/s²",
+      TotalMass => "kg",
+      PowerBalance => "MW",
+      HydrogenBalance => "L/s",
+      BatteryDuration => "h",
+    }
+  }
+
+  /// Gets the value of this result from `calculated`, or None if it has no value.
+  pub fn get(&self, calculated: &GridCalculated) -> Option<f64> {
+    use SweepResult::*;
+    match self {
+      UpAcceleration => calculated.thruster_acceleration[Direction::Up].acceleration_filled_gravity,
+      FrontAcceleration => calculated.thruster_acceleration[Direction::Front].acceleration_filled_gravity,
+      TotalMass => Some(calculated.total_mass_filled.to_kilograms()),
+      PowerBalance => Some(calculated.power_upto_battery_charge.balance.to_megawatts()),
+      HydrogenBalance => Some(calculated.hydrogen_upto_tank_fill.balance_with_tank),
+      BatteryDuration => calculated.power_upto_battery_charge.battery_duration
+        .filter(|d| !d.is_infinite())
+        .map(|d| d.to_hours()),
+    }
+  }
+}
+
+impl Display for SweepResult {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use SweepResult::*;
+    match self {
+      UpAcceleration => f.write_str("Up Acceleration"),
+      FrontAcceleration => f.write_str("Front Acceleration"),
+      TotalMass => f.write_str("Total Mass"),
+      PowerBalance => f.write_str("Power Balance"),
+      HydrogenBalance => f.write_str("Hydrogen Balance"),
+      BatteryDuration => f.write_str("Battery Duration"),
+    }
+  }
+}
+
+/// Point of a sweep.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SweepPoint {
+  /// Value of the swept option
+  pub option: f64,
+  /// Value of the observed result, or None if it has no value
+  pub result: Option<f64>,
+}
+
+/// Calculates `result` for `points` evenly spaced values of `option` over `range`, with all other options taken from
+/// `calculator`. Returns no points if `points` is 0, and only the start of `range` if `points` is 1.
+pub fn sweep(
+  calculator: &GridCalculator,
+  data: &Data,
+  option: SweepOption,
+  result: SweepResult,
+  range: RangeInclusive<f64>,
+  points: usize,
+) -> Vec<SweepPoint> {
+  let (start, end) = range.into_inner();
+  let step = if points > 1 { (end - start) / (points - 1) as f64 } else { 0.0 };
+  let mut calculator = calculator.clone();
+  (0..points).map(|i| {
+    let value = start + step * i as f64;
+    option.set(&mut calculator, value);
+    let calculated = calculator.calculate(data);
+    SweepPoint { option: value, result: result.get(&calculated) }
+  }).collect()
+}
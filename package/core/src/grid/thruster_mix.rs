@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::{Block, BlockId, filter, GridSize, Thruster, ThrusterType};
+use crate::data::Data;
+use crate::grid::direction::{CountPerDirection, Direction, PerDirection};
+
+/// Environment a grid flies in.
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Environment {
+  /// Planetary influence 0-1
+  pub planetary_influence: f64,
+  /// Gravity multiplier 0-* (g)
+  pub gravity_multiplier: f64,
+}
+
+impl Environment {
+  /// Planet surface: full planetary influence and 1 g gravity.
+  pub const PLANET_SURFACE: Self = Self { planetary_influence: 1.0, gravity_multiplier: 1.0 };
+  /// Space: no planetary influence and no gravity.
+  pub const SPACE: Self = Self { planetary_influence: 0.0, gravity_multiplier: 0.0 };
+}
+
+/// What a thruster mix minimizes.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum ThrusterMixObjective {
+  /// Mass of thrusters
+  #[default] Mass,
+  /// Hydrogen consumption of thrusters at full thrust, then mass of thrusters
+  Hydrogen,
+}
+
+impl ThrusterMixObjective {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use ThrusterMixObjective::*;
+    const ITEMS: [ThrusterMixObjective; 2] = [Mass, Hydrogen];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for ThrusterMixObjective {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use ThrusterMixObjective::*;
+    match self {
+      Mass => f.write_str("Minimize Mass"),
+      Hydrogen => f.write_str("Minimize Hydrogen"),
+    }
+  }
+}
+
+/// Problem to suggest a thruster mix for.
+#[derive(Clone, Debug)]
+pub struct ThrusterMixProblem {
+  /// Environments the grid must reach the target accelerations in.
+  pub environments: Vec<Environment>,
+  /// Target acceleration per direction, on top of counteracting gravity for the up direction (m/s^2)
+  pub target_acceleration: PerDirection<f64>,
+  /// Mass of the grid without thrusters (kg)
+  pub base_mass: f64,
+  pub objective: ThrusterMixObjective,
+}
+
+/// Suggested mix of thrusters.
+#[derive(Default, Clone, Debug)]
+pub struct ThrusterMix {
+  /// Thruster counts per direction
+  pub blocks: HashMap<BlockId, CountPerDirection>,
+  /// Mass of all thrusters (kg)
+  pub thruster_mass: f64,
+  /// Hydrogen consumption of all thrusters at full thrust (L/s)
+  pub hydrogen_consumption: f64,
+}
+
+/// Maximum number of thrusters of one type in one direction that is searched.
+const MAX_COUNT: u64 = 200;
+/// Maximum number of times thrusters are chosen again for the total mass including the mass of chosen thrusters.
+const MAX_ITERATIONS: usize = 16;
+
+/// Suggests a mix of atmospheric, ion, and hydrogen thrusters of `grid_size` that are available with
+/// `enabled_mod_ids`, that reaches the target accelerations of `problem` in all of its environments, while minimizing
+/// the objective of `problem`. Uses the thruster with the highest force per mass of each type, and searches all
+/// combinations of atmospheric and ion thruster counts per direction, completing each combination with hydrogen
+/// thrusters. Returns None if no mix reaches the target accelerations.
+pub fn suggest_thruster_mix(
+  data: &Data,
+  grid_size: GridSize,
+  enabled_mod_ids: &HashSet<u64>,
+  problem: &ThrusterMixProblem,
+) -> Option<ThrusterMix> {
+  let candidates: Vec<Candidate> = [ThrusterType::Atmospheric, ThrusterType::Ion, ThrusterType::Hydrogen].into_iter()
+    .filter_map(|ty| Candidate::best_of_type(data, grid_size, enabled_mod_ids, ty))
+    .collect();
+
+  // The mass of chosen thrusters changes the forces needed, so choose thrusters until the choice is stable.
+  let mut thruster_mass = 0.0;
+  let mut counts: PerDirection<Vec<u64>> = PerDirection::default();
+  for _ in 0..MAX_ITERATIONS {
+    let total_mass = problem.base_mass + thruster_mass;
+    let mut new_counts: PerDirection<Vec<u64>> = PerDirection::default();
+    for direction in Direction::items() {
+      new_counts[direction] = choose_counts(&candidates, problem, total_mass, direction)?;
+    }
+    thruster_mass = new_counts.iter()
+      .map(|counts| counts.iter().zip(&candidates).map(|(count, c)| *count as f64 * c.mass).sum::<f64>())
+      .sum();
+    let stable = new_counts == counts;
+    counts = new_counts;
+    if stable { break; }
+  }
+
+  let mut mix = ThrusterMix { thruster_mass, ..ThrusterMix::default() };
+  for (direction, counts) in counts.iter_with_direction() {
+    for (count, candidate) in counts.iter().zip(&candidates) {
+      if *count == 0 { continue; }
+      mix.blocks.entry(candidate.block.id_cloned()).or_default()[direction] += *count as f64;
+      mix.hydrogen_consumption += *count as f64 * candidate.hydrogen_consumption;
+    }
+  }
+  Some(mix)
+}
+
+struct Candidate<'a> {
+  block: &'a Block<Thruster>,
+  mass: f64,
+  hydrogen_consumption: f64,
+}
+
+impl<'a> Candidate<'a> {
+  fn best_of_type(data: &'a Data, grid_size: GridSize, enabled_mod_ids: &HashSet<u64>, ty: ThrusterType) -> Option<Self> {
+    data.blocks.thrusters.values()
+      .filter(|b| filter(b, grid_size, enabled_mod_ids) && b.details.ty == ty)
+      .map(|block| {
+        let mass = block.mass(&data.components);
+        let hydrogen_consumption = if block.details.fuel_gas_id.is_some() {
+          block.details.actual_max_consumption(&data.gas_properties)
+        } else {
+          0.0
+        };
+        Self { block, mass, hydrogen_consumption }
+      })
+      .max_by(|a, b| a.force_per_mass().total_cmp(&b.force_per_mass()))
+  }
+
+  #[inline]
+  fn force_per_mass(&self) -> f64 {
+    if self.mass > 0.0 { self.block.details.force / self.mass } else { f64::INFINITY }
+  }
+
+  #[inline]
+  fn force(&self, environment: &Environment) -> f64 {
+    self.block.details.force * self.block.details.effectiveness(environment.planetary_influence).max(0.0)
+  }
+}
+
+/// Chooses counts of `candidates` in `direction` for a grid of `total_mass`, or None if the target acceleration of
+/// `problem` cannot be reached.
+fn choose_counts(candidates: &[Candidate], problem: &ThrusterMixProblem, total_mass: f64, direction: Direction) -> Option<Vec<u64>> {
+  let required_forces: Vec<f64> = problem.environments.iter().map(|e| {
+    let gravity = if direction == Direction::Up { 9.81 * e.gravity_multiplier } else { 0.0 };
+    total_mass * (problem.target_acceleration[direction] + gravity)
+  }).collect();
+  let mut counts = vec![0; candidates.len()];
+  if required_forces.iter().all(|f| *f <= 0.0) { return Some(counts); }
+  let (last, searched) = candidates.split_last()?;
+
+  let mut best: Option<(Vec<u64>, (f64, f64))> = None;
+  loop {
+    // Complete the searched counts with the last candidate.
+    let mut last_count = 0;
+    let mut feasible = true;
+    for (environment, required_force) in problem.environments.iter().zip(&required_forces) {
+      let searched_force: f64 = searched.iter().zip(&counts).map(|(c, count)| c.force(environment) * *count as f64).sum();
+      let remaining_force = required_force - searched_force;
+      if remaining_force <= 0.0 { continue; }
+      let last_force = last.force(environment);
+      if last_force <= 0.0 {
+        feasible = false;
+        break;
+      }
+      last_count = last_count.max((remaining_force / last_force).ceil() as u64);
+    }
+    if feasible && last_count <= MAX_COUNT {
+      let mut candidate_counts = counts.clone();
+      *candidate_counts.last_mut().unwrap() = last_count; // Unwrap OK: there is at least one candidate.
+      let cost = cost(candidates, &candidate_counts, problem.objective);
+      if best.as_ref().is_none_or(|(_, best_cost)| cost < *best_cost) {
+        best = Some((candidate_counts, cost));
+      }
+    }
+    // Go to the next combination of searched counts.
+    let mut index = 0;
+    loop {
+      if index == searched.len() { return best.map(|(counts, _)| counts); }
+      if counts[index] < MAX_COUNT {
+        counts[index] += 1;
+        break;
+      }
+      counts[index] = 0;
+      index += 1;
+    }
+  }
+}
+
+/// Cost of `counts` of `candidates` for `objective`, as a primary and secondary cost.
+fn cost(candidates: &[Candidate], counts: &[u64], objective: ThrusterMixObjective) -> (f64, f64) {
+  let mass: f64 = candidates.iter().zip(counts).map(|(c, count)| c.mass * *count as f64).sum();
+  let hydrogen: f64 = candidates.iter().zip(counts).map(|(c, count)| c.hydrogen_consumption * *count as f64).sum();
+  let count: f64 = counts.iter().map(|c| *c as f64).sum();
+  match objective {
+    ThrusterMixObjective::Mass => (mass, count),
+    ThrusterMixObjective::Hydrogen => (hydrogen, mass),
+  }
+}
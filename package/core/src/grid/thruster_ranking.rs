@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::{Block, filter, GridSize, Thruster};
+use crate::data::Data;
+
+/// Resource a thruster consumes.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum ThrusterResource {
+  /// Power (MW)
+  Power,
+  /// Hydrogen (L/s)
+  Hydrogen,
+}
+
+impl ThrusterResource {
+  /// Unit of consumption of this resource.
+  #[inline]
+  pub fn unit(&self) -> &'static str {
+    match self {
+      ThrusterResource::Power => "MW",
+      ThrusterResource::Hydrogen => "L/s",
+    }
+  }
+}
+
+impl Display for ThrusterResource {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ThrusterResource::Power => f.write_str("Power"),
+      ThrusterResource::Hydrogen => f.write_str("Hydrogen"),
+    }
+  }
+}
+
+/// Order in which thrusters are ranked.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Debug)]
+pub enum ThrusterRankOrder {
+  /// Force per consumption, grouped by resource
+  #[default] ForcePerConsumption,
+  /// Force per mass
+  ForcePerMass,
+}
+
+impl ThrusterRankOrder {
+  #[inline]
+  pub fn items() -> impl IntoIterator<Item=Self> {
+    use ThrusterRankOrder::*;
+    const ITEMS: [ThrusterRankOrder; 2] = [ForcePerConsumption, ForcePerMass];
+    ITEMS.into_iter()
+  }
+}
+
+impl Display for ThrusterRankOrder {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    use ThrusterRankOrder::*;
+    match self {
+      ForcePerConsumption => f.write_str("Force per Consumption"),
+      ForcePerMass => f.write_str("Force per Mass"),
+    }
+  }
+}
+
+/// Thruster with its force, consumption, and mass at a planetary influence.
+#[derive(Clone, Debug)]
+pub struct ThrusterRank<'a> {
+  pub block: &'a Block<Thruster>,
+  pub resource: ThrusterResource,
+  /// Force at the planetary influence (N)
+  pub force: f64,
+  /// Maximum consumption at the planetary influence (MW or L/s, depending on `resource`)
+  pub consumption: f64,
+  /// Mass (kg)
+  pub mass: f64,
+  /// Force per consumption (N/MW or N/(L/s), depending on `resource`)
+  pub force_per_consumption: f64,
+  /// Force per mass (N/kg)
+  pub force_per_mass: f64,
+}
+
+/// Ranks the thrusters of `grid_size` that are available with `enabled_mod_ids`, at `planetary_influence` 0-1, by
+/// `order` from best to worst. Thrusters that produce no force at `planetary_influence` are left out.
+pub fn rank_thrusters<'a>(
+  data: &'a Data,
+  grid_size: GridSize,
+  enabled_mod_ids: &HashSet<u64>,
+  planetary_influence: f64,
+  order: ThrusterRankOrder,
+) -> Vec<ThrusterRank<'a>> {
+  let mut ranks: Vec<_> = data.blocks.thrusters.values()
+    .filter(|b| filter(b, grid_size, enabled_mod_ids))
+    .filter_map(|block| {
+      let effectiveness = block.details.effectiveness(planetary_influence);
+      if effectiveness <= 0.0 { return None; }
+      let resource = if block.details.fuel_gas_id.is_some() { ThrusterResource::Hydrogen } else { ThrusterResource::Power };
+      let force = block.details.force * effectiveness;
+      let consumption = block.details.actual_max_consumption(&data.gas_properties) * effectiveness;
+      let mass = block.mass(&data.components);
+      Some(ThrusterRank {
+        block,
+        resource,
+        force,
+        consumption,
+        mass,
+        force_per_consumption: if consumption > 0.0 { force / consumption } else { f64::INFINITY },
+        force_per_mass: if mass > 0.0 { force / mass } else { f64::INFINITY },
+      })
+    })
+    .collect();
+  let descending = |a: f64, b: f64| b.partial_cmp(&a).unwrap_or(Ordering::Equal);
+  match order {
+    ThrusterRankOrder::ForcePerConsumption => ranks.sort_by(|a, b| a.resource.cmp(&b.resource)
+      .then_with(|| descending(a.force_per_consumption, b.force_per_consumption))),
+    ThrusterRankOrder::ForcePerMass => ranks.sort_by(|a, b| descending(a.force_per_mass, b.force_per_mass)),
+  }
+  ranks
+}
@@ -0,0 +1,183 @@
+//! Typed units for values in calculation results, to prevent mixing up values of different quantities or units. Values
+//! are stored as `f64` in a fixed base unit, so that calculations on them are as fast as on plain numbers. Values
+//! serialize as their plain number in the base unit.
+//!
+//! Gas flow rates (L/s), such as hydrogen thruster consumption and tank input and output, are plain numbers. They are
+//! rates rather than volumes, like all hydrogen and oxygen balances, and there is no flow rate unit.
+
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use serde::{Deserialize, Serialize};
+
+/// Mass, stored in kilograms.
+#[repr(transparent)]
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Mass(f64);
+
+impl Mass {
+  #[inline]
+  pub const fn from_kilograms(kilograms: f64) -> Self { Self(kilograms) }
+  #[inline]
+  pub fn from_tonnes(tonnes: f64) -> Self { Self(tonnes * 1_000.0) }
+  #[inline]
+  pub fn to_kilograms(&self) -> f64 { self.0 }
+  #[inline]
+  pub fn to_tonnes(&self) -> f64 { self.0 / 1_000.0 }
+
+  pub const UNIT: &'static str = "kg";
+}
+
+/// Force, stored in newtons.
+#[repr(transparent)]
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Force(f64);
+
+impl Force {
+  #[inline]
+  pub const fn from_newtons(newtons: f64) -> Self { Self(newtons) }
+  #[inline]
+  pub fn from_kilonewtons(kilonewtons: f64) -> Self { Self(kilonewtons * 1_000.0) }
+  #[inline]
+  pub fn to_newtons(&self) -> f64 { self.0 }
+  #[inline]
+  pub fn to_kilonewtons(&self) -> f64 { self.0 / 1_000.0 }
+  #[inline]
+  pub fn to_meganewtons(&self) -> f64 { self.0 / 1_000_000.0 }
+
+  pub const UNIT: &'static str = "N";
+}
+
+impl Div<Mass> for Force {
+  type Output = f64;
+  /// Divides this force by `mass`, giving an acceleration (m/s^2).
+  #[inline]
+  fn div(self, mass: Mass) -> f64 { self.0 / mass.0 }
+}
+
+/// Power, stored in megawatts.
+#[repr(transparent)]
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Power(f64);
+
+impl Power {
+  #[inline]
+  pub const fn from_megawatts(megawatts: f64) -> Self { Self(megawatts) }
+  #[inline]
+  pub fn from_kilowatts(kilowatts: f64) -> Self { Self(kilowatts / 1_000.0) }
+  #[inline]
+  pub fn to_megawatts(&self) -> f64 { self.0 }
+  #[inline]
+  pub fn to_kilowatts(&self) -> f64 { self.0 * 1_000.0 }
+
+  pub const UNIT: &'static str = "MW";
+}
+
+/// Volume, stored in liters.
+#[repr(transparent)]
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Volume(f64);
+
+impl Volume {
+  #[inline]
+  pub const fn from_liters(liters: f64) -> Self { Self(liters) }
+  #[inline]
+  pub fn from_cubic_meters(cubic_meters: f64) -> Self { Self(cubic_meters * 1_000.0) }
+  #[inline]
+  pub fn to_liters(&self) -> f64 { self.0 }
+  #[inline]
+  pub fn to_cubic_meters(&self) -> f64 { self.0 / 1_000.0 }
+
+  pub const UNIT: &'static str = "L";
+}
+
+/// Energy, stored in megawatt hours.
+#[repr(transparent)]
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Energy(f64);
+
+impl Energy {
+  #[inline]
+  pub const fn from_megawatt_hours(megawatt_hours: f64) -> Self { Self(megawatt_hours) }
+  #[inline]
+  pub fn to_megawatt_hours(&self) -> f64 { self.0 }
+
+  pub const UNIT: &'static str = "MWh";
+}
+
+
+/// Implements formatting and arithmetic on the base unit of `$unit`.
+macro_rules! impl_unit {
+  ($unit:ident) => {
+    impl $unit {
+      /// Gets the maximum of this value and `other`.
+      #[inline]
+      pub fn max(self, other: Self) -> Self { Self(self.0.max(other.0)) }
+      /// Gets the minimum of this value and `other`.
+      #[inline]
+      pub fn min(self, other: Self) -> Self { Self(self.0.min(other.0)) }
+    }
+
+    impl Display for $unit {
+      /// Formats as a value in the base unit with the unit symbol, such as `1.50 kg`.
+      #[inline]
+      fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(precision) = f.precision() {
+          write!(f, "{:.*} {}", precision, self.0, Self::UNIT)
+        } else {
+          write!(f, "{} {}", self.0, Self::UNIT)
+        }
+      }
+    }
+
+    impl Add for $unit {
+      type Output = Self;
+      #[inline]
+      fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) }
+    }
+    impl AddAssign for $unit {
+      #[inline]
+      fn add_assign(&mut self, rhs: Self) { self.0 += rhs.0 }
+    }
+    impl Sub for $unit {
+      type Output = Self;
+      #[inline]
+      fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) }
+    }
+    impl SubAssign for $unit {
+      #[inline]
+      fn sub_assign(&mut self, rhs: Self) { self.0 -= rhs.0 }
+    }
+    impl Neg for $unit {
+      type Output = Self;
+      #[inline]
+      fn neg(self) -> Self { Self(-self.0) }
+    }
+    impl Mul<f64> for $unit {
+      type Output = Self;
+      #[inline]
+      fn mul(self, rhs: f64) -> Self { Self(self.0 * rhs) }
+    }
+    impl Div<f64> for $unit {
+      type Output = Self;
+      #[inline]
+      fn div(self, rhs: f64) -> Self { Self(self.0 / rhs) }
+    }
+    impl Div for $unit {
+      type Output = f64;
+      #[inline]
+      fn div(self, rhs: Self) -> f64 { self.0 / rhs.0 }
+    }
+    impl Sum for $unit {
+      #[inline]
+      fn sum<I: Iterator<Item=Self>>(iter: I) -> Self { Self(iter.map(|v| v.0).sum()) }
+    }
+  };
+}
+
+impl_unit!(Mass);
+impl_unit!(Force);
+impl_unit!(Power);
+impl_unit!(Volume);
+impl_unit!(Energy);
@@ -0,0 +1,44 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::blocks::BlockId;
+
+/// Warning about a calculation, pointing out configurations that are likely to be a mistake.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[non_exhaustive]
+pub enum Warning {
+  /// Atmospheric thrusters `ids` have a non-zero count, but provide no force at the current planetary influence.
+  AtmosphericThrustersWithoutForce { ids: Vec<BlockId> },
+  /// Drills have a non-zero count, but there are no storage blocks to store ore besides the drills themselves.
+  DrillsWithoutStorage,
+  /// Blocks `ids` have a non-zero count, but are not built far enough to be functional.
+  NonFunctionalBlocks { ids: Vec<BlockId> },
+  /// Block `id` has count `count`, exceeding the `limit` of the server profile.
+  BlockLimitExceeded { id: BlockId, count: f64, limit: u64 },
+  /// Grid has `count` blocks, exceeding the `limit` of the server profile.
+  BlockCountLimitExceeded { count: f64, limit: u64 },
+  /// Grid has `pcu` PCU, exceeding the `limit` of the server profile.
+  PcuLimitExceeded { pcu: u64, limit: u64 },
+  /// Blocks `ids` have a non-zero count, but are hidden by the extraction configuration, and are ignored because
+  /// [ignore_hidden_blocks](crate::grid::GridCalculator::ignore_hidden_blocks) is enabled.
+  HiddenBlocksIgnored { ids: Vec<BlockId> },
+  /// Small grid blocks `small_port_ids` only have small conveyor ports and blocks `large_port_ids` only have large
+  /// conveyor ports, but no blocks connect both port sizes.
+  ConveyorPortSizeMismatch { small_port_ids: Vec<BlockId>, large_port_ids: Vec<BlockId> },
+}
+
+impl Display for Warning {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Warning::AtmosphericThrustersWithoutForce { ids } => write!(f, "{} atmospheric thruster type(s) provide no force at the current planetary influence. Atmospheric thrusters need an atmosphere; increase the planetary influence or use other thrusters.", ids.len()),
+      Warning::DrillsWithoutStorage => f.write_str("Drills have no storage to move ore into and will only fill their own inventories. Add cargo containers or other storage blocks connected by conveyors."),
+      Warning::NonFunctionalBlocks { ids } => write!(f, "{} block type(s) are not built far enough to be functional, and only contribute the mass of their placed components.", ids.len()),
+      Warning::BlockLimitExceeded { id, count, limit } => write!(f, "{} '{}' blocks exceed the server limit of {}.", count, id, limit),
+      Warning::BlockCountLimitExceeded { count, limit } => write!(f, "{} blocks exceed the server limit of {} blocks per grid.", count, limit),
+      Warning::PcuLimitExceeded { pcu, limit } => write!(f, "{} PCU exceeds the server limit of {} PCU per grid.", pcu, limit),
+      Warning::HiddenBlocksIgnored { ids } => write!(f, "{} block type(s) are hidden by the game data and are ignored. Disable 'Ignore Hidden Blocks' to calculate them.", ids.len()),
+      Warning::ConveyorPortSizeMismatch { small_port_ids, large_port_ids } => write!(f, "{} block type(s) only have small conveyor ports and {} block type(s) only have large conveyor ports, which do not connect to each other. Connect them with conveyor converters, and keep in mind that large items cannot pass through small conveyor ports.", small_port_ids.len(), large_port_ids.len()),
+    }
+  }
+}
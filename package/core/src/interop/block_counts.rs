@@ -0,0 +1,160 @@
+//! Block count lists in the XML fragment format used by community tools and blueprint analyzers: one `Block` element
+//! per block type with its `TypeId`, `SubtypeId`, and `Count`.
+//!
+//! ```xml
+//! <BlockCounts>
+//!   <Block>
+//!     <Id>
+//!       <TypeId>Thrust</TypeId>
+//!       <SubtypeId>LargeBlockLargeHydrogenThrust</SubtypeId>
+//!     </Id>
+//!     <Count>4</Count>
+//!     <Direction>Up</Direction>
+//!   </Block>
+//! </BlockCounts>
+//! ```
+//!
+//! The optional `Direction` element is written for directional blocks such as thrusters, and the optional `ModId`
+//! element for blocks from mods, so that block counts round-trip.
+
+use std::fmt::Write;
+
+use crate::data::blocks::BlockId;
+use crate::grid::direction::Direction;
+use crate::grid::GridCalculator;
+
+/// Block count entry: a block with an optional direction and mod.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BlockCount {
+  pub type_id: String,
+  pub subtype_id: String,
+  pub mod_id: Option<u64>,
+  pub direction: Option<Direction>,
+  pub count: f64,
+}
+
+impl BlockCount {
+  /// Creates a block count entry from `block_id`, which has the form `TypeId.SubtypeId` or `TypeId.SubtypeId@ModId`.
+  pub fn from_block_id(block_id: &str, direction: Option<Direction>, count: f64) -> Self {
+    let (id, mod_id) = match block_id.rsplit_once('@') {
+      Some((id, mod_id)) if mod_id.parse::<u64>().is_ok() => (id, mod_id.parse().ok()),
+      _ => (block_id, None),
+    };
+    let (type_id, subtype_id) = id.split_once('.').unwrap_or((id, ""));
+    Self { type_id: type_id.to_string(), subtype_id: subtype_id.to_string(), mod_id, direction, count }
+  }
+
+  /// Gets the block identifier of this entry.
+  pub fn block_id(&self) -> BlockId {
+    if let Some(mod_id) = self.mod_id {
+      format!("{}.{}@{}", self.type_id, self.subtype_id, mod_id)
+    } else {
+      format!("{}.{}", self.type_id, self.subtype_id)
+    }
+  }
+}
+
+impl GridCalculator {
+  /// Gets all non-zero block counts of this calculator, sorted by block identifier and direction.
+  pub fn block_counts(&self) -> Vec<BlockCount> {
+    let mut block_counts: Vec<_> = self.blocks.iter()
+      .filter(|(_, count)| **count != 0.0)
+      .map(|(id, count)| BlockCount::from_block_id(id, None, *count))
+      .chain(self.directional_blocks.iter().flat_map(|(id, count_per_direction)| {
+        count_per_direction.iter_with_direction()
+          .filter(|(_, count)| **count != 0.0)
+          .map(|(direction, count)| BlockCount::from_block_id(id, Some(direction), *count))
+      }))
+      .collect();
+    block_counts.sort_by_key(|b| (b.block_id(), b.direction));
+    block_counts
+  }
+
+  /// Sets the block counts of this calculator from `block_counts`, adding to existing counts.
+  pub fn add_block_counts(&mut self, block_counts: impl IntoIterator<Item=BlockCount>) {
+    for block_count in block_counts {
+      let id = block_count.block_id();
+      if let Some(direction) = block_count.direction {
+        self.directional_blocks.entry(id).or_default()[direction] += block_count.count;
+      } else {
+        *self.blocks.entry(id).or_default() += block_count.count;
+      }
+    }
+  }
+}
+
+/// Writes `block_counts` as a block count list XML document.
+pub fn to_xml<'a>(block_counts: impl IntoIterator<Item=&'a BlockCount>) -> String {
+  let mut xml = String::new();
+  xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+  xml.push_str("<BlockCounts>\n");
+  for block_count in block_counts {
+    xml.push_str("  <Block>\n");
+    xml.push_str("    <Id>\n");
+    let _ = writeln!(xml, "      <TypeId>{}</TypeId>", escape(&block_count.type_id));
+    let _ = writeln!(xml, "      <SubtypeId>{}</SubtypeId>", escape(&block_count.subtype_id));
+    xml.push_str("    </Id>\n");
+    let _ = writeln!(xml, "    <Count>{}</Count>", block_count.count);
+    if let Some(direction) = block_count.direction {
+      let _ = writeln!(xml, "    <Direction>{}</Direction>", direction);
+    }
+    if let Some(mod_id) = block_count.mod_id {
+      let _ = writeln!(xml, "    <ModId>{}</ModId>", mod_id);
+    }
+    xml.push_str("  </Block>\n");
+  }
+  xml.push_str("</BlockCounts>\n");
+  xml
+}
+
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+
+// Reading
+
+#[cfg(feature = "extract")]
+pub mod read {
+  use roxmltree::Document;
+  use thiserror::Error;
+
+  use crate::grid::direction::Direction;
+  use crate::interop::block_counts::BlockCount;
+  use crate::xml::{NodeExt, XmlError};
+
+  #[derive(Error, Debug)]
+  pub enum Error {
+    #[error("Could not XML parse block counts")]
+    ParseFail(#[from] roxmltree::Error),
+    #[error("Unrecognized direction '{0}'")]
+    UnrecognizedDirection(String),
+    #[error(transparent)]
+    XmlFail(#[from] XmlError),
+  }
+
+  /// Reads block counts from a block count list XML document.
+  pub fn from_xml(xml: &str) -> Result<Vec<BlockCount>, Error> {
+    let doc = Document::parse(xml)?;
+    let root_element = doc.root().first_child_elem()?;
+    let mut block_counts = Vec::new();
+    for block in root_element.children_elems("Block") {
+      let id_node = block.child_elem("Id")?;
+      let type_id = id_node.parse_child_elem("TypeId")?;
+      let subtype_id = id_node.parse_child_elem_opt("SubtypeId")?.unwrap_or_default();
+      let count = block.parse_child_elem("Count")?;
+      let mod_id = block.parse_child_elem_opt("ModId")?;
+      let direction = match block.child_elem_opt("Direction") {
+        Some(node) => {
+          let text = node.text_or_err()?.trim();
+          let direction = Direction::items().into_iter().find(|d| d.to_string() == text)
+            .ok_or_else(|| Error::UnrecognizedDirection(text.to_string()))?;
+          Some(direction)
+        }
+        None => None,
+      };
+      block_counts.push(BlockCount { type_id, subtype_id, mod_id, direction, count });
+    }
+    Ok(block_counts)
+  }
+}
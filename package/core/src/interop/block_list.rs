@@ -0,0 +1,53 @@
+//! Plain text lists of the blocks of a grid, for pasting into forum posts or using as a shopping list while building.
+//! Blocks are grouped by category in the order of the block lists, sorted by name, with one line per block and
+//! direction:
+//!
+//! ```text
+//! Thrusters
+//! 4x Large Hydrogen Thruster (Up)
+//! 2x Large Hydrogen Thruster (Down)
+//!
+//! Power
+//! 1x Large Reactor
+//! ```
+//!
+//! Blocks that are not in the data are listed by their ID under `Unknown`.
+
+use std::fmt::Write;
+
+use crate::data::blocks::BlockCategory;
+use crate::data::Data;
+use crate::grid::GridCalculator;
+
+/// Writes all non-zero block counts of `calculator` as a plain text block list, with names localized from `data` and
+/// directions labelled with the direction labels of `calculator`.
+pub fn to_text(calculator: &GridCalculator, data: &Data) -> String {
+  let labels = &calculator.direction_labels;
+  let mut entries: Vec<_> = calculator.block_counts().into_iter()
+    .map(|block_count| {
+      let id = block_count.block_id();
+      let category = data.blocks.category(&id);
+      let name = data.blocks.get_data(&id).map_or_else(|| id.clone(), |d| d.name(&data.localization).to_string());
+      (category, name, block_count.direction, block_count.count)
+    })
+    .collect();
+  // Unknown blocks go last.
+  entries.sort_by(|a, b| (a.0.is_none(), a.0, &a.1, a.2).cmp(&(b.0.is_none(), b.0, &b.1, b.2)));
+
+  let mut text = String::new();
+  let mut current_category = None;
+  for (category, name, direction, count) in entries {
+    if current_category != Some(category) {
+      if current_category.is_some() {
+        text.push('\n');
+      }
+      let _ = writeln!(text, "{}", category.map_or_else(|| "Unknown".to_string(), |c: BlockCategory| c.to_string()));
+      current_category = Some(category);
+    }
+    match direction {
+      Some(direction) => { let _ = writeln!(text, "{}x {} ({})", count, name, labels.get(direction)); }
+      None => { let _ = writeln!(text, "{}x {}", count, name); }
+    }
+  }
+  text
+}
@@ -0,0 +1,6 @@
+//! Interoperability with other Space Engineers tooling.
+
+pub mod block_counts;
+pub mod block_list;
+pub mod resource_chain;
+pub mod result_csv;
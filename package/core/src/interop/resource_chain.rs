@@ -0,0 +1,102 @@
+//! Power and hydrogen resource chains as Graphviz DOT graphs, for generating diagrams for guides.
+//!
+//! Producers and storage feed a power or hydrogen node, which feeds the consumer groups of the chain in priority order.
+//! Edges are labelled with the flow from the current calculation, and groups that do not get all of their resource are
+//! coloured red. Groups without consumption are left out.
+
+use std::fmt::Write;
+
+use crate::grid::{GridCalculated, GridCalculator, HydrogenChainGroup, PowerChainGroup};
+
+/// Renders the power and hydrogen chains of `calculated`, calculated from `calculator`, as a DOT graph.
+pub fn to_dot(calculator: &GridCalculator, calculated: &GridCalculated) -> String {
+  let labels = &calculator.direction_labels;
+  let mut dot = String::new();
+  dot.push_str("digraph \"Resource Chain\" {\n");
+  dot.push_str("  rankdir=LR;\n");
+  dot.push_str("  node [shape=box];\n");
+
+  // Power producers and storage
+  let _ = writeln!(dot, "  power [label=\"Power\\n{:.2} MW\", shape=ellipse];", calculated.power_generation.to_megawatts());
+  let mut other_generation = calculated.power_generation.to_megawatts();
+  if let Some(battery) = &calculated.battery {
+    let _ = writeln!(dot, "  battery [label=\"Batteries\\n{:.2} MWh\", shape=cylinder];", battery.capacity.to_megawatt_hours());
+    if calculator.battery_mode.is_discharging() {
+      edge(&mut dot, "battery", "power", battery.maximum_output.to_megawatts(), "MW", false);
+      other_generation -= battery.maximum_output.to_megawatts();
+    }
+  }
+  if let Some(hydrogen_engine) = &calculated.hydrogen_engine {
+    let _ = writeln!(dot, "  hydrogen_engine [label=\"Hydrogen Engines\\n{:.0} L\", shape=cylinder];", hydrogen_engine.capacity.to_liters());
+    if calculator.hydrogen_engine_enabled {
+      edge(&mut dot, "hydrogen_engine", "power", hydrogen_engine.maximum_output.to_megawatts(), "MW", false);
+      other_generation -= hydrogen_engine.maximum_output.to_megawatts();
+    }
+  }
+  if other_generation > 0.0 {
+    dot.push_str("  reactor [label=\"Reactors and Other Producers\"];\n");
+    edge(&mut dot, "reactor", "power", other_generation, "MW", false);
+  }
+
+  // Power consumers in priority order
+  for (priority, group) in PowerChainGroup::items().into_iter().enumerate() {
+    let power = calculated.power_upto(group);
+    if power.consumption.to_megawatts() <= 0.0 { continue; }
+    let node = match group {
+      // Generators and batteries also produce or store resources, so they get named nodes that other edges refer to.
+      PowerChainGroup::Generator => "generator".to_string(),
+      PowerChainGroup::BatteryCharge if calculated.battery.is_some() => "battery".to_string(),
+      _ => format!("power_{}", priority),
+    };
+    if node != "battery" {
+      let _ = writeln!(dot, "  {} [label=\"{}. {}\"];", node, priority + 1, escape(&group.label(labels)));
+    }
+    edge(&mut dot, "power", &node, power.consumption.to_megawatts(), "MW", power.balance.to_megawatts() < 0.0);
+  }
+
+  // Hydrogen producers and storage
+  if calculated.hydrogen_generation > 0.0 || calculated.hydrogen_tank.is_some() || calculated.hydrogen_engine.is_some() {
+    let _ = writeln!(dot, "  hydrogen [label=\"Hydrogen\\n{:.2} L/s\", shape=ellipse];", calculated.hydrogen_generation);
+    if calculated.hydrogen_generation > 0.0 {
+      if calculated.power_upto(PowerChainGroup::Generator).consumption.to_megawatts() <= 0.0 {
+        dot.push_str("  generator [label=\"O2/H2 Generators\"];\n");
+      }
+      edge(&mut dot, "generator", "hydrogen", calculated.hydrogen_generation, "L/s", false);
+    }
+    if let Some(hydrogen_tank) = &calculated.hydrogen_tank {
+      let _ = writeln!(dot, "  hydrogen_tank [label=\"Hydrogen Tanks\\n{:.0} L\", shape=cylinder];", hydrogen_tank.capacity.to_liters());
+      if calculator.hydrogen_tank_mode.is_providing() {
+        edge(&mut dot, "hydrogen_tank", "hydrogen", hydrogen_tank.maximum_output, "L/s", false);
+      }
+    }
+
+    // Hydrogen consumers in priority order
+    for (priority, group) in HydrogenChainGroup::items().into_iter().enumerate() {
+      let hydrogen = calculated.hydrogen_upto(group);
+      if hydrogen.consumption <= 0.0 { continue; }
+      let node = match group {
+        HydrogenChainGroup::EngineFill if calculated.hydrogen_engine.is_some() => "hydrogen_engine".to_string(),
+        HydrogenChainGroup::TankFill if calculated.hydrogen_tank.is_some() => "hydrogen_tank".to_string(),
+        _ => {
+          let node = format!("hydrogen_{}", priority);
+          let _ = writeln!(dot, "  {} [label=\"{}. {}\"];", node, priority + 1, escape(&group.label(labels)));
+          node
+        }
+      };
+      edge(&mut dot, "hydrogen", &node, hydrogen.consumption, "L/s", hydrogen.balance_with_tank < 0.0);
+    }
+  }
+
+  dot.push_str("}\n");
+  dot
+}
+
+/// Writes an edge from node `from` to node `to` with a `flow` in `unit`, coloured red if `lacking`.
+fn edge(dot: &mut String, from: &str, to: &str, flow: f64, unit: &str, lacking: bool) {
+  let color = if lacking { ", color=red, fontcolor=red" } else { "" };
+  let _ = writeln!(dot, "  {} -> {} [label=\"{:.2} {}\"{}];", from, to, flow, unit, color);
+}
+
+fn escape(text: &str) -> String {
+  text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,54 @@
+//! Calculation results as CSV, for spreadsheets and scripts. Results are flattened into one `path,value` row per value,
+//! with paths into the `GridCalculated` JSON separated by dots, and array elements by index:
+//!
+//! ```text
+//! path,value
+//! total_mass_empty,12000.0
+//! thruster_acceleration.0.force,400000.0
+//! ```
+//!
+//! Values that are not calculated (None) are empty, and empty objects and arrays are left out.
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use crate::grid::GridCalculated;
+
+/// Writes all values of `calculated` as CSV rows with a `path,value` header.
+pub fn to_csv(calculated: &GridCalculated) -> String {
+  let mut csv = String::new();
+  csv.push_str("path,value\n");
+  let value = serde_json::to_value(calculated).unwrap_or_default();
+  for_each_value(String::new(), &value, &mut |path, value| {
+    let _ = match value {
+      Value::Null => writeln!(csv, "{},", escape(&path)),
+      Value::String(text) => writeln!(csv, "{},{}", escape(&path), escape(text)),
+      value => writeln!(csv, "{},{}", escape(&path), value),
+    };
+  });
+  csv
+}
+
+/// Calls `f` with the path and value of each value in `value` that is not an object or array, in order.
+pub(crate) fn for_each_value(path: String, value: &Value, f: &mut impl FnMut(String, &Value)) {
+  let child_path = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+  match value {
+    Value::Object(map) => for (key, value) in map {
+      for_each_value(child_path(key), value, f);
+    }
+    Value::Array(values) => for (index, value) in values.iter().enumerate() {
+      for_each_value(child_path(&index.to_string()), value, f);
+    }
+    value => f(path, value),
+  }
+}
+
+/// Quotes `field` if it contains a separator, quote, or line break, doubling quotes inside it.
+fn escape(field: &str) -> String {
+  if field.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    field.to_string()
+  }
+}
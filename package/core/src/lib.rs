@@ -3,5 +3,9 @@
 pub mod grid;
 pub mod data;
 pub mod error;
+pub mod interop;
+pub mod precision;
+#[cfg(feature = "schema")]
+pub mod schema;
 #[cfg(feature = "extract")]
 pub mod xml;
@@ -0,0 +1,64 @@
+//! Serialization of `f64` values with explicit precision control.
+//!
+//! Values edited in the GUI accumulate floating point noise (e.g., `0.1 + 0.2 = 0.30000000000000004`). Serializing
+//! with [`serialize`] rounds values to [`DECIMALS`] decimal places first, so that saved files contain `0.3`, and
+//! saving a loaded file produces exactly the same output.
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Serializer};
+
+use crate::grid::direction::PerDirection;
+
+/// Number of decimal places that values are rounded to.
+pub const DECIMALS: i32 = 9;
+
+/// Rounds `value` to [`DECIMALS`] decimal places. Non-finite values, and values too large to be rounded without losing
+/// integer precision, are returned unchanged. Rounding is idempotent: `round(round(v)) == round(v)`.
+pub fn round(value: f64) -> f64 {
+  const MAX_EXACT_INTEGER: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+  let scale = 10f64.powi(DECIMALS);
+  let scaled = value * scale;
+  if !scaled.is_finite() || scaled.abs() >= MAX_EXACT_INTEGER {
+    return value;
+  }
+  scaled.round() / scale
+}
+
+/// Serializes `value` rounded with [`round`]. Use with `#[serde(serialize_with = "crate::precision::serialize")]`.
+pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.serialize_f64(round(*value))
+}
+
+/// Serializes `count` as an integer when it is whole, and otherwise rounded with [`round`]. Files with only whole counts
+/// therefore stay the same as when counts were integers.
+pub fn serialize_count<S: Serializer>(count: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+  Count(*count).serialize(serializer)
+}
+
+/// Serializes a map of counts with [`serialize_count`]. Use with
+/// `#[serde(serialize_with = "crate::precision::serialize_counts")]`.
+pub fn serialize_counts<S: Serializer, K: Serialize>(counts: &HashMap<K, f64>, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.collect_map(counts.iter().map(|(key, count)| (key, Count(*count))))
+}
+
+/// Serializes a map of counts per direction with [`serialize_count`]. Use with
+/// `#[serde(serialize_with = "crate::precision::serialize_counts_per_direction")]`.
+pub fn serialize_counts_per_direction<S: Serializer, K: Serialize>(counts: &HashMap<K, PerDirection<f64>>, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.collect_map(counts.iter().map(|(key, counts)| (key, counts.map(Count))))
+}
+
+#[derive(Copy, Clone)]
+struct Count(f64);
+
+impl Serialize for Count {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    const MAX_EXACT_INTEGER: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+    let count = round(self.0);
+    if count.fract() == 0.0 && (0.0..MAX_EXACT_INTEGER).contains(&count) {
+      serializer.serialize_u64(count as u64)
+    } else {
+      serializer.serialize_f64(count)
+    }
+  }
+}
@@ -0,0 +1,23 @@
+//! JSON Schemas of the data and grid calculator (saved grid) formats.
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+use crate::data::Data;
+use crate::grid::GridCalculator;
+use crate::grid::server_profile::ServerProfile;
+
+/// Gets the JSON Schema of [`Data`], the format of `data.json`.
+pub fn data_schema() -> RootSchema {
+  schema_for!(Data)
+}
+
+/// Gets the JSON Schema of [`GridCalculator`], the format of saved grids.
+pub fn grid_calculator_schema() -> RootSchema {
+  schema_for!(GridCalculator)
+}
+
+/// Gets the JSON Schema of [`ServerProfile`], the format of shared server profiles.
+pub fn server_profile_schema() -> RootSchema {
+  schema_for!(ServerProfile)
+}
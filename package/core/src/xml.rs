@@ -48,10 +48,10 @@ impl From<ParseBoolError> for XmlError {
 // XML convenience extension
 
 pub trait NodeExt<'a, 'input: 'a> {
-  fn child_elem(&self, tag: &'static str) -> Result<Node, XmlError>;
-  fn child_elem_opt(&self, tag: &'static str) -> Option<Node>;
-  fn first_child_elem(&self) -> Result<Node, XmlError>;
-  fn children_elems(&self, tag: &'static str) -> ElemChildren;
+  fn child_elem(&self, tag: &'static str) -> Result<Node<'a, 'input>, XmlError>;
+  fn child_elem_opt(&self, tag: &'static str) -> Option<Node<'a, 'input>>;
+  fn first_child_elem(&self) -> Result<Node<'a, 'input>, XmlError>;
+  fn children_elems(&self, tag: &'static str) -> ElemChildren<'a, 'input>;
 
   fn text_or_err(&self) -> Result<&str, XmlError>;
 
@@ -62,7 +62,7 @@ pub trait NodeExt<'a, 'input: 'a> {
 }
 
 impl<'a, 'input: 'a> NodeExt<'a, 'input> for Node<'a, 'input> {
-  fn child_elem(&self, tag: &'static str) -> Result<Node, XmlError> {
+  fn child_elem(&self, tag: &'static str) -> Result<Node<'a, 'input>, XmlError> {
     for node in self.children() {
       if !node.is_element() { continue }
       if !node.has_tag_name(tag) { continue }
@@ -70,7 +70,7 @@ impl<'a, 'input: 'a> NodeExt<'a, 'input> for Node<'a, 'input> {
     }
     Err(XmlError::StructureFail(Backtrace::capture()))
   }
-  fn child_elem_opt(&self, tag: &'static str) -> Option<Node> {
+  fn child_elem_opt(&self, tag: &'static str) -> Option<Node<'a, 'input>> {
     for node in self.children() {
       if !node.is_element() { continue }
       if !node.has_tag_name(tag) { continue }
@@ -78,11 +78,11 @@ impl<'a, 'input: 'a> NodeExt<'a, 'input> for Node<'a, 'input> {
     }
     None
   }
-  fn first_child_elem(&self) -> Result<Node, XmlError> {
+  fn first_child_elem(&self) -> Result<Node<'a, 'input>, XmlError> {
     self.first_element_child()
       .ok_or_else(|| XmlError::StructureFail(Backtrace::capture()))
   }
-  fn children_elems(&self, tag: &'static str) -> ElemChildren {
+  fn children_elems(&self, tag: &'static str) -> ElemChildren<'a, 'input> {
     ElemChildren { children: self.children(), tag }
   }
 
@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+
+use hashlink::LinkedHashMap;
+
+use secalc_core::data::blocks::{AirVent, Assembler, Battery, Block, BlockData, Container, Drill, Generator, GridSize, Gyroscope, HydrogenEngine, HydrogenTank, InventoryConstraint, JumpDrive, OxygenFarm, OxygenTank, Railgun, Reactor, Refinery, Thruster, ThrusterType, Weapon};
+use secalc_core::data::bottles::Bottle;
+use secalc_core::data::components::Component;
+use secalc_core::data::Data;
+use secalc_core::data::gas_properties::GasProperty;
+
+pub const HYDROGEN_TANK: &str = "OxygenTank.TestHydrogenTank";
+pub const GENERATOR: &str = "OxygenGenerator.TestGenerator";
+pub const HYDROGEN_THRUSTER: &str = "Thrust.TestHydrogenThrust";
+pub const BATTERY: &str = "BatteryBlock.TestBattery";
+pub const REACTOR: &str = "Reactor.TestReactor";
+pub const CONTAINER: &str = "CargoContainer.TestContainer";
+pub const DRILL: &str = "Drill.TestDrill";
+pub const ION_THRUSTER: &str = "Thrust.TestIonThrust";
+pub const ATMOSPHERIC_THRUSTER: &str = "Thrust.TestAtmosphericThrust";
+pub const HYDROGEN_ENGINE: &str = "HydrogenEngine.TestHydrogenEngine";
+pub const JUMP_DRIVE: &str = "JumpDrive.TestJumpDrive";
+pub const GYROSCOPE: &str = "Gyro.TestGyroscope";
+pub const REFINERY: &str = "Refinery.TestRefinery";
+pub const ASSEMBLER: &str = "Assembler.TestAssembler";
+pub const OXYGEN_TANK: &str = "OxygenTank.TestOxygenTank";
+pub const OXYGEN_FARM: &str = "OxygenFarm.TestOxygenFarm";
+pub const AIR_VENT: &str = "AirVent.TestAirVent";
+pub const TURRET: &str = "LargeGatlingTurret.TestTurret";
+pub const FIXED_WEAPON: &str = "SmallGatlingGun.TestFixedWeapon";
+pub const INTERIOR_TURRET: &str = "InteriorTurret.TestInteriorTurret";
+pub const RAILGUN: &str = "SmallMissileLauncherReload.TestRailgun";
+
+/// Creates synthetic game data with a small set of large grid blocks with easy to reason about numbers.
+pub fn data() -> Data {
+  let mut data = Data::default();
+  data.gas_properties.gas_properties.insert("Hydrogen".to_string(), GasProperty { name: "Hydrogen".to_string(), energy_density: 0.001 });
+  // Hydrogen bottle of 120 L hydrogen, and oxygen bottle of 100 L oxygen, both of 120 L and 30 kg.
+  for (id, gas_id, capacity) in [("HydrogenBottle", "Hydrogen", 120.0), ("OxygenBottle", "Oxygen", 100.0)] {
+    data.bottles.bottles.insert(id.to_string(), Bottle { name: id.to_string(), gas_id: gas_id.to_string(), mass: 30.0, volume: 120.0, capacity });
+  }
+  insert(&mut data.blocks.hydrogen_tanks, HYDROGEN_TANK, HydrogenTank {
+    capacity: 1000.0, // Maximum input and output: 50 L/s.
+    operational_power_consumption: 0.001,
+    idle_power_consumption: 0.000001,
+  });
+  insert(&mut data.blocks.generators, GENERATOR, Generator {
+    ice_consumption: 1.0,
+    inventory_volume_ice: 100.0,
+    operational_power_consumption: 0.5,
+    idle_power_consumption: 0.001,
+    oxygen_generation: 10.0,
+    hydrogen_generation: 20.0,
+  });
+  insert(&mut data.blocks.oxygen_tanks, OXYGEN_TANK, OxygenTank {
+    capacity: 1000.0, // Maximum output: 50 L/s.
+    operational_power_consumption: 0.001,
+    idle_power_consumption: 0.000001,
+  });
+  insert(&mut data.blocks.oxygen_farms, OXYGEN_FARM, OxygenFarm {
+    oxygen_generation: 1.0,
+    operational_power_consumption: 0.001,
+  });
+  insert(&mut data.blocks.air_vents, AIR_VENT, AirVent {
+    oxygen_consumption: 30.0,
+    operational_power_consumption: 0.01,
+    idle_power_consumption: 0.001,
+  });
+  insert(&mut data.blocks.thrusters, HYDROGEN_THRUSTER, Thruster {
+    ty: ThrusterType::Hydrogen,
+    fuel_gas_id: Some("Hydrogen".to_string()),
+    force: 100000.0,
+    max_consumption: 0.03, // 30 L/s
+    min_consumption: 0.0,
+    min_planetary_influence: 0.0,
+    max_planetary_influence: 1.0,
+    effectiveness_at_min_influence: 1.0,
+    effectiveness_at_max_influence: 1.0,
+    needs_atmosphere_for_influence: false,
+  });
+  // Ion thruster of 50 kN at 1 MW and 1000 kg that only works in space, and atmospheric thruster of 200 kN at 2 MW and
+  // 1000 kg that only works in atmosphere.
+  data.components.components.insert("Plate".to_string(), Component { name: "Plate".to_string(), mass: 100.0, volume: 1.0, max_stack_amount: None });
+  for (id, ty, force, max_consumption, effectiveness_at_min_influence, effectiveness_at_max_influence) in [
+    (ION_THRUSTER, ThrusterType::Ion, 50000.0, 1.0, 1.0, 0.0),
+    (ATMOSPHERIC_THRUSTER, ThrusterType::Atmospheric, 200000.0, 2.0, 0.0, 1.0),
+  ] {
+    let details = Thruster {
+      ty,
+      fuel_gas_id: None,
+      force,
+      max_consumption,
+      min_consumption: 0.0,
+      min_planetary_influence: 0.0,
+      max_planetary_influence: 1.0,
+      effectiveness_at_min_influence,
+      effectiveness_at_max_influence,
+      needs_atmosphere_for_influence: false,
+    };
+    let mut block_data = BlockData { id: id.to_string(), name: id.to_string(), size: GridSize::Large, has_physics: true, ..BlockData::default() };
+    block_data.components.insert("Plate".to_string(), 10.0);
+    data.blocks.thrusters.insert(id.to_string(), Block::new(block_data, details));
+  }
+  insert(&mut data.blocks.batteries, BATTERY, Battery {
+    capacity: 1.0,
+    input: 1.0,
+    output: 1.0,
+  });
+  insert(&mut data.blocks.hydrogen_engines, HYDROGEN_ENGINE, HydrogenEngine {
+    fuel_capacity: 1000.0,
+    max_power_generation: 1.0,
+    max_fuel_consumption: 10.0, // 100 s at full load.
+  });
+  insert(&mut data.blocks.jump_drives, JUMP_DRIVE, JumpDrive {
+    capacity: 3.0,
+    operational_power_consumption: 1.0,
+    power_efficiency: 0.8,
+    max_jump_distance: 2000000.0,
+    max_jump_mass: 1000000.0,
+  });
+  insert(&mut data.blocks.railguns, RAILGUN, Railgun {
+    capacity: 0.5,
+    operational_power_consumption: 2.0,
+    idle_power_consumption: 0.001,
+  });
+  insert(&mut data.blocks.gyroscopes, GYROSCOPE, Gyroscope {
+    torque: 1000.0,
+    operational_power_consumption: 0.01,
+  });
+  insert(&mut data.blocks.refineries, REFINERY, Refinery {
+    refine_speed: 2.0,
+    material_efficiency: 1.0,
+    operational_power_consumption: 0.5,
+    idle_power_consumption: 0.001,
+  });
+  insert(&mut data.blocks.assemblers, ASSEMBLER, Assembler {
+    assembly_speed: 1.0,
+    operational_power_consumption: 0.25,
+    idle_power_consumption: 0.001,
+  });
+  insert(&mut data.blocks.reactors, REACTOR, Reactor {
+    max_power_generation: 10.0,
+    max_fuel_consumption: 0.001,
+  });
+  insert(&mut data.blocks.containers, CONTAINER, Container {
+    inventory_volume_any: 1000.0,
+    store_any: true,
+    constraint: InventoryConstraint::Other,
+  });
+  insert(&mut data.blocks.drills, DRILL, Drill {
+    inventory_volume_ore: 100.0,
+    operational_power_consumption: 0.002,
+    idle_power_consumption: 0.000001,
+    ore_rate: 10.0,
+  });
+  insert(&mut data.blocks.turrets, TURRET, Weapon {
+    inventory_volume_ammo: 160.0, // 10 gatling ammo boxes.
+    operational_power_consumption: 0.002,
+    idle_power_consumption: 0.002,
+  });
+  insert(&mut data.blocks.fixed_weapons, FIXED_WEAPON, Weapon {
+    inventory_volume_ammo: 64.0,
+    operational_power_consumption: 0.001,
+    idle_power_consumption: 0.0001,
+  });
+  insert(&mut data.blocks.interior_turrets, INTERIOR_TURRET, Weapon {
+    inventory_volume_ammo: 2.0, // 10 rifle magazines.
+    operational_power_consumption: 0.002,
+    idle_power_consumption: 0.002,
+  });
+  data
+}
+
+fn insert<T>(map: &mut LinkedHashMap<String, Block<T>>, id: &str, details: T) {
+  let data = BlockData { id: id.to_string(), name: id.to_string(), size: GridSize::Large, has_physics: true, ..BlockData::default() };
+  map.insert(id.to_string(), Block::new(data, details));
+}
+
+pub fn assert_approx_eq(actual: f64, expected: f64) {
+  assert!((actual - expected).abs() < 1e-9, "expected {} but got {}", expected, actual);
+}
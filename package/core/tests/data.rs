@@ -0,0 +1,1131 @@
+mod common;
+
+mod conveyor_ports {
+  use secalc_core::data::blocks::{Block, BlockData, ConveyorPorts, Container, GridSize, InventoryConstraint};
+  use secalc_core::data::Data;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::warning::Warning;
+
+  use crate::common::*;
+
+  const SMALL_PORT_CONTAINER: &str = "CargoContainer.TestSmallPortContainer";
+  const LARGE_PORT_CONTAINER: &str = "CargoContainer.TestLargePortContainer";
+  const CONVERTER_CONTAINER: &str = "CargoContainer.TestConverterContainer";
+
+  fn data() -> Data {
+    let mut data = crate::common::data();
+    for (id, conveyor_ports) in [(SMALL_PORT_CONTAINER, ConveyorPorts::Small), (LARGE_PORT_CONTAINER, ConveyorPorts::Large), (CONVERTER_CONTAINER, ConveyorPorts::SmallAndLarge)] {
+      let block_data = BlockData { id: id.to_string(), name: id.to_string(), size: GridSize::Small, has_physics: true, conveyor_ports: Some(conveyor_ports), ..BlockData::default() };
+      let details = Container { inventory_volume_any: 100.0, store_any: true, constraint: InventoryConstraint::Other };
+      data.blocks.containers.insert(id.to_string(), Block::new(block_data, details));
+    }
+    data
+  }
+
+  fn mismatch(calculator: &GridCalculator) -> Option<Warning> {
+    calculator.calculate(&data()).warnings.into_iter().find(|w| matches!(w, Warning::ConveyorPortSizeMismatch { .. }))
+  }
+
+  #[test]
+  fn mixed_port_sizes_warn() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(SMALL_PORT_CONTAINER.to_string(), 1.0);
+    calculator.blocks.insert(LARGE_PORT_CONTAINER.to_string(), 2.0);
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    assert_eq!(mismatch(&calculator), Some(Warning::ConveyorPortSizeMismatch {
+      small_port_ids: vec![SMALL_PORT_CONTAINER.to_string()],
+      large_port_ids: vec![LARGE_PORT_CONTAINER.to_string()],
+    }));
+  }
+
+  #[test]
+  fn single_port_size_or_converter_does_not_warn() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(SMALL_PORT_CONTAINER.to_string(), 1.0);
+    assert_eq!(mismatch(&calculator), None);
+    calculator.blocks.insert(LARGE_PORT_CONTAINER.to_string(), 0.0);
+    assert_eq!(mismatch(&calculator), None);
+    calculator.blocks.insert(LARGE_PORT_CONTAINER.to_string(), 1.0);
+    calculator.blocks.insert(CONVERTER_CONTAINER.to_string(), 1.0);
+    assert_eq!(mismatch(&calculator), None);
+  }
+
+  #[test]
+  fn conveyor_ports_default_to_unknown() {
+    let json = r#"{ "id": "A", "name": "A", "size": "Small", "components": {}, "has_physics": true, "mod_id": null, "hidden": false, "rename": null }"#;
+    let data: BlockData = serde_json::from_str(json).unwrap();
+    assert_eq!(data.conveyor_ports, None);
+  }
+}
+
+mod custom_blocks {
+  use secalc_core::data::blocks::{GridSize, ThrusterType};
+  use secalc_core::data::custom_blocks::{CustomBlock, CustomBlockDetails};
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn custom_hydrogen_thruster() -> CustomBlock {
+    CustomBlock {
+      name: "Prototype Thruster".to_string(),
+      size: GridSize::Large,
+      mass: 2000.0,
+      details: CustomBlockDetails::Thruster { ty: ThrusterType::Hydrogen, force: 400000.0, max_consumption: 40.0 },
+    }
+  }
+
+  #[test]
+  fn custom_blocks_are_merged_into_data() {
+    let mut data = data();
+    let custom_block = custom_hydrogen_thruster();
+    data.add_custom_blocks([&custom_block]);
+    let block = data.blocks.thrusters.get(&custom_block.id()).expect("custom thruster was not added");
+    assert_eq!(block.name(&data.localization), "Prototype Thruster");
+    assert_approx_eq(block.mass(&data.components), 2000.0);
+    assert_approx_eq(block.details.actual_max_consumption(&data.gas_properties), 40.0);
+    assert!(data.blocks.thruster_blocks(GridSize::Large, &Default::default()).any(|b| b.id == custom_block.id()));
+  }
+
+  #[test]
+  fn custom_blocks_are_calculated() {
+    let mut data = data();
+    let custom_block = custom_hydrogen_thruster();
+    data.add_custom_blocks([&custom_block]);
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(custom_block.id()).or_default()[Direction::Up] = 1.0;
+    let calculated = calculator.calculate(&data);
+    assert_approx_eq(calculated.total_mass_empty.to_kilograms(), 2000.0);
+  }
+
+  #[test]
+  fn custom_blocks_replace_blocks_with_same_id() {
+    let mut data = data();
+    let mut custom_block = CustomBlock { details: CustomBlockDetails::Battery { capacity: 1.0, input: 2.0, output: 3.0 }, ..CustomBlock::default() };
+    data.add_custom_blocks([&custom_block]);
+    custom_block.details = CustomBlockDetails::Battery { capacity: 4.0, input: 5.0, output: 6.0 };
+    data.add_custom_blocks([&custom_block]);
+    assert_approx_eq(data.blocks.batteries.get(&custom_block.id()).unwrap().capacity, 4.0);
+  }
+}
+
+mod direction_labels {
+  use secalc_core::grid::direction::{Direction, DirectionLabels};
+  use secalc_core::grid::forces::ForceSource;
+  use secalc_core::grid::GridCalculator;
+
+  #[test]
+  fn default_labels_are_direction_names() {
+    let labels = DirectionLabels::default();
+    assert!(labels.is_default());
+    for direction in Direction::items() {
+      assert_eq!(labels.get(direction), direction.to_string());
+    }
+    assert_eq!(labels.pair(Direction::Front, Direction::Back), "Front/Back");
+  }
+
+  #[test]
+  fn custom_labels_replace_direction_names() {
+    let mut labels = DirectionLabels::default();
+    *labels.custom_mut(Direction::Front) = "Forward".to_string();
+    *labels.custom_mut(Direction::Back) = "  ".to_string(); // Blank labels are not custom.
+    assert!(!labels.is_default());
+    assert_eq!(labels.get(Direction::Front), "Forward");
+    assert_eq!(labels.get(Direction::Back), "Back");
+    assert_eq!(labels.pair(Direction::Front, Direction::Back), "Forward/Back");
+    assert_eq!(ForceSource::Thrusters(Direction::Front).label(&labels), "Forward Thrusters");
+    assert_eq!(ForceSource::Wheels.label(&labels), "Wheels");
+  }
+
+  #[test]
+  fn labels_are_stored_with_calculator() {
+    let mut calculator = GridCalculator::default();
+    *calculator.direction_labels.custom_mut(Direction::Front) = "Forward".to_string();
+    let json = serde_json::to_string(&calculator).unwrap();
+    let calculator = GridCalculator::from_json(json.as_bytes()).unwrap();
+    assert_eq!(calculator.direction_labels.get(Direction::Front), "Forward");
+    // Calculators saved before direction labels existed have default labels.
+    let calculator = GridCalculator::from_json("{}".as_bytes()).unwrap();
+    assert!(calculator.direction_labels.is_default());
+  }
+}
+
+mod enum_names {
+  use std::fmt::Debug;
+  use std::str::FromStr;
+
+  use serde::de::DeserializeOwned;
+  use serde::Serialize;
+
+  use secalc_core::data::blocks::GridSize;
+  use secalc_core::grid::{BatteryMode, HydrogenTankMode};
+
+  fn assert_round_trips<T>(items: impl IntoIterator<Item=T>) where
+    T: Copy + PartialEq + Debug + ToString + FromStr + Serialize + DeserializeOwned,
+    T::Err: Debug
+  {
+    for item in items {
+      let name = item.to_string();
+      assert_eq!(T::from_str(&name).unwrap(), item);
+      assert_eq!(T::from_str(&name.to_lowercase()).unwrap(), item);
+      let json = serde_json::to_string(&item).unwrap();
+      assert_eq!(json, format!("\"{}\"", name));
+      assert_eq!(serde_json::from_str::<T>(&json).unwrap(), item);
+    }
+  }
+
+  #[test]
+  fn grid_size_round_trips() {
+    assert_round_trips(GridSize::items());
+  }
+
+  #[test]
+  fn battery_mode_round_trips() {
+    assert_round_trips(BatteryMode::items());
+  }
+
+  #[test]
+  fn hydrogen_tank_mode_round_trips() {
+    assert_round_trips(HydrogenTankMode::items());
+  }
+
+  #[test]
+  fn unrecognized_name_lists_expected_names() {
+    let error = GridSize::from_str("Medium").unwrap_err();
+    assert_eq!(error.to_string(), "Unrecognized grid size 'Medium'; expected one of: Small, Large");
+    assert!(BatteryMode::from_str("").is_err());
+  }
+}
+
+#[cfg(feature = "extract")]
+mod extract {
+  use std::path::PathBuf;
+
+  use secalc_core::data::blocks::{GridSize, InventoryConstraint, ThrusterType};
+  use secalc_core::data::blocks::extract::RawDefinition;
+  use secalc_core::data::cube_sizes::CubeSizes;
+  use secalc_core::data::Data;
+  use secalc_core::data::extract::{CancellationToken, ExtractConfig, ExtractError, ExtractStep, ExtractTask, resolve_workshop_directory};
+  use secalc_core::data::mods::Mod;
+  use secalc_core::data::power_groups::PowerGroup;
+  use secalc_core::data::validate::DataProblem;
+
+  use crate::common::assert_approx_eq;
+
+  fn fixtures_directory() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+  }
+
+  fn extract(extract_config: ExtractConfig) -> Data {
+    let fixtures_directory = fixtures_directory();
+    Data::extract_from_se_dir(fixtures_directory.join("se"), Some(fixtures_directory.join("workshop")), extract_config)
+      .expect("extracting fixture data should succeed")
+  }
+
+  #[test]
+  fn extracts_components_gas_properties_and_localization() {
+    let data = extract(ExtractConfig::default());
+    assert_eq!(data.components.components.len(), 2);
+    let steel_plate = data.components.get("SteelPlate").unwrap();
+    assert_eq!(steel_plate.name(&data.localization), "Steel Plate");
+    assert_approx_eq(steel_plate.mass, 20.0);
+    assert_approx_eq(steel_plate.volume, 3.0);
+    assert_approx_eq(data.gas_properties.get("Hydrogen").unwrap().energy_density, 0.001556);
+    assert_approx_eq(data.gas_properties.get("Oxygen").unwrap().energy_density, 0.0);
+  }
+
+  #[test]
+  fn extracts_bottles() {
+    let data = extract(ExtractConfig::default());
+    assert_eq!(data.bottles.bottles.len(), 2);
+    let hydrogen_bottle = data.bottles.get_by_gas("Hydrogen").unwrap();
+    assert_eq!(hydrogen_bottle.name(&data.localization), "Hydrogen Bottle");
+    assert_approx_eq(hydrogen_bottle.mass, 30.0);
+    assert_approx_eq(hydrogen_bottle.volume, 120.0);
+    assert_approx_eq(hydrogen_bottle.capacity, 120.0);
+    let oxygen_bottle = data.bottles.get_by_gas("Oxygen").unwrap();
+    assert_approx_eq(oxygen_bottle.capacity, 100.0);
+  }
+
+  #[test]
+  fn extracts_blocks_from_all_cube_blocks_files() {
+    let data = extract(ExtractConfig::default());
+    let blocks = &data.blocks;
+    assert_eq!(blocks.batteries.len(), 2);
+    assert_eq!(blocks.thrusters.len(), 2);
+    assert_eq!(blocks.containers.len(), 4);
+    assert_eq!(blocks.hydrogen_tanks.len(), 1, "oxygen tanks should not be extracted as hydrogen tanks");
+    assert_eq!(blocks.oxygen_tanks.len(), 1);
+    assert_eq!(blocks.generators.len(), 1);
+    assert_eq!(blocks.drills.len(), 1);
+
+    let battery = blocks.batteries.get("BatteryBlock.LargeBlockBatteryBlock").unwrap();
+    assert_eq!(battery.name(&data.localization), "Battery");
+    assert_eq!(battery.data.size, GridSize::Large);
+    assert_approx_eq(*battery.data.components.get("SteelPlate").unwrap(), 80.0); // Duplicate components are summed.
+    assert_approx_eq(battery.mass(&data.components), 80.0 * 20.0 + 40.0 * 25.0);
+    assert_approx_eq(battery.capacity, 3.0);
+    assert_approx_eq(battery.input, 12.0);
+    assert_approx_eq(battery.output, 12.0);
+    assert_eq!(battery.data.build_time, Some(40.0));
+
+    let hydrogen_thruster = blocks.thrusters.get("Thrust.LargeBlockLargeHydrogenThrust").unwrap();
+    assert_eq!(hydrogen_thruster.ty, ThrusterType::Hydrogen);
+    assert_eq!(hydrogen_thruster.fuel_gas_id.as_deref(), Some("Hydrogen"));
+    assert_approx_eq(hydrogen_thruster.actual_max_consumption(&data.gas_properties), 6.426 / 0.001556);
+    let ion_thruster = blocks.thrusters.get("Thrust.SmallBlockSmallThrust").unwrap();
+    assert_eq!(ion_thruster.ty, ThrusterType::Ion);
+    assert_eq!(ion_thruster.data.size, GridSize::Small);
+    assert_approx_eq(ion_thruster.effectiveness_at_max_influence, 0.3);
+
+    let container = blocks.containers.get("CargoContainer.LargeBlockSmallContainer").unwrap();
+    assert!(container.store_any);
+    assert_approx_eq(container.inventory_volume_any, 8000.0);
+    assert_eq!(container.data.build_time, Some(10.0), "blocks without a build time should get the default build time");
+    let locker = blocks.containers.get("CargoContainer.LargeBlockLockerRoom").unwrap();
+    assert!(!locker.store_any);
+    assert_eq!(locker.constraint, InventoryConstraint::Other);
+    let ore_bin = blocks.containers.get("CargoContainer.LargeBlockOreBin").unwrap();
+    assert!(!ore_bin.store_any);
+    assert_eq!(ore_bin.constraint, InventoryConstraint::Ore);
+    let ice_bin = blocks.containers.get("CargoContainer.LargeBlockIceBin").unwrap();
+    assert_eq!(ice_bin.constraint, InventoryConstraint::Ice);
+
+    let generator = blocks.generators.get("OxygenGenerator.").unwrap();
+    assert_approx_eq(generator.hydrogen_generation, 600.0);
+    assert_approx_eq(generator.oxygen_generation, 300.0);
+    assert_approx_eq(generator.inventory_volume_ice, 4000.0);
+
+    let gyroscope = blocks.gyroscopes.get("Gyro.LargeBlockGyro").unwrap();
+    assert_approx_eq(gyroscope.torque, 33600000.0);
+    assert_approx_eq(gyroscope.operational_power_consumption, 0.00003);
+
+    let oxygen_tank = blocks.oxygen_tanks.get("OxygenTank.").unwrap();
+    assert_approx_eq(oxygen_tank.capacity, 100000.0);
+    let oxygen_farm = blocks.oxygen_farms.get("OxygenFarm.LargeBlockOxygenFarm").unwrap();
+    assert_approx_eq(oxygen_farm.oxygen_generation, 0.9);
+    assert_approx_eq(oxygen_farm.operational_power_consumption, 0.0003);
+    let air_vent = blocks.air_vents.get("AirVent.").unwrap();
+    assert_approx_eq(air_vent.oxygen_consumption, 300.0);
+    assert_approx_eq(air_vent.idle_power_consumption, 0.001);
+
+    let refinery = blocks.refineries.get("Refinery.LargeRefinery").unwrap();
+    assert_approx_eq(refinery.refine_speed, 1.3);
+    assert_approx_eq(refinery.material_efficiency, 1.0);
+    assert_approx_eq(refinery.operational_power_consumption, 0.56);
+    assert_approx_eq(refinery.idle_power_consumption, 0.001);
+    let assembler = blocks.assemblers.get("Assembler.LargeAssembler").unwrap();
+    assert_approx_eq(assembler.assembly_speed, 1.0); // Defaults to 1 when not defined.
+    assert_approx_eq(assembler.operational_power_consumption, 0.56);
+
+    let drill = blocks.drills.get("Drill.SmallBlockDrill").unwrap();
+    assert_approx_eq(drill.inventory_volume_ore, 3.0 * 3.0 * 4.0 * 0.125 * 0.5 * 1000.0);
+    assert_approx_eq(drill.ore_rate, (4.0 / 3.0) * std::f64::consts::PI * 1.2f64.powi(3) * 1000.0 / 1.5);
+
+    let turret = blocks.turrets.get("LargeGatlingTurret.").unwrap();
+    assert_approx_eq(turret.inventory_volume_ammo, 384.0);
+    assert_approx_eq(turret.operational_power_consumption, 0.002);
+    let interior_turret = blocks.interior_turrets.get("InteriorTurret.LargeInteriorTurret").unwrap();
+    assert_approx_eq(interior_turret.inventory_volume_ammo, 10.0);
+    let fixed_weapon = blocks.fixed_weapons.get("SmallGatlingGun.").unwrap();
+    assert_approx_eq(fixed_weapon.inventory_volume_ammo, 64.0);
+    assert_approx_eq(fixed_weapon.operational_power_consumption, 0.0002);
+    assert_eq!(blocks.turrets.len(), 1, "interior turrets should not be extracted as turrets");
+  }
+
+  #[test]
+  fn extracts_cube_sizes() {
+    let data = extract(ExtractConfig::default());
+    assert_approx_eq(data.cube_sizes.get(GridSize::Small), 0.5);
+    assert_approx_eq(data.cube_sizes.get(GridSize::Large), 2.5);
+
+    let cube_sizes = CubeSizes::from_sbc_file(fixtures_directory().join("configuration_custom_cube_sizes.sbc")).unwrap();
+    assert_approx_eq(cube_sizes.small, 1.0);
+    assert_approx_eq(cube_sizes.large, 2.5); // Not defined, so falls back to the default.
+    let cube_sizes = CubeSizes::from_se_dir(fixtures_directory().join("workshop")).unwrap();
+    assert_eq!(cube_sizes, CubeSizes::default()); // No Configuration.sbc, so falls back to the defaults.
+  }
+
+  #[test]
+  fn non_public_blocks_are_hidden() {
+    let data = extract(ExtractConfig::default());
+    assert!(data.blocks.batteries.get("BatteryBlock.LargeBlockDebugBattery").unwrap().data.hidden);
+    assert!(!data.blocks.batteries.get("BatteryBlock.LargeBlockBatteryBlock").unwrap().data.hidden);
+  }
+
+  #[test]
+  fn extracts_mod_blocks_and_localization() {
+    let extract_config = ExtractConfig { extract_mods: vec![Mod(1000, "Mod Batteries".to_string())], ..ExtractConfig::default() };
+    let data = extract(extract_config);
+    assert_eq!(data.blocks.batteries.len(), 3);
+    let battery = data.blocks.batteries.get("BatteryBlock.LargeBatteryMk2@1000").unwrap();
+    assert_eq!(battery.data.mod_id, Some(1000));
+    assert_eq!(battery.name(&data.localization), "Battery Mk2");
+    assert_approx_eq(battery.capacity, 9.0);
+  }
+
+  #[test]
+  fn mods_are_not_extracted_unless_configured() {
+    let data = extract(ExtractConfig::default());
+    assert!(data.blocks.batteries.get("BatteryBlock.LargeBatteryMk2@1000").is_none());
+  }
+
+  #[test]
+  fn extracts_mods_from_multiple_workshop_directories() {
+    let fixtures_directory = fixtures_directory();
+    assert_eq!(resolve_workshop_directory(fixtures_directory.join("dedicated_server")), fixtures_directory.join("dedicated_server/content/244850"));
+    assert_eq!(resolve_workshop_directory(fixtures_directory.join("workshop")), fixtures_directory.join("workshop"));
+
+    let extract_config = ExtractConfig {
+      extract_mods: vec![Mod(1000, "Mod Batteries".to_string()), Mod(3000, "Server Batteries".to_string()), Mod(4000, "Missing".to_string())],
+      ..ExtractConfig::default()
+    };
+    let workshop_directories = [fixtures_directory.join("workshop"), fixtures_directory.join("dedicated_server")];
+    let data = Data::extract_from_se_dir(fixtures_directory.join("se"), workshop_directories, extract_config)
+      .expect("extracting fixture data should succeed");
+    assert!(data.blocks.batteries.get("BatteryBlock.LargeBatteryMk2@1000").is_some());
+    let server_battery = data.blocks.batteries.get("BatteryBlock.LargeServerBattery@3000").unwrap();
+    assert_eq!(server_battery.data.mod_id, Some(3000));
+    assert_approx_eq(server_battery.capacity, 6.0);
+  }
+
+  #[test]
+  fn detects_mod_overrides() {
+    let extract_config = ExtractConfig {
+      extract_mods: vec![Mod(1000, "Mod Batteries".to_string()), Mod(2000, "Battery Overrides".to_string())],
+      ..ExtractConfig::default()
+    };
+    let data = extract(extract_config);
+    let overrides = &data.mods.overrides;
+    assert_eq!(overrides.len(), 2, "{:?}", overrides);
+    let vanilla_override = overrides.iter().find(|o| o.id == "BatteryBlock.LargeBlockBatteryBlock").unwrap();
+    assert_eq!(vanilla_override.mod_id, Some(2000));
+    assert_eq!(vanilla_override.overridden_mod_id, None);
+    let mod_override = overrides.iter().find(|o| o.id == "BatteryBlock.LargeBatteryMk2").unwrap();
+    assert_eq!(mod_override.mod_id, Some(2000));
+    assert_eq!(mod_override.overridden_mod_id, Some(1000));
+  }
+
+  #[test]
+  fn extracts_selected_language_with_english_fallback() {
+    let data = extract(ExtractConfig { language: Some("de".to_string()), ..ExtractConfig::default() });
+    assert_eq!(data.blocks.batteries.get("BatteryBlock.LargeBlockBatteryBlock").unwrap().name(&data.localization), "Batterie");
+    assert_eq!(data.components.get("SteelPlate").unwrap().name(&data.localization), "Stahlplatte");
+    assert_eq!(data.blocks.drills.get("Drill.SmallBlockDrill").unwrap().name(&data.localization), "Drill");
+    let english = extract(ExtractConfig::default());
+    assert!(english.localization.selected_language.is_empty());
+    assert_eq!(english.blocks.batteries.get("BatteryBlock.LargeBlockBatteryBlock").unwrap().name(&english.localization), "Battery");
+  }
+
+  #[test]
+  fn adds_fallbacks_for_unresolved_localization_keys() {
+    let extract_config = ExtractConfig {
+      extract_mods: vec![Mod(1000, "Mod Batteries".to_string()), Mod(2000, "Battery Overrides".to_string())],
+      ..ExtractConfig::default()
+    };
+    let data = extract(extract_config);
+    let fallbacks: Vec<_> = data.localization.fallbacks.iter().collect();
+    assert_eq!(fallbacks, [(&"Description_OverriddenBatteryMk2".to_string(), &"Overridden Battery Mk2".to_string())]);
+    let battery = data.blocks.batteries.get("BatteryBlock.LargeBatteryMk2@2000").unwrap();
+    assert_eq!(battery.description(&data.localization), Some("Overridden Battery Mk2"));
+    assert!(data.validate().problems.contains(&DataProblem::UnresolvedLocalizationKey {
+      key: "Description_OverriddenBatteryMk2".to_string(),
+      fallback: "Overridden Battery Mk2".to_string(),
+    }));
+    assert!(extract(ExtractConfig::default()).localization.fallbacks.is_empty());
+  }
+
+  #[test]
+  fn no_overrides_without_conflicting_mods() {
+    let extract_config = ExtractConfig { extract_mods: vec![Mod(1000, "Mod Batteries".to_string())], ..ExtractConfig::default() };
+    assert!(extract(extract_config).mods.overrides.is_empty());
+  }
+
+  #[test]
+  fn assigns_configured_power_groups() {
+    let extract_config = ExtractConfig {
+      assign_power_group_by_exact_id: vec![("Drill.SmallBlockDrill".to_string(), PowerGroup::Production)],
+      ..ExtractConfig::default()
+    };
+    let data = extract(extract_config);
+    assert_eq!(data.power_groups.get("Drill.SmallBlockDrill"), Some(PowerGroup::Production));
+    assert_eq!(data.power_groups.get("BatteryBlock.LargeBlockBatteryBlock"), None);
+  }
+
+  #[test]
+  fn extraction_reports_progress_for_each_step() {
+    let fixtures_directory = fixtures_directory();
+    let extract_config = ExtractConfig { extract_mods: vec![Mod(1000, "Mod Batteries".to_string())], ..ExtractConfig::default() };
+    let mut progress = Vec::new();
+    Data::extract_from_se_dir_cancellable(fixtures_directory.join("se"), Some(fixtures_directory.join("workshop")), extract_config, &CancellationToken::default(), |p| progress.push(p))
+      .expect("extracting fixture data should succeed");
+    let steps: Vec<ExtractStep> = progress.iter().map(|p| p.step).collect();
+    assert_eq!(steps, vec![
+      ExtractStep::Localization,
+      ExtractStep::ModLocalization(1000),
+      ExtractStep::Blocks,
+      ExtractStep::ModBlocks(1000),
+      ExtractStep::Components,
+      ExtractStep::Bottles,
+      ExtractStep::GasProperties,
+    ]);
+    assert!(progress.iter().enumerate().all(|(i, p)| p.completed == i && p.total == 7));
+  }
+
+  #[test]
+  fn cancelled_extraction_stops_before_next_step() {
+    let fixtures_directory = fixtures_directory();
+    let cancellation_token = CancellationToken::default();
+    let mut steps = Vec::new();
+    let result = Data::extract_from_se_dir_cancellable(fixtures_directory.join("se"), Some(fixtures_directory.join("workshop")), ExtractConfig::default(), &cancellation_token, |p| {
+      steps.push(p.step);
+      if p.step == ExtractStep::Blocks { cancellation_token.cancel(); }
+    });
+    assert!(matches!(result, Err(ExtractError::Cancelled)));
+    assert_eq!(steps, vec![ExtractStep::Localization, ExtractStep::Blocks]);
+  }
+
+  #[test]
+  fn extract_task_extracts_on_background_thread() {
+    let fixtures_directory = fixtures_directory();
+    let task = ExtractTask::spawn(fixtures_directory.join("se"), Some(fixtures_directory.join("workshop")), ExtractConfig::default());
+    let data = task.join().expect("extracting fixture data should succeed");
+    assert_eq!(data.components.components.len(), 2);
+  }
+
+  #[test]
+  fn finds_raw_definition_values_with_source() {
+    let fixtures_directory = fixtures_directory();
+    let definitions = RawDefinition::find(fixtures_directory.join("se"), Some(fixtures_directory.join("workshop")), "BatteryBlock.LargeBlockBatteryBlock")
+      .expect("finding raw definitions should succeed");
+    assert_eq!(definitions.len(), 1);
+    let definition = &definitions[0];
+    assert!(definition.file.ends_with("CubeBlocks_Power.sbc"), "{:?}", definition.file);
+    assert_eq!(definition.line, 4);
+    let value = |path: &str| definition.values.iter().find(|(p, _)| p == path).map(|(_, v)| v.as_str());
+    assert_eq!(value("/Id/SubtypeId"), Some("LargeBlockBatteryBlock"));
+    assert_eq!(value("/MaxStoredPower"), Some("3"));
+    assert_eq!(value("/Components/Component[2]@Count"), Some("20"));
+  }
+
+  #[test]
+  fn finds_raw_definitions_of_mod_blocks_in_mod_directory() {
+    let fixtures_directory = fixtures_directory();
+    let definitions = RawDefinition::find(fixtures_directory.join("se"), Some(fixtures_directory.join("workshop")), "BatteryBlock.LargeBatteryMk2@1000")
+      .expect("finding raw definitions should succeed");
+    assert_eq!(definitions.len(), 1);
+    assert!(definitions[0].file.starts_with(fixtures_directory.join("workshop/1000")));
+    let none = RawDefinition::find(fixtures_directory.join("se"), Some(fixtures_directory.join("workshop")), "BatteryBlock.DoesNotExist")
+      .expect("finding raw definitions should succeed");
+    assert!(none.is_empty());
+  }
+
+  #[test]
+  fn records_block_sources_when_enabled() {
+    let extract_config = ExtractConfig { extract_mods: vec![Mod(1000, "Mod Batteries".to_string())], record_block_sources: true, ..ExtractConfig::default() };
+    let data = extract(extract_config);
+    let source = data.blocks.batteries.get("BatteryBlock.LargeBlockBatteryBlock").unwrap().data.source.clone().unwrap();
+    assert_eq!(source.file, "Content/Data/CubeBlocks/CubeBlocks_Power.sbc");
+    assert_eq!(source.line, 4);
+    let mod_source = data.blocks.batteries.get("BatteryBlock.LargeBatteryMk2@1000").unwrap().data.source.clone().unwrap();
+    assert_eq!(mod_source.file, "Data/CubeBlocks_ModBatteries.sbc");
+  }
+
+  #[test]
+  fn does_not_record_block_sources_by_default() {
+    let data = extract(ExtractConfig::default());
+    assert!(data.blocks.batteries.values().all(|b| b.data.source.is_none()));
+  }
+}
+
+#[cfg(feature = "extract")]
+mod extract_filter {
+  use std::collections::HashSet;
+  use std::fs::File;
+  use std::path::PathBuf;
+
+  use regex::{Regex, RegexSet};
+  use roxmltree::Document;
+
+  use secalc_core::data::blocks::BlockData;
+  use secalc_core::data::Data;
+  use secalc_core::data::extract::ExtractConfig;
+  use secalc_core::data::localization::Localization;
+
+  /// Hide and rename rules for [`BlockData::from_def`], empty by default.
+  #[derive(Default)]
+  struct Rules {
+    exact_name: HashSet<String>,
+    regex_name: Vec<&'static str>,
+    exact_subtype_id: HashSet<String>,
+    regex_subtype_id: Vec<&'static str>,
+    exact_id: HashSet<String>,
+    regex_id: Vec<&'static str>,
+    rename: Vec<(&'static str, &'static str)>,
+  }
+
+  fn exact(names: &[&str]) -> HashSet<String> {
+    names.iter().map(|n| n.to_string()).collect()
+  }
+
+  /// Creates a synthetic large grid block definition with `subtype_id`, `display_name`, and optional `public` flag.
+  fn definition(subtype_id: &str, display_name: &str, public: Option<&str>) -> String {
+    let public = public.map(|p| format!("<Public>{}</Public>", p)).unwrap_or_default();
+    format!(r#"<Definition>
+  <Id><TypeId>BatteryBlock</TypeId><SubtypeId>{}</SubtypeId></Id>
+  <DisplayName>{}</DisplayName>
+  <CubeSize>Large</CubeSize>
+  <Components><Component Subtype="SteelPlate" Count="10" /></Components>
+  {}
+</Definition>"#, subtype_id, display_name, public)
+  }
+
+  fn from_def(xml: &str, mod_id: Option<u64>, rules: &Rules) -> BlockData {
+    let document = Document::parse(xml).unwrap();
+    let rename: Vec<_> = rules.rename.iter().map(|(r, n)| (Regex::new(r).unwrap(), n.to_string())).collect();
+    BlockData::from_def(
+      &document.root_element(),
+      &Localization::default(),
+      mod_id,
+      &rules.exact_name,
+      &RegexSet::new(&rules.regex_name).unwrap(),
+      &rules.exact_subtype_id,
+      &RegexSet::new(&rules.regex_subtype_id).unwrap(),
+      &rules.exact_id,
+      &RegexSet::new(&rules.regex_id).unwrap(),
+      &rename,
+    ).unwrap()
+  }
+
+  #[test]
+  fn no_rules_shows_block() {
+    let data = from_def(&definition("LargeBattery", "Battery", None), None, &Rules::default());
+    assert_eq!(data.id, "BatteryBlock.LargeBattery");
+    assert!(!data.hidden);
+    assert_eq!(data.rename, None);
+  }
+
+  #[test]
+  fn description_is_localized() {
+    let xml = r#"<Definition>
+  <Id><TypeId>BatteryBlock</TypeId><SubtypeId>LargeBattery</SubtypeId></Id>
+  <DisplayName>DisplayName_Block_Battery</DisplayName>
+  <Description>Description_Battery</Description>
+  <CubeSize>Large</CubeSize>
+  <Components><Component Subtype="SteelPlate" Count="10" /></Components>
+</Definition>"#;
+    let data = from_def(xml, None, &Rules::default());
+    assert_eq!(data.description.as_deref(), Some("Description_Battery"));
+    let mut localization = Localization::default();
+    localization.localization.insert("Description_Battery".to_string(), "Stores power.".to_string());
+    assert_eq!(data.description(&localization), Some("Stores power."));
+    assert_eq!(from_def(&definition("LargeBattery", "Battery", None), None, &Rules::default()).description, None);
+  }
+
+  #[test]
+  fn hide_by_exact_name() {
+    let rules = Rules { exact_name: exact(&["Battery"]), ..Rules::default() };
+    assert!(from_def(&definition("LargeBattery", "Battery", None), None, &rules).hidden);
+    // Exact names must match the whole name.
+    assert!(!from_def(&definition("LargeBattery", "Battery Mk2", None), None, &rules).hidden);
+  }
+
+  #[test]
+  fn hide_by_regex_name() {
+    let rules = Rules { regex_name: vec!["^Warfare "], ..Rules::default() };
+    assert!(from_def(&definition("LargeBattery", "Warfare Battery", None), None, &rules).hidden);
+    assert!(!from_def(&definition("LargeBattery", "Battery (Warfare)", None), None, &rules).hidden);
+  }
+
+  #[test]
+  fn hide_by_subtype_id() {
+    let rules = Rules { exact_subtype_id: exact(&["LargeBattery"]), ..Rules::default() };
+    assert!(from_def(&definition("LargeBattery", "Battery", None), None, &rules).hidden);
+    assert!(!from_def(&definition("SmallBattery", "Battery", None), None, &rules).hidden);
+
+    let rules = Rules { regex_subtype_id: vec!["^Small"], ..Rules::default() };
+    assert!(from_def(&definition("SmallBattery", "Battery", None), None, &rules).hidden);
+    assert!(!from_def(&definition("LargeBattery", "Battery", None), None, &rules).hidden);
+  }
+
+  #[test]
+  fn hide_by_id() {
+    let rules = Rules { exact_id: exact(&["BatteryBlock.LargeBattery"]), ..Rules::default() };
+    assert!(from_def(&definition("LargeBattery", "Battery", None), None, &rules).hidden);
+    // Mod block IDs have a `@<mod ID>` suffix, which exact IDs must include.
+    assert!(!from_def(&definition("LargeBattery", "Battery", None), Some(1000), &rules).hidden);
+
+    let rules = Rules { regex_id: vec!["@1000$"], ..Rules::default() };
+    assert!(from_def(&definition("LargeBattery", "Battery", None), Some(1000), &rules).hidden);
+    assert!(!from_def(&definition("LargeBattery", "Battery", None), None, &rules).hidden);
+  }
+
+  #[test]
+  fn rename_by_regex() {
+    let rules = Rules { rename: vec![("Wheel Suspension (.+) Right", "$1 Wheel Suspension"), ("Wheel", "Never Used")], ..Rules::default() };
+    let data = from_def(&definition("Suspension", "Wheel Suspension 3x3 Right", None), None, &rules);
+    assert_eq!(data.rename.as_deref(), Some("3x3 Wheel Suspension"));
+    assert_eq!(data.name(&Localization::default()), "3x3 Wheel Suspension");
+    let data = from_def(&definition("Battery", "Battery", None), None, &rules);
+    assert_eq!(data.rename, None);
+  }
+
+  #[test]
+  fn public_flag() {
+    let rules = Rules::default();
+    assert!(from_def(&definition("LargeBattery", "Battery", Some("false")), None, &rules).hidden);
+    assert!(!from_def(&definition("LargeBattery", "Battery", Some("true")), None, &rules).hidden);
+    // Unparseable public flags are treated as public.
+    assert!(!from_def(&definition("LargeBattery", "Battery", Some("maybe")), None, &rules).hidden);
+    // Non-public blocks stay hidden, and public blocks can still be hidden by rules.
+    let rules = Rules { exact_name: exact(&["Battery"]), ..Rules::default() };
+    assert!(from_def(&definition("LargeBattery", "Battery", Some("false")), None, &rules).hidden);
+    assert!(from_def(&definition("LargeBattery", "Battery", Some("true")), None, &rules).hidden);
+    // Hidden blocks are still renamed.
+    let rules = Rules { rename: vec![("Battery", "Cell")], ..Rules::default() };
+    assert_eq!(from_def(&definition("LargeBattery", "Battery", Some("false")), None, &rules).rename.as_deref(), Some("Cell"));
+  }
+
+  fn fixtures_directory() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+  }
+
+  #[test]
+  fn fixture_filter_config() {
+    let fixtures_directory = fixtures_directory();
+    let config_file = File::open(fixtures_directory.join("extract_config_filters.ron")).unwrap();
+    let extract_config: ExtractConfig = ron::de::from_reader(config_file).unwrap();
+    let data = Data::extract_from_se_dir(fixtures_directory.join("se"), None::<PathBuf>, extract_config).unwrap();
+    let blocks = &data.blocks;
+    let is_hidden = |id: &str| blocks.get_data(&id.to_string()).unwrap().hidden;
+    assert!(is_hidden("CargoContainer.LargeBlockLockerRoom"), "hidden by exact name");
+    assert!(is_hidden("Thrust.SmallBlockSmallThrust"), "hidden by regex name");
+    assert!(is_hidden("CargoContainer.LargeBlockOreBin"), "hidden by exact subtype ID");
+    assert!(is_hidden("CargoContainer.LargeBlockIceBin"), "hidden by regex subtype ID");
+    assert!(is_hidden("Drill.SmallBlockDrill"), "hidden by exact ID");
+    assert!(is_hidden("OxygenTank.LargeHydrogenTank"), "hidden by regex ID");
+    assert!(is_hidden("BatteryBlock.LargeBlockDebugBattery"), "hidden by public flag");
+    assert!(!is_hidden("CargoContainer.LargeBlockSmallContainer"));
+    assert!(!is_hidden("BatteryBlock.LargeBlockBatteryBlock"));
+    assert!(!is_hidden("Thrust.LargeBlockLargeHydrogenThrust"));
+
+    let name = |id: &str| blocks.get_data(&id.to_string()).unwrap().name(&data.localization).to_string();
+    assert_eq!(name("CargoContainer.LargeBlockSmallContainer"), "Tiny Cargo Container");
+    assert_eq!(name("Thrust.LargeBlockLargeHydrogenThrust"), "Hydrogen Thruster (Large)");
+    assert_eq!(name("Thrust.SmallBlockSmallThrust"), "Small Ion Thrust");
+    assert_eq!(name("BatteryBlock.LargeBlockBatteryBlock"), "Battery");
+  }
+
+  #[test]
+  fn bundled_filter_config_is_valid() {
+    let config_file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/../../data/extract_config.ron")).unwrap();
+    let extract_config: ExtractConfig = ron::de::from_reader(config_file).unwrap();
+    for regex in extract_config.hide_block_by_regex_name.iter()
+      .chain(&extract_config.hide_block_by_regex_subtype_id)
+      .chain(&extract_config.hide_block_by_regex_id)
+      .chain(extract_config.rename_block_by_regex.iter().map(|(regex, _)| regex))
+    {
+      assert!(Regex::new(regex).is_ok(), "invalid regex '{}'", regex);
+    }
+  }
+}
+
+mod hidden_blocks {
+  use secalc_core::data::Data;
+  use secalc_core::data::validate::GridProblem;
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::warning::Warning;
+
+  use crate::common::*;
+
+  fn data() -> Data {
+    let mut data = crate::common::data();
+    data.blocks.thrusters.get_mut(ION_THRUSTER).unwrap().data.hidden = true;
+    data
+  }
+
+  fn calculator(ignore_hidden_blocks: bool) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    calculator.ignore_hidden_blocks = ignore_hidden_blocks;
+    calculator
+  }
+
+  #[test]
+  fn hidden_blocks_are_calculated_by_default() {
+    let data = data();
+    let calculated = calculator(false).calculate(&data);
+    assert_approx_eq(calculated.total_mass_empty.to_kilograms(), 2000.0);
+    assert!(!calculated.warnings.iter().any(|w| matches!(w, Warning::HiddenBlocksIgnored { .. })));
+    assert_eq!(calculator(false).validate(&data).problems, vec![GridProblem::HiddenBlock { block_id: ION_THRUSTER.to_string(), ignored: false }]);
+  }
+
+  #[test]
+  fn hidden_blocks_are_ignored_with_warning() {
+    let data = data();
+    let calculated = calculator(true).calculate(&data);
+    assert_approx_eq(calculated.total_mass_empty.to_kilograms(), 0.0);
+    assert_approx_eq(calculated.forces.thrusters[Direction::Up].to_newtons(), 0.0);
+    assert!(calculated.warnings.contains(&Warning::HiddenBlocksIgnored { ids: vec![ION_THRUSTER.to_string()] }));
+    assert_eq!(calculator(true).validate(&data).problems, vec![GridProblem::HiddenBlock { block_id: ION_THRUSTER.to_string(), ignored: true }]);
+  }
+
+  #[test]
+  fn unknown_blocks_are_reported() {
+    let mut calculator = calculator(false);
+    calculator.blocks.insert("Unknown.Block".to_string(), 1.0);
+    let validation = calculator.validate(&crate::common::data());
+    assert_eq!(validation.problems, vec![GridProblem::UnknownBlock { block_id: "Unknown.Block".to_string() }]);
+    assert!(GridCalculator::new().validate(&crate::common::data()).is_valid());
+  }
+}
+
+mod localization {
+  use secalc_core::data::localization::{humanize_key, Localization};
+
+  #[test]
+  fn humanizes_keys() {
+    assert_eq!(humanize_key("DisplayName_Block_LargeHydrogenThrust"), "Large Hydrogen Thrust");
+    assert_eq!(humanize_key("{LOC:DisplayName_Item_SteelPlate}"), "Steel Plate");
+    assert_eq!(humanize_key("Description_BatteryMk2"), "Battery Mk2");
+    assert_eq!(humanize_key("My_Mod__Block_"), "My Mod Block");
+  }
+
+  fn localization() -> Localization {
+    let mut localization = Localization::default();
+    localization.localization.insert("DisplayName_Block_Battery".to_string(), "Battery".to_string());
+    localization.localization.insert("DisplayName_Block_Drill".to_string(), "Drill".to_string());
+    localization.selected_language.insert("DisplayName_Block_Battery".to_string(), "Batterie".to_string());
+    localization
+  }
+
+  #[test]
+  fn falls_back_from_selected_language_to_english_to_key() {
+    let localization = localization();
+    assert_eq!(localization.get("DisplayName_Block_Battery"), "Batterie");
+    assert_eq!(localization.get("{LOC:DisplayName_Block_Battery}"), "Batterie");
+    assert_eq!(localization.get("DisplayName_Block_Drill"), "Drill");
+    assert_eq!(localization.get("DisplayName_Block_Missing"), "DisplayName_Block_Missing");
+    assert_eq!(localization.get("Ore Bin"), "Ore Bin");
+  }
+
+  #[test]
+  fn adds_fallbacks_for_unresolved_keys_only() {
+    let mut localization = localization();
+    let unresolved = localization.add_fallbacks(["DisplayName_Block_Battery", "Ore Bin", "{LOC:DisplayName_Block_Missing}", "Description_Missing"]);
+    assert_eq!(unresolved, ["{LOC:DisplayName_Block_Missing}", "Description_Missing"]);
+    assert_eq!(localization.get("{LOC:DisplayName_Block_Missing}"), "Missing");
+    assert_eq!(localization.get("Description_Missing"), "Missing");
+    assert_eq!(localization.get("Ore Bin"), "Ore Bin");
+    assert!(localization.add_fallbacks(["Description_Missing"]).is_empty(), "fallbacks should only be added once");
+  }
+}
+
+mod overrides {
+  use secalc_core::data::overrides::{BlockOverride, OverrideProblem, Overrides};
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn overrides(id: &str, block_override: BlockOverride) -> Overrides {
+    let mut overrides = Overrides::default();
+    overrides.blocks.insert(id.to_string(), block_override);
+    overrides
+  }
+
+  #[test]
+  fn overrides_patch_block_stats() {
+    let mut data = data();
+    let problems = data.apply_overrides(&overrides(BATTERY, BlockOverride { capacity: Some(2.0), power_generation: Some(3.0), power_consumption: Some(4.0), ..BlockOverride::default() }));
+    assert!(problems.is_empty(), "{:?}", problems);
+    let battery = &data.blocks.batteries.get(BATTERY).unwrap().details;
+    assert_approx_eq(battery.capacity, 2.0);
+    assert_approx_eq(battery.output, 3.0);
+    assert_approx_eq(battery.input, 4.0);
+  }
+
+  #[test]
+  fn overridden_stats_are_calculated() {
+    let mut data = data();
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    let force = calculator.calculate(&data).forces.thrusters[Direction::Up].to_newtons();
+    data.apply_overrides(&overrides(ION_THRUSTER, BlockOverride { force: Some(2.0 * force), ..BlockOverride::default() }));
+    assert_approx_eq(calculator.calculate(&data).forces.thrusters[Direction::Up].to_newtons(), 2.0 * force);
+  }
+
+  #[test]
+  fn power_generation_override_scales_fuel_consumption() {
+    let mut data = data();
+    data.apply_overrides(&overrides(REACTOR, BlockOverride { power_generation: Some(20.0), ..BlockOverride::default() }));
+    let reactor = &data.blocks.reactors.get(REACTOR).unwrap().details;
+    assert_approx_eq(reactor.max_power_generation, 20.0);
+    assert_approx_eq(reactor.max_fuel_consumption, 0.002);
+  }
+
+  #[test]
+  fn unapplicable_overrides_are_reported() {
+    let mut data = data();
+    let mut overrides = overrides("Unknown.Block", BlockOverride { force: Some(1.0), ..BlockOverride::default() });
+    overrides.blocks.insert(CONTAINER.to_string(), BlockOverride { capacity: Some(1.0), ..BlockOverride::default() });
+    overrides.blocks.insert(HYDROGEN_THRUSTER.to_string(), BlockOverride { force: Some(1.0), power_consumption: Some(1.0), ..BlockOverride::default() });
+    let problems = data.apply_overrides(&overrides);
+    assert_eq!(problems, vec![
+      OverrideProblem::UnsupportedField { block_id: CONTAINER.to_string(), field: "capacity" },
+      OverrideProblem::UnsupportedField { block_id: HYDROGEN_THRUSTER.to_string(), field: "power_consumption" },
+      OverrideProblem::UnknownBlock { block_id: "Unknown.Block".to_string() },
+    ]);
+    assert_approx_eq(data.blocks.thrusters.get(HYDROGEN_THRUSTER).unwrap().details.force, 1.0);
+    assert_eq!(problems[0].to_string(), "Block 'CargoContainer.TestContainer' has no 'capacity' stat to override");
+  }
+
+  #[test]
+  fn overrides_round_trip_json() {
+    let overrides = overrides(BATTERY, BlockOverride { capacity: Some(2.0), ..BlockOverride::default() });
+    let mut json = Vec::new();
+    overrides.to_json(&mut json).unwrap();
+    assert!(!String::from_utf8_lossy(&json).contains("force"));
+    assert_eq!(Overrides::from_json(json.as_slice()).unwrap(), overrides);
+    assert!(Overrides::from_json(r#"{ "blocks": { "Battery": { "capacity": "full" } } }"#.as_bytes()).is_err());
+  }
+}
+
+mod preset {
+  use secalc_core::grid::{BatteryMode, GridCalculator, HydrogenTankMode};
+  use secalc_core::grid::preset::OptionPreset;
+
+  #[test]
+  fn apply_sets_only_options_in_preset() {
+    let mut calculator = GridCalculator { battery_fill: 42.0, hydrogen_tank_fill: 13.0, ..GridCalculator::default() };
+    let preset = OptionPreset {
+      name: "Test".to_string(),
+      battery_mode: Some(BatteryMode::Recharge),
+      hydrogen_tank_mode: Some(HydrogenTankMode::Stockpile),
+      railgun_charging: Some(false),
+      ..OptionPreset::default()
+    };
+    assert!(!preset.is_applied_to(&calculator));
+    preset.apply(&mut calculator);
+    assert!(preset.is_applied_to(&calculator));
+    assert_eq!(calculator.battery_mode, BatteryMode::Recharge);
+    assert_eq!(calculator.hydrogen_tank_mode, HydrogenTankMode::Stockpile);
+    assert!(!calculator.railgun_charging);
+    // Options that are not in the preset are unchanged.
+    assert_eq!(calculator.battery_fill, 42.0);
+    assert_eq!(calculator.hydrogen_tank_fill, 13.0);
+    assert!(calculator.jump_drive_charging);
+  }
+
+  #[test]
+  fn from_calculator_round_trips() {
+    let calculator = GridCalculator {
+      battery_mode: BatteryMode::Off,
+      battery_fill: 50.0,
+      hydrogen_engine_enabled: false,
+      ..GridCalculator::default()
+    };
+    let preset = OptionPreset::from_calculator("Custom", &calculator);
+    assert_eq!(preset.name, "Custom");
+    let mut other = GridCalculator::default();
+    assert!(!preset.is_applied_to(&other));
+    preset.apply(&mut other);
+    assert!(preset.is_applied_to(&other));
+    assert_eq!(other.battery_mode, BatteryMode::Off);
+    assert_eq!(other.battery_fill, 50.0);
+    assert!(!other.hydrogen_engine_enabled);
+  }
+
+  #[test]
+  fn built_in_presets_have_unique_names() {
+    let presets = OptionPreset::built_in();
+    let names: std::collections::HashSet<_> = presets.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names.len(), presets.len());
+    assert!(names.contains("Docked Charging"));
+    assert!(names.contains("Silent Running"));
+  }
+}
+
+mod round_trip {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::precision;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator {
+      gravity_multiplier: 0.1 + 0.2,
+      container_multiplier: 3.0 * 1.1,
+      planetary_influence: 1.0 / 3.0,
+      additional_mass: 123_456_789.123,
+      hover_pitch: -12.5,
+      battery_fill: 0.7 * 100.0,
+      hydrogen_tank_fill: 57.0 + 1e-14,
+      any_fill_with_ice: 1e-12,
+      ..GridCalculator::default()
+    };
+    calculator.blocks.insert(crate::common::HYDROGEN_TANK.to_string(), 3.0);
+    calculator.directional_blocks.entry(crate::common::HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 4.0;
+    calculator
+  }
+
+  #[test]
+  fn round_is_idempotent() {
+    for value in [0.1 + 0.2, 1.0 / 3.0, 2.0 / 3.0, 57.0 + 1e-14, -12.345678901234, 1e20, 0.0, -0.0] {
+      let rounded = precision::round(value);
+      assert_eq!(rounded, precision::round(rounded), "rounding {} is not idempotent", value);
+    }
+    assert_eq!(precision::round(0.1 + 0.2), 0.3);
+    assert!(precision::round(f64::NAN).is_nan());
+    assert_eq!(precision::round(f64::INFINITY), f64::INFINITY);
+  }
+
+  #[test]
+  fn saved_calculator_has_no_floating_point_noise() {
+    let json = serde_json::to_string(&calculator()).unwrap();
+    assert!(json.contains("\"gravity_multiplier\":0.3,"), "{}", json);
+    assert!(json.contains("\"container_multiplier\":3.3,"), "{}", json);
+    assert!(json.contains("\"battery_fill\":70.0,"), "{}", json);
+    assert!(json.contains("\"hydrogen_tank_fill\":57.0,"), "{}", json);
+    assert!(json.contains("\"any_fill_with_ice\":0.0,"), "{}", json);
+  }
+
+  #[test]
+  fn save_load_save_round_trips_exactly() {
+    let saved = serde_json::to_string_pretty(&calculator()).unwrap();
+    let loaded: GridCalculator = serde_json::from_str(&saved).unwrap();
+    let saved_again = serde_json::to_string_pretty(&loaded).unwrap();
+    assert_eq!(saved, saved_again);
+
+    let loaded_again: GridCalculator = serde_json::from_str(&saved_again).unwrap();
+    assert_eq!(loaded.gravity_multiplier.to_bits(), loaded_again.gravity_multiplier.to_bits());
+    assert_eq!(loaded.planetary_influence.to_bits(), loaded_again.planetary_influence.to_bits());
+    assert_eq!(loaded.additional_mass.to_bits(), loaded_again.additional_mass.to_bits());
+    assert_eq!(loaded.hover_pitch, -12.5);
+    assert_eq!(loaded.blocks, loaded_again.blocks);
+    assert_eq!(loaded.directional_blocks.len(), 1);
+  }
+
+  #[test]
+  fn digest_ignores_floating_point_noise() {
+    let noisy = GridCalculator { gravity_multiplier: 0.1 + 0.2, ..GridCalculator::default() };
+    let exact = GridCalculator { gravity_multiplier: 0.3, ..GridCalculator::default() };
+    assert_eq!(noisy.digest(), exact.digest());
+  }
+}
+
+mod schema {
+  use secalc_core::data::{Data, ReadError as DataReadError};
+  use secalc_core::grid::{GridCalculator, ReadError as GridReadError};
+
+  #[test]
+  fn grid_calculator_read_error_has_path() {
+    let json = r#"{ "gravity_multiplier": 1.0, "thruster_direction_enabled": [true, "yes", true, true, true, true] }"#;
+    let Err(GridReadError::FromJSONFail { path, .. }) = GridCalculator::from_json(json.as_bytes()) else {
+      panic!("reading an invalid grid calculator should fail");
+    };
+    assert_eq!(path, "thruster_direction_enabled[1]");
+  }
+
+  #[test]
+  fn grid_calculator_json_round_trip() {
+    let calculator = GridCalculator { additional_mass: 1000.0, ..GridCalculator::default() };
+    let mut json = Vec::new();
+    calculator.to_json(&mut json).unwrap();
+    let read = GridCalculator::from_json(json.as_slice()).unwrap();
+    assert_eq!(read.additional_mass, 1000.0);
+  }
+
+  #[test]
+  fn data_read_error_has_path() {
+    let json = r#"{ "mods": { "mods": { "1000": 5 } } }"#;
+    let Err(DataReadError::FromJSONFail { path, .. }) = Data::from_json(json.as_bytes()) else {
+      panic!("reading invalid data should fail");
+    };
+    assert_eq!(path, "mods.mods.1000");
+  }
+
+  #[cfg(feature = "schema")]
+  #[test]
+  fn schemas_describe_top_level_fields() {
+    let schema = serde_json::to_value(secalc_core::schema::grid_calculator_schema()).unwrap();
+    assert!(schema["properties"]["blocks"].is_object());
+    assert!(schema["properties"]["thruster_direction_enabled"].is_object());
+    let schema = serde_json::to_value(secalc_core::schema::data_schema()).unwrap();
+    assert!(schema["properties"]["blocks"].is_object());
+    assert!(schema["properties"]["localization"].is_object());
+  }
+}
+
+mod server_profile {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::server_profile::{ReadError, ServerProfile};
+  use secalc_core::grid::warning::Warning;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(JUMP_DRIVE.to_string(), 3.0);
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Down] = 2.0;
+    calculator
+  }
+
+  #[test]
+  fn no_warnings_within_limits() {
+    let mut profile = ServerProfile::default();
+    profile.block_limits.insert(JUMP_DRIVE.to_string(), 3);
+    profile.block_limits.insert(ION_THRUSTER.to_string(), 4);
+    profile.max_block_count = Some(8);
+    assert!(profile.check(&calculator(), &data()).is_empty());
+  }
+
+  #[test]
+  fn warns_for_exceeded_limits() {
+    let mut profile = ServerProfile::default();
+    profile.block_limits.insert(JUMP_DRIVE.to_string(), 2);
+    // Directional counts are summed over all directions.
+    profile.block_limits.insert(ION_THRUSTER.to_string(), 3);
+    profile.block_limits.insert(BATTERY.to_string(), 0);
+    profile.max_block_count = Some(7);
+    let warnings = profile.check(&calculator(), &data());
+    assert_eq!(warnings, vec![
+      Warning::BlockLimitExceeded { id: JUMP_DRIVE.to_string(), count: 3.0, limit: 2 },
+      Warning::BlockLimitExceeded { id: ION_THRUSTER.to_string(), count: 4.0, limit: 3 },
+      Warning::BlockCountLimitExceeded { count: 8.0, limit: 7 },
+    ]);
+  }
+
+  #[test]
+  fn json_round_trip() {
+    let mut profile = ServerProfile { name: "Test Server".to_string(), max_pcu: Some(50000), ..ServerProfile::default() };
+    profile.block_limits.insert(JUMP_DRIVE.to_string(), 2);
+    let mut json = Vec::new();
+    profile.to_json(&mut json).unwrap();
+    assert_eq!(ServerProfile::from_json(json.as_slice()).unwrap(), profile);
+  }
+
+  #[test]
+  fn read_error_has_path() {
+    let json = r#"{ "name": "Test Server", "block_limits": { "JumpDrive.LargeJumpDrive": -1 } }"#;
+    let Err(ReadError::FromJSONFail { path, .. }) = ServerProfile::from_json(json.as_bytes()) else {
+      panic!("reading an invalid server profile should fail");
+    };
+    assert_eq!(path, "block_limits.JumpDrive.LargeJumpDrive");
+  }
+
+  #[test]
+  fn warns_for_exceeded_pcu_only_when_known() {
+    let profile = ServerProfile { max_pcu: Some(100), ..ServerProfile::default() };
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 5.0);
+    let mut data = data();
+    assert!(profile.check(&calculator, &data).is_empty());
+    data.blocks.reactors.get_mut(REACTOR).unwrap().data.pcu = Some(25);
+    assert_eq!(profile.check(&calculator, &data), vec![Warning::PcuLimitExceeded { pcu: 125, limit: 100 }]);
+  }
+}
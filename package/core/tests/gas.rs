@@ -0,0 +1,240 @@
+mod common;
+
+mod hydrogen_tank {
+  use secalc_core::grid::{GridCalculated, GridCalculator, HydrogenTankMode};
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  fn calculator(mode: HydrogenTankMode, fill: f64, generators: f64, up_thrusters: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.hydrogen_tank_mode = mode;
+    calculator.hydrogen_tank_fill = fill;
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 1.0);
+    calculator.blocks.insert(GENERATOR.to_string(), generators);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = up_thrusters;
+    calculator
+  }
+
+  #[test]
+  fn stockpile_fills_at_maximum_input_without_competing_consumers() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::Stockpile, 0.0, 3.0, 0.0).calculate(&data);
+    // 60 L/s generated, tank input limited to 50 L/s: 1000 L takes 20 s.
+    let tank = calculated.hydrogen_tank.unwrap();
+    assert_approx_eq(tank.fill_duration.unwrap().to_seconds(), 20.0);
+  }
+
+  #[test]
+  fn stockpile_fills_only_with_left_over_hydrogen_after_thrusters() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::Stockpile, 0.0, 2.0, 1.0).calculate(&data);
+    // 40 L/s generated, 30 L/s used by thrusters, 10 L/s left for tanks: 1000 L takes 100 s.
+    let tank = calculated.hydrogen_tank.unwrap();
+    assert_approx_eq(tank.fill_duration.unwrap().to_seconds(), 100.0);
+    // Stockpiling tanks do not provide hydrogen.
+    assert!(calculated.hydrogen_upto_up_down_thruster.tank_duration.is_none());
+    assert_approx_eq(calculated.hydrogen_upto_up_down_thruster.balance_with_tank, 10.0);
+  }
+
+  #[test]
+  fn stockpile_never_fills_when_thrusters_use_all_hydrogen() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::Stockpile, 50.0, 1.0, 1.0).calculate(&data);
+    let tank = calculated.hydrogen_tank.unwrap();
+    assert!(tank.fill_duration.unwrap().to_seconds().is_infinite());
+  }
+
+  #[test]
+  fn on_drains_only_the_deficit_not_covered_by_generators() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::On, 100.0, 1.0, 2.0).calculate(&data);
+    // 60 L/s used by thrusters, 20 L/s generated, 40 L/s provided by tanks: 1000 L lasts 25 s.
+    let thrusters = calculated.hydrogen_upto_up_down_thruster;
+    assert_approx_eq(thrusters.tank_duration.unwrap().to_seconds(), 25.0);
+    assert_approx_eq(thrusters.balance_without_tank, -40.0);
+    assert_approx_eq(thrusters.balance_with_tank, 10.0);
+  }
+
+  #[test]
+  fn on_drain_is_limited_by_maximum_output() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::On, 100.0, 0.0, 3.0).calculate(&data);
+    // 90 L/s used by thrusters, but tanks output at most 50 L/s: 1000 L lasts 20 s.
+    let thrusters = calculated.hydrogen_upto_up_down_thruster;
+    assert_approx_eq(thrusters.tank_duration.unwrap().to_seconds(), 20.0);
+    assert_approx_eq(thrusters.balance_with_tank, -40.0);
+  }
+
+  #[test]
+  fn on_is_not_drained_when_generators_cover_consumption() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::On, 100.0, 2.0, 1.0).calculate(&data);
+    let thrusters = calculated.hydrogen_upto_up_down_thruster;
+    assert!(thrusters.tank_duration.unwrap().to_seconds().is_infinite());
+  }
+
+  #[test]
+  fn not_drained_round_trips_through_json() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::On, 100.0, 2.0, 1.0).calculate(&data);
+    let json = serde_json::to_string(&calculated).unwrap();
+    let deserialized: GridCalculated = serde_json::from_str(&json).unwrap();
+    assert!(deserialized.hydrogen_upto_up_down_thruster.tank_duration.unwrap().is_infinite());
+  }
+
+  #[test]
+  fn on_does_not_fill_while_covering_a_deficit() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::On, 50.0, 1.0, 1.0).calculate(&data);
+    let tank = calculated.hydrogen_tank.unwrap();
+    assert!(tank.fill_duration.unwrap().to_seconds().is_infinite());
+  }
+
+  #[test]
+  fn off_neither_fills_nor_drains() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::Off, 50.0, 1.0, 1.0).calculate(&data);
+    assert!(calculated.hydrogen_tank.unwrap().fill_duration.is_none());
+    assert!(calculated.hydrogen_upto_up_down_thruster.tank_duration.is_none());
+  }
+
+  #[test]
+  fn multiple_tank_types_all_contribute_to_input() {
+    let mut data = data();
+    let mut second = data.blocks.hydrogen_tanks.get(HYDROGEN_TANK).unwrap().clone();
+    second.data.id = "OxygenTank.TestHydrogenTank2".to_string();
+    data.blocks.hydrogen_tanks.insert(second.data.id.clone(), second);
+    let mut calculator = calculator(HydrogenTankMode::Stockpile, 0.0, 10.0, 0.0);
+    calculator.blocks.insert("OxygenTank.TestHydrogenTank2".to_string(), 1.0);
+    let calculated = calculator.calculate(&data);
+    // 200 L/s generated, tanks input limited to 2 * 50 L/s: 2000 L takes 20 s.
+    assert_approx_eq(calculated.hydrogen_upto_tank_fill.consumption, 100.0);
+    assert_approx_eq(calculated.hydrogen_tank.unwrap().fill_duration.unwrap().to_seconds(), 20.0);
+  }
+
+  #[test]
+  fn tanks_use_operational_power_when_transferring() {
+    let data = data();
+    for (mode, fill) in [(HydrogenTankMode::On, 100.0), (HydrogenTankMode::On, 0.0), (HydrogenTankMode::Stockpile, 50.0)] {
+      let calculated = calculator(mode, fill, 0.0, 0.0).calculate(&data);
+      assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.001);
+    }
+  }
+
+  #[test]
+  fn full_stockpiling_tanks_use_only_idle_power() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::Stockpile, 100.0, 0.0, 0.0).calculate(&data);
+    assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.0);
+    assert_approx_eq(calculated.power_idle.consumption.to_megawatts(), 0.000001);
+
+    let mut calculator = calculator(HydrogenTankMode::Stockpile, 100.0, 0.0, 0.0);
+    calculator.hydrogen_tank_always_operational = true;
+    assert_approx_eq(calculator.calculate(&data).power_upto_utility.consumption.to_megawatts(), 0.001);
+  }
+
+  #[test]
+  fn off_tanks_use_no_power() {
+    let data = data();
+    let calculated = calculator(HydrogenTankMode::Off, 50.0, 0.0, 0.0).calculate(&data);
+    assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.0);
+    assert_approx_eq(calculated.power_idle.consumption.to_megawatts(), 0.0);
+  }
+
+  #[test]
+  fn on_drain_duration_includes_hydrogen_bottles() {
+    let data = data();
+    let mut calculator = calculator(HydrogenTankMode::On, 100.0, 1.0, 2.0);
+    calculator.blocks.insert(CONTAINER.to_string(), 1.0);
+    calculator.any_fill_with_hydrogen_bottles = 24.0;
+    let calculated = calculator.calculate(&data);
+    // 2 bottles of 120 L hydrogen refill the tank: 1240 L at 40 L/s lasts 31 s.
+    assert_approx_eq(calculated.hydrogen_bottle_capacity.to_liters(), 240.0);
+    assert_approx_eq(calculated.hydrogen_upto_up_down_thruster.tank_duration.unwrap().to_seconds(), 31.0);
+  }
+}
+
+mod oxygen {
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  #[test]
+  fn air_vents_consume_oxygen_that_farms_and_tanks_provide() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(AIR_VENT.to_string(), 1.0);
+    calculator.blocks.insert(OXYGEN_FARM.to_string(), 2.0);
+    calculator.blocks.insert(OXYGEN_TANK.to_string(), 1.0);
+    calculator.oxygen_tank_fill = 50.0;
+    let calculated = calculator.calculate(&data());
+    let oxygen = calculated.oxygen.unwrap();
+    assert_approx_eq(oxygen.farm_generation, 2.0);
+    assert_approx_eq(oxygen.air_vent_consumption, 30.0);
+    assert_approx_eq(oxygen.balance, -28.0);
+    assert_approx_eq(oxygen.tank_capacity.to_liters(), 1000.0);
+    assert_approx_eq(oxygen.tank_duration.unwrap().to_seconds(), 500.0 / 28.0);
+  }
+
+  #[test]
+  fn tank_duration_includes_oxygen_bottles() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(AIR_VENT.to_string(), 1.0);
+    calculator.blocks.insert(OXYGEN_FARM.to_string(), 2.0);
+    calculator.blocks.insert(OXYGEN_TANK.to_string(), 1.0);
+    calculator.blocks.insert(CONTAINER.to_string(), 1.0);
+    calculator.oxygen_tank_fill = 50.0;
+    calculator.any_fill_with_oxygen_bottles = 24.0;
+    let calculated = calculator.calculate(&data());
+    // 2 bottles of 100 L oxygen refill the tank: 700 L at 28 L/s lasts 25 s.
+    assert_approx_eq(calculated.oxygen_bottle_capacity.to_liters(), 200.0);
+    assert_approx_eq(calculated.oxygen.unwrap().tank_duration.unwrap().to_seconds(), 25.0);
+  }
+
+  #[test]
+  fn tanks_last_forever_when_generation_covers_consumption() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(OXYGEN_FARM.to_string(), 1.0);
+    calculator.blocks.insert(OXYGEN_TANK.to_string(), 1.0);
+    let oxygen = calculator.calculate(&data()).oxygen.unwrap();
+    assert_approx_eq(oxygen.balance, 1.0);
+    assert!(oxygen.tank_duration.unwrap().is_infinite());
+  }
+
+  #[test]
+  fn air_vents_that_are_not_pressurizing_consume_no_oxygen_or_operational_power() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(AIR_VENT.to_string(), 1.0);
+    calculator.air_vents_pressurizing = false;
+    let calculated = calculator.calculate(&data());
+    assert_approx_eq(calculated.oxygen.unwrap().consumption, 0.0);
+    assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.0);
+    calculator.air_vents_pressurizing = true;
+    assert_approx_eq(calculator.calculate(&data()).power_upto_utility.consumption.to_megawatts(), 0.01);
+  }
+
+  #[test]
+  fn generators_cover_oxygen_demand_that_farms_do_not() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    calculator.blocks.insert(OXYGEN_FARM.to_string(), 2.0);
+    calculator.generator_oxygen_throttling = true;
+    calculator.oxygen_demand = 5.0;
+    let calculated = calculator.calculate(&data());
+    let generator = calculated.generator.unwrap();
+    // Farms cover 2 of the 5 L/s demand, so the generator runs 3 / 10 of the time.
+    assert_approx_eq(generator.utilization, 30.0);
+    assert_approx_eq(generator.oxygen_balance, 0.0);
+    let oxygen = calculated.oxygen.unwrap();
+    assert_approx_eq(oxygen.generator_generation, 3.0);
+    assert_approx_eq(oxygen.generation, 5.0);
+    assert_approx_eq(oxygen.balance, 0.0);
+  }
+
+  #[test]
+  fn no_oxygen_without_oxygen_blocks_or_demand() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    assert!(calculator.calculate(&data()).oxygen.is_none());
+  }
+}
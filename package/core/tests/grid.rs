@@ -0,0 +1,682 @@
+mod common;
+
+#[cfg(feature = "extract")]
+mod block_counts {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::interop::block_counts::{read, to_xml};
+
+  #[test]
+  fn block_counts_round_trip_through_xml() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert("BatteryBlock.LargeBlockBatteryBlock".to_string(), 3.0);
+    calculator.blocks.insert("CargoContainer.LargeBlockLargeContainer".to_string(), 0.0);
+    calculator.blocks.insert("BatteryBlock.LargeBatteryMk2@2275681005".to_string(), 2.0);
+    let thrusters = calculator.directional_blocks.entry("Thrust.LargeBlockLargeHydrogenThrust".to_string()).or_default();
+    thrusters[Direction::Up] = 4.0;
+    thrusters[Direction::Back] = 1.0;
+
+    let block_counts = calculator.block_counts();
+    assert_eq!(block_counts.len(), 4);
+    let xml = to_xml(&block_counts);
+    assert!(xml.contains("<TypeId>Thrust</TypeId>"));
+    assert!(xml.contains("<SubtypeId>LargeBlockLargeHydrogenThrust</SubtypeId>"));
+    let read_block_counts = read::from_xml(&xml).unwrap();
+    assert_eq!(read_block_counts, block_counts);
+
+    let mut read_calculator = GridCalculator::new();
+    read_calculator.add_block_counts(read_block_counts);
+    assert_eq!(read_calculator.block_counts(), block_counts);
+  }
+}
+
+mod block_list {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::interop::block_list::to_text;
+
+  use crate::common::*;
+
+  #[test]
+  fn empty_grid_has_empty_list() {
+    assert_eq!(to_text(&GridCalculator::new(), &data()), "");
+  }
+
+  #[test]
+  fn lists_non_zero_blocks_by_category() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 2.0);
+    calculator.blocks.insert(BATTERY.to_string(), 1.0);
+    calculator.blocks.insert(CONTAINER.to_string(), 0.0);
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 3.0);
+    calculator.blocks.insert("Unknown.Block".to_string(), 1.0);
+    let thrusters = calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 4.0;
+    thrusters[Direction::Down] = 2.0;
+    *calculator.direction_labels.custom_mut(Direction::Down) = "Belly".to_string();
+
+    let text = to_text(&calculator, &data());
+    assert_eq!(text, "\
+Thrusters
+4x Thrust.TestHydrogenThrust (Up)
+2x Thrust.TestHydrogenThrust (Belly)
+
+Power
+1x BatteryBlock.TestBattery
+2x Reactor.TestReactor
+
+Hydrogen
+3x OxygenTank.TestHydrogenTank
+
+Unknown
+1x Unknown.Block
+");
+  }
+}
+
+mod build_progress {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::warning::Warning;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 0.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    calculator
+  }
+
+  #[test]
+  fn fully_built_by_default() {
+    let calculated = calculator().calculate(&data());
+    assert_approx_eq(calculated.total_mass_empty.to_kilograms(), 2000.0);
+    assert_approx_eq(calculated.thruster_acceleration[Direction::Up].force.to_newtons(), 100000.0);
+    assert_approx_eq(calculated.power_generation.to_megawatts(), 10.0);
+    assert!(calculated.warnings.is_empty());
+  }
+
+  #[test]
+  fn partially_built_blocks_scale_mass_and_stop_functioning() {
+    let mut calculator = calculator();
+    calculator.build_progress.insert(ION_THRUSTER.to_string(), 25.0);
+    calculator.build_progress.insert(REACTOR.to_string(), 50.0);
+    let calculated = calculator.calculate(&data());
+    assert_approx_eq(calculated.total_mass_empty.to_kilograms(), 500.0);
+    assert_approx_eq(calculated.thruster_acceleration[Direction::Up].force.to_newtons(), 0.0);
+    assert_approx_eq(calculated.power_generation.to_megawatts(), 0.0);
+    assert_eq!(calculated.warnings, vec![Warning::NonFunctionalBlocks { ids: vec![REACTOR.to_string(), ION_THRUSTER.to_string()] }]);
+  }
+
+  #[test]
+  fn partially_built_blocks_function_above_threshold() {
+    let mut calculator = calculator();
+    calculator.functional_build_progress = 20.0;
+    calculator.build_progress.insert(ION_THRUSTER.to_string(), 25.0);
+    let calculated = calculator.calculate(&data());
+    assert_approx_eq(calculated.total_mass_empty.to_kilograms(), 500.0);
+    assert_approx_eq(calculated.thruster_acceleration[Direction::Up].force.to_newtons(), 100000.0);
+    assert!(calculated.warnings.is_empty());
+  }
+}
+
+mod crew {
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator(crew_count: u64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(CONTAINER.to_string(), 1.0);
+    calculator.crew_count = crew_count;
+    calculator.crew_mass_per_engineer = 120.0;
+    calculator
+  }
+
+  #[test]
+  fn crew_adds_to_empty_and_filled_mass() {
+    let data = data();
+    let without_crew = calculator(0).calculate(&data);
+    let with_crew = calculator(3).calculate(&data);
+    assert_approx_eq(without_crew.crew_mass.to_kilograms(), 0.0);
+    assert_approx_eq(with_crew.crew_mass.to_kilograms(), 360.0);
+    assert_approx_eq(with_crew.total_mass_empty.to_kilograms(), without_crew.total_mass_empty.to_kilograms() + 360.0);
+    assert_approx_eq(with_crew.total_mass_filled.to_kilograms(), without_crew.total_mass_filled.to_kilograms() + 360.0);
+  }
+
+  #[test]
+  fn crew_options_round_trip() {
+    let json = serde_json::to_string(&calculator(3)).unwrap();
+    let calculator: GridCalculator = serde_json::from_str(&json).unwrap();
+    assert_eq!(calculator.crew_count, 3);
+    assert_approx_eq(calculator.crew_mass_per_engineer, 120.0);
+  }
+}
+
+mod docking {
+  use secalc_core::grid::docking::DockSupply;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(BATTERY.to_string(), 1.0);
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 1.0);
+    calculator.battery_fill = 50.0;
+    calculator.hydrogen_tank_fill = 0.0;
+    calculator
+  }
+
+  #[test]
+  fn dock_charging_is_limited_by_supply_and_maximum_input() {
+    let calculator = calculator();
+    let calculated = calculator.calculate(&data());
+    // Supply exceeds the 1 MW battery input, which stores 0.8 MW: 0.5 MWh takes 0.625 h. 20 L/s is below the 50 L/s
+    // tank input: 1000 L takes 50 s.
+    let charging = calculated.dock_charging(&calculator, &DockSupply { power: 10.0, hydrogen: 20.0 });
+    assert_approx_eq(charging.battery_charge_duration.unwrap().to_hours(), 0.625);
+    assert_approx_eq(charging.hydrogen_tank_fill_duration.unwrap().to_seconds(), 50.0);
+    assert!(charging.hydrogen_engine_fill_duration.is_none());
+    assert_approx_eq(charging.total_duration.unwrap().to_hours(), 0.625);
+  }
+
+  #[test]
+  fn dock_charging_without_supply_is_infinite() {
+    let calculator = calculator();
+    let calculated = calculator.calculate(&data());
+    let charging = calculated.dock_charging(&calculator, &DockSupply::default());
+    assert!(charging.battery_charge_duration.unwrap().is_infinite());
+    assert!(charging.hydrogen_tank_fill_duration.unwrap().is_infinite());
+    assert!(charging.total_duration.unwrap().is_infinite());
+  }
+
+  #[test]
+  fn dock_charging_when_full_is_zero() {
+    let mut calculator = calculator();
+    calculator.battery_fill = 100.0;
+    calculator.hydrogen_tank_fill = 100.0;
+    let calculated = calculator.calculate(&data());
+    let charging = calculated.dock_charging(&calculator, &DockSupply::default());
+    assert_approx_eq(charging.total_duration.unwrap().to_minutes(), 0.0);
+  }
+
+  #[test]
+  fn dock_supply_from_station_uses_surplus() {
+    let mut station = GridCalculator::new();
+    station.blocks.insert(REACTOR.to_string(), 1.0);
+    station.blocks.insert(GENERATOR.to_string(), 1.0);
+    let supply = DockSupply::from_station(&station.calculate(&data()));
+    assert_approx_eq(supply.power, 10.0 - 0.5);
+    assert_approx_eq(supply.hydrogen, 20.0);
+  }
+}
+
+mod drilling {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::warning::Warning;
+
+  use crate::common::*;
+
+  fn calculator(drills: f64, containers: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(DRILL.to_string(), drills);
+    calculator.blocks.insert(CONTAINER.to_string(), containers);
+    calculator
+  }
+
+  #[test]
+  fn drills_fill_cargo_at_ore_rate() {
+    let calculated = calculator(2.0, 1.0).calculate(&data());
+    let drilling = calculated.drilling.unwrap();
+    assert_approx_eq(drilling.count, 2.0);
+    assert_approx_eq(drilling.ore_rate, 20.0);
+    assert_approx_eq(drilling.drill_volume, 200.0);
+    assert_approx_eq(drilling.storage_volume, 1000.0);
+    // 1200 L at 20 L/s.
+    assert_approx_eq(drilling.fill_duration.unwrap().to_seconds(), 60.0);
+    assert!(calculated.warnings.is_empty());
+  }
+
+  #[test]
+  fn mining_uses_drill_ore_rate_without_mining_rate() {
+    let mining = calculator(2.0, 1.0).calculate(&data()).mining.unwrap();
+    assert_approx_eq(mining.fill_duration.to_seconds(), 60.0);
+  }
+
+  #[test]
+  fn drills_without_storage_warn() {
+    let calculated = calculator(1.0, 0.0).calculate(&data());
+    assert_approx_eq(calculated.drilling.unwrap().storage_volume, 0.0);
+    assert_eq!(calculated.warnings, [Warning::DrillsWithoutStorage]);
+  }
+
+  #[test]
+  fn no_drilling_without_drills() {
+    assert!(calculator(0.0, 1.0).calculate(&data()).drilling.is_none());
+  }
+}
+
+mod fill_items {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::items::FillItem;
+
+  use crate::common::*;
+
+  /// Grid with a 1000 L container, half filled with steel plates.
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(CONTAINER.to_string(), 1.0);
+    calculator.any_fill_with_steel_plates = 50.0;
+    calculator
+  }
+
+  #[test]
+  fn items_show_effective_volume() {
+    let mut calculator = calculator();
+    calculator.container_multiplier = 2.0;
+    let calculated = calculator.calculate(&data());
+    let steel_plate = calculated.items.iter().find(|i| i.name == "Steel Plate").unwrap();
+    assert_approx_eq(steel_plate.count, calculated.total_items_steel_plate);
+    assert_approx_eq(steel_plate.count, 2000.0 * 0.5 / 3.0);
+    assert_approx_eq(steel_plate.volume.to_liters(), 3.0);
+    assert_approx_eq(steel_plate.effective_volume.to_liters(), 1.5);
+  }
+
+  #[test]
+  fn items_from_options_do_not_change_results() {
+    let data = data();
+    let mut calculator = calculator();
+    let calculated = calculator.calculate(&data);
+    calculator.any_fill_items = Some(calculator.any_fill_items_from_options(&data));
+    let overridden = calculator.calculate(&data);
+    assert_approx_eq(overridden.total_mass_filled.to_kilograms(), calculated.total_mass_filled.to_kilograms());
+    // Overridden items are counted under their own names instead.
+    assert_approx_eq(overridden.total_items_steel_plate, 0.0);
+    let steel_plates = overridden.items.iter().filter(|i| i.name == "Steel Plate").map(|i| i.count).sum::<f64>();
+    assert_approx_eq(steel_plates, calculated.total_items_steel_plate);
+  }
+
+  #[test]
+  fn items_from_options_take_bottle_sizes_from_data() {
+    let mut data = data();
+    let hydrogen_bottle = data.bottles.bottles.get_mut("HydrogenBottle").unwrap();
+    hydrogen_bottle.volume = 60.0;
+    hydrogen_bottle.mass = 20.0;
+    data.bottles.bottles.remove("OxygenBottle");
+    let mut calculator = calculator();
+    calculator.any_fill_with_hydrogen_bottles = 50.0;
+    calculator.any_fill_with_steel_plates = 0.0;
+    let items = calculator.any_fill_items_from_options(&data);
+    let bottle = items.iter().find(|i| i.name == "H2 Bottle").unwrap();
+    assert_approx_eq(bottle.volume, 60.0);
+    assert_approx_eq(bottle.mass, 20.0);
+    // Bottles missing from the data are left out, and not counted.
+    assert!(items.iter().all(|i| i.name != "O2 Bottle"));
+    let calculated = calculator.calculate(&data);
+    assert_approx_eq(calculated.total_items_hydrogen_bottle, 500.0 / 60.0);
+    assert!(calculated.items.iter().all(|i| i.name != "O2 Bottle"));
+  }
+
+  #[test]
+  fn override_replaces_any_fill() {
+    let mut calculator = calculator();
+    calculator.any_fill_items = Some(vec![FillItem::new("Computer", 1.0, 0.2, 25.0)]);
+    calculator.round_items_to_stacks = true;
+    let calculated = calculator.calculate(&data());
+    let computer = calculated.items.last().unwrap();
+    assert_eq!(computer.name, "Computer");
+    assert_approx_eq(computer.count, 250.0);
+    assert_approx_eq(calculated.total_mass_filled.to_kilograms() - calculated.total_mass_empty.to_kilograms(), 250.0 * 0.2);
+  }
+
+  #[test]
+  fn no_override_is_not_serialized() {
+    let json = serde_json::to_value(calculator()).unwrap();
+    assert!(json.get("any_fill_items").is_none());
+  }
+}
+
+mod fleet {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::fleet::FleetCalculated;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn carrier() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(GENERATOR.to_string(), 0.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    calculator
+  }
+
+  fn drone() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Down] = 1.0;
+    calculator
+  }
+
+  #[test]
+  fn fleet_aggregates_grids() {
+    let mut data = data();
+    data.blocks.reactors.get_mut(REACTOR).unwrap().data.pcu = Some(25);
+    data.blocks.thrusters.get_mut(ION_THRUSTER).unwrap().data.pcu = Some(15);
+    let (carrier, drone) = (carrier(), drone());
+    let (carrier_calculated, drone_calculated) = (carrier.calculate(&data), drone.calculate(&data));
+    let fleet = FleetCalculated::calculate([(&carrier, &carrier_calculated), (&drone, &drone_calculated), (&drone, &drone_calculated)], &data, 1.0);
+    assert_eq!(fleet.grid_count, 3);
+    assert_approx_eq(fleet.block_count, 3.0 + 2.0 + 2.0);
+    assert_eq!(fleet.pcu, Some(25 + 6 * 15));
+    assert_approx_eq(fleet.total_mass_empty.to_kilograms(), 6000.0);
+    assert_approx_eq(fleet.power_generation.to_megawatts(), 10.0);
+    assert_approx_eq(fleet.build_cost["Plate"], 60.0);
+  }
+
+  #[test]
+  fn fleet_pcu_is_unknown_when_block_pcu_is_unknown() {
+    let data = data();
+    let drone = drone();
+    let calculated = drone.calculate(&data);
+    let fleet = FleetCalculated::calculate([(&drone, &calculated)], &data, 1.0);
+    assert_eq!(fleet.pcu, None);
+  }
+
+  #[test]
+  fn fleet_build_time_sums_block_build_times_at_welder_speed() {
+    let mut data = data();
+    data.blocks.reactors.get_mut(REACTOR).unwrap().data.build_time = Some(40.0);
+    data.blocks.thrusters.get_mut(ION_THRUSTER).unwrap().data.build_time = Some(20.0);
+    let (carrier, drone) = (carrier(), drone());
+    let (carrier_calculated, drone_calculated) = (carrier.calculate(&data), drone.calculate(&data));
+    assert_approx_eq(carrier.build_time(&data, 1.0).unwrap(), 40.0 + 2.0 * 20.0);
+    let fleet = FleetCalculated::calculate([(&carrier, &carrier_calculated), (&drone, &drone_calculated)], &data, 2.0);
+    assert_approx_eq(fleet.build_time.unwrap(), (40.0 + 4.0 * 20.0) / 2.0);
+  }
+
+  #[test]
+  fn fleet_build_time_is_unknown_when_block_build_time_is_unknown() {
+    let data = data();
+    let drone = drone();
+    let calculated = drone.calculate(&data);
+    let fleet = FleetCalculated::calculate([(&drone, &calculated)], &data, 1.0);
+    assert_eq!(fleet.build_time, None);
+  }
+}
+
+mod fractional_counts {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator(scale: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 2.0 * scale);
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0 * scale);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 3.0 * scale;
+    calculator
+  }
+
+  #[test]
+  fn fractional_counts_scale_results() {
+    let data = data();
+    let whole = calculator(1.0).calculate(&data);
+    let half = calculator(0.5).calculate(&data);
+    assert_approx_eq(half.total_mass_empty.to_kilograms(), whole.total_mass_empty.to_kilograms() * 0.5);
+    assert_approx_eq(half.power_generation.to_megawatts(), whole.power_generation.to_megawatts() * 0.5);
+    assert_approx_eq(half.hydrogen_generation, whole.hydrogen_generation * 0.5);
+    assert_approx_eq(half.forces.thrusters[Direction::Up].to_newtons(), whole.forces.thrusters[Direction::Up].to_newtons() * 0.5);
+  }
+
+  #[test]
+  fn whole_counts_serialize_as_integers() {
+    let json = serde_json::to_value(calculator(1.0)).unwrap();
+    assert!(json["blocks"][REACTOR].is_u64());
+    assert!(json["directional_blocks"][ION_THRUSTER][Direction::Up.into_index()].is_u64());
+
+    let json = serde_json::to_value(calculator(0.5)).unwrap();
+    assert_approx_eq(json["blocks"][GENERATOR].as_f64().unwrap(), 0.5);
+    assert_approx_eq(json["directional_blocks"][ION_THRUSTER][Direction::Up.into_index()].as_f64().unwrap(), 1.5);
+  }
+
+  #[test]
+  fn integer_counts_deserialize() {
+    let json = format!(r#"{{ "blocks": {{ "{}": 2 }}, "directional_blocks": {{ "{}": [3, 0, 0, 0, 0, 1] }} }}"#, REACTOR, ION_THRUSTER);
+    let calculator = GridCalculator::from_json(json.as_bytes()).unwrap();
+    assert_approx_eq(calculator.blocks[REACTOR], 2.0);
+    assert_approx_eq(calculator.directional_blocks[ION_THRUSTER][Direction::Up], 3.0);
+
+    let mut json = Vec::new();
+    calculator.to_json(&mut json).unwrap();
+    let round_trip = GridCalculator::from_json(json.as_slice()).unwrap();
+    assert_eq!(round_trip.digest(), calculator.digest());
+  }
+
+  #[test]
+  fn fractional_generator_counts_round_up_additional_generators() {
+    // A hydrogen thruster consumes 30 L/s at full burn, and each generator generates 20 L/s.
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(GENERATOR.to_string(), 1.5);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    let requirement = calculator.calculate(&data()).hydrogen_generator_requirement.unwrap();
+    assert_eq!(requirement.generator_count, 2);
+    assert_eq!(requirement.additional_generator_count, 1);
+  }
+}
+
+mod item_stacks {
+  use secalc_core::data::components::{Component, STEEL_PLATE_COMPONENT_ID};
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(CONTAINER.to_string(), 1.0);
+    calculator.any_fill_with_steel_plates = 50.0;
+    calculator.any_fill_with_hydrogen_bottles = 50.0;
+    calculator
+  }
+
+  #[test]
+  fn items_are_fractional_by_default() {
+    let calculated = calculator().calculate(&data());
+    assert!(calculated.total_items_steel_plate.fract() > 0.0);
+    assert_eq!(calculated.total_stacks_steel_plate, None);
+    assert_eq!(calculated.total_stacks_hydrogen_bottle, None);
+  }
+
+  #[test]
+  fn items_are_rounded_to_stacks() {
+    let mut data = data();
+    data.components.components.insert(STEEL_PLATE_COMPONENT_ID.to_string(), Component { name: "Steel Plate".to_string(), mass: 20.0, volume: 3.0, max_stack_amount: Some(100.0) });
+    let mut calculator = calculator();
+    let fractional = calculator.calculate(&data);
+    calculator.round_items_to_stacks = true;
+    let calculated = calculator.calculate(&data);
+
+    assert_eq!(calculated.total_items_steel_plate, fractional.total_items_steel_plate.floor());
+    assert_eq!(calculated.total_items_hydrogen_bottle, fractional.total_items_hydrogen_bottle.floor());
+    assert_eq!(calculated.total_stacks_steel_plate, Some((calculated.total_items_steel_plate / 100.0).ceil()));
+    // Bottles do not stack.
+    assert_eq!(calculated.total_stacks_hydrogen_bottle, Some(calculated.total_items_hydrogen_bottle));
+    assert_approx_eq(calculated.hydrogen_bottle_capacity.to_liters(), calculated.total_items_hydrogen_bottle * 120.0);
+  }
+
+  #[test]
+  fn unlimited_stacks_have_no_stack_count() {
+    let mut calculator = calculator();
+    calculator.round_items_to_stacks = true;
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.total_stacks_steel_plate, None);
+  }
+}
+
+mod mining {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  const ORE_WEIGHT_PER_VOLUME: f64 = 1.0 / 0.37;
+
+  fn calculator(generators: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.mining_rate = 10.0;
+    calculator.blocks.insert(CONTAINER.to_string(), 1.0);
+    calculator.blocks.insert(GENERATOR.to_string(), generators);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator
+  }
+
+  #[test]
+  fn mining_fills_cargo_at_mining_rate() {
+    let mining = calculator(1.0).calculate(&data()).mining.unwrap();
+    // 1000 L at 10 L/s.
+    assert_approx_eq(mining.fill_duration.to_seconds(), 100.0);
+    assert_approx_eq(mining.mass_full.to_kilograms(), 1000.0 * ORE_WEIGHT_PER_VOLUME);
+    // 100 kN thrust against the weight of the ore.
+    assert_approx_eq(mining.ascent_acceleration_full.unwrap(), (100000.0 - mining.mass_full.to_kilograms() * 9.81) / mining.mass_full.to_kilograms());
+  }
+
+  #[test]
+  fn mining_hydrogen_lasts_when_generators_cover_hovering() {
+    let mining = calculator(1.0).calculate(&data()).mining.unwrap();
+    // Hovering uses a fraction of the 30 L/s of the up thruster, plus 1 L/s idle, which 20 L/s of generation covers.
+    assert!(mining.hydrogen_consumption < 20.0);
+    assert!(mining.hydrogen_duration.is_none());
+    assert!(mining.hydrogen_lasts());
+  }
+
+  #[test]
+  fn mining_hydrogen_runs_out_without_generators_or_tanks() {
+    let mining = calculator(0.0).calculate(&data()).mining.unwrap();
+    assert_approx_eq(mining.hydrogen_duration.unwrap().to_seconds(), 0.0);
+    assert!(!mining.hydrogen_lasts());
+  }
+
+  #[test]
+  fn no_mining_without_mining_rate() {
+    let mut calculator = calculator(1.0);
+    calculator.mining_rate = 0.0;
+    assert!(calculator.calculate(&data()).mining.is_none());
+  }
+}
+
+mod scale {
+  use secalc_core::data::blocks::BlockCategory;
+  use secalc_core::grid::{GridCalculator, ScaleRounding};
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 3.0);
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    let thrusters = calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 5.0;
+    thrusters[Direction::Down] = 1.0;
+    calculator
+  }
+
+  #[test]
+  fn scale_rounds_counts() {
+    let mut calculator = calculator();
+    calculator.scale_block_counts(1.5, ScaleRounding::Nearest, |_| true);
+    assert_eq!(calculator.blocks[HYDROGEN_TANK], 5.0); // 4.5
+    assert_eq!(calculator.blocks[GENERATOR], 2.0); // 1.5
+    assert_eq!(calculator.directional_blocks[HYDROGEN_THRUSTER][Direction::Up], 8.0); // 7.5
+    assert_eq!(calculator.directional_blocks[HYDROGEN_THRUSTER][Direction::Down], 2.0); // 1.5
+
+    let mut calculator = self::calculator();
+    calculator.scale_block_counts(0.5, ScaleRounding::Down, |_| true);
+    assert_eq!(calculator.blocks[HYDROGEN_TANK], 1.0);
+    assert_eq!(calculator.blocks[GENERATOR], 0.0);
+
+    let mut calculator = self::calculator();
+    calculator.scale_block_counts(0.5, ScaleRounding::Up, |_| true);
+    assert_eq!(calculator.blocks[HYDROGEN_TANK], 2.0);
+    assert_eq!(calculator.directional_blocks[HYDROGEN_THRUSTER][Direction::Down], 1.0);
+  }
+
+  #[test]
+  fn scale_only_selected_categories() {
+    let data = data();
+    let mut calculator = calculator();
+    calculator.scale_block_counts(2.0, ScaleRounding::Nearest, |id| data.blocks.category(id) == Some(BlockCategory::Thrusters));
+    assert_eq!(calculator.blocks[HYDROGEN_TANK], 3.0);
+    assert_eq!(calculator.directional_blocks[HYDROGEN_THRUSTER][Direction::Up], 10.0);
+  }
+}
+
+mod subgrid {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::subgrid::Subgrid;
+
+  use crate::common::*;
+
+  fn drillship() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    calculator.planetary_influence = 0.0;
+    calculator.additional_mass = 1000.0;
+    let mut drill_head = Subgrid { name: "Drill Head".to_string(), additional_mass: 2000.0, ..Subgrid::default() };
+    drill_head.blocks.insert(DRILL.to_string(), 2.0);
+    drill_head.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.subgrids.push(drill_head);
+    calculator
+  }
+
+  #[test]
+  fn subgrids_are_combined() {
+    let data = data();
+    let calculator = drillship();
+    let calculated = calculator.calculate(&data);
+    let combined = calculator.combined_with_subgrids();
+    assert!(combined.subgrids.is_empty());
+    assert_approx_eq(combined.blocks[DRILL], 2.0);
+    assert_approx_eq(combined.directional_blocks[ION_THRUSTER][Direction::Up], 3.0);
+    assert_approx_eq(combined.additional_mass, 3000.0);
+    // Thrusters of all grids move the mass of all grids.
+    let combined_calculated = combined.calculate(&data);
+    assert_approx_eq(calculated.total_mass_empty.to_kilograms(), combined_calculated.total_mass_empty.to_kilograms());
+    assert_approx_eq(calculated.forces.thrusters[Direction::Up].to_newtons(), 150_000.0);
+    assert_eq!(calculated.thruster_acceleration[Direction::Up].acceleration_empty_no_gravity, combined_calculated.thruster_acceleration[Direction::Up].acceleration_empty_no_gravity);
+    assert!(calculated.drilling.is_some());
+  }
+
+  #[test]
+  fn subgrids_have_breakdown() {
+    let data = data();
+    let calculated = drillship().calculate(&data);
+    let [main, drill_head] = calculated.grid_breakdown.as_slice() else { panic!("expected 2 grids in breakdown") };
+    assert_eq!(main.name, None);
+    assert_approx_eq(main.block_count, 3.0);
+    assert_approx_eq(main.total_mass_empty.to_kilograms(), 3000.0);
+    assert_approx_eq(main.power_generation.to_megawatts(), calculated.power_generation.to_megawatts());
+    assert_eq!(drill_head.name.as_deref(), Some("Drill Head"));
+    assert_approx_eq(drill_head.block_count, 3.0);
+    assert_approx_eq(drill_head.power_generation.to_megawatts(), 0.0);
+    assert_approx_eq((main.total_mass_empty + drill_head.total_mass_empty).to_kilograms(), calculated.total_mass_empty.to_kilograms());
+    assert_approx_eq((main.power_consumption + drill_head.power_consumption).to_megawatts(), calculated.power_upto_left_right_thruster.total_consumption.to_megawatts());
+    assert!(GridCalculator::new().calculate(&data).grid_breakdown.is_empty());
+  }
+
+  #[test]
+  fn subgrids_round_trip_through_json() {
+    let calculator = drillship();
+    let mut json = Vec::new();
+    calculator.to_json(&mut json).unwrap();
+    let round_trip = GridCalculator::from_json(json.as_slice()).unwrap();
+    assert_eq!(round_trip.subgrids.len(), 1);
+    assert_eq!(round_trip.digest(), calculator.digest());
+  }
+}
@@ -0,0 +1,803 @@
+mod common;
+
+mod armament {
+  use secalc_core::data::blocks::WeaponGroup;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn weapons() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(TURRET.to_string(), 2.0);
+    calculator.blocks.insert(INTERIOR_TURRET.to_string(), 1.0);
+    calculator
+  }
+
+  #[test]
+  fn armament_sums_ammo_storage_per_group() {
+    let calculated = weapons().calculate(&data());
+    let armament = calculated.armament.unwrap();
+    let groups: Vec<_> = armament.groups.iter().map(|g| (g.group, g.count, g.ammo_volume.to_liters())).collect();
+    assert_eq!(groups, vec![(WeaponGroup::Turret, 2.0, 320.0), (WeaponGroup::InteriorTurret, 1.0, 2.0)]);
+    assert_approx_eq(armament.ammo_volume.to_liters(), 322.0);
+    assert_approx_eq(armament.idle_power_consumption.to_megawatts(), 0.006);
+    assert_approx_eq(armament.operational_power_consumption.to_megawatts(), 0.006);
+  }
+
+  #[test]
+  fn ammo_mass_is_included_in_filled_mass() {
+    let mut calculator = weapons();
+    let calculated = calculator.calculate(&data());
+    let ammo_mass = 20.0 * 35.0 + 10.0 * 0.45;
+    assert_approx_eq(calculated.armament.unwrap().ammo_mass.to_kilograms(), ammo_mass);
+    assert_approx_eq(calculated.total_mass_filled.to_kilograms() - calculated.total_mass_empty.to_kilograms(), ammo_mass);
+
+    calculator.ammo_fill = 50.0;
+    let calculated = calculator.calculate(&data());
+    assert_approx_eq(calculated.armament.unwrap().ammo_mass.to_kilograms(), ammo_mass / 2.0);
+  }
+
+  #[test]
+  fn weapons_consume_idle_and_utility_power() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(FIXED_WEAPON.to_string(), 2.0);
+    let calculated = calculator.calculate(&data());
+    assert_approx_eq(calculated.power_idle.consumption.to_megawatts(), 0.0002);
+    assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.002);
+  }
+
+  #[test]
+  fn no_armament_without_weapons() {
+    let calculated = GridCalculator::new().calculate(&data());
+    assert!(calculated.armament.is_none());
+  }
+}
+
+mod battery_charge {
+  use secalc_core::grid::{BatteryMode, GridCalculator};
+
+  use crate::common::*;
+
+  fn calculator(reactors: f64, batteries: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.battery_mode = BatteryMode::Recharge;
+    calculator.battery_fill = 0.0;
+    calculator.blocks.insert(REACTOR.to_string(), reactors);
+    calculator.blocks.insert(BATTERY.to_string(), batteries);
+    calculator
+  }
+
+  #[test]
+  fn battery_charge_is_capped_at_maximum_input() {
+    let battery = calculator(1.0, 2.0).calculate(&data()).battery.unwrap();
+    // 10 MW surplus, but only 2 MW input: 2 MWh at 2 MW with 80% efficiency.
+    assert_approx_eq(battery.charge_duration.unwrap().to_hours(), 2.0 / (2.0 * 0.8));
+    assert_approx_eq(battery.wasted_surplus.unwrap().to_megawatts(), 8.0);
+  }
+
+  #[test]
+  fn battery_charge_is_capped_at_surplus() {
+    let battery = calculator(1.0, 20.0).calculate(&data()).battery.unwrap();
+    // 20 MW input, but only 10 MW surplus: 20 MWh at 10 MW with 80% efficiency.
+    assert_approx_eq(battery.charge_duration.unwrap().to_hours(), 20.0 / (10.0 * 0.8));
+    assert_approx_eq(battery.wasted_surplus.unwrap().to_megawatts(), 0.0);
+  }
+
+  #[test]
+  fn no_wasted_surplus_when_not_charging() {
+    let mut calculator = calculator(1.0, 2.0);
+    calculator.battery_mode = BatteryMode::Discharge;
+    let battery = calculator.calculate(&data()).battery.unwrap();
+    assert_eq!(battery.charge_duration, None);
+    assert_eq!(battery.wasted_surplus, None);
+  }
+}
+
+mod consumption_shares {
+  use secalc_core::grid::{GridCalculator, HydrogenChainGroup, PowerChainGroup};
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  #[test]
+  fn power_shares_sum_to_one() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Front] = 1.0;
+    let calculated = calculator.calculate(&data());
+    let sum: f64 = calculated.power_shares.iter().map(|(_, f)| f).sum();
+    assert_approx_eq(sum, 1.0);
+    let share = |group: PowerChainGroup| calculated.power_shares.iter().find(|(g, _)| *g == group).unwrap().1;
+    // Generator consumes 0.5 MW, and each thruster direction pair 2 MW.
+    assert_approx_eq(share(PowerChainGroup::Generator), 0.5 / 4.5);
+    assert_approx_eq(share(PowerChainGroup::UpDownThruster), 2.0 / 4.5);
+    assert_approx_eq(share(PowerChainGroup::FrontBackThruster), 2.0 / 4.5);
+    assert_approx_eq(share(PowerChainGroup::LeftRightThruster), 0.0);
+  }
+
+  #[test]
+  fn hydrogen_shares_per_thruster_axis() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 3.0;
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Left] = 1.0;
+    let calculated = calculator.calculate(&data());
+    let share = |group: HydrogenChainGroup| calculated.hydrogen_shares.iter().find(|(g, _)| *g == group).unwrap().1;
+    assert_approx_eq(share(HydrogenChainGroup::UpDownThruster), 0.75);
+    assert_approx_eq(share(HydrogenChainGroup::LeftRightThruster), 0.25);
+    assert_approx_eq(share(HydrogenChainGroup::FrontBackThruster), 0.0);
+  }
+
+  #[test]
+  fn shares_are_zero_without_consumption() {
+    let calculated = GridCalculator::new().calculate(&data());
+    assert!(calculated.power_shares.iter().all(|(_, f)| *f == 0.0));
+    assert!(calculated.hydrogen_shares.iter().all(|(_, f)| *f == 0.0));
+  }
+}
+
+mod fuel_cost {
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  /// Grid with a 1 MWh battery and a 3 MWh jump drive.
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(BATTERY.to_string(), 1.0);
+    calculator.blocks.insert(JUMP_DRIVE.to_string(), 1.0);
+    calculator
+  }
+
+  #[test]
+  fn no_fuel_cost_without_batteries_and_jump_drives() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    assert!(calculator.calculate(&data()).fuel_cost.is_none());
+  }
+
+  #[test]
+  fn charge_includes_charging_losses() {
+    let fuel_cost = calculator().calculate(&data()).fuel_cost.unwrap();
+    assert_approx_eq(fuel_cost.battery_energy.to_megawatt_hours(), 1.0 / 0.8);
+    assert_approx_eq(fuel_cost.jump_drive_energy.to_megawatt_hours(), 3.0 / 0.8);
+    assert_approx_eq(fuel_cost.energy().to_megawatt_hours(), 5.0);
+    assert!(fuel_cost.uranium_mass.is_none());
+    assert!(fuel_cost.hydrogen.is_none());
+    assert!(fuel_cost.ice_mass.is_none());
+  }
+
+  #[test]
+  fn reactors_consume_uranium() {
+    let mut calculator = calculator();
+    calculator.blocks.insert(REACTOR.to_string(), 2.0);
+    let calculated = calculator.calculate(&data());
+    let reactor = calculated.reactor.as_ref().unwrap();
+    assert_approx_eq(reactor.maximum_output.to_megawatts(), 20.0);
+    assert_approx_eq(reactor.maximum_fuel_consumption, 0.002);
+    // 5 MWh at 0.001 ingots per 10 MW*s: 5 * 3600 * 0.0001 = 1.8 ingots of 1 kg, independent of the reactor count.
+    assert_approx_eq(calculated.fuel_cost.unwrap().uranium_mass.unwrap().to_kilograms(), 1.8);
+  }
+
+  #[test]
+  fn reactors_without_fuel_consumption_have_no_uranium_cost() {
+    let mut data = data();
+    data.blocks.reactors.get_mut(REACTOR).unwrap().details.max_fuel_consumption = 0.0;
+    let mut calculator = calculator();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    assert!(calculator.calculate(&data).fuel_cost.unwrap().uranium_mass.is_none());
+  }
+
+  #[test]
+  fn hydrogen_engines_consume_ice_through_generators() {
+    let mut calculator = calculator();
+    calculator.blocks.insert(HYDROGEN_ENGINE.to_string(), 1.0);
+    let fuel_cost = calculator.calculate(&data()).fuel_cost.unwrap();
+    // 5 MWh at 10 L per 1 MW*s.
+    assert_approx_eq(fuel_cost.hydrogen.unwrap().to_liters(), 180000.0);
+    assert!(fuel_cost.ice_mass.is_none());
+
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    let fuel_cost = calculator.calculate(&data()).fuel_cost.unwrap();
+    // Generators convert 1 ice into 20 L hydrogen.
+    assert_approx_eq(fuel_cost.ice_mass.unwrap().to_kilograms(), 9000.0);
+  }
+}
+
+mod generator {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator(generators: f64, hydrogen_thrusters: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(GENERATOR.to_string(), generators);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = hydrogen_thrusters;
+    calculator
+  }
+
+  #[test]
+  fn generator_requirement() {
+    // 3 hydrogen thrusters consume 90 L/s at full burn, and each generator generates 20 L/s from 1 ice/s.
+    let requirement = calculator(2.0, 3.0).calculate(&data()).hydrogen_generator_requirement.unwrap();
+    assert_approx_eq(requirement.consumption, 90.0);
+    assert_eq!(requirement.generator_count, 5);
+    assert_eq!(requirement.additional_generator_count, 3);
+    assert_approx_eq(requirement.ice_consumption, 5.0);
+  }
+
+  #[test]
+  fn no_additional_generators_when_generation_suffices() {
+    let requirement = calculator(4.0, 1.0).calculate(&data()).hydrogen_generator_requirement.unwrap();
+    assert_eq!(requirement.generator_count, 2);
+    assert_eq!(requirement.additional_generator_count, 0);
+    assert_approx_eq(requirement.ice_consumption, 2.0);
+  }
+
+  #[test]
+  fn no_generator_requirement_without_generators() {
+    let calculated = calculator(0.0, 3.0).calculate(&data());
+    assert!(calculated.hydrogen_generator_requirement.is_none());
+  }
+
+  /// Calculator with generators that cover 10 L/s of hydrogen demand from hydrogen engines, out of 20 L/s, and 8 L/s of
+  /// oxygen demand, out of 10 L/s.
+  fn throttling_calculator(generator_oxygen_throttling: bool) -> GridCalculator {
+    let mut calculator = calculator(1.0, 0.0);
+    calculator.blocks.insert(HYDROGEN_ENGINE.to_string(), 1.0);
+    calculator.oxygen_demand = 8.0;
+    calculator.generator_oxygen_throttling = generator_oxygen_throttling;
+    calculator
+  }
+
+  #[test]
+  fn generator_utilization_without_oxygen_throttling() {
+    let calculated = throttling_calculator(false).calculate(&data());
+    let generator = calculated.generator.unwrap();
+    assert_approx_eq(generator.utilization, 50.0);
+    assert_approx_eq(generator.ice_consumption, 0.5);
+    assert_approx_eq(generator.hydrogen_output, 10.0);
+    assert_approx_eq(generator.oxygen_output, 5.0);
+    assert_approx_eq(generator.oxygen_balance, -3.0);
+  }
+
+  #[test]
+  fn generator_utilization_with_oxygen_throttling() {
+    let calculated = throttling_calculator(true).calculate(&data());
+    let generator = calculated.generator.unwrap();
+    assert_approx_eq(generator.utilization, 80.0);
+    assert_approx_eq(generator.ice_consumption, 0.8);
+    assert_approx_eq(generator.hydrogen_output, 16.0);
+    assert_approx_eq(generator.oxygen_output, 8.0);
+    assert_approx_eq(generator.oxygen_balance, 0.0);
+  }
+
+  #[test]
+  fn generator_ice_duration_follows_utilization() {
+    for (generator_oxygen_throttling, ice_consumption) in [(false, 0.5), (true, 0.8)] {
+      let calculated = throttling_calculator(generator_oxygen_throttling).calculate(&data());
+      assert!(calculated.total_items_ice > 0.0);
+      let generator = calculated.generator.unwrap();
+      assert_approx_eq(generator.ice_duration.unwrap().to_seconds(), calculated.total_items_ice / ice_consumption);
+    }
+  }
+}
+
+mod gyroscope {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::gyroscope::{GridDimensions, RotationAxis};
+
+  use crate::common::*;
+
+  fn gyroscopes() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(GYROSCOPE.to_string(), 2.0);
+    calculator.additional_mass = 1200.0;
+    calculator
+  }
+
+  #[test]
+  fn gyroscopes_sum_torque_and_power() {
+    let calculated = gyroscopes().calculate(&data());
+    let gyroscope = calculated.gyroscope.unwrap();
+    assert_approx_eq(gyroscope.count, 2.0);
+    assert_approx_eq(gyroscope.torque, 2000.0);
+    assert_approx_eq(gyroscope.maximum_input, 0.02);
+    assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.02);
+  }
+
+  #[test]
+  fn gyroscopes_estimate_rotational_acceleration_per_axis() {
+    let mut calculator = gyroscopes();
+    calculator.dimensions = GridDimensions { width: 1.0, height: 2.0, length: 3.0 };
+    let calculated = calculator.calculate(&data());
+    let gyroscope = calculated.gyroscope.unwrap();
+    // Solid cuboid: I = m * (a^2 + b^2) / 12 for the two dimensions perpendicular to the axis.
+    let expected = |a: f64, b: f64| (2000.0 / (1200.0 * (a * a + b * b) / 12.0)).to_degrees();
+    assert_approx_eq(gyroscope.rotation(RotationAxis::Pitch).unwrap().acceleration_filled.unwrap(), expected(2.0, 3.0));
+    assert_approx_eq(gyroscope.rotation(RotationAxis::Yaw).unwrap().acceleration_filled.unwrap(), expected(1.0, 3.0));
+    assert_approx_eq(gyroscope.rotation(RotationAxis::Roll).unwrap().acceleration_empty.unwrap(), expected(1.0, 2.0));
+  }
+
+  #[test]
+  fn gyroscopes_do_not_estimate_rotation_without_dimensions() {
+    let calculated = gyroscopes().calculate(&data());
+    assert!(calculated.gyroscope.unwrap().rotation.is_empty());
+  }
+
+  #[test]
+  fn no_gyroscope_calculation_without_gyroscopes() {
+    assert!(GridCalculator::new().calculate(&data()).gyroscope.is_none());
+  }
+}
+
+mod jump_drive {
+  use secalc_core::grid::{BatteryMode, GridCalculator, PowerSourcePriority};
+
+  use crate::common::*;
+
+  fn calculator(batteries: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(BATTERY.to_string(), batteries);
+    calculator.blocks.insert(JUMP_DRIVE.to_string(), 1.0);
+    calculator
+  }
+
+  #[test]
+  fn minimum_battery_fill() {
+    let jump_drive = calculator(10.0).calculate(&data()).jump_drive.unwrap();
+    // 3 MWh at 1 MW with 80% efficiency takes 3.75 h, draining 3.75 MWh from 10 MWh of batteries.
+    assert_approx_eq(jump_drive.charge_duration.unwrap().to_hours(), 3.75);
+    assert_approx_eq(jump_drive.minimum_battery_fill.unwrap(), 37.5);
+  }
+
+  #[test]
+  fn minimum_battery_fill_exceeds_full_batteries() {
+    let jump_drive = calculator(1.0).calculate(&data()).jump_drive.unwrap();
+    // Draining 3.75 MWh from 1 MWh of batteries.
+    assert_approx_eq(jump_drive.minimum_battery_fill.unwrap(), 375.0);
+  }
+
+  #[test]
+  fn no_minimum_battery_fill_without_jump_drive_input() {
+    let mut calculator = calculator(1.0);
+    // Drills consume all 1 MW of battery output before jump drives are charged.
+    calculator.blocks.insert(DRILL.to_string(), 500.0);
+    let jump_drive = calculator.calculate(&data()).jump_drive.unwrap();
+    assert_eq!(jump_drive.minimum_battery_fill, None);
+  }
+
+  #[test]
+  fn minimum_battery_fill_follows_power_source_priority() {
+    let mut calculator = calculator(10.0);
+    calculator.blocks.insert(HYDROGEN_ENGINE.to_string(), 1.0);
+    calculator.power_source_priority = PowerSourcePriority::EnginesFirst;
+    let jump_drive = calculator.calculate(&data()).jump_drive.unwrap();
+    // Hydrogen engines supply all 1 MW, so batteries are not drained.
+    assert_approx_eq(jump_drive.minimum_battery_fill.unwrap(), 0.0);
+    calculator.power_source_priority = PowerSourcePriority::BatteriesFirst;
+    let jump_drive = calculator.calculate(&data()).jump_drive.unwrap();
+    assert_approx_eq(jump_drive.minimum_battery_fill.unwrap(), 37.5);
+  }
+
+  #[test]
+  fn wasted_surplus_beyond_jump_drive_input() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(JUMP_DRIVE.to_string(), 1.0);
+    let jump_drive = calculator.calculate(&data()).jump_drive.unwrap();
+    // 10 MW generated, but only 1 MW input: 3 MWh at 1 MW with 80% efficiency.
+    assert_approx_eq(jump_drive.charge_duration.unwrap().to_hours(), 3.75);
+    assert_approx_eq(jump_drive.wasted_surplus.unwrap().to_megawatts(), 9.0);
+
+    calculator.jump_drive_charging = false;
+    let jump_drive = calculator.calculate(&data()).jump_drive.unwrap();
+    assert_eq!(jump_drive.wasted_surplus, None);
+  }
+
+  #[test]
+  fn wasted_surplus_beyond_railgun_input() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(RAILGUN.to_string(), 1.0);
+    let railgun = calculator.calculate(&data()).railgun.unwrap();
+    // 10 MW generated, but only 2 MW input: 0.5 MWh at 2 MW.
+    assert_approx_eq(railgun.charge_duration.unwrap().to_hours(), 0.25);
+    assert_approx_eq(railgun.wasted_surplus.unwrap().to_megawatts(), 8.0);
+  }
+
+  #[test]
+  fn wasted_surplus_is_per_charging_sink() {
+    let mut calculator = GridCalculator::new();
+    calculator.battery_mode = BatteryMode::Recharge;
+    calculator.battery_fill = 0.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(RAILGUN.to_string(), 1.0);
+    calculator.blocks.insert(JUMP_DRIVE.to_string(), 1.0);
+    calculator.blocks.insert(BATTERY.to_string(), 2.0);
+    let calculated = calculator.calculate(&data());
+    // 10 MW generated, of which railguns take 2 MW, jump drives 1 MW, and batteries 2 MW.
+    assert_approx_eq(calculated.railgun.unwrap().wasted_surplus.unwrap().to_megawatts(), 8.0);
+    assert_approx_eq(calculated.jump_drive.unwrap().wasted_surplus.unwrap().to_megawatts(), 7.0);
+    assert_approx_eq(calculated.battery.unwrap().wasted_surplus.unwrap().to_megawatts(), 5.0);
+  }
+}
+
+mod power_groups {
+  use secalc_core::data::power_groups::PowerGroup;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(DRILL.to_string(), 1.0);
+    calculator
+  }
+
+  #[test]
+  fn drills_are_utility_by_default() {
+    let calculated = calculator().calculate(&data());
+    assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.002);
+    assert_approx_eq(calculated.power_upto_generator.consumption.to_megawatts(), 0.0);
+  }
+
+  #[test]
+  fn assigned_power_group_moves_consumption_in_power_chain() {
+    let mut data = data();
+    data.power_groups.power_groups.insert(DRILL.to_string(), PowerGroup::Production);
+    let calculated = calculator().calculate(&data);
+    assert_approx_eq(calculated.power_upto_utility.consumption.to_megawatts(), 0.0);
+    assert_approx_eq(calculated.power_upto_generator.consumption.to_megawatts(), 0.002);
+    // Total consumption is unchanged.
+    assert_approx_eq(calculated.power_upto_battery_charge.total_consumption.to_megawatts(), 0.002);
+  }
+}
+
+mod power_priority {
+  use secalc_core::grid::{GridCalculator, PowerSourcePriority};
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::assert_approx_eq;
+
+  /// Calculator in space with 1 MW of batteries, 1 MW of hydrogen engines, and a 1 MW ion thruster pointing up.
+  fn calculator(power_source_priority: PowerSourcePriority) -> GridCalculator {
+    let mut calculator = GridCalculator { power_source_priority, planetary_influence: 0.0, ..GridCalculator::default() };
+    calculator.blocks.insert(crate::common::BATTERY.to_string(), 1.0);
+    calculator.blocks.insert(crate::common::HYDROGEN_ENGINE.to_string(), 1.0);
+    calculator.directional_blocks.entry(crate::common::ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator
+  }
+
+  #[test]
+  fn attribute_load() {
+    assert_eq!(PowerSourcePriority::BatteriesFirst.attribute(1.5, 1.0, 1.0), (1.0, 0.5));
+    assert_eq!(PowerSourcePriority::EnginesFirst.attribute(1.5, 1.0, 1.0), (0.5, 1.0));
+    assert_eq!(PowerSourcePriority::Proportional.attribute(1.5, 1.0, 2.0), (0.5, 1.0));
+    // Load exceeding the combined output is not attributed.
+    assert_eq!(PowerSourcePriority::BatteriesFirst.attribute(5.0, 1.0, 1.0), (1.0, 1.0));
+    assert_eq!(PowerSourcePriority::Proportional.attribute(1.0, 0.0, 0.0), (0.0, 0.0));
+  }
+
+  #[test]
+  fn batteries_first_drains_only_batteries() {
+    let calculated = calculator(PowerSourcePriority::BatteriesFirst).calculate(&crate::common::data());
+    let power = &calculated.power_upto_up_down_thruster;
+    assert_approx_eq(power.total_consumption.to_megawatts(), 1.0);
+    assert_approx_eq(power.battery_drain.to_megawatts(), 1.0);
+    assert_approx_eq(power.engine_drain.to_megawatts(), 0.0);
+    assert_approx_eq(power.battery_duration.unwrap().to_hours(), 1.0);
+    assert!(power.engine_duration.is_none());
+  }
+
+  #[test]
+  fn engines_first_drains_only_engines() {
+    let calculated = calculator(PowerSourcePriority::EnginesFirst).calculate(&crate::common::data());
+    let power = &calculated.power_upto_up_down_thruster;
+    assert_approx_eq(power.battery_drain.to_megawatts(), 0.0);
+    assert_approx_eq(power.engine_drain.to_megawatts(), 1.0);
+    assert!(power.battery_duration.is_none());
+    assert_approx_eq(power.engine_duration.unwrap().to_seconds(), 100.0);
+  }
+
+  #[test]
+  fn proportional_shares_load() {
+    let calculated = calculator(PowerSourcePriority::Proportional).calculate(&crate::common::data());
+    let power = &calculated.power_upto_up_down_thruster;
+    assert_approx_eq(power.battery_drain.to_megawatts(), 0.5);
+    assert_approx_eq(power.engine_drain.to_megawatts(), 0.5);
+    assert_approx_eq(power.battery_duration.unwrap().to_hours(), 2.0);
+    assert_approx_eq(power.engine_duration.unwrap().to_seconds(), 200.0);
+    // Balance does not depend on the priority.
+    let batteries_first = calculator(PowerSourcePriority::BatteriesFirst).calculate(&crate::common::data());
+    assert_approx_eq(power.balance.to_megawatts(), batteries_first.power_upto_up_down_thruster.balance.to_megawatts());
+  }
+
+  #[test]
+  fn reactors_take_load_first() {
+    let mut calculator = calculator(PowerSourcePriority::BatteriesFirst);
+    calculator.blocks.insert(crate::common::REACTOR.to_string(), 1.0);
+    let calculated = calculator.calculate(&crate::common::data());
+    let power = &calculated.power_upto_up_down_thruster;
+    assert_approx_eq(power.battery_drain.to_megawatts(), 0.0);
+    assert_approx_eq(power.engine_drain.to_megawatts(), 0.0);
+    assert!(power.battery_duration.is_none());
+    assert!(power.engine_duration.is_none());
+
+    // Only the 1 MW deficit above the 10 MW reactor is taken from batteries.
+    calculator.directional_blocks.entry(crate::common::ION_THRUSTER.to_string()).or_default()[Direction::Up] = 11.0;
+    let calculated = calculator.calculate(&crate::common::data());
+    let power = &calculated.power_upto_up_down_thruster;
+    assert_approx_eq(power.battery_drain.to_megawatts(), 1.0);
+    assert_approx_eq(power.engine_drain.to_megawatts(), 0.0);
+    assert_approx_eq(power.battery_duration.unwrap().to_hours(), 1.0);
+  }
+}
+
+mod production {
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn production() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REFINERY.to_string(), 2.0);
+    calculator.blocks.insert(ASSEMBLER.to_string(), 1.0);
+    calculator
+  }
+
+  #[test]
+  fn refineries_and_assemblers_sum_rates_and_power() {
+    let calculated = production().calculate(&data());
+    let production = calculated.production.unwrap();
+    assert_approx_eq(production.refinery_count, 2.0);
+    assert_approx_eq(production.assembler_count, 1.0);
+    assert_approx_eq(production.ore_rate, 2.0 * 2.0 * 20.0);
+    assert_approx_eq(production.ingot_rate, 2.0 * 2.0 * 20.0 * 0.7);
+    assert_approx_eq(production.assembly_speed, 1.0);
+    assert_approx_eq(production.maximum_input, 2.0 * 0.5 + 0.25);
+    let production_consumption = calculated.power_upto_generator.consumption.to_megawatts() - calculated.power_upto_jump_drive_charge.consumption.to_megawatts();
+    assert_approx_eq(production_consumption, 1.25);
+  }
+
+  #[test]
+  fn refinery_ore_rate_and_ingot_yield_are_configurable() {
+    let mut calculator = production();
+    calculator.refinery_ore_rate = 10.0;
+    calculator.refinery_ingot_yield = 50.0;
+    let production = calculator.calculate(&data()).production.unwrap();
+    assert_approx_eq(production.ore_rate, 2.0 * 2.0 * 10.0);
+    assert_approx_eq(production.ingot_rate, 2.0 * 2.0 * 10.0 * 0.5);
+  }
+
+  #[test]
+  fn refineries_process_ore_at_cargo_fill() {
+    let mut calculator = production();
+    calculator.blocks.insert(CONTAINER.to_string(), 1.0);
+    calculator.any_fill_with_ore = 50.0;
+    let production = calculator.calculate(&data()).production.unwrap();
+    let ore_mass = 1000.0 * 0.5 / 0.37;
+    assert_approx_eq(production.ore_mass, ore_mass);
+    assert_approx_eq(production.process_duration.unwrap().to_seconds(), ore_mass / 80.0);
+  }
+
+  #[test]
+  fn no_production_without_refineries_or_assemblers() {
+    assert!(GridCalculator::new().calculate(&data()).production.is_none());
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(ASSEMBLER.to_string(), 1.0);
+    assert!(calculator.calculate(&data()).production.unwrap().process_duration.is_none());
+  }
+}
+
+mod safety_margin {
+  use secalc_core::grid::{GridCalculator, PowerChainGroup};
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  /// Calculator on a planet with a 10 MW reactor and 8 MW of atmospheric thrusters pointing up.
+  fn power_calculator(safety_margin: f64) -> GridCalculator {
+    let mut calculator = GridCalculator { safety_margin, ..GridCalculator::default() };
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 4.0;
+    calculator
+  }
+
+  #[test]
+  fn budget_equals_balance_without_margin() {
+    let calculated = power_calculator(0.0).calculate(&data());
+    let power = &calculated.power_upto_up_down_thruster;
+    assert_approx_eq(power.budget.to_megawatts(), power.balance.to_megawatts());
+    assert_eq!(calculated.first_over_budget_group, None);
+  }
+
+  #[test]
+  fn budget_subtracts_margin_of_total_consumption() {
+    let calculated = power_calculator(10.0).calculate(&data());
+    let power = &calculated.power_upto_up_down_thruster;
+    assert_approx_eq(power.budget.to_megawatts(), power.balance.to_megawatts() - power.total_consumption.to_megawatts() * 0.1);
+    assert!(power.budget.to_megawatts() > 0.0);
+    assert_eq!(calculated.first_over_budget_group, None);
+  }
+
+  #[test]
+  fn margin_marks_group_over_budget_without_cutting_out() {
+    let calculated = power_calculator(30.0).calculate(&data());
+    assert!(calculated.power_upto_up_down_thruster.balance.to_megawatts() > 0.0);
+    assert!(calculated.power_upto_up_down_thruster.budget.to_megawatts() < 0.0);
+    assert_eq!(calculated.first_unpowered_group, None);
+    assert_eq!(calculated.first_over_budget_group, Some(PowerChainGroup::UpDownThruster));
+  }
+
+  #[test]
+  fn hydrogen_budget_subtracts_margin_of_total_consumption() {
+    let mut calculator = GridCalculator { safety_margin: 20.0, ..GridCalculator::default() };
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    let calculated = calculator.calculate(&data());
+    let hydrogen = &calculated.hydrogen_upto_up_down_thruster;
+    assert_approx_eq(hydrogen.budget, hydrogen.balance_with_tank - hydrogen.total_consumption * 0.2);
+  }
+
+  #[test]
+  fn generator_requirement_includes_margin() {
+    // 2 hydrogen thrusters consume 60 L/s at full burn, and each generator generates 20 L/s.
+    let requirement = |safety_margin: f64| {
+      let mut calculator = GridCalculator { safety_margin, ..GridCalculator::default() };
+      calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+      calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+      calculator.calculate(&data()).hydrogen_generator_requirement.unwrap()
+    };
+    assert_eq!(requirement(0.0).generator_count, 3);
+    let with_margin = requirement(50.0);
+    assert_approx_eq(with_margin.consumption, 90.0);
+    assert_eq!(with_margin.generator_count, 5);
+    assert_eq!(with_margin.additional_generator_count, 4);
+  }
+}
+
+mod silent_running {
+  use secalc_core::grid::{BatteryMode, GridCalculator};
+  use secalc_core::grid::scenario::Scenario;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.battery_mode = BatteryMode::Recharge;
+    calculator.blocks.insert(BATTERY.to_string(), 1.0);
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    calculator
+  }
+
+  #[test]
+  fn silent_running_uses_only_batteries() {
+    let data = data();
+    let calculated = calculator().calculate(&data);
+    // Settings of the calculator are used outside of silent running: reactor generates, battery is recharging.
+    assert_approx_eq(calculated.power_generation.to_megawatts(), 10.0);
+    let silent_running = calculated.silent_running.unwrap();
+    // 1 MWh battery at 0.001 MW idle consumption of the generator.
+    assert_approx_eq(silent_running.idle.unwrap().to_minutes(), 1000.0 * 60.0);
+    // 1 MWh battery at 0.5 MW operational consumption of the generator.
+    assert_approx_eq(silent_running.full_thrust.unwrap().to_minutes(), 2.0 * 60.0);
+  }
+
+  #[test]
+  fn silent_running_scenario_does_not_modify_calculator() {
+    let data = data();
+    let calculator = calculator();
+    let calculated = calculator.calculate_scenario(&data, &Scenario::SILENT_RUNNING);
+    assert_approx_eq(calculated.power_generation.to_megawatts(), 1.0);
+    assert_eq!(calculator.battery_mode, BatteryMode::Recharge);
+  }
+
+  #[test]
+  fn no_silent_running_without_batteries() {
+    let data = data();
+    let mut calculator = calculator();
+    calculator.blocks.remove(BATTERY);
+    assert!(calculator.calculate(&data).silent_running.is_none());
+  }
+}
+
+mod unpowered_group {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::{BatteryMode, GridCalculator, PowerChainGroup};
+
+  use crate::common::*;
+
+  #[test]
+  fn no_unpowered_group_when_generation_suffices() {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 1.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 5.0;
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.first_unpowered_group, None);
+  }
+
+  #[test]
+  fn first_unpowered_group_is_first_group_exceeding_generation() {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 1.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    // 4 * 2 MW up thrusters is within 10 MW generation, but adding 4 * 2 MW front thrusters is not.
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 4.0;
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Front] = 4.0;
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.first_unpowered_group, Some(PowerChainGroup::FrontBackThruster));
+    assert!(calculated.power_upto(PowerChainGroup::UpDownThruster).balance.to_megawatts() >= 0.0);
+  }
+
+  #[test]
+  fn charging_batteries_is_never_unpowered() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(BATTERY.to_string(), 20.0);
+    calculator.battery_mode = BatteryMode::Recharge;
+    calculator.battery_fill = 50.0;
+    let calculated = calculator.calculate(&data());
+    assert!(calculated.power_upto(PowerChainGroup::BatteryCharge).balance.to_megawatts() < 0.0);
+    assert_eq!(calculated.first_unpowered_group, None);
+  }
+}
+
+mod worst_thruster {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::{GridCalculator, HydrogenChainGroup, PowerChainGroup};
+
+  use crate::common::*;
+
+  #[test]
+  fn worst_power_thruster_pair_is_pair_with_highest_consumption() {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 1.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    let thrusters = calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 2.0;
+    thrusters[Direction::Back] = 4.0;
+    thrusters[Direction::Left] = 1.0;
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.power_worst_thruster_group, Some(PowerChainGroup::FrontBackThruster));
+    // Only the 4 * 2 MW front/back thrusters, not all thrusters, are on top of the non-thruster groups.
+    assert_approx_eq(calculated.power_worst_thruster.consumption.to_megawatts(), 8.0);
+    assert_approx_eq(calculated.power_worst_thruster.total_consumption.to_megawatts(), 8.0);
+    assert_approx_eq(calculated.power_worst_thruster.balance.to_megawatts(), 2.0);
+    assert!(calculated.power_upto_left_right_thruster.balance.to_megawatts() < 0.0);
+  }
+
+  #[test]
+  fn worst_hydrogen_thruster_pair_is_pair_with_highest_consumption() {
+    let mut calculator = GridCalculator::new();
+    let thrusters = calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 1.0;
+    thrusters[Direction::Right] = 2.0;
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.hydrogen_worst_thruster_group, Some(HydrogenChainGroup::LeftRightThruster));
+    assert_approx_eq(calculated.hydrogen_worst_thruster.consumption, 60.0);
+    assert_approx_eq(calculated.hydrogen_worst_thruster.balance_without_tank, -60.0);
+  }
+
+  #[test]
+  fn worst_thruster_pair_prefers_earlier_pair_on_ties() {
+    let mut calculator = GridCalculator::new();
+    let thrusters = calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Front] = 1.0;
+    thrusters[Direction::Down] = 1.0;
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.hydrogen_worst_thruster_group, Some(HydrogenChainGroup::UpDownThruster));
+  }
+
+  #[test]
+  fn no_worst_thruster_pair_without_thrusters() {
+    let calculated = GridCalculator::new().calculate(&data());
+    assert_eq!(calculated.power_worst_thruster_group, None);
+    assert_eq!(calculated.hydrogen_worst_thruster_group, None);
+    assert_approx_eq(calculated.power_worst_thruster.consumption.to_megawatts(), 0.0);
+  }
+}
@@ -0,0 +1,698 @@
+mod common;
+
+mod calculated_diff {
+  use std::fs::File;
+
+  use secalc_core::data::Data;
+  use secalc_core::grid::{GridCalculated, GridCalculator};
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::reference::REFERENCE_GRIDS;
+
+  use crate::common::*;
+
+  fn calculator(thrusters: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = thrusters;
+    calculator.additional_mass = 1000.0;
+    calculator
+  }
+
+  #[test]
+  fn calculated_round_trips_through_json() {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/../../data/data.json")).unwrap();
+    let data = Data::from_json(file).unwrap();
+    let calculated = [GridCalculator::new().calculate(&data)].into_iter()
+      .chain(REFERENCE_GRIDS.iter().map(|reference| reference.calculator().calculate(&data)));
+    for calculated in calculated {
+      let json = serde_json::to_value(&calculated).unwrap();
+      let round_trip: GridCalculated = serde_json::from_value(json.clone()).unwrap();
+      assert_eq!(serde_json::to_value(&round_trip).unwrap(), json);
+      assert!(calculated.diff(&round_trip).is_empty());
+    }
+  }
+
+  #[test]
+  fn diff_reports_changed_values() {
+    let data = data();
+    let one = calculator(1.0).calculate(&data);
+    let two = calculator(2.0).calculate(&data);
+    assert!(one.diff(&one).is_empty());
+
+    let diff = one.diff(&two);
+    let force = diff.get("forces.thrusters.0").unwrap();
+    assert_approx_eq(force.before.unwrap(), one.forces.thrusters[Direction::Up].to_newtons());
+    assert_approx_eq(force.after.unwrap(), two.forces.thrusters[Direction::Up].to_newtons());
+    assert_approx_eq(force.delta().unwrap(), two.forces.thrusters[Direction::Up].to_newtons() - one.forces.thrusters[Direction::Up].to_newtons());
+    // Unchanged values are left out.
+    assert!(diff.get("power_generation").is_none());
+    assert!(diff.get("stamp.app_version").is_none());
+  }
+
+  #[test]
+  fn diff_reports_values_that_become_calculated() {
+    let data = data();
+    let without_thrusters = calculator(0.0).calculate(&data);
+    let with_thrusters = calculator(1.0).calculate(&data);
+    let diff = without_thrusters.diff(&with_thrusters);
+    let braking = diff.get("thruster_acceleration.0.braking_empty_no_gravity.distance").unwrap();
+    assert_eq!(braking.before, None);
+    assert!(braking.after.is_some());
+    assert_eq!(braking.delta(), None);
+  }
+}
+
+mod calculated_json {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  #[test]
+  fn calculated_serializes_to_single_line_json() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 1.0);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    let calculated = calculator.calculate(&data());
+    let json = serde_json::to_string(&calculated).unwrap();
+    assert!(!json.contains('\n'));
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_approx_eq(value["total_mass_empty"].as_f64().unwrap(), calculated.total_mass_empty.to_kilograms());
+    assert_approx_eq(value["forces"]["thrusters"][Direction::Up.into_index()].as_f64().unwrap(), calculated.forces.thrusters[Direction::Up].to_newtons());
+  }
+}
+
+mod explain {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  #[test]
+  fn acceleration_explanation_shows_effectiveness_force_and_mass() {
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.planetary_influence = 0.5;
+    let calculated = calculator.calculate(&data());
+    let a = &calculated.thruster_acceleration[Direction::Up];
+    assert_approx_eq(a.nominal_force.to_newtons(), 200000.0);
+    assert_approx_eq(a.force.to_newtons(), 100000.0);
+    let explanation = calculated.explain_acceleration(&calculator, Direction::Up, true, false).unwrap();
+    assert_eq!(explanation, "force 200.00 kN × effectiveness 0.50 = 100.00 kN\nforce 100.00 kN / mass 1.00 t = 100.00 m/s²");
+    let explanation = calculated.explain_acceleration(&calculator, Direction::Up, true, true).unwrap();
+    assert!(explanation.ends_with("(force 100.00 kN − weight 1.00 t × 9.81 m/s² × 1.00 g) / mass 1.00 t = 90.19 m/s²"), "{}", explanation);
+  }
+
+  #[test]
+  fn acceleration_explanation_is_none_without_mass() {
+    let calculator = GridCalculator::new();
+    let calculated = calculator.calculate(&data());
+    assert!(calculated.explain_acceleration(&calculator, Direction::Up, false, false).is_none());
+  }
+
+  #[test]
+  fn power_explanation_shows_generation_and_consumption() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(DRILL.to_string(), 1.0);
+    let calculated = calculator.calculate(&data());
+    let explanation = calculated.explain_power(&calculator, &calculated.power_upto_utility);
+    assert_eq!(explanation.balance, "generation 10.00 MW − total consumption 0.00 MW = 10.00 MW");
+    assert!(explanation.battery_duration.is_none());
+  }
+}
+
+mod grid_search {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::search::{fuzzy_matches, search_grids};
+
+  use crate::common::*;
+
+  #[test]
+  fn fuzzy_matches_characters_in_order() {
+    assert!(fuzzy_matches("ion", "Small Ion Thruster"));
+    assert!(fuzzy_matches("ION thr", "Small Ion Thruster"));
+    assert!(fuzzy_matches("sit", "Small Ion Thruster"));
+    assert!(!fuzzy_matches("tis", "Small Ion Thruster"));
+    assert!(fuzzy_matches("", "Small Ion Thruster"));
+  }
+
+  #[test]
+  fn search_lists_grids_with_matching_blocks() {
+    let data = data();
+    let mut miner = GridCalculator::new();
+    miner.blocks.insert(REACTOR.to_string(), 1.0);
+    miner.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    miner.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Down] = 1.0;
+    let mut hauler = GridCalculator::new();
+    hauler.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Back] = 4.0;
+    let mut station = GridCalculator::new();
+    station.blocks.insert(REACTOR.to_string(), 2.0);
+    // Zero counts do not match.
+    station.directional_blocks.entry(ION_THRUSTER.to_string()).or_default();
+    let grids = [("Miner", &miner), ("Station", &station), ("Hauler", &hauler)];
+
+    let matches = search_grids(grids, &data, "TestIonThrust");
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].grid_name, "Hauler");
+    assert_eq!(matches[0].count(), 4.0);
+    assert_eq!(matches[1].grid_name, "Miner");
+    assert_eq!(matches[1].count(), 3.0);
+
+    let matches = search_grids(grids, &data, "reactor");
+    assert_eq!(matches.iter().map(|m| m.grid_name).collect::<Vec<_>>(), ["Miner", "Station"]);
+
+    assert!(search_grids(grids, &data, "  ").is_empty());
+    assert!(search_grids(grids, &data, "Railgun").is_empty());
+  }
+}
+
+mod reference {
+  use std::fs::File;
+
+  use secalc_core::data::Data;
+  use secalc_core::grid::reference::REFERENCE_GRIDS;
+
+  #[test]
+  fn reference_grids_only_have_bundled_blocks() {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/../../data/data.json")).unwrap();
+    let data = Data::from_json(file).unwrap();
+    for reference in REFERENCE_GRIDS {
+      let calculator = reference.calculator();
+      for id in calculator.blocks.keys().chain(calculator.directional_blocks.keys()) {
+        let block = data.blocks.get_data(id);
+        assert!(block.is_some(), "Reference grid '{}' has unknown block '{}'", reference.name, id);
+        assert_eq!(block.unwrap().size, reference.grid_size, "Reference grid '{}' has block '{}' of another size", reference.name, id);
+      }
+    }
+  }
+
+  #[test]
+  fn reference_grids_can_hover() {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/../../data/data.json")).unwrap();
+    let data = Data::from_json(file).unwrap();
+    for reference in REFERENCE_GRIDS {
+      let calculated = reference.calculator().calculate(&data);
+      assert!(calculated.pitched_hover_filled.can_hover, "Reference grid '{}' cannot hover when filled", reference.name);
+    }
+  }
+}
+
+mod resource_chain {
+  use secalc_core::grid::{BatteryMode, GridCalculator};
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::interop::resource_chain::to_dot;
+
+  use crate::common::*;
+
+  #[test]
+  fn empty_grid_has_only_power_node() {
+    let calculator = GridCalculator::new();
+    let dot = to_dot(&calculator, &calculator.calculate(&data()));
+    assert!(dot.starts_with("digraph \"Resource Chain\" {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("  power [label=\"Power\\n0.00 MW\", shape=ellipse];"));
+    assert!(!dot.contains("->"));
+    assert!(!dot.contains("hydrogen"));
+  }
+
+  #[test]
+  fn power_chain_edges() {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 0.0;
+    calculator.battery_mode = BatteryMode::Discharge;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(BATTERY.to_string(), 2.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 12.0;
+    let dot = to_dot(&calculator, &calculator.calculate(&data()));
+    assert!(dot.contains("  battery -> power [label=\"2.00 MW\"];"), "{}", dot);
+    assert!(dot.contains("  reactor -> power [label=\"10.00 MW\"];"), "{}", dot);
+    // 12 MW of up thrusters is covered by the 12 MW of generation, so the group is not lacking.
+    assert!(dot.contains("  power -> power_5 [label=\"12.00 MW\"];"), "{}", dot);
+    assert!(dot.contains("  power_5 [label=\"6. Up/Down Thrusters\"];"), "{}", dot);
+  }
+
+  #[test]
+  fn lacking_groups_are_red() {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 0.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 20.0;
+    let dot = to_dot(&calculator, &calculator.calculate(&data()));
+    assert!(dot.contains("  power -> power_5 [label=\"20.00 MW\", color=red, fontcolor=red];"), "{}", dot);
+  }
+
+  #[test]
+  fn hydrogen_chain_edges() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 1.0);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    let dot = to_dot(&calculator, &calculator.calculate(&data()));
+    assert!(dot.contains("  generator -> hydrogen [label=\"20.00 L/s\"];"), "{}", dot);
+    assert!(dot.contains("  hydrogen_tank [label=\"Hydrogen Tanks\\n1000 L\", shape=cylinder];"), "{}", dot);
+    assert!(dot.contains("  hydrogen -> hydrogen_1 [label=\"30.00 L/s\"];"), "{}", dot);
+  }
+}
+
+mod result_csv {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::interop::result_csv::to_csv;
+
+  use crate::common::*;
+
+  #[test]
+  fn result_rows_per_value() {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 0.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    let calculated = calculator.calculate(&data());
+    let csv = to_csv(&calculated);
+    assert!(csv.starts_with("path,value\n"));
+    let row = |path: &str| csv.lines().find_map(|line| line.strip_prefix(path).and_then(|rest| rest.strip_prefix(','))).map(str::to_string);
+    assert_approx_eq(row("total_mass_empty").unwrap().parse().unwrap(), calculated.total_mass_empty.to_kilograms());
+    let up = Direction::Up.into_index();
+    assert_approx_eq(row(&format!("thruster_acceleration.{}.force", up)).unwrap().parse().unwrap(), 100000.0);
+    // Values that are not calculated are empty.
+    assert_eq!(row("jump_drive").unwrap(), "");
+    // Every row has a path and a value.
+    assert!(csv.lines().all(|line| line.contains(',')));
+  }
+
+  #[test]
+  fn fields_with_separators_are_quoted() {
+    let id = "Reactor.Test,\"Quoted\"";
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(id.to_string(), 1.0);
+    calculator.build_progress.insert(id.to_string(), 0.0);
+    let csv = to_csv(&calculator.calculate(&data()));
+    assert!(csv.contains(",\"Reactor.Test,\"\"Quoted\"\"\"\n"), "{}", csv);
+  }
+}
+
+mod result_text {
+  use std::fmt::Write;
+
+  use secalc_core::data::Data;
+  use secalc_core::grid::{GridCalculated, GridCalculator};
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::forces::ForceUnit;
+  use secalc_core::grid::sweep::SweepResult;
+  use secalc_core::interop::block_list;
+
+  use crate::common::*;
+
+  /// Renders the labelled results, force sources, explanations, warnings, and block list of `calculated` into plain text,
+  /// in the order that GUIs and exports show them.
+  fn render(calculator: &GridCalculator, calculated: &GridCalculated, data: &Data) -> String {
+    let mut text = String::new();
+    writeln!(text, "# Results").unwrap();
+    for result in SweepResult::items() {
+      match result.get(calculated) {
+        Some(value) => writeln!(text, "{}: {:.2} {}", result, value, result.unit()).unwrap(),
+        None => writeln!(text, "{}: - {}", result, result.unit()).unwrap(),
+      }
+    }
+    writeln!(text, "\n# Forces").unwrap();
+    for (source, force) in calculated.forces.iter() {
+      writeln!(text, "{}: {:.2} {}", source.label(&calculator.direction_labels), ForceUnit::Kilonewton.convert(force), ForceUnit::Kilonewton.symbol()).unwrap();
+    }
+    writeln!(text, "\n# Acceleration").unwrap();
+    for direction in Direction::items() {
+      if let Some(explanation) = calculated.explain_acceleration(calculator, direction, true, true) {
+        writeln!(text, "{}: {}", calculator.direction_labels.get(direction), explanation).unwrap();
+      }
+    }
+    writeln!(text, "\n# Power").unwrap();
+    let power = calculated.explain_power(calculator, &calculated.power_upto_left_right_thruster);
+    writeln!(text, "Balance: {}", power.balance).unwrap();
+    writeln!(text, "Budget: {}", power.budget).unwrap();
+    writeln!(text, "Battery Duration: {}", power.battery_duration.as_deref().unwrap_or("-")).unwrap();
+    writeln!(text, "\n# Hydrogen").unwrap();
+    let hydrogen = calculated.explain_hydrogen(calculator, &calculated.hydrogen_upto_left_right_thruster);
+    writeln!(text, "Balance without Tank: {}", hydrogen.balance_without_tank).unwrap();
+    writeln!(text, "Balance with Tank: {}", hydrogen.balance_with_tank).unwrap();
+    writeln!(text, "Budget: {}", hydrogen.budget).unwrap();
+    writeln!(text, "Tank Duration: {}", hydrogen.tank_duration.as_deref().unwrap_or("-")).unwrap();
+    writeln!(text, "\n# Warnings").unwrap();
+    for warning in &calculated.warnings {
+      writeln!(text, "{}", warning).unwrap();
+    }
+    writeln!(text, "\n# Block List").unwrap();
+    text.push_str(&block_list::to_text(calculator, data));
+    text
+  }
+
+  #[test]
+  fn renders_empty_grid() {
+    let data = data();
+    let calculator = GridCalculator::new();
+    let calculated = calculator.calculate(&data);
+    insta::assert_snapshot!(render(&calculator, &calculated, &data));
+  }
+
+  #[test]
+  fn renders_hydrogen_ship() {
+    let data = data();
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(BATTERY.to_string(), 2.0);
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 2.0);
+    calculator.blocks.insert(GENERATOR.to_string(), 1.0);
+    calculator.blocks.insert(CONTAINER.to_string(), 4.0);
+    let thrusters = calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 4.0;
+    thrusters[Direction::Front] = 2.0;
+    thrusters[Direction::Back] = 2.0;
+    *calculator.direction_labels.custom_mut(Direction::Front) = "Forward".to_string();
+    let calculated = calculator.calculate(&data);
+    insta::assert_snapshot!(render(&calculator, &calculated, &data));
+  }
+}
+
+mod simulation {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::simulation::{SimulationOptions, ThrustPhase};
+
+  use crate::common::*;
+
+  fn options(phases: Vec<ThrustPhase>) -> SimulationOptions {
+    SimulationOptions { time_step: 10.0, phases }
+  }
+
+  /// Grid with a 1 MWh battery at 1 MW output, and a 1 MW ion thruster facing up, in space.
+  fn battery_calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(BATTERY.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.planetary_influence = 0.0;
+    calculator.battery_fill = 100.0;
+    calculator
+  }
+
+  #[test]
+  fn battery_drains_with_thrust() {
+    let result = battery_calculator().simulate(&data(), &options(vec![ThrustPhase::new(1800.0, Direction::Up, 100.0)]));
+    assert_eq!(result.samples.len(), 181);
+    let first = result.samples.first().unwrap();
+    assert_approx_eq(first.time, 0.0);
+    assert_approx_eq(first.battery_charge.unwrap(), 100.0);
+    let last = result.samples.last().unwrap();
+    assert_approx_eq(last.time, 1800.0);
+    assert_approx_eq(last.battery_charge.unwrap(), 50.0);
+    assert!(last.hydrogen_tank_fill.is_none());
+    assert!(result.first_power_shortage().is_none());
+  }
+
+  #[test]
+  fn power_runs_short_when_battery_is_empty() {
+    let result = battery_calculator().simulate(&data(), &options(vec![ThrustPhase::new(4000.0, Direction::Up, 50.0)]));
+    // Half thrust uses 0.5 MW, emptying the battery after 2 hours.
+    assert!(result.first_power_shortage().is_none());
+    let result = battery_calculator().simulate(&data(), &options(vec![ThrustPhase::new(4000.0, Direction::Up, 100.0)]));
+    assert_approx_eq(result.first_power_shortage().unwrap(), 3610.0);
+    assert_approx_eq(result.samples.last().unwrap().battery_charge.unwrap(), 0.0);
+  }
+
+  #[test]
+  fn idle_thrusters_do_not_drain() {
+    let result = battery_calculator().simulate(&data(), &options(vec![ThrustPhase { duration: 600.0, ..ThrustPhase::default() }]));
+    assert!(result.samples.iter().all(|s| s.battery_charge == Some(100.0)));
+    assert!(result.first_power_shortage().is_none());
+  }
+
+  #[test]
+  fn phases_run_in_order() {
+    let result = battery_calculator().simulate(&data(), &options(vec![
+      ThrustPhase::new(900.0, Direction::Up, 100.0),
+      ThrustPhase::new(905.0, Direction::Down, 100.0), // No thrusters facing down.
+      ThrustPhase::new(900.0, Direction::Up, 100.0),
+    ]));
+    let at = |time: f64| result.samples.iter().find(|s| (s.time - time).abs() < 1e-6).unwrap().battery_charge.unwrap();
+    assert_approx_eq(at(900.0), 75.0);
+    assert_approx_eq(at(1805.0), 75.0);
+    assert_approx_eq(at(2705.0), 50.0);
+  }
+
+  #[test]
+  fn hydrogen_tanks_supply_thrusters() {
+    // 1000 L tank supplying a 30 L/s hydrogen thruster, with a reactor for power.
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 1.0);
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.hydrogen_tank_fill = 100.0;
+    let result = calculator.simulate(&data(), &options(vec![ThrustPhase::new(20.0, Direction::Up, 100.0)]));
+    let last = result.samples.last().unwrap();
+    // Thrusters use 30 L/s, emptying the tank after 33.3 s.
+    assert_approx_eq(last.hydrogen_tank_fill.unwrap(), 40.0);
+    assert!(result.first_hydrogen_shortage().is_none());
+    let result = calculator.simulate(&data(), &options(vec![ThrustPhase::new(40.0, Direction::Up, 100.0)]));
+    assert_approx_eq(result.first_hydrogen_shortage().unwrap(), 40.0);
+    assert!(result.first_power_shortage().is_none());
+  }
+}
+
+mod snapshot {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::snapshot::Snapshot;
+
+  use crate::common::*;
+
+  fn grids() -> Vec<(String, GridCalculator)> {
+    let mut reactor = GridCalculator::new();
+    reactor.blocks.insert(REACTOR.to_string(), 1.0);
+    let mut thruster = GridCalculator::new();
+    thruster.blocks.insert(REACTOR.to_string(), 1.0);
+    thruster.planetary_influence = 0.0;
+    thruster.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    vec![("thruster".to_string(), thruster), ("reactor".to_string(), reactor)]
+  }
+
+  fn snapshot(grids: &[(String, GridCalculator)]) -> Snapshot {
+    Snapshot::calculate(grids.iter().map(|(name, calculator)| (name.clone(), calculator)), &data())
+  }
+
+  #[test]
+  fn snapshot_is_deterministic() {
+    let grids = grids();
+    let snapshot = snapshot(&grids);
+    assert_eq!(snapshot.grids.keys().collect::<Vec<_>>(), vec!["reactor", "thruster"]);
+    assert!(snapshot.grids["reactor"].get("stamp").is_none());
+
+    let mut json = Vec::new();
+    snapshot.to_json(&mut json).unwrap();
+    let mut json_again = Vec::new();
+    self::snapshot(&grids).to_json(&mut json_again).unwrap();
+    assert_eq!(json, json_again);
+    let read = Snapshot::from_json(json.as_slice()).unwrap();
+    assert!(read.diff(&snapshot, 1e-9).is_empty());
+  }
+
+  #[test]
+  fn check_reports_changed_results() {
+    let mut grids = grids();
+    let expected = snapshot(&grids);
+    grids[0].1.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    grids.pop();
+    let actual = snapshot(&grids);
+
+    let differences = expected.diff(&actual, 1e-9);
+    let force = differences.iter().find(|d| d.grid == "thruster" && d.path == "thruster_acceleration.0.force").unwrap();
+    assert_approx_eq(force.expected.as_ref().unwrap().as_f64().unwrap(), 50000.0);
+    assert_approx_eq(force.actual.as_ref().unwrap().as_f64().unwrap(), 100000.0);
+    let removed = differences.iter().find(|d| d.grid == "reactor").unwrap();
+    assert!(removed.path.is_empty() && removed.actual.is_none());
+    assert!(removed.to_string().ends_with("got (missing)"));
+  }
+}
+
+mod summary {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::summary::StatSummary;
+
+  use crate::common::*;
+
+  #[test]
+  fn unchanged_grid_has_no_changes() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    let summary = StatSummary::from_calculated(&calculator.calculate(&data()));
+    assert!(summary.changes(&summary).is_empty());
+  }
+
+  #[test]
+  fn changes_between_saves() {
+    let data = data();
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.planetary_influence = 0.0;
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    let older = StatSummary::from_calculated(&calculator.calculate(&data));
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    let newer = StatSummary::from_calculated(&calculator.calculate(&data));
+
+    let changes = older.changes(&newer);
+    let mass = changes.iter().find(|c| c.stat == "mass").unwrap();
+    assert_approx_eq(mass.delta, 1.0);
+    assert_eq!(mass.to_string(), "mass +1.00 t");
+    let power_balance = changes.iter().find(|c| c.stat == "power balance").unwrap();
+    assert_approx_eq(power_balance.delta, -1.0);
+    assert_eq!(power_balance.to_string(), "power balance −1.00 MW");
+    assert!(changes.iter().all(|c| c.stat != "power generation"));
+  }
+}
+
+mod sweep {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::sweep::{sweep, SweepOption, SweepResult};
+
+  use crate::common::*;
+
+  #[test]
+  fn sweep_calculates_result_over_option_range() {
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.gravity_multiplier = 0.0;
+    let points = sweep(&calculator, &data(), SweepOption::PlanetaryInfluence, SweepResult::UpAcceleration, 0.0..=1.0, 3);
+    assert_eq!(points.len(), 3);
+    let options: Vec<f64> = points.iter().map(|p| p.option).collect();
+    assert_eq!(options, vec![0.0, 0.5, 1.0]);
+    // Atmospheric thruster of 200 kN and 1000 kg is linearly more effective with planetary influence.
+    assert_approx_eq(points[0].result.unwrap(), 0.0);
+    assert_approx_eq(points[1].result.unwrap(), 100.0);
+    assert_approx_eq(points[2].result.unwrap(), 200.0);
+  }
+
+  #[test]
+  fn sweep_does_not_modify_calculator() {
+    let calculator = GridCalculator::new();
+    sweep(&calculator, &data(), SweepOption::AdditionalMass, SweepResult::TotalMass, 0.0..=1000.0, 2);
+    assert_eq!(SweepOption::AdditionalMass.get(&calculator), 0.0);
+  }
+
+  #[test]
+  fn sweep_option_get_set_round_trip() {
+    let mut calculator = GridCalculator::new();
+    for option in SweepOption::items() {
+      option.set(&mut calculator, 0.25);
+      assert_eq!(option.get(&calculator), 0.25, "{}", option);
+    }
+  }
+}
+
+mod validate {
+  use std::fs::File;
+
+  use secalc_core::data::Data;
+  use secalc_core::data::blocks::ComponentMass;
+  use secalc_core::data::power_groups::PowerGroup;
+  use secalc_core::data::validate::{DataProblem, GridProblem};
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  #[test]
+  fn synthetic_data_is_valid() {
+    let validation = crate::common::data().validate();
+    assert!(validation.is_valid(), "{:?}", validation.problems);
+  }
+
+  #[test]
+  fn bundled_data_is_valid() {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/../../data/data.json")).unwrap();
+    let validation = Data::from_json(file).unwrap().validate();
+    assert!(validation.is_valid(), "{:?}", validation.problems);
+  }
+
+  #[test]
+  fn missing_component_is_reported() {
+    let mut data = crate::common::data();
+    data.components.components.remove("Plate");
+    let problems = data.validate().problems;
+    assert_eq!(problems.len(), 2);
+    for block_id in [crate::common::ION_THRUSTER, crate::common::ATMOSPHERIC_THRUSTER] {
+      assert!(problems.contains(&DataProblem::UnknownComponent { block_id: block_id.to_string(), component_id: "Plate".to_string() }), "{:?}", problems);
+    }
+  }
+
+  #[test]
+  fn missing_fuel_gas_is_reported() {
+    let mut data = crate::common::data();
+    data.gas_properties.gas_properties.clear();
+    let problems = data.validate().problems;
+    assert_eq!(problems, vec![DataProblem::UnknownFuelGas { block_id: crate::common::HYDROGEN_THRUSTER.to_string(), gas_id: "Hydrogen".to_string() }]);
+    assert_eq!(problems[0].to_string(), "Thruster 'Thrust.TestHydrogenThrust' uses unknown fuel gas 'Hydrogen'");
+  }
+
+  #[test]
+  fn missing_mod_and_power_group_block_are_reported() {
+    let mut data = crate::common::data();
+    data.blocks.reactors.get_mut(crate::common::REACTOR).unwrap().data.mod_id = Some(42);
+    data.power_groups.power_groups.insert("Drill.Unknown".to_string(), PowerGroup::Utility);
+    let problems = data.validate().problems;
+    assert_eq!(problems, vec![
+      DataProblem::UnknownMod { block_id: crate::common::REACTOR.to_string(), mod_id: 42 },
+      DataProblem::UnknownPowerGroupBlock { block_id: "Drill.Unknown".to_string() },
+    ]);
+  }
+
+  #[test]
+  fn component_masses_include_unknown_components() {
+    let mut data = crate::common::data();
+    data.blocks.thrusters.get_mut(crate::common::ION_THRUSTER).unwrap().data.components.insert("Unknown".to_string(), 3.0);
+    let block = &data.blocks.thrusters.get(crate::common::ION_THRUSTER).unwrap().data;
+    assert_eq!(block.component_masses(&data.components), vec![
+      ComponentMass { component_id: "Plate".to_string(), count: 10.0, mass: Some(100.0) },
+      ComponentMass { component_id: "Unknown".to_string(), count: 3.0, mass: None },
+    ]);
+    assert_eq!(block.unknown_component_ids(&data.components).collect::<Vec<_>>(), vec!["Unknown"]);
+    crate::common::assert_approx_eq(block.mass(&data.components), 1000.0);
+  }
+
+  #[test]
+  fn grid_with_unknown_components_is_reported() {
+    let mut data = crate::common::data();
+    data.components.components.remove("Plate");
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(crate::common::REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(crate::common::ION_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    let problems = calculator.validate(&data).problems;
+    assert_eq!(problems, vec![GridProblem::UnknownComponents { block_id: crate::common::ION_THRUSTER.to_string(), component_ids: vec!["Plate".to_string()] }]);
+    assert_eq!(problems[0].to_string(), "Block 'Thrust.TestIonThrust' is made of unknown component(s) 'Plate', its mass is understated");
+  }
+}
+
+mod warnings {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::warning::Warning;
+
+  use crate::common::*;
+
+  fn calculator(planetary_influence: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.planetary_influence = planetary_influence;
+    calculator
+  }
+
+  #[test]
+  fn atmospheric_thrusters_without_force_in_space() {
+    let calculated = calculator(0.0).calculate(&data());
+    assert!(calculated.warnings.iter().any(|w| matches!(w, Warning::AtmosphericThrustersWithoutForce { ids } if ids == &[ATMOSPHERIC_THRUSTER.to_string()])));
+  }
+
+  #[test]
+  fn no_atmospheric_thrusters_without_force_in_atmosphere() {
+    let calculated = calculator(1.0).calculate(&data());
+    assert!(!calculated.warnings.iter().any(|w| matches!(w, Warning::AtmosphericThrustersWithoutForce { .. })));
+  }
+}
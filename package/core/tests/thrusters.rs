@@ -0,0 +1,630 @@
+mod common;
+
+mod ascent {
+  use secalc_core::grid::ascent::{AscentOptions, Planet};
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator(thruster: &str, count: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(thruster.to_string()).or_default()[Direction::Up] = count;
+    calculator.additional_mass = 1000.0;
+    calculator
+  }
+
+  fn options(planet: Planet) -> AscentOptions {
+    AscentOptions { planet, ..AscentOptions::default() }
+  }
+
+  fn moon() -> Planet {
+    Planet::presets().into_iter().find(|p| p.name == "Moon").unwrap()
+  }
+
+  #[test]
+  fn planet_gravity_and_atmosphere() {
+    let planet = Planet::default();
+    assert_approx_eq(planet.gravity(0.0), 1.0);
+    assert_approx_eq(planet.gravity(planet.hill_height), 1.0);
+    assert_approx_eq(planet.gravity(12000.0), (66.0f64 / 72.0).powi(7));
+    // Gravity at twice the radius is about 1/100 g, which is too weak to pull on grids.
+    assert_approx_eq(planet.gravity(60000.0), 0.0);
+    assert_approx_eq(planet.planetary_influence(0.0), 1.0);
+    assert_approx_eq(planet.planetary_influence(15000.0), 0.5);
+    assert_approx_eq(planet.planetary_influence(40000.0), 0.0);
+    let space_altitude = planet.space_altitude();
+    assert!(space_altitude > planet.atmosphere_height);
+    assert!(planet.gravity(space_altitude - 1.0) > 0.0);
+    assert_approx_eq(planet.gravity(space_altitude + 1.0), 0.0);
+    // Without gravity falloff, the atmosphere decides where space starts.
+    let planet = Planet { surface_gravity: 0.01, ..Planet::default() };
+    assert_approx_eq(planet.space_altitude(), planet.atmosphere_height);
+  }
+
+  #[test]
+  fn ion_thrusters_reach_space_from_moon() {
+    // 4 ion thrusters of 50 kN each lifting 5 t against 0.25 g.
+    let options = options(moon());
+    let plan = calculator(ION_THRUSTER, 4.0).plan_ascent(&data(), &options);
+    assert!(plan.reaches_target());
+    assert!(plan.stall_altitude.is_none());
+    assert_approx_eq(plan.target_altitude, options.planet.space_altitude());
+    assert!(plan.maximum_altitude >= plan.target_altitude);
+    // Cannot go faster than the speed limit.
+    assert!(plan.time.unwrap() >= plan.target_altitude / options.speed_limit);
+    assert!(plan.samples.iter().all(|s| s.speed <= options.speed_limit));
+    assert!(plan.power_consumed > 0.0);
+    assert_approx_eq(plan.hydrogen_consumed, 0.0);
+  }
+
+  #[test]
+  fn atmospheric_thrusters_stall_in_thin_atmosphere() {
+    // 200 kN atmospheric thruster lifting 19 t (186.39 kN) at 1 g, while its force falls with the atmosphere.
+    let mut calculator = calculator(ATMOSPHERIC_THRUSTER, 1.0);
+    calculator.additional_mass = 18000.0;
+    let plan = calculator.plan_ascent(&data(), &options(Planet::default()));
+    assert!(!plan.reaches_target());
+    let stall_altitude = plan.stall_altitude.unwrap();
+    assert!(stall_altitude > 0.0);
+    assert!(stall_altitude < Planet::default().atmosphere_height);
+  }
+
+  #[test]
+  fn cannot_lift_off_without_enough_thrust() {
+    let mut calculator = calculator(ION_THRUSTER, 1.0);
+    calculator.additional_mass = 100000.0;
+    let plan = calculator.plan_ascent(&data(), &options(moon()));
+    assert!(!plan.reaches_target());
+    assert_eq!(plan.stall_altitude, Some(0.0));
+    assert_eq!(plan.samples.len(), 1);
+  }
+
+  #[test]
+  fn hydrogen_runs_out() {
+    // 1000 L of hydrogen lasts about 33 s for a 30 L/s hydrogen thruster lifting 10 t, which is not enough to leave an
+    // earth-like planet.
+    let mut calculator = calculator(HYDROGEN_THRUSTER, 1.0);
+    calculator.additional_mass = 9000.0;
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 1.0);
+    calculator.hydrogen_tank_fill = 100.0;
+    let plan = calculator.plan_ascent(&data(), &options(Planet::default()));
+    assert!(!plan.reaches_target());
+    assert!(plan.stall_altitude.is_some());
+    assert!(plan.hydrogen_consumed <= 1000.0 + 1e-6);
+    assert!(plan.hydrogen_consumed > 900.0);
+  }
+}
+
+mod braking {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::{BrakingCalculated, GridCalculator};
+
+  use crate::common::*;
+
+  fn calculator(additional_mass: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.planetary_influence = 0.0;
+    calculator.additional_mass = additional_mass;
+    calculator
+  }
+
+  #[test]
+  fn braking_from_speed() {
+    let braking = BrakingCalculated::new(100.0, 5.0).unwrap();
+    assert_approx_eq(braking.distance, 1000.0);
+    assert_approx_eq(braking.time.to_seconds(), 20.0);
+    assert!(BrakingCalculated::new(100.0, 0.0).is_none());
+    assert!(BrakingCalculated::new(100.0, -1.0).is_none());
+  }
+
+  #[test]
+  fn braking_per_direction() {
+    // 50 kN ion thruster of 1000 kg with 1000 kg additional mass: 25 m/s^2 without gravity, and 15.19 m/s^2 with gravity.
+    let mut calculator = calculator(1000.0);
+    calculator.braking_speed = 50.0;
+    let calculated = calculator.calculate(&data());
+    let up = &calculated.thruster_acceleration[Direction::Up];
+    let braking = up.braking_empty_no_gravity.unwrap();
+    assert_approx_eq(braking.distance, 50.0);
+    assert_approx_eq(braking.time.to_seconds(), 2.0);
+    let deceleration = 25.0 - 9.81;
+    let braking = up.braking_empty_gravity.unwrap();
+    assert_approx_eq(braking.distance, 50.0 * 50.0 / (2.0 * deceleration));
+    assert_approx_eq(braking.time.to_seconds(), 50.0 / deceleration);
+    // Directions without thrusters cannot brake.
+    assert!(calculated.thruster_acceleration[Direction::Down].braking_empty_no_gravity.is_none());
+  }
+
+  #[test]
+  fn no_braking_when_gravity_exceeds_thrust() {
+    // 50 kN ion thruster with 11000 kg cannot counter 98.1 kN of gravity.
+    let calculated = calculator(10000.0).calculate(&data());
+    let up = &calculated.thruster_acceleration[Direction::Up];
+    assert!(up.braking_filled_no_gravity.is_some());
+    assert!(up.braking_filled_gravity.is_none());
+  }
+}
+
+mod descent {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  fn calculator(ion_thrusters: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 0.0;
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = ion_thrusters;
+    calculator
+  }
+
+  #[test]
+  fn max_safe_speed_stops_at_ground() {
+    let calculator = calculator(1.0);
+    let calculated = calculator.calculate(&data());
+    let descent = calculated.descent.unwrap();
+    assert_approx_eq(descent.braking_force.to_newtons(), 50000.0);
+    let deceleration = descent.deceleration.unwrap();
+    assert_approx_eq(deceleration, 50000.0 / 1000.0 - 9.81);
+    // Falling freely during the reaction time and then braking covers exactly the descent altitude.
+    let v = descent.max_safe_speed.unwrap();
+    let t = calculator.descent_reaction_time;
+    let u = v + 9.81 * t;
+    assert_approx_eq(v * t + 9.81 * t * t / 2.0 + u * u / (2.0 * deceleration), calculator.descent_altitude);
+  }
+
+  #[test]
+  fn longer_reaction_time_lowers_max_safe_speed() {
+    let mut calculator = calculator(1.0);
+    let fast = calculator.calculate(&data()).descent.unwrap().max_safe_speed.unwrap();
+    calculator.descent_reaction_time = 5.0;
+    let slow = calculator.calculate(&data()).descent.unwrap().max_safe_speed.unwrap();
+    assert!(slow < fast);
+  }
+
+  #[test]
+  fn braking_force_is_limited_by_power() {
+    // 20 ion thrusters need 20 MW, but the reactor only provides 10 MW.
+    let calculated = calculator(20.0).calculate(&data());
+    let descent = calculated.descent.unwrap();
+    assert_approx_eq(calculated.thruster_acceleration[Direction::Up].force.to_newtons(), 20.0 * 50000.0);
+    assert_approx_eq(descent.braking_force.to_newtons(), 10.0 * 50000.0);
+  }
+
+  #[test]
+  fn cannot_brake_without_power() {
+    let mut calculator = calculator(1.0);
+    calculator.blocks.clear();
+    let descent = calculator.calculate(&data()).descent.unwrap();
+    assert_approx_eq(descent.braking_force.to_newtons(), 0.0);
+    assert_eq!(descent.deceleration, None);
+    assert_eq!(descent.max_safe_speed, None);
+  }
+}
+
+mod efficiency {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::efficiency::EfficiencyCalculated;
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  fn calculator() -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.additional_mass = 2000.0;
+    calculator.blocks.insert(HYDROGEN_TANK.to_string(), 1.0);
+    calculator
+  }
+
+  #[test]
+  fn stats_are_normalized_by_empty_mass() {
+    let efficiency = calculator().calculate(&data()).efficiency();
+    assert_approx_eq(efficiency.hydrogen_capacity_per_mass.unwrap(), 500.0);
+    assert_approx_eq(efficiency.power_generation_per_mass.unwrap(), 0.0);
+    assert!(efficiency.battery_capacity_per_mass.is_none());
+  }
+
+  #[test]
+  fn serializes() {
+    let efficiency = calculator().calculate(&data()).efficiency();
+    let value = serde_json::to_value(efficiency).unwrap();
+    assert_approx_eq(value["hydrogen_capacity_per_mass"].as_f64().unwrap(), 500.0);
+    assert!(value["battery_capacity_per_mass"].is_null());
+    let deserialized: EfficiencyCalculated = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized.hydrogen_capacity_per_mass, efficiency.hydrogen_capacity_per_mass);
+  }
+
+  #[test]
+  fn no_stats_per_mass_without_mass() {
+    let mut calculator = calculator();
+    calculator.additional_mass = 0.0;
+    let efficiency = calculator.calculate(&data()).efficiency();
+    assert!(efficiency.hydrogen_capacity_per_mass.is_none());
+    assert!(efficiency.power_generation_per_mass.is_none());
+  }
+
+  #[test]
+  fn no_thrust_per_power_with_hydrogen_thrusters() {
+    let mut calculator = calculator();
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    assert!(calculator.calculate(&data()).efficiency().thrust_per_power.is_none());
+  }
+
+  #[test]
+  fn thrust_per_power_of_all_directions() {
+    let mut calculator = GridCalculator::new();
+    calculator.planetary_influence = 0.0;
+    let thrusters = calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default();
+    for direction in Direction::items() {
+      thrusters[direction] = 1.0;
+    }
+    // 50 kN at 1 MW per ion thruster in vacuum.
+    assert_approx_eq(calculator.calculate(&data()).efficiency().thrust_per_power.unwrap(), 50000.0);
+  }
+}
+
+mod forces {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::forces::{ForceSource, ForceUnit};
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::units::Force;
+
+  use crate::common::*;
+
+  #[test]
+  fn forces_combine_thrusters_per_direction_and_wheels() {
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 2.0;
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Back] = 1.0;
+    let calculated = calculator.calculate(&data());
+    let forces: Vec<(ForceSource, Force)> = calculated.forces.iter().collect();
+    assert_eq!(forces.len(), 7);
+    assert_eq!(forces[6], (ForceSource::Wheels, Force::from_newtons(0.0)));
+    assert_approx_eq(calculated.forces.thrusters[Direction::Up].to_newtons(), 200000.0);
+    assert_approx_eq(calculated.forces.thrusters[Direction::Back].to_newtons(), 100000.0);
+    assert_approx_eq(calculated.forces.thrusters[Direction::Down].to_newtons(), 0.0);
+  }
+
+  #[test]
+  fn force_units_convert_from_newton() {
+    assert_approx_eq(ForceUnit::Newton.convert(Force::from_newtons(2_400_000.0)), 2_400_000.0);
+    assert_approx_eq(ForceUnit::Kilonewton.convert(Force::from_newtons(2_400_000.0)), 2_400.0);
+    assert_approx_eq(ForceUnit::Meganewton.convert(Force::from_newtons(2_400_000.0)), 2.4);
+  }
+}
+
+mod massless {
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  #[test]
+  fn empty_grid_has_no_acceleration() {
+    let calculated = GridCalculator::new().calculate(&data());
+    assert_eq!(calculated.total_mass_empty.to_kilograms(), 0.0);
+    for a in calculated.thruster_acceleration.iter() {
+      assert_eq!(a.acceleration_empty_no_gravity, None);
+      assert_eq!(a.acceleration_empty_gravity, None);
+      assert_eq!(a.acceleration_filled_no_gravity, None);
+      assert_eq!(a.acceleration_filled_gravity, None);
+    }
+    assert!(calculated.jump_drive.is_none());
+    assert!(calculated.mining.is_none());
+  }
+
+  #[test]
+  fn massless_grid_has_no_jump_distance() {
+    // Blocks in the test data without components have no mass.
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(JUMP_DRIVE.to_string(), 1.0);
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.total_mass_empty.to_kilograms(), 0.0);
+    let jump_drive = calculated.jump_drive.unwrap();
+    assert_eq!(jump_drive.max_distance_empty, None);
+    assert_eq!(jump_drive.max_distance_filled, None);
+    assert!(jump_drive.charge_duration.is_some_and(|d| d.to_minutes().is_finite()));
+  }
+
+  #[test]
+  fn jump_distance_with_mass() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(JUMP_DRIVE.to_string(), 1.0);
+    calculator.additional_mass = 2_000_000.0;
+    let calculated = calculator.calculate(&data());
+    let jump_drive = calculated.jump_drive.unwrap();
+    // 2000 km * 1000 t / 2000 t = 1000 km.
+    assert_approx_eq(jump_drive.max_distance_empty.unwrap(), 1000.0);
+  }
+
+  #[test]
+  fn negative_mass_has_no_acceleration() {
+    let mut calculator = GridCalculator::new();
+    calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.additional_mass = -1000.0;
+    let calculated = calculator.calculate(&data());
+    assert!(calculated.total_mass_empty.to_kilograms() < 0.0);
+    let up = &calculated.thruster_acceleration[Direction::Up];
+    assert_eq!(up.acceleration_empty_no_gravity, None);
+    assert_eq!(up.acceleration_empty_gravity, None);
+  }
+}
+
+mod pitched_hover {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::{GridCalculated, GridCalculator};
+
+  use crate::common::*;
+
+  /// Grid in space with 50 kN of up and back thrust.
+  fn calculated() -> GridCalculated {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Back] = 1.0;
+    calculator.planetary_influence = 0.0;
+    calculator.calculate(&data())
+  }
+
+  #[test]
+  fn level_hover_needs_only_up_thrust() {
+    // 4000 kg in 1 g weighs 39 240 N.
+    let hover = calculated().pitched_hover(0.0, 1.0, 0.0, 4000.0);
+    assert_approx_eq(hover.required_up_force.to_newtons(), 39240.0);
+    assert_approx_eq(hover.required_horizontal_force.to_newtons(), 0.0);
+    assert_approx_eq(hover.up_utilization.unwrap(), 39240.0 / 50000.0);
+    assert!(hover.can_hover);
+  }
+
+  #[test]
+  fn vertical_hover_needs_only_horizontal_thrust() {
+    let hover = calculated().pitched_hover(90.0, 1.0, 0.0, 4000.0);
+    assert!(hover.required_up_force.to_newtons().abs() < 1e-6);
+    assert_approx_eq(hover.required_horizontal_force.to_newtons(), 39240.0);
+    assert_eq!(hover.horizontal_direction, Direction::Back);
+    assert_approx_eq(hover.horizontal_utilization.unwrap(), 39240.0 / 50000.0);
+    assert!(hover.can_hover);
+
+    // Nose-up needs front thrust, which the grid does not have.
+    let hover = calculated().pitched_hover(-90.0, 1.0, 0.0, 4000.0);
+    assert_eq!(hover.horizontal_direction, Direction::Front);
+    assert_eq!(hover.horizontal_utilization, None);
+    assert!(!hover.can_hover);
+  }
+
+  #[test]
+  fn external_lift_reduces_required_thrust() {
+    let hover = calculated().pitched_hover(0.0, 1.0, 9240.0, 4000.0);
+    assert_approx_eq(hover.required_up_force.to_newtons(), 30000.0);
+    assert_approx_eq(hover.up_utilization.unwrap(), 0.6);
+
+    // Lift exceeding the weight needs no thrust.
+    let hover = calculated().pitched_hover(30.0, 1.0, 50000.0, 4000.0);
+    assert_approx_eq(hover.required_up_force.to_newtons(), 0.0);
+    assert_approx_eq(hover.required_horizontal_force.to_newtons(), 0.0);
+    assert!(hover.can_hover);
+  }
+}
+
+mod thruster_idle {
+  use secalc_core::data::Data;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::direction::Direction;
+
+  use crate::common::*;
+
+  /// Test data with hydrogen thrusters that idle at 1 L/s.
+  fn idle_data() -> Data {
+    let mut data = data();
+    data.blocks.thrusters.get_mut(HYDROGEN_THRUSTER).unwrap().details.min_consumption = 0.001;
+    data
+  }
+
+  fn calculator(disabled_thrusters_off: bool) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.disabled_thrusters_off = disabled_thrusters_off;
+    calculator.thruster_direction_enabled[Direction::Left] = false;
+    let thrusters = calculator.directional_blocks.entry(HYDROGEN_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 2.0;
+    thrusters[Direction::Left] = 3.0;
+    calculator
+  }
+
+  #[test]
+  fn disabled_thrusters_off_have_no_idle_consumption() {
+    let calculated = calculator(true).calculate(&idle_data());
+    assert_approx_eq(calculated.hydrogen_thruster_idle[Direction::Up], 2.0);
+    assert_approx_eq(calculated.hydrogen_thruster_idle[Direction::Left], 0.0);
+    assert_approx_eq(calculated.hydrogen_idle.consumption, 2.0);
+  }
+
+  #[test]
+  fn disabled_thrusters_on_have_idle_consumption_but_no_force() {
+    let calculated = calculator(false).calculate(&idle_data());
+    assert_approx_eq(calculated.hydrogen_thruster_idle[Direction::Left], 3.0);
+    assert_approx_eq(calculated.hydrogen_idle.consumption, 5.0);
+    assert_approx_eq(calculated.thruster_acceleration[Direction::Left].force.to_newtons(), 0.0);
+  }
+}
+
+mod thruster_mass {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  #[test]
+  fn thruster_mass_per_direction() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    let thrusters = calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 2.0;
+    thrusters[Direction::Back] = 1.0;
+    calculator.additional_mass = 1000.0;
+    let calculated = calculator.calculate(&data());
+    assert_approx_eq(calculated.thruster_mass[Direction::Up].to_kilograms(), 2000.0);
+    assert_approx_eq(calculated.thruster_mass[Direction::Back].to_kilograms(), 1000.0);
+    assert_approx_eq(calculated.thruster_mass[Direction::Down].to_kilograms(), 0.0);
+    let thruster_mass: f64 = calculated.thruster_mass.iter().map(|m| m.to_kilograms()).sum();
+    assert_approx_eq(thruster_mass + 1000.0, calculated.total_mass_empty.to_kilograms());
+  }
+
+  #[test]
+  fn mass_contributors_are_attributed_to_directions() {
+    let mut calculator = GridCalculator::new();
+    let thrusters = calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default();
+    thrusters[Direction::Up] = 2.0;
+    thrusters[Direction::Back] = 1.0;
+    let calculated = calculator.calculate(&data());
+    let contributors: Vec<_> = calculated.top_mass_contributors.iter().map(|c| (c.id.as_str(), c.direction, c.value)).collect();
+    assert_eq!(contributors, vec![
+      (ION_THRUSTER, Some(Direction::Up), 2000.0),
+      (ION_THRUSTER, Some(Direction::Back), 1000.0),
+    ]);
+  }
+}
+
+mod thruster_mix {
+  use std::collections::HashSet;
+
+  use secalc_core::data::blocks::GridSize;
+  use secalc_core::grid::direction::{Direction, PerDirection};
+  use secalc_core::grid::thruster_mix::{Environment, suggest_thruster_mix, ThrusterMixObjective, ThrusterMixProblem};
+
+  use crate::common::*;
+
+  fn problem(objective: ThrusterMixObjective) -> ThrusterMixProblem {
+    let mut target_acceleration = PerDirection::default();
+    target_acceleration[Direction::Front] = 1.0;
+    ThrusterMixProblem {
+      environments: vec![Environment::PLANET_SURFACE, Environment::SPACE],
+      target_acceleration,
+      base_mass: 10000.0,
+      objective,
+    }
+  }
+
+  #[test]
+  fn minimize_mass_uses_massless_hydrogen_thrusters() {
+    let mix = suggest_thruster_mix(&data(), GridSize::Large, &HashSet::new(), &problem(ThrusterMixObjective::Mass)).unwrap();
+    assert_eq!(mix.blocks.len(), 1);
+    let counts = &mix.blocks[HYDROGEN_THRUSTER];
+    // Hovering at the surface needs 98.1 kN up, accelerating needs 10 kN front.
+    assert_eq!(counts[Direction::Up], 1.0);
+    assert_eq!(counts[Direction::Front], 1.0);
+    assert_eq!(counts[Direction::Down], 0.0);
+    assert_approx_eq(mix.thruster_mass, 0.0);
+    assert_approx_eq(mix.hydrogen_consumption, 60.0);
+  }
+
+  #[test]
+  fn minimize_hydrogen_mixes_atmospheric_and_ion_thrusters() {
+    let mix = suggest_thruster_mix(&data(), GridSize::Large, &HashSet::new(), &problem(ThrusterMixObjective::Hydrogen)).unwrap();
+    assert!(!mix.blocks.contains_key(HYDROGEN_THRUSTER));
+    // Gravity only needs to be countered at the surface, so atmospheric thrusters suffice for up.
+    assert_eq!(mix.blocks[ATMOSPHERIC_THRUSTER][Direction::Up], 1.0);
+    assert_eq!(mix.blocks.get(ION_THRUSTER).map(|c| c[Direction::Up]).unwrap_or(0.0), 0.0);
+    // Front needs thrust in both environments.
+    assert_eq!(mix.blocks[ATMOSPHERIC_THRUSTER][Direction::Front], 1.0);
+    assert_eq!(mix.blocks[ION_THRUSTER][Direction::Front], 1.0);
+    assert_approx_eq(mix.thruster_mass, 3000.0);
+    assert_approx_eq(mix.hydrogen_consumption, 0.0);
+  }
+
+  #[test]
+  fn no_mix_without_thrusters() {
+    assert!(suggest_thruster_mix(&data(), GridSize::Small, &HashSet::new(), &problem(ThrusterMixObjective::Mass)).is_none());
+  }
+}
+
+mod thruster_power_type {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+
+  use crate::common::*;
+
+  fn calculator(planetary_influence: f64) -> GridCalculator {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Front] = 1.0;
+    calculator.directional_blocks.entry(ATMOSPHERIC_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    calculator.planetary_influence = planetary_influence;
+    calculator
+  }
+
+  #[test]
+  fn power_consumption_is_split_per_type_in_space() {
+    let calculated = calculator(0.0).calculate(&data());
+    assert_approx_eq(calculated.ion_thruster_consumption[Direction::Up].to_megawatts(), 1.0);
+    assert_approx_eq(calculated.ion_thruster_consumption[Direction::Front].to_megawatts(), 1.0);
+    assert_approx_eq(calculated.atmospheric_thruster_consumption[Direction::Up].to_megawatts(), 0.0);
+  }
+
+  #[test]
+  fn power_consumption_is_split_per_type_in_atmosphere() {
+    let calculated = calculator(1.0).calculate(&data());
+    assert_approx_eq(calculated.ion_thruster_consumption[Direction::Up].to_megawatts(), 0.0);
+    assert_approx_eq(calculated.ion_thruster_consumption[Direction::Front].to_megawatts(), 0.0);
+    assert_approx_eq(calculated.atmospheric_thruster_consumption[Direction::Up].to_megawatts(), 2.0);
+  }
+
+  #[test]
+  fn power_consumption_per_type_sums_to_total() {
+    let calculated = calculator(0.5).calculate(&data());
+    for direction in Direction::items() {
+      let sum = calculated.ion_thruster_consumption[direction].to_megawatts() + calculated.atmospheric_thruster_consumption[direction].to_megawatts();
+      assert_approx_eq(sum, calculated.power_thruster_consumption[direction].to_megawatts());
+    }
+  }
+}
+
+mod thruster_ranking {
+  use std::collections::HashSet;
+
+  use secalc_core::data::blocks::GridSize;
+  use secalc_core::grid::thruster_ranking::{rank_thrusters, ThrusterRankOrder, ThrusterResource};
+
+  use crate::common::*;
+
+  #[test]
+  fn rank_in_space_leaves_out_atmospheric_thrusters() {
+    let data = data();
+    let ranks = rank_thrusters(&data, GridSize::Large, &HashSet::new(), 0.0, ThrusterRankOrder::ForcePerConsumption);
+    let ids: Vec<_> = ranks.iter().map(|r| r.block.id().as_str()).collect();
+    // Grouped by resource: power before hydrogen.
+    assert_eq!(ids, [ION_THRUSTER, HYDROGEN_THRUSTER]);
+    assert_eq!(ranks[0].resource, ThrusterResource::Power);
+    assert_approx_eq(ranks[0].force_per_consumption, 50000.0);
+    assert_eq!(ranks[1].resource, ThrusterResource::Hydrogen);
+    assert_approx_eq(ranks[1].consumption, 30.0);
+    assert_approx_eq(ranks[1].force_per_consumption, 100000.0 / 30.0);
+  }
+
+  #[test]
+  fn rank_by_force_per_mass() {
+    let data = data();
+    let ranks = rank_thrusters(&data, GridSize::Large, &HashSet::new(), 0.5, ThrusterRankOrder::ForcePerMass);
+    let ids: Vec<_> = ranks.iter().map(|r| r.block.id().as_str()).collect();
+    // Massless hydrogen thruster first, then the atmospheric thruster at half effectiveness (100 kN), then the ion
+    // thruster at half effectiveness (25 kN).
+    assert_eq!(ids, [HYDROGEN_THRUSTER, ATMOSPHERIC_THRUSTER, ION_THRUSTER]);
+    assert_approx_eq(ranks[1].force, 100000.0);
+    assert_approx_eq(ranks[1].mass, 1000.0);
+    assert_approx_eq(ranks[1].force_per_mass, 100.0);
+    assert_approx_eq(ranks[2].force_per_mass, 25.0);
+  }
+
+  #[test]
+  fn rank_only_grid_size() {
+    let data = data();
+    assert!(rank_thrusters(&data, GridSize::Small, &HashSet::new(), 0.0, ThrusterRankOrder::default()).is_empty());
+  }
+}
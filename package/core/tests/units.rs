@@ -0,0 +1,91 @@
+mod common;
+
+mod duration {
+  use secalc_core::grid::duration::{Duration, DurationFormat};
+
+  #[test]
+  fn unit_format() {
+    assert_eq!(format!("{:.2}", Duration::from_minutes(90.0)), "1.50 hours");
+    assert_eq!(format!("{:.1}", Duration::from_seconds(30.0)), "30.0 secs");
+    assert_eq!(format!("{}", Duration::from_minutes(f64::INFINITY)), "inf");
+    assert_eq!(Duration::from_minutes(2.5).format(DurationFormat::Unit, 2), "2.50 mins");
+  }
+
+  #[test]
+  fn clock_format() {
+    assert_eq!(format!("{:#}", Duration::from_seconds(3725.0)), "1:02:05");
+    assert_eq!(format!("{:#}", Duration::from_seconds(59.6)), "0:01:00");
+    assert_eq!(Duration::from_hours(25.0).format(DurationFormat::Clock, 2), "25:00:00");
+    // Falls back to the unit format when the duration cannot be read as a clock.
+    assert_eq!(Duration::from_hours(2000.0).format(DurationFormat::Clock, 2), "83.33 days");
+    assert_eq!(Duration::from_minutes(f64::INFINITY).to_clock_string(), None);
+  }
+
+  #[test]
+  fn arithmetic() {
+    let a = Duration::from_minutes(3.0);
+    let b = Duration::from_seconds(60.0);
+    assert_eq!(a + b, Duration::from_minutes(4.0));
+    assert_eq!(a - b, Duration::from_minutes(2.0));
+    assert_eq!(a * 2.0, Duration::from_minutes(6.0));
+    assert_eq!(a / 3.0, b);
+    assert_eq!(a / b, 3.0);
+    assert_eq!([a, b].into_iter().sum::<Duration>(), Duration::from_minutes(4.0));
+    assert_eq!(Duration::from_hours(1.5).to_hours(), 1.5);
+  }
+
+  #[test]
+  fn serde_as_minutes() {
+    let duration = Duration::from_hours(1.0);
+    let json = serde_json::to_string(&duration).unwrap();
+    assert_eq!(json, "60.0");
+    assert_eq!(serde_json::from_str::<Duration>(&json).unwrap(), duration);
+  }
+
+  #[test]
+  fn serde_infinite() {
+    let json = serde_json::to_string(&Duration::from_minutes(f64::INFINITY)).unwrap();
+    assert_eq!(json, "\"inf\"");
+    assert!(serde_json::from_str::<Duration>(&json).unwrap().is_infinite());
+    assert!(serde_json::from_str::<Duration>("\"forever\"").is_err());
+  }
+}
+
+mod quantities {
+  use secalc_core::grid::direction::Direction;
+  use secalc_core::grid::GridCalculator;
+  use secalc_core::grid::units::{Energy, Force, Mass, Power, Volume};
+
+  use crate::common::*;
+
+  #[test]
+  fn conversions() {
+    assert_approx_eq(Mass::from_tonnes(1.5).to_kilograms(), 1500.0);
+    assert_approx_eq(Force::from_kilonewtons(2400.0).to_meganewtons(), 2.4);
+    assert_approx_eq(Power::from_kilowatts(500.0).to_megawatts(), 0.5);
+    assert_approx_eq(Volume::from_cubic_meters(2.0).to_liters(), 2000.0);
+    assert_approx_eq((Energy::from_megawatt_hours(1.0) * 3.0).to_megawatt_hours(), 3.0);
+    assert_approx_eq(Force::from_newtons(1000.0) / Mass::from_kilograms(500.0), 2.0);
+    assert_eq!(format!("{:.2}", Mass::from_kilograms(1.5)), "1.50 kg");
+    assert_eq!(format!("{:.2}", Energy::from_megawatt_hours(0.5)), "0.50 MWh");
+  }
+
+  #[test]
+  fn calculated_results_are_typed() {
+    let mut calculator = GridCalculator::new();
+    calculator.blocks.insert(REACTOR.to_string(), 1.0);
+    calculator.blocks.insert(CONTAINER.to_string(), 2.0);
+    calculator.planetary_influence = 0.0;
+    calculator.directional_blocks.entry(ION_THRUSTER.to_string()).or_default()[Direction::Up] = 1.0;
+    let calculated = calculator.calculate(&data());
+    assert_eq!(calculated.power_generation, Power::from_megawatts(10.0));
+    assert_eq!(calculated.total_volume_any, Volume::from_liters(2000.0));
+    assert_eq!(calculated.thruster_acceleration[Direction::Up].force, Force::from_kilonewtons(50.0));
+    assert_eq!(calculated.forces.thrusters[Direction::Up], calculated.thruster_acceleration[Direction::Up].force);
+    assert!(calculated.total_mass_filled >= calculated.total_mass_empty);
+
+    // Typed values serialize as plain numbers in their base unit.
+    let value = serde_json::to_value(&calculated).unwrap();
+    assert_approx_eq(value["power_generation"].as_f64().unwrap(), 10.0);
+  }
+}
@@ -2,10 +2,7 @@
 
 use tracing_subscriber::prelude::*;
 
-use crate::app::App;
-
-mod app;
-mod widget;
+use secalc_app::App;
 
 fn main() {
   #[cfg(target_arch = "wasm32")] { // Setup panics to log to the console on WASM.
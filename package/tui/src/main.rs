@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use secalc_core::data::blocks::{BlockId, GridSize};
+use secalc_core::data::Data;
+use secalc_core::grid::direction::Direction;
+use secalc_core::grid::sweep::{SweepOption, SweepResult};
+use secalc_core::grid::{GridCalculated, GridCalculator};
+
+#[derive(Parser, Debug)]
+#[command(name = "SECalc TUI", about = "Space Engineers Calculator terminal UI")]
+struct Cli {
+  #[arg(env = "SECALC_DATA_FILE")]
+  /// Extracted game data file to calculate with
+  data_file: PathBuf,
+  #[arg(env = "SECALC_TUI_GRID_FILE")]
+  /// Grid file (`GridCalculator` JSON) to load, and to save to with 's'. Starts with an empty grid if the file does not
+  /// exist
+  grid_file: Option<PathBuf>,
+  #[arg(long, default_value = "Large")]
+  /// Size of the blocks to list
+  grid_size: GridSize,
+  #[arg(long = "mod-id")]
+  /// ID of a mod whose blocks to list. Can be given multiple times
+  mod_ids: Vec<u64>,
+}
+
+fn main() -> Result<()> {
+  dotenvy::dotenv().ok();
+  let cli = Cli::parse();
+  let data_reader = File::open(&cli.data_file)
+    .with_context(|| format!("Failed to open data file '{}' for reading", cli.data_file.display()))?;
+  let data = Data::from_json(BufReader::new(data_reader))
+    .with_context(|| format!("Failed to read data from file '{}'", cli.data_file.display()))?;
+  let calculator = match &cli.grid_file {
+    Some(grid_file) if grid_file.exists() => {
+      let grid_reader = File::open(grid_file)
+        .with_context(|| format!("Failed to open grid file '{}' for reading", grid_file.display()))?;
+      GridCalculator::from_json(BufReader::new(grid_reader))
+        .with_context(|| format!("Failed to read grid from file '{}'", grid_file.display()))?
+    }
+    _ => GridCalculator::default(),
+  };
+  let enabled_mod_ids = cli.mod_ids.into_iter().collect();
+  let mut app = App::new(data, calculator, cli.grid_file, cli.grid_size, &enabled_mod_ids);
+
+  let mut terminal = ratatui::init();
+  let result = app.run(&mut terminal);
+  ratatui::restore();
+  result
+}
+
+/// Pane that receives keyboard input.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+enum Pane {
+  #[default] Options,
+  Blocks,
+}
+
+/// Block that can be edited in the blocks pane.
+struct BlockRow {
+  id: BlockId,
+  name: String,
+  /// Is this block counted per direction, such as thrusters?
+  directional: bool,
+}
+
+struct App {
+  data: Data,
+  calculator: GridCalculator,
+  calculated: GridCalculated,
+  grid_file: Option<PathBuf>,
+  blocks: Vec<BlockRow>,
+
+  pane: Pane,
+  options: ListState,
+  block_rows: ListState,
+  /// Direction in which counts of directional blocks are edited.
+  direction: Direction,
+  status: String,
+  quit: bool,
+}
+
+impl App {
+  fn new(data: Data, calculator: GridCalculator, grid_file: Option<PathBuf>, grid_size: GridSize, enabled_mod_ids: &HashSet<u64>) -> Self {
+    let localization = &data.localization;
+    let mut blocks: Vec<_> = data.blocks.thruster_blocks(grid_size, enabled_mod_ids)
+      .map(|b| BlockRow { id: b.id_cloned(), name: b.name(localization).to_string(), directional: true })
+      .collect();
+    let other_blocks = data.blocks.storage_blocks(grid_size, enabled_mod_ids)
+      .chain(data.blocks.wheel_suspension_blocks(grid_size, enabled_mod_ids))
+      .chain(data.blocks.power_blocks(grid_size, enabled_mod_ids))
+      .chain(data.blocks.hydrogen_blocks(grid_size, enabled_mod_ids))
+      .chain(data.blocks.oxygen_blocks(grid_size, enabled_mod_ids))
+      .chain(data.blocks.weapon_blocks(grid_size, enabled_mod_ids))
+      .chain(data.blocks.other_blocks(grid_size, enabled_mod_ids))
+      .map(|b| BlockRow { id: b.id_cloned(), name: b.name(localization).to_string(), directional: false });
+    blocks.extend(other_blocks);
+    let calculated = calculator.calculate(&data);
+    Self {
+      data,
+      calculator,
+      calculated,
+      grid_file,
+      blocks,
+      pane: Pane::default(),
+      options: ListState::default().with_selected(Some(0)),
+      block_rows: ListState::default().with_selected(Some(0)),
+      direction: Direction::default(),
+      status: "Tab: switch pane, Up/Down: select, Left/Right: decrease/increase, d: direction, s: save, q: quit".to_string(),
+      quit: false,
+    }
+  }
+
+  fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+    while !self.quit {
+      terminal.draw(|frame| self.draw(frame))?;
+      if let Event::Key(key) = event::read()? {
+        if key.kind == KeyEventKind::Press {
+          self.handle_key(key.code);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn handle_key(&mut self, code: KeyCode) {
+    match code {
+      KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+      KeyCode::Tab => self.pane = if self.pane == Pane::Options { Pane::Blocks } else { Pane::Options },
+      KeyCode::Up | KeyCode::Char('k') => self.selection().select_previous(),
+      KeyCode::Down | KeyCode::Char('j') => self.selection().select_next(),
+      KeyCode::Right | KeyCode::Char('+') | KeyCode::Char('l') => self.change(1),
+      KeyCode::Left | KeyCode::Char('-') | KeyCode::Char('h') => self.change(-1),
+      KeyCode::Char('d') => {
+        let directions: Vec<_> = Direction::items().into_iter().collect();
+        let index = directions.iter().position(|d| *d == self.direction).unwrap_or(0);
+        self.direction = directions[(index + 1) % directions.len()];
+      }
+      KeyCode::Char('s') => self.save(),
+      _ => {}
+    }
+  }
+
+  fn selection(&mut self) -> &mut ListState {
+    match self.pane {
+      Pane::Options => &mut self.options,
+      Pane::Blocks => &mut self.block_rows,
+    }
+  }
+
+  /// Changes the selected option by `steps` steps of 1% of its default range, or the count of the selected block by
+  /// `steps`, and recalculates.
+  fn change(&mut self, steps: i64) {
+    match self.pane {
+      Pane::Options => {
+        let Some(option) = self.options.selected().and_then(|i| SweepOption::items().into_iter().nth(i)) else { return };
+        let (start, end) = option.default_range(&self.calculator).into_inner();
+        let value = option.get(&self.calculator) + (end - start) / 100.0 * steps as f64;
+        option.set(&mut self.calculator, value.clamp(start, end));
+      }
+      Pane::Blocks => {
+        let Some(row) = self.block_rows.selected().and_then(|i| self.blocks.get(i)) else { return };
+        let count = if row.directional {
+          &mut self.calculator.directional_blocks.entry(row.id.clone()).or_default()[self.direction]
+        } else {
+          self.calculator.blocks.entry(row.id.clone()).or_default()
+        };
+        *count = (*count + steps as f64).max(0.0);
+      }
+    }
+    self.calculated = self.calculator.calculate(&self.data);
+  }
+
+  fn save(&mut self) {
+    let Some(grid_file) = &self.grid_file else {
+      self.status = "Cannot save: no grid file was given".to_string();
+      return;
+    };
+    let result = File::create(grid_file)
+      .map_err(|e| e.to_string())
+      .and_then(|file| self.calculator.to_json(BufWriter::new(file)).map_err(|e| e.to_string()));
+    self.status = match result {
+      Ok(()) => format!("Saved grid to '{}'", grid_file.display()),
+      Err(e) => format!("Failed to save grid to '{}': {}", grid_file.display(), e),
+    };
+  }
+
+  fn draw(&mut self, frame: &mut Frame) {
+    let [main, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [options, blocks, results] = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(40), Constraint::Percentage(30)]).areas(main);
+    let highlight = Style::new().reversed();
+
+    let option_lines = SweepOption::items().into_iter()
+      .map(|o| format!("{}: {:.2} {}", o, o.get(&self.calculator), o.unit()));
+    let option_list = List::new(option_lines)
+      .block(pane_block("Options", self.pane == Pane::Options))
+      .highlight_style(highlight);
+    frame.render_stateful_widget(option_list, options, &mut self.options);
+
+    let labels = &self.calculator.direction_labels;
+    let block_lines = self.blocks.iter().map(|row| {
+      let count = if row.directional {
+        self.calculator.directional_blocks.get(&row.id).map_or(0.0, |c| c[self.direction])
+      } else {
+        self.calculator.blocks.get(&row.id).copied().unwrap_or(0.0)
+      };
+      let line = format!("{}: {}", row.name, count);
+      if count > 0.0 { Line::from(line).bold() } else { Line::from(line) }
+    });
+    let blocks_title = format!("Blocks (thrusters: {})", labels.get(self.direction));
+    let block_list = List::new(block_lines)
+      .block(pane_block(&blocks_title, self.pane == Pane::Blocks))
+      .highlight_style(highlight);
+    frame.render_stateful_widget(block_list, blocks, &mut self.block_rows);
+
+    let mut result_lines: Vec<Line> = SweepResult::items().into_iter()
+      .map(|r| match r.get(&self.calculated) {
+        Some(value) => Line::from(format!("{}: {:.2} {}", r, value, r.unit())),
+        None => Line::from(format!("{}: -", r)),
+      })
+      .collect();
+    for warning in &self.calculated.warnings {
+      result_lines.push(Line::from(""));
+      result_lines.push(Line::from(warning.to_string()).yellow());
+    }
+    let result_paragraph = Paragraph::new(result_lines)
+      .block(Block::bordered().title("Results"))
+      .wrap(Wrap { trim: true });
+    frame.render_widget(result_paragraph, results);
+
+    frame.render_widget(Line::from(self.status.as_str()).dim(), status);
+  }
+}
+
+fn pane_block(title: &str, focused: bool) -> Block<'_> {
+  let block = Block::bordered().title(title);
+  if focused { block.bold() } else { block }
+}
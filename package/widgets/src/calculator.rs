@@ -0,0 +1,227 @@
+use std::fmt::Display;
+use std::ops::{Deref, DerefMut, RangeInclusive};
+
+use egui::{Button, ComboBox, DragValue, emath, Label, Response, Sense, Ui, Vec2, WidgetText};
+use egui::emath::Numeric;
+use thousands::SeparatorPolicy;
+
+use secalc_core::grid::direction::{CountPerDirection, Direction, DirectionLabels, PerDirection};
+
+/// Drag speed of count editors, in counts per dragged point. Dragging 50 points changes a count by 1, allowing precise
+/// edits of small counts.
+pub const COUNT_DRAG_SPEED: f64 = 0.02;
+
+/// Grid rows for editing calculator options and block counts, each with a reset button.
+pub struct CalculatorUi<'ui> {
+  ui: &'ui mut Ui,
+  _number_separator_policy: SeparatorPolicy<'static>,
+  edit_size: f32,
+  changed: bool,
+}
+
+impl<'ui> CalculatorUi<'ui> {
+  pub fn new(ui: &'ui mut Ui, number_separator_policy: SeparatorPolicy<'static>, edit_size: f32, ) -> Self {
+    Self { ui, _number_separator_policy: number_separator_policy, edit_size, changed: false }
+  }
+
+  /// Returns whether any value was changed through this UI.
+  #[inline]
+  pub fn changed(&self) -> bool { self.changed }
+
+
+  pub fn edit_row<N: Numeric + Display>(
+    &mut self,
+    label: impl Into<WidgetText>,
+    suffix: Option<impl Into<WidgetText>>,
+    value: &mut N,
+    speed: impl Into<f64>,
+    clamp_range: RangeInclusive<N>,
+    reset_value: N
+  ) -> Response {
+    let label_response = self.ui.add(Label::new(label).sense(Sense::click()));
+    self.drag(value, speed, clamp_range);
+    if let Some(suffix) = suffix {
+      self.ui.label(suffix);
+    }
+    self.reset_button_with(value, reset_value);
+    self.ui.end_row();
+    label_response
+  }
+
+  pub fn edit_suffix_row<N: Numeric + Display>(
+    &mut self,
+    label: impl Into<WidgetText>,
+    suffix: impl Into<WidgetText>,
+    value: &mut N,
+    speed: impl Into<f64>,
+    clamp_range: RangeInclusive<N>,
+    reset_value: N
+  ) -> Response {
+    self.edit_row(label, Some(suffix), value, speed, clamp_range, reset_value)
+  }
+
+  pub fn edit_percentage_row(&mut self, label: impl Into<WidgetText>, value: &mut f64, reset_value: f64) -> Response {
+    self.edit_suffix_row(label, "%", value, 0.2, 0.0..=100.0, reset_value)
+  }
+
+  pub fn edit_count_row(&mut self, label: impl Into<WidgetText>, value: &mut f64) -> Response {
+    let label_response = self.ui.add(Label::new(label).sense(Sense::click()));
+    self.unlabelled_edit_count(value);
+    self.reset_button_with(value, 0.0);
+    self.ui.end_row();
+    label_response
+  }
+
+
+  pub fn checkbox_row(&mut self, label: impl Into<WidgetText>, suffix: Option<impl Into<WidgetText>>, value: &mut bool, reset_value: bool) {
+    self.ui.label(label);
+    self.changed |= self.checkbox(value, "").changed();
+    if let Some(suffix) = suffix {
+      self.ui.label(suffix);
+    }
+    self.reset_button_with(value, reset_value);
+    self.ui.end_row();
+  }
+
+  pub fn checkbox_suffix_row(&mut self, label: impl Into<WidgetText>, suffix: impl Into<WidgetText>, value: &mut bool, reset_value: bool) {
+    self.checkbox_row(label, Some(suffix), value, reset_value)
+  }
+
+  pub fn combobox_row<T: PartialEq + Display + Copy>(
+    &mut self,
+    label: impl Into<WidgetText>,
+    id_source: impl std::hash::Hash,
+    suffix: Option<impl Into<WidgetText>>,
+    value: &mut T,
+    values: impl IntoIterator<Item=T>,
+    reset_value: T
+  ) {
+    self.ui.label(label);
+    let style = self.ui.style_mut();
+    style.spacing.interact_size = Vec2::new(0.0, 24.0); // HACK: fix combo box not starting at the top
+    self.changed |= ComboBox::from_id_source(id_source)
+      .width(self.edit_size - 8.0)
+      .selected_text(format!("{}", value))
+      .show_ui(self.ui, |ui| {
+        for v in values {
+          self.changed |= ui.selectable_value(value, v, format!("{}", v)).changed();
+        }
+      }).response.changed();
+    self.ui.reset_style();
+    if let Some(suffix) = suffix {
+      self.ui.label(suffix);
+    }
+    self.reset_button_with(value, reset_value);
+    self.ui.end_row();
+  }
+
+  pub fn combobox_suffix_row<T: PartialEq + Display + Copy>(
+    &mut self,
+    label: impl Into<WidgetText>,
+    id_source: impl std::hash::Hash,
+    suffix: impl Into<WidgetText>,
+    value: &mut T,
+    values: impl IntoIterator<Item=T>,
+    reset_value: T
+  ) {
+    self.combobox_row(label, id_source, Some(suffix), value, values, reset_value)
+  }
+
+
+  pub fn header_count_directed_row(&mut self, labels: &DirectionLabels) {
+    self.ui.label("");
+    for direction in Direction::items() {
+      self.ui.label(labels.get(direction));
+    }
+    self.ui.label("");
+    self.ui.end_row();
+  }
+
+  /// Header row for [`Self::edit_count_directed_row`], with a checkbox per direction to enable or disable it.
+  pub fn header_count_directed_enabled_row(&mut self, enabled: &mut PerDirection<bool>, labels: &DirectionLabels) {
+    self.ui.label("");
+    for direction in Direction::items() {
+      self.changed |= self.ui.checkbox(&mut enabled[direction], labels.get(direction)).changed();
+    }
+    self.reset_button_with_hover_tooltip(enabled, PerDirection::splat(true), "Double-click to enable all");
+    self.ui.end_row();
+  }
+
+  pub fn edit_count_directed_row(&mut self, label: impl Into<WidgetText>, count_per_direction: &mut CountPerDirection) -> Response {
+    let label_response = self.ui.add(Label::new(label).sense(Sense::click()));
+    self.unlabelled_edit_count(count_per_direction.up_mut());
+    self.unlabelled_edit_count(count_per_direction.down_mut());
+    self.unlabelled_edit_count(count_per_direction.front_mut());
+    self.unlabelled_edit_count(count_per_direction.back_mut());
+    self.unlabelled_edit_count(count_per_direction.left_mut());
+    self.unlabelled_edit_count(count_per_direction.right_mut());
+    self.reset_button_with_hover_tooltip(count_per_direction, CountPerDirection::default(), "Double-click to reset all to 0");
+    self.ui.end_row();
+    label_response
+  }
+
+  /// Row with a picker for adding one of `blocks`, given as (ID, name) pairs, to a block list that only shows used
+  /// blocks. Returns the ID of the picked block, or None if no block was picked. Shows nothing if `blocks` is empty.
+  pub fn add_block_row<Id: Clone>(&mut self, id_source: impl std::hash::Hash, blocks: Vec<(Id, String)>) -> Option<Id> {
+    if blocks.is_empty() { return None; }
+    let mut picked = None;
+    ComboBox::from_id_source(id_source)
+      .selected_text("Add block…")
+      .show_ui(self.ui, |ui| {
+        for (id, name) in blocks {
+          if ui.selectable_label(false, name).clicked() {
+            picked = Some(id);
+          }
+        }
+      });
+    self.ui.end_row();
+    picked
+  }
+
+  /// Edits block count `value`, which steps in whole numbers when dragged, but can be typed in as a fraction.
+  pub fn unlabelled_edit_count(&mut self, value: &mut f64) {
+    let drag_value = DragValue::new(value)
+      .speed(COUNT_DRAG_SPEED)
+      .clamp_range(0.0..=f64::MAX)
+      .max_decimals(0)
+      .custom_formatter(|value, _| emath::format_with_decimals_in_range(value, 0..=2));
+    self.changed |= self.ui.add_sized([self.edit_size, self.ui.available_height()], drag_value).changed();
+  }
+
+
+  pub fn drag<N: Numeric>(&mut self, value: &mut N, speed: impl Into<f64>, clamp_range: RangeInclusive<N>) {
+    let drag_value = DragValue::new(value)
+      .speed(speed)
+      .clamp_range(clamp_range)
+      //.custom_formatter(|value, range| emath::format_with_decimals_in_range(value, range).separate_by_policy(self.number_separator_policy))
+      ;
+    self.changed |= self.ui.add_sized([self.edit_size, self.ui.available_height()], drag_value).changed();
+  }
+
+
+  pub fn reset_button(&mut self, enabled: bool) -> Response {
+    self.ui.add_enabled(enabled, Button::new("↺"))
+  }
+
+  pub fn reset_button_with<T: PartialEq + Display + Copy>(&mut self, value: &mut T, reset_value: T) {
+    self.reset_button_with_hover_tooltip(value, reset_value, format!("Double-click to reset to {}", reset_value))
+  }
+
+  pub fn reset_button_with_hover_tooltip<T: PartialEq>(&mut self, value: &mut T, reset_value: T, hover_tooltip: impl Into<WidgetText>) {
+    let response = self.reset_button(*value != reset_value)
+      .on_hover_text_at_pointer(hover_tooltip);
+    if response.double_clicked() {
+      *value = reset_value;
+      self.changed = true;
+    }
+  }
+}
+
+impl<'ui> Deref for CalculatorUi<'ui> {
+  type Target = Ui;
+  fn deref(&self) -> &Self::Target { self.ui }
+}
+
+impl<'ui> DerefMut for CalculatorUi<'ui> {
+  fn deref_mut(&mut self) -> &mut Self::Target { self.ui }
+}
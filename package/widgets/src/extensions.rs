@@ -1,10 +1,18 @@
-use eframe::emath::Rangef;
 use egui::{Button, CollapsingHeader, CollapsingResponse, Color32, Grid, Id, InnerResponse, Response, Sense, Stroke, Ui, vec2, Widget, WidgetText};
 use egui::collapsing_header::CollapsingState;
+use egui::emath::Rangef;
 use egui::output::OpenUrl;
 
 pub trait UiExtensions {
   fn open_collapsing_header_with_grid<R>(&mut self, header: &str, add_contents: impl FnOnce(&mut Ui) -> R) -> CollapsingResponse<InnerResponse<R>>;
+  /// Open collapsing header with a grid as body, and a checkbox with `toggle_label` for `toggle` next to the header.
+  fn open_collapsing_header_with_grid_and_toggle<R>(
+    &mut self,
+    header: &str,
+    toggle_label: impl Into<WidgetText>,
+    toggle: &mut bool,
+    add_contents: impl FnOnce(&mut Ui) -> R
+  ) -> Option<InnerResponse<InnerResponse<R>>>;
   fn open_collapsing_header<R>(&mut self, header: &str, add_contents: impl FnOnce(&mut Ui) -> R) -> CollapsingResponse<R>;
 
   fn open_collapsing_state<HR, BR>(
@@ -33,6 +41,22 @@ impl UiExtensions for Ui {
     })
   }
 
+  fn open_collapsing_header_with_grid_and_toggle<R>(
+    &mut self,
+    header: &str,
+    toggle_label: impl Into<WidgetText>,
+    toggle: &mut bool,
+    add_contents: impl FnOnce(&mut Ui) -> R
+  ) -> Option<InnerResponse<InnerResponse<R>>> {
+    let (_, _, body) = self.open_collapsing_state(header, |ui| {
+      ui.label(header);
+      ui.checkbox(toggle, toggle_label);
+    }, |ui| {
+      Grid::new(format!("{} Grid", header)).striped(true).min_col_width(1.0).show(ui, add_contents)
+    });
+    body
+  }
+
   fn open_collapsing_header<R>(&mut self, header: &str, add_body: impl FnOnce(&mut Ui) -> R) -> CollapsingResponse<R> {
     CollapsingHeader::new(header).default_open(true).show(self, add_body)
   }
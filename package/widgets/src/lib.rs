@@ -0,0 +1,5 @@
+pub mod extensions;
+pub mod calculator;
+pub mod result;
+pub mod sparkline;
+pub mod share_bar;
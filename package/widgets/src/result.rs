@@ -0,0 +1,209 @@
+use std::borrow::Borrow;
+use std::ops::{Deref, DerefMut};
+
+use egui::{Align, Context, Layout, Response, TextFormat, TextStyle, Ui, WidgetText};
+use egui::text::LayoutJob;
+use thousands::{Separable, SeparatorPolicy};
+
+use secalc_core::grid::{HydrogenCalculated, PitchedHoverCalculated, PowerCalculated, ThrusterAccelerationCalculated};
+use secalc_core::grid::direction::{Direction, DirectionLabels, PerDirection};
+use secalc_core::grid::duration::{Duration, DurationFormat};
+use secalc_core::grid::explain::{HydrogenExplanation, PowerExplanation};
+use secalc_core::grid::forces::ForceUnit;
+
+use crate::extensions::UiExtensions;
+use crate::share_bar::ShareBar;
+
+/// Grid rows for showing calculation results, with right-aligned values and units.
+pub struct ResultUi<'ui> {
+  ui: &'ui mut Ui,
+  number_separator_policy: SeparatorPolicy<'static>,
+  duration_format: DurationFormat,
+}
+
+impl<'ui> ResultUi<'ui> {
+  pub fn new(ui: &'ui mut Ui, number_separator_policy: SeparatorPolicy<'static>, duration_format: DurationFormat) -> Self {
+    Self { ui, number_separator_policy, duration_format }
+  }
+
+
+  pub fn show_row(&mut self, label: impl Into<WidgetText>, value: impl Borrow<str>, unit: impl Into<WidgetText>) {
+    self.ui.label(label);
+    self.right_align_value_with_unit(value, unit);
+    self.ui.end_row();
+  }
+
+  pub fn show_optional_row(&mut self, label: impl Into<WidgetText>, value: Option<impl Borrow<str>>, unit: impl Into<WidgetText>) {
+    self.ui.label(label);
+    self.right_align_optional_value_with_unit(value, unit);
+    self.ui.end_row();
+  }
+
+
+  pub fn right_align_label(&mut self, label: impl Into<WidgetText>) {
+    self.ui.with_layout(Layout::right_to_left(Align::Center), |ui| ui.label(label));
+  }
+
+
+  pub fn right_align_value_with_unit(&mut self, value: impl Borrow<str>, unit: impl Into<WidgetText>) -> Response {
+    self.ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+      ui.label(unit);
+      ui.monospace(value.borrow().separate_by_policy(self.number_separator_policy));
+    }).response
+  }
+
+  pub fn right_align_optional_value_with_unit(&mut self, value: Option<impl Borrow<str>>, unit: impl Into<WidgetText>) -> Response {
+    if let Some(value) = value {
+      self.right_align_value_with_unit(value, unit)
+    } else {
+      self.right_align_value_with_unit("-", unit)
+    }
+  }
+
+
+  pub fn show_optional_duration_row(&mut self, label: impl Into<WidgetText>, duration: Option<Duration>) {
+    self.ui.label(label);
+    self.right_align_optional_duration(duration);
+    self.ui.end_row();
+  }
+
+  pub fn right_align_duration(&mut self, duration: Duration) -> Response {
+    match (self.duration_format, duration.to_clock_string()) {
+      (DurationFormat::Clock, Some(clock)) => self.right_align_value_with_unit(clock, ""),
+      _ => {
+        let (value, unit) = duration.to_f64_and_unit();
+        self.right_align_value_with_unit(format!("{:.2}", value), unit)
+      }
+    }
+  }
+
+  /// Formats `duration` with the duration format of this UI.
+  #[inline]
+  pub fn format_duration(&self, duration: Duration) -> String {
+    duration.format(self.duration_format, 2)
+  }
+
+  pub fn right_align_optional_duration(&mut self, duration: Option<Duration>) -> Response {
+    if let Some(duration) = duration {
+      self.right_align_duration(duration)
+    } else {
+      self.right_align_value_with_unit("-", Duration::DEFAULT_UNIT)
+    }
+  }
+
+
+  /// Shows a row of accelerations in `direction` labelled with `labels`, with explanations from
+  /// `explain(filled, gravity)` as tooltips.
+  pub fn acceleration_row(&mut self, direction: Direction, labels: &DirectionLabels, acceleration: &PerDirection<ThrusterAccelerationCalculated>, explain: impl Fn(bool, bool) -> Option<String>, force_unit: ForceUnit, ctx: &Context) {
+    let acceleration_label = self.acceleration_layout_job(ctx);
+    self.right_align_label(labels.get(direction));
+    self.ui.vertical_separator_unpadded();
+    let a = acceleration.get(direction);
+    for (value, filled, gravity) in [
+      (a.acceleration_filled_gravity, true, true),
+      (a.acceleration_filled_no_gravity, true, false),
+      (a.acceleration_empty_gravity, false, true),
+      (a.acceleration_empty_no_gravity, false, false),
+    ] {
+      let response = self.right_align_optional_value_with_unit(value.map(|a| format!("{:.2}", a)), acceleration_label.clone());
+      show_explanation(response, explain(filled, gravity));
+      self.ui.vertical_separator_unpadded();
+    }
+    self.right_align_value_with_unit(format!("{:.2}", force_unit.convert(a.force)), force_unit.symbol());
+    self.ui.end_row();
+  }
+
+  /// Shows a row of stopping distances and times in `direction` labelled with `labels`.
+  pub fn braking_row(&mut self, direction: Direction, labels: &DirectionLabels, acceleration: &PerDirection<ThrusterAccelerationCalculated>) {
+    self.right_align_label(labels.get(direction));
+    self.ui.vertical_separator_unpadded();
+    let a = acceleration.get(direction);
+    for braking in [a.braking_filled_gravity, a.braking_filled_no_gravity, a.braking_empty_gravity, a.braking_empty_no_gravity] {
+      self.right_align_optional_value_with_unit(braking.map(|b| format!("{:.0}", b.distance)), "m");
+      self.right_align_optional_duration(braking.map(|b| b.time));
+      self.ui.vertical_separator_unpadded();
+    }
+    self.ui.end_row();
+  }
+
+  pub fn acceleration_layout_job(&mut self, ctx: &Context) -> LayoutJob {
+    let mut acceleration = LayoutJob::default();
+    let color = ctx.style().visuals.text_color();
+    acceleration.append("m/s", 0.0, TextFormat { font_id: TextStyle::Body.resolve(&ctx.style()), color, ..TextFormat::default() });
+    acceleration.append("2", 0.0, TextFormat { font_id: TextStyle::Small.resolve(&ctx.style()), color, valign: Align::Min, ..TextFormat::default() });
+    acceleration
+  }
+
+  pub fn pitched_hover_rows(&mut self, label: &str, hover: &PitchedHoverCalculated, labels: &DirectionLabels) {
+    let percentage = |u: Option<f64>| u.map(|u| format!("{:.1}", u * 100.0));
+    self.show_optional_row(format!("{} ({}):", labels.get(Direction::Up), label), percentage(hover.up_utilization), "%");
+    self.show_optional_row(format!("{} ({}):", labels.get(hover.horizontal_direction), label), percentage(hover.horizontal_utilization), "%");
+    self.ui.label(format!("Can Hover ({}):", label));
+    self.right_align_label(if hover.can_hover { "Yes" } else { "No" });
+    self.ui.end_row();
+  }
+
+  /// Shows a row with a bar and percentage of `fraction` 0-1, the share of `label` in a whole.
+  pub fn share_row(&mut self, label: impl Into<WidgetText>, fraction: f64) {
+    self.ui.label(label);
+    self.ui.add(ShareBar::new(fraction));
+    self.right_align_value_with_unit(format!("{:.1}", fraction * 100.0), "%");
+    self.ui.end_row();
+  }
+
+  pub fn power_row(&mut self, label: impl Into<WidgetText>, power_formatter: impl Fn(f64) -> String, power: &PowerCalculated, explanation: PowerExplanation) {
+    self.ui.label(label);
+    self.ui.vertical_separator_unpadded();
+    self.right_align_value_with_unit(power_formatter(power.consumption.to_megawatts()), "MW");
+    self.ui.vertical_separator_unpadded();
+    self.right_align_value_with_unit(power_formatter(power.total_consumption.to_megawatts()), "MW");
+    self.ui.vertical_separator_unpadded();
+    let response = self.right_align_value_with_unit(power_formatter(power.balance.to_megawatts()), "MW");
+    show_explanation(response, Some(explanation.balance));
+    self.ui.vertical_separator_unpadded();
+    let response = self.right_align_value_with_unit(power_formatter(power.budget.to_megawatts()), "MW");
+    show_explanation(response, Some(explanation.budget));
+    self.ui.vertical_separator_unpadded();
+    let response = self.right_align_optional_duration(power.battery_duration);
+    show_explanation(response, explanation.battery_duration);
+    self.ui.vertical_separator_unpadded();
+    self.right_align_optional_duration(power.engine_duration);
+    self.ui.end_row();
+  }
+
+  pub fn hydrogen_row(&mut self, label: impl Into<WidgetText>, hydrogen_formatter: impl Fn(f64) -> String, hydrogen: &HydrogenCalculated, explanation: HydrogenExplanation) {
+    self.ui.label(label);
+    self.ui.vertical_separator_unpadded();
+    self.right_align_value_with_unit(hydrogen_formatter(hydrogen.consumption), "L/s");
+    self.ui.vertical_separator_unpadded();
+    self.right_align_value_with_unit(hydrogen_formatter(hydrogen.total_consumption), "L/s");
+    self.ui.vertical_separator_unpadded();
+    let response = self.right_align_value_with_unit(hydrogen_formatter(hydrogen.balance_without_tank), "L/s");
+    show_explanation(response, Some(explanation.balance_without_tank));
+    self.ui.vertical_separator_unpadded();
+    let response = self.right_align_value_with_unit(hydrogen_formatter(hydrogen.balance_with_tank), "L/s");
+    show_explanation(response, Some(explanation.balance_with_tank));
+    self.ui.vertical_separator_unpadded();
+    let response = self.right_align_value_with_unit(hydrogen_formatter(hydrogen.budget), "L/s");
+    show_explanation(response, Some(explanation.budget));
+    self.ui.vertical_separator_unpadded();
+    let response = self.right_align_optional_duration(hydrogen.tank_duration);
+    show_explanation(response, explanation.tank_duration);
+    self.ui.end_row();
+  }
+}
+
+fn show_explanation(response: Response, explanation: Option<String>) {
+  if let Some(explanation) = explanation {
+    response.on_hover_text_at_pointer(explanation);
+  }
+}
+
+impl<'ui> Deref for ResultUi<'ui> {
+  type Target = Ui;
+  fn deref(&self) -> &Self::Target { self.ui }
+}
+
+impl<'ui> DerefMut for ResultUi<'ui> {
+  fn deref_mut(&mut self) -> &mut Self::Target { self.ui }
+}
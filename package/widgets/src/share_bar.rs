@@ -0,0 +1,36 @@
+use egui::{Rect, Response, Sense, Ui, Vec2, Widget};
+
+/// Horizontal bar filled to a fraction 0-1 of its width, for showing the share of a part in a whole.
+pub struct ShareBar {
+  fraction: f64,
+  size: Vec2,
+}
+
+impl ShareBar {
+  pub fn new(fraction: f64) -> Self {
+    Self { fraction, size: Vec2::new(160.0, 12.0) }
+  }
+
+  #[inline]
+  pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+    self.size = size.into();
+    self
+  }
+}
+
+impl Widget for ShareBar {
+  fn ui(self, ui: &mut Ui) -> Response {
+    let (rect, response) = ui.allocate_exact_size(self.size, Sense::hover());
+    if !ui.is_rect_visible(rect) { return response; }
+
+    let visuals = ui.visuals();
+    ui.painter().rect_filled(rect, 2.0, visuals.extreme_bg_color);
+    let fraction = self.fraction.clamp(0.0, 1.0) as f32;
+    if fraction > 0.0 {
+      let filled = Rect::from_min_size(rect.min, Vec2::new(rect.width() * fraction, rect.height()));
+      ui.painter().rect_filled(filled, 2.0, visuals.selection.bg_fill);
+    }
+    ui.painter().rect_stroke(rect, 2.0, visuals.widgets.noninteractive.bg_stroke);
+    response
+  }
+}
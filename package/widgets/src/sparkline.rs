@@ -0,0 +1,69 @@
+use egui::{pos2, Pos2, Response, Sense, Shape, Stroke, Ui, Vec2, Widget};
+
+/// Small inline line plot without axes, of points with an x and optional y value. Points without a y value are drawn as
+/// gaps in the line.
+pub struct Sparkline<'a> {
+  points: &'a [(f64, Option<f64>)],
+  marker: Option<f64>,
+  size: Vec2,
+}
+
+impl<'a> Sparkline<'a> {
+  pub fn new(points: &'a [(f64, Option<f64>)]) -> Self {
+    Self { points, marker: None, size: Vec2::new(240.0, 60.0) }
+  }
+
+  /// Draws a vertical marker at x value `marker`, for example at the current value.
+  #[inline]
+  pub fn marker(mut self, marker: f64) -> Self {
+    self.marker = Some(marker);
+    self
+  }
+
+  #[inline]
+  pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+    self.size = size.into();
+    self
+  }
+}
+
+impl Widget for Sparkline<'_> {
+  fn ui(self, ui: &mut Ui) -> Response {
+    let (rect, response) = ui.allocate_exact_size(self.size, Sense::hover());
+    if !ui.is_rect_visible(rect) { return response; }
+
+    let visuals = ui.visuals();
+    ui.painter().rect_stroke(rect, 0.0, visuals.widgets.noninteractive.bg_stroke);
+    let (x_min, x_max) = min_max(self.points.iter().map(|(x, _)| *x));
+    let (y_min, y_max) = min_max(self.points.iter().filter_map(|(_, y)| *y));
+    let to_screen = |x: f64, y: f64| -> Pos2 {
+      let x_fraction = if x_max > x_min { (x - x_min) / (x_max - x_min) } else { 0.5 };
+      let y_fraction = if y_max > y_min { (y - y_min) / (y_max - y_min) } else { 0.5 };
+      pos2(rect.left() + rect.width() * x_fraction as f32, rect.bottom() - rect.height() * y_fraction as f32)
+    };
+
+    if let Some(marker) = self.marker {
+      if marker >= x_min && marker <= x_max {
+        let x = to_screen(marker, y_min).x;
+        ui.painter().vline(x, rect.y_range(), Stroke::new(1.0, visuals.warn_fg_color));
+      }
+    }
+    let stroke = Stroke::new(1.5, visuals.hyperlink_color);
+    let mut line = Vec::new();
+    for (x, y) in self.points {
+      if let Some(y) = y {
+        line.push(to_screen(*x, *y));
+      } else if !line.is_empty() {
+        ui.painter().add(Shape::line(std::mem::take(&mut line), stroke));
+      }
+    }
+    if !line.is_empty() {
+      ui.painter().add(Shape::line(line, stroke));
+    }
+    response
+  }
+}
+
+fn min_max(values: impl Iterator<Item=f64>) -> (f64, f64) {
+  values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)))
+}